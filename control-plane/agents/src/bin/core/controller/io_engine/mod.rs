@@ -12,15 +12,15 @@ use crate::controller::io_engine::types::{
 };
 use agents::errors::SvcError;
 use stor_port::{
-    transport_api::v0::BlockDevices,
+    transport_api::{v0::BlockDevices, ResourceKind},
     types::v0::transport::{
         AddNexusChild, ApiVersion, CreateNexus, CreatePool, CreateReplica, CreateReplicaSnapshot,
-        DestroyNexus, DestroyPool, DestroyReplica, DestroyReplicaSnapshot, FaultNexusChild,
-        GetBlockDevices, GetRebuildRecord, ImportPool, IoEngCreateSnapshotClone, ListRebuildRecord,
-        ListReplicaSnapshots, ListSnapshotClones, Nexus, NexusChildAction, NexusChildActionContext,
-        NexusChildActionKind, NexusId, PoolState, RebuildHistory, Register, RemoveNexusChild,
-        Replica, ReplicaId, ReplicaSnapshot, ResizeNexus, ResizeReplica, ShareNexus, ShareReplica,
-        ShutdownNexus, UnshareNexus, UnshareReplica,
+        DestroyNexus, DestroyPool, DestroyReplica, DestroyReplicaSnapshot, ExpandPool,
+        FaultNexusChild, GetBlockDevices, GetRebuildRecord, ImportPool, IoEngCreateSnapshotClone,
+        ListRebuildRecord, ListReplicaSnapshots, ListSnapshotClones, Nexus, NexusChildAction,
+        NexusChildActionContext, NexusChildActionKind, NexusId, PoolState, RebuildHistory,
+        Register, RemoveNexusChild, Replica, ReplicaId, ReplicaSnapshot, ResizeNexus,
+        ResizeReplica, ShareNexus, ShareReplica, ShutdownNexus, UnshareNexus, UnshareReplica,
     },
 };
 
@@ -63,6 +63,8 @@ pub(crate) trait PoolApi {
     async fn destroy_pool(&self, request: &DestroyPool) -> Result<(), SvcError>;
     /// Import a pool on the node via gRPC.
     async fn import_pool(&self, request: &ImportPool) -> Result<PoolState, SvcError>;
+    /// Expand a pool with more disks on the node via gRPC.
+    async fn expand_pool(&self, request: &ExpandPool) -> Result<PoolState, SvcError>;
 }
 
 #[async_trait]
@@ -109,6 +111,51 @@ pub(crate) trait NexusApi<Sht> {
     async fn shutdown_nexus(&self, request: &ShutdownNexus) -> Result<Sht, SvcError>;
 }
 
+/// Check that the io-engine api is capable of sharing the nexus as requested.
+/// Neither the v0 nor the v1 io-engine api carries the DH-HMAC-CHAP secrets of a nexus target, so
+/// sharing with secrets is refused rather than silently sharing the target without authentication.
+/// todo: allow it for the api versions which carry the secrets, once available.
+pub(crate) fn share_nexus_capable(request: &ShareNexus, api: ApiVersion) -> Result<(), SvcError> {
+    if request.dhchap.is_some() {
+        return Err(SvcError::Unimplemented {
+            resource: ResourceKind::Nexus,
+            request: "share_nexus".to_string(),
+            source: tonic::Status::unimplemented(format!(
+                "DH-HMAC-CHAP is not supported by the {api:?} io-engine api"
+            )),
+        });
+    }
+    Ok(())
+}
+
+/// Check that the io-engine api is capable of adding disks to a pool.
+/// No io-engine api version carries the disks to add to a pool yet, so the expansion is refused up
+/// front rather than starting a pool operation which could only be rolled back.
+/// todo: allow it once an io-engine api version which can add disks to a pool is available.
+pub(crate) fn expand_pool_capable() -> Result<(), SvcError> {
+    Err(SvcError::Unimplemented {
+        resource: ResourceKind::Pool,
+        request: "expand_pool".to_string(),
+        source: tonic::Status::unimplemented(
+            "adding disks to a pool is not supported by the io-engine api".to_string(),
+        ),
+    })
+}
+
+/// Check that the io-engine api is capable of authenticating the volume targets.
+/// No io-engine api version carries the DH-HMAC-CHAP secrets yet, so the authentication is refused
+/// up front rather than persisting a volume which could never be published.
+/// todo: allow it once an io-engine api version which carries the secrets is available.
+pub(crate) fn volume_auth_capable(request: &str) -> Result<(), SvcError> {
+    Err(SvcError::Unimplemented {
+        resource: ResourceKind::Volume,
+        request: request.to_string(),
+        source: tonic::Status::unimplemented(
+            "DH-HMAC-CHAP is not supported by the io-engine api".to_string(),
+        ),
+    })
+}
+
 #[async_trait]
 pub(crate) trait NexusShareApi<Share, Unshare> {
     /// Share a nexus on the node via gRPC.
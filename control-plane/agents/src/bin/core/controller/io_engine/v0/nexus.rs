@@ -9,9 +9,9 @@ use rpc::io_engine::Null;
 use stor_port::{
     transport_api::ResourceKind,
     types::v0::transport::{
-        AddNexusChild, Child, CreateNexus, DestroyNexus, FaultNexusChild, Nexus, NexusChildAction,
-        NexusChildActionContext, NexusId, RemoveNexusChild, ResizeNexus, ShareNexus, ShutdownNexus,
-        UnshareNexus,
+        AddNexusChild, ApiVersion, Child, CreateNexus, DestroyNexus, FaultNexusChild, Nexus,
+        NexusChildAction, NexusChildActionContext, NexusId, RemoveNexusChild, ResizeNexus,
+        ShareNexus, ShutdownNexus, UnshareNexus,
     },
 };
 
@@ -109,6 +109,7 @@ impl crate::controller::io_engine::NexusApi<()> for super::RpcClient {
 #[async_trait::async_trait]
 impl crate::controller::io_engine::NexusShareApi<String, ()> for super::RpcClient {
     async fn share_nexus(&self, request: &ShareNexus) -> Result<String, SvcError> {
+        crate::controller::io_engine::share_nexus_capable(request, ApiVersion::V0)?;
         let share = self
             .client()
             .publish_nexus(request.to_rpc())
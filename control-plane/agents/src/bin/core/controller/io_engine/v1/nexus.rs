@@ -11,7 +11,7 @@ use rpc::v1::nexus::ListNexusOptions;
 use stor_port::{
     transport_api::ResourceKind,
     types::v0::transport::{
-        AddNexusChild, CreateNexus, DestroyNexus, FaultNexusChild, GetRebuildRecord,
+        AddNexusChild, ApiVersion, CreateNexus, DestroyNexus, FaultNexusChild, GetRebuildRecord,
         ListRebuildRecord, Nexus, NexusChildAction, NexusChildActionContext, NexusId, NodeId,
         RebuildHistory, RemoveNexusChild, ResizeNexus, ShareNexus, ShutdownNexus, UnshareNexus,
     },
@@ -175,6 +175,7 @@ impl crate::controller::io_engine::NexusApi<()> for super::RpcClient {
 #[async_trait::async_trait]
 impl crate::controller::io_engine::NexusShareApi<String, ()> for super::RpcClient {
     async fn share_nexus(&self, request: &ShareNexus) -> Result<String, SvcError> {
+        crate::controller::io_engine::share_nexus_capable(request, ApiVersion::V1)?;
         let rpc_nexus =
             self.nexus()
                 .publish_nexus(request.to_rpc())
@@ -210,6 +211,7 @@ impl crate::controller::io_engine::NexusShareApi<String, ()> for super::RpcClien
 #[async_trait::async_trait]
 impl crate::controller::io_engine::NexusShareApi<Nexus, Nexus> for super::RpcClient {
     async fn share_nexus(&self, request: &ShareNexus) -> Result<Nexus, SvcError> {
+        crate::controller::io_engine::share_nexus_capable(request, ApiVersion::V1)?;
         let response =
             self.nexus()
                 .publish_nexus(request.to_rpc())
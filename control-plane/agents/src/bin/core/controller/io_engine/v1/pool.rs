@@ -3,7 +3,7 @@ use agents::errors::{GrpcRequest as GrpcRequestError, SvcError};
 use rpc::v1::pool::ListPoolOptions;
 use stor_port::{
     transport_api::ResourceKind,
-    types::v0::transport::{CreatePool, DestroyPool, ImportPool, PoolState},
+    types::v0::transport::{CreatePool, DestroyPool, ExpandPool, ImportPool, PoolState},
 };
 
 use snafu::ResultExt;
@@ -84,4 +84,17 @@ impl crate::controller::io_engine::PoolApi for super::RpcClient {
         let pool = rpc_pool_to_agent(&rpc_pool.into_inner(), &request.node);
         Ok(pool)
     }
+
+    /// The pinned v1 io-engine api can only grow a pool onto its resized disk, it can't add
+    /// disks to a pool.
+    /// todo: issue the rpc once the io-engine api carries the disks to add to a pool.
+    async fn expand_pool(&self, _request: &ExpandPool) -> Result<PoolState, SvcError> {
+        Err(SvcError::Unimplemented {
+            resource: ResourceKind::Pool,
+            request: "expand_pool".to_string(),
+            source: tonic::Status::unimplemented(
+                "adding disks to a pool is not supported by the V1 io-engine api",
+            ),
+        })
+    }
 }
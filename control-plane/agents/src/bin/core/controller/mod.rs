@@ -3,6 +3,12 @@
 pub(crate) mod io_engine;
 /// Various policies' definitions(e.g. rebuild policy)
 pub(crate) mod policies;
+/// sealing of the volume targets' secrets
+pub(crate) mod nvmf_auth;
+/// long-running operations of the rest servers
+pub(crate) mod operations;
+/// error signals of the pools
+pub(crate) mod pool_errors;
 /// reconciliation logic
 pub(crate) mod reconciler;
 /// registry with node and all its resources
@@ -15,5 +21,7 @@ pub(crate) mod scheduling;
 pub(crate) mod states;
 /// generic task pollers (eg used by the reconcilers)
 mod task_poller;
+/// usage history of volumes and pools
+pub(crate) mod usage;
 /// helper wrappers over the resources
 pub(crate) mod wrapper;
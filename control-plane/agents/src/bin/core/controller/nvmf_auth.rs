@@ -0,0 +1,111 @@
+use crate::NvmfAuthArgs;
+use agents::errors::SvcError;
+use base64::{engine::general_purpose, Engine as _};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use stor_port::types::v0::{
+    store::auth::{NvmfAuthRef, NvmfAuthSecret},
+    transport::DhChapKeys,
+};
+
+/// Seals the DH-HMAC-CHAP secrets of the volume targets before they're persisted, so the store
+/// never holds them in the clear.
+/// The secrets are encrypted with AES-256-GCM, using the volume and generation of the secrets as
+/// the associated data, so sealed secrets can't be swapped between volumes or generations.
+pub(crate) struct NvmfAuthSealer {
+    key: Option<LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl std::fmt::Debug for NvmfAuthSealer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NvmfAuthSealer")
+            .field("enabled", &self.key.is_some())
+            .finish()
+    }
+}
+
+impl NvmfAuthSealer {
+    /// Return a new `Self` with the given parameters.
+    /// Without a sealing key, volume target authentication is disabled.
+    pub(crate) fn new(args: &NvmfAuthArgs) -> Result<Self, SvcError> {
+        let key = match args.seal_key() {
+            None => None,
+            Some(key) => {
+                let key = general_purpose::STANDARD
+                    .decode(key.trim())
+                    .ok()
+                    .and_then(|key| UnboundKey::new(&AES_256_GCM, &key).ok())
+                    .ok_or_else(|| SvcError::NvmfAuthDisabled {
+                        details: "the sealing key must be 32 bytes, base64 encoded".to_string(),
+                    })?;
+                Some(LessSafeKey::new(key))
+            }
+        };
+        Ok(Self {
+            key,
+            rng: SystemRandom::new(),
+        })
+    }
+    fn key(&self) -> Result<&LessSafeKey, SvcError> {
+        self.key.as_ref().ok_or_else(|| SvcError::NvmfAuthDisabled {
+            details: "no sealing key has been configured".to_string(),
+        })
+    }
+    /// Check that volume target authentication is enabled.
+    pub(crate) fn enabled(&self) -> Result<(), SvcError> {
+        self.key().map(|_| ())
+    }
+    /// Seal the `keys`, returning the secrets to be stored under the `auth_ref`.
+    pub(crate) fn seal(
+        &self,
+        auth_ref: &NvmfAuthRef,
+        keys: &DhChapKeys,
+    ) -> Result<NvmfAuthSecret, SvcError> {
+        let key = self.key()?;
+        let error = || SvcError::NvmfAuthSeal {
+            id: auth_ref.volume().to_string(),
+        };
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce).map_err(|_| error())?;
+        let mut data = serde_json::to_vec(keys).map_err(|_| error())?;
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(Self::aad(auth_ref)),
+            &mut data,
+        )
+        .map_err(|_| error())?;
+
+        let sealed = [nonce.as_slice(), data.as_slice()].concat();
+        Ok(NvmfAuthSecret::new(
+            auth_ref.volume(),
+            auth_ref.generation(),
+            general_purpose::STANDARD.encode(sealed),
+        ))
+    }
+    /// Open the sealed `secret`, returning its keys.
+    pub(crate) fn open(&self, secret: &NvmfAuthSecret) -> Result<DhChapKeys, SvcError> {
+        let key = self.key()?;
+        let auth_ref = secret.auth_ref();
+        let error = || SvcError::NvmfAuthSeal {
+            id: auth_ref.volume().to_string(),
+        };
+        let mut sealed = general_purpose::STANDARD
+            .decode(secret.sealed())
+            .map_err(|_| error())?;
+        if sealed.len() < NONCE_LEN {
+            return Err(error());
+        }
+        let mut data = sealed.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&sealed).map_err(|_| error())?;
+        let data = key
+            .open_in_place(nonce, Aad::from(Self::aad(&auth_ref)), &mut data)
+            .map_err(|_| error())?;
+        serde_json::from_slice(data).map_err(|_| error())
+    }
+    fn aad(auth_ref: &NvmfAuthRef) -> Vec<u8> {
+        format!("{}/{}", auth_ref.volume(), auth_ref.generation()).into_bytes()
+    }
+}
@@ -0,0 +1,131 @@
+use crate::controller::registry::Registry;
+use agents::errors::SvcError;
+use std::collections::HashMap;
+use stor_port::{
+    pstor::{Store, API_VERSION},
+    types::v0::{
+        store::{
+            definitions::{key_prefix_obj, ObjectKey, StorableObjectType},
+            operation::AsyncOperationKey,
+        },
+        transport::{AsyncOperation, AsyncOperationId},
+    },
+};
+
+/// The long-running operations of the REST servers, persisted in the store so that they're
+/// visible from all REST replicas.
+#[derive(Debug)]
+pub(crate) struct AsyncOperations {
+    ttl: std::time::Duration,
+    operations: parking_lot::Mutex<HashMap<AsyncOperationId, AsyncOperation>>,
+}
+
+impl AsyncOperations {
+    /// Return a new empty `Self` which expires operations after the given `ttl`.
+    pub(crate) fn new(ttl: std::time::Duration) -> Self {
+        Self {
+            ttl,
+            operations: Default::default(),
+        }
+    }
+    /// Load all the operations from the store.
+    pub(crate) async fn init<S: Store>(
+        &self,
+        store: &mut S,
+        etcd_max_page_size: i64,
+    ) -> Result<(), SvcError> {
+        let prefix = key_prefix_obj(StorableObjectType::AsyncOperation, API_VERSION);
+        let entries = store
+            .get_values_paged_all(&prefix, etcd_max_page_size)
+            .await?;
+        let mut operations = self.operations.lock();
+        for (key, value) in entries {
+            match serde_json::from_value::<AsyncOperation>(value) {
+                Ok(operation) => {
+                    operations.insert(operation.id.clone(), operation);
+                }
+                Err(error) => {
+                    tracing::warn!(%key, %error, "Failed to deserialise an async operation");
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Get the operation with the given id or all of them, excluding the expired ones.
+    fn get(&self, id: Option<&AsyncOperationId>) -> Vec<AsyncOperation> {
+        self.operations
+            .lock()
+            .values()
+            .filter(|operation| id.map_or(true, |id| &operation.id == id))
+            .filter(|operation| !operation.expired(self.ttl))
+            .cloned()
+            .collect()
+    }
+    fn insert(&self, operation: AsyncOperation) {
+        self.operations
+            .lock()
+            .insert(operation.id.clone(), operation);
+    }
+    /// Get the running operations which have been abandoned by their REST server.
+    fn abandoned(&self) -> Vec<AsyncOperation> {
+        self.operations
+            .lock()
+            .values()
+            .filter(|operation| operation.abandoned())
+            .cloned()
+            .collect()
+    }
+    /// Get the ids of the expired operations.
+    fn expired(&self) -> Vec<AsyncOperationId> {
+        self.operations
+            .lock()
+            .values()
+            .filter(|operation| operation.expired(self.ttl))
+            .map(|operation| operation.id.clone())
+            .collect()
+    }
+    fn remove(&self, id: &AsyncOperationId) {
+        self.operations.lock().remove(id);
+    }
+}
+
+impl Registry {
+    /// Persist the given operation, adding it or updating its existing record.
+    pub(crate) async fn put_async_operation(
+        &self,
+        operation: &AsyncOperation,
+    ) -> Result<(), SvcError> {
+        self.store_obj(operation).await?;
+        self.async_operations().insert(operation.clone());
+        Ok(())
+    }
+
+    /// Get the operation with the given id or all of them, excluding the expired ones.
+    pub(crate) fn get_async_operations(
+        &self,
+        id: Option<&AsyncOperationId>,
+    ) -> Vec<AsyncOperation> {
+        self.async_operations().get(id)
+    }
+
+    /// Fail the abandoned operations and delete the expired ones from the store every minute.
+    pub(crate) async fn async_operations_purger(&self) {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            for mut operation in self.async_operations().abandoned() {
+                operation.abandon();
+                if let Err(error) = self.put_async_operation(&operation).await {
+                    tracing::warn!(id = %operation.id, %error, "Failed to fail an abandoned async operation");
+                }
+            }
+            for id in self.async_operations().expired() {
+                match self.delete_kv(&AsyncOperationKey::from(&id).key()).await {
+                    Ok(()) => self.async_operations().remove(&id),
+                    Err(error) => {
+                        tracing::warn!(%id, %error, "Failed to delete an expired async operation");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{controller::registry::Registry, PoolErrorArgs};
+use agents::errors::SvcError;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use stor_port::types::v0::transport::{ChildUri, PoolId, ReplicaId};
+
+/// An error signal which hints that the backing device of a pool is failing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub(crate) enum PoolErrorKind {
+    /// A replica could not be created on the pool because of a device error.
+    ReplicaCreate,
+    /// A nexus child on the pool was faulted with IO errors.
+    ChildIoError,
+    /// A nexus child on the pool ran out of space.
+    /// A thin pool may run out of space without any device fault, so only the repeated
+    /// occurrences within the window are counted.
+    ChildNoSpace,
+}
+
+impl std::fmt::Display for PoolErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReplicaCreate => write!(f, "replica create failure"),
+            Self::ChildIoError => write!(f, "child IO error"),
+            Self::ChildNoSpace => write!(f, "child out of space"),
+        }
+    }
+}
+
+/// Recent error signals of all pools, used by the pool reconciler to mark failing pools as
+/// degraded.
+#[derive(Debug)]
+pub(crate) struct PoolErrors {
+    args: PoolErrorArgs,
+    errors: parking_lot::Mutex<HashMap<PoolId, Vec<(Instant, PoolErrorKind)>>>,
+    /// The children which are out of space, so that each ENOSPC episode is recorded only once.
+    no_space: parking_lot::Mutex<HashMap<ChildUri, PoolId>>,
+}
+
+impl PoolErrors {
+    /// Return a new empty `Self` with the given parameters.
+    pub(crate) fn new(args: PoolErrorArgs) -> Self {
+        Self {
+            args,
+            errors: Default::default(),
+            no_space: Default::default(),
+        }
+    }
+    /// The number of error signals within the window after which a pool is marked as degraded.
+    /// Zero disables the marking.
+    pub(crate) fn threshold(&self) -> usize {
+        self.args.pool_error_threshold
+    }
+    /// Drain the degraded pools, rather than only cordoning them.
+    pub(crate) fn drain(&self) -> bool {
+        self.args.pool_error_drain
+    }
+    fn window(&self) -> Duration {
+        self.args.pool_error_window.into()
+    }
+    /// Record an error signal against the given pool.
+    pub(crate) fn record(&self, pool: &PoolId, kind: PoolErrorKind) {
+        tracing::debug!(pool.id = %pool, error = %kind, "Recording pool error");
+        self.errors
+            .lock()
+            .entry(pool.clone())
+            .or_default()
+            .push((Instant::now(), kind));
+    }
+    /// Record the given child of the pool running out of space, unless it's already been
+    /// recorded since it last went out of space.
+    pub(crate) fn record_no_space(&self, pool: &PoolId, child: &ChildUri) {
+        let mut no_space = self.no_space.lock();
+        if no_space.contains_key(child) {
+            return;
+        }
+        no_space.insert(child.clone(), pool.clone());
+        self.record(pool, PoolErrorKind::ChildNoSpace);
+    }
+    /// The given child has been onlined, so its next ENOSPC is a new occurrence.
+    pub(crate) fn online_no_space(&self, child: &ChildUri) {
+        self.no_space.lock().remove(child);
+    }
+    /// Get the weighted number of the given error signals, to be compared with the threshold.
+    pub(crate) fn weight(errors: &HashMap<PoolErrorKind, usize>) -> usize {
+        errors
+            .iter()
+            .map(|(kind, count)| match kind {
+                PoolErrorKind::ChildNoSpace => count.saturating_sub(1),
+                _ => *count,
+            })
+            .sum()
+    }
+    /// Get the error signals of the given pool within the window, per kind.
+    pub(crate) fn recent(&self, pool: &PoolId) -> HashMap<PoolErrorKind, usize> {
+        let window = self.window();
+        let mut errors = self.errors.lock();
+        let Some(pool_errors) = errors.get_mut(pool) else {
+            return HashMap::new();
+        };
+        pool_errors.retain(|(at, _)| at.elapsed() <= window);
+        let recent = pool_errors
+            .iter()
+            .fold(HashMap::new(), |mut recent, (_, kind)| {
+                *recent.entry(*kind).or_insert(0) += 1;
+                recent
+            });
+        if pool_errors.is_empty() {
+            errors.remove(pool);
+        }
+        recent
+    }
+    /// Forget the error signals of the given pool.
+    pub(crate) fn clear(&self, pool: &PoolId) {
+        self.errors.lock().remove(pool);
+        self.no_space.lock().retain(|_, child_pool| child_pool != pool);
+    }
+}
+
+impl PoolErrorKind {
+    /// Check if the io-engine failed the request because of a device error.
+    /// Other failures, eg: running out of space or invalid arguments, say nothing about the
+    /// health of the pool's device.
+    pub(crate) fn is_device_error(error: &SvcError) -> bool {
+        match error {
+            SvcError::GrpcRequestError { source, .. } => {
+                matches!(source.code(), tonic::Code::Internal | tonic::Code::DataLoss)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Registry {
+    /// Get the pool of the given replica, from its spec.
+    pub(crate) fn replica_pool(&self, replica: &ReplicaId) -> Option<PoolId> {
+        self.specs()
+            .replica_rsc(replica)
+            .map(|replica| replica.lock().pool_name().clone())
+    }
+}
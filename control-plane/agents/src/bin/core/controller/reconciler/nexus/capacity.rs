@@ -31,6 +31,14 @@ pub(crate) async fn enospc_children_onliner(
             nexus.warn_span(|| {
                 tracing::info!(child.uri = child.uri.as_str(), "Found child with enospc")
             });
+            let registry = context.registry();
+            if let Some(pool) = nexus
+                .as_ref()
+                .replica_uri(&child.uri)
+                .and_then(|replica| registry.replica_pool(replica.uuid()))
+            {
+                registry.pool_errors().record_no_space(&pool, &child.uri);
+            }
 
             if let Err(error) = online_enospc(nexus, child, context.registry()).await {
                 nexus.warn_span(|| {
@@ -139,6 +147,7 @@ async fn online_enospc(
         registry,
     ))
     .await?;
+    registry.pool_errors().online_no_space(&child.uri);
     nexus.info_span(|| {
         tracing::info!(
             child.uri = %child.uri.as_str(),
@@ -5,6 +5,7 @@ use crate::{
     controller::{
         io_engine::{NexusApi, NexusChildActionApi},
         policies::rebuild_policies::RuleSet,
+        pool_errors::PoolErrorKind,
         reconciler::{ReCreate, Reconciler},
         resources::{
             operations::{ResourceResize, ResourceSharing},
@@ -34,8 +35,9 @@ use stor_port::{
             volume::VolumeSpec,
         },
         transport::{
-            Child, ChildUri, CreateNexus, Nexus, NexusChildActionContext, NexusShareProtocol,
-            NexusStatus, NodeStatus, ReplicaId, ResizeNexus, ShareNexus, UnshareNexus,
+            Child, ChildStateReason, ChildUri, CreateNexus, Nexus, NexusChildActionContext,
+            NexusShareProtocol, NexusStatus, NodeStatus, ReplicaId, ResizeNexus, ShareNexus,
+            UnshareNexus,
         },
     },
 };
@@ -222,12 +224,26 @@ async fn faulted_children_remover(
     nexus.warn_span(|| {
         tracing::warn!(%child.uri, %child.state, %child.state_reason, ?faulted_at, "Attempting to remove faulted child")
     });
+    // the child's replica is no longer part of the nexus once removed, so find its pool now
+    let io_error_pool = match child.state_reason {
+        ChildStateReason::IoError => nexus
+            .as_ref()
+            .replica_uri(&child.uri)
+            .and_then(|replica| context.registry().replica_pool(replica.uuid())),
+        _ => None,
+    };
     nexus
         .remove_vol_child_by_uri(volume, context.registry(), &nexus_state, &child.uri)
         .await?;
     nexus.warn_span(|| {
         tracing::warn!(%child.uri, %child.state, %child.state_reason, ?faulted_at, "Successfully removed faulted child")
     });
+    if let Some(pool) = io_error_pool {
+        context
+            .registry()
+            .pool_errors()
+            .record(&pool, PoolErrorKind::ChildIoError);
+    }
     Ok(())
 }
 
@@ -514,10 +530,18 @@ pub(super) async fn fixup_nexus_protocol(
                 match NexusShareProtocol::try_from(nexus_spec.share) {
                     Ok(protocol) => {
                         let allowed_host = nexus.lock().allowed_hosts.clone();
+                        let dhchap = match &nexus_spec.owner {
+                            Some(owner) => {
+                                let volume = context.specs().volume_clone(owner)?;
+                                context.registry().volume_target_auth(&volume).await?
+                            }
+                            None => None,
+                        };
                         nexus
                             .share(
                                 context.registry(),
-                                &ShareNexus::new(&nexus_state, protocol, allowed_host),
+                                &ShareNexus::new(&nexus_state, protocol, allowed_host)
+                                    .with_dhchap(dhchap),
                             )
                             .await?;
                         nexus_spec
@@ -0,0 +1,90 @@
+use crate::controller::{
+    resources::{operations::ResourceCordon, OperationGuardArc},
+    task_poller::{PollContext, PollResult, PollTimer, PollerState, TaskPoller},
+};
+use agents::errors::SvcError;
+use chrono::Utc;
+use events_api::event::{EventAction, EventCategory, EventMessage, EventMeta, EventSource};
+use stor_port::types::v0::store::node::NodeSpec;
+
+/// Node cordon expiry reconciler.
+/// Removes the cordon and drain labels which were applied with a TTL once they expire.
+#[derive(Debug)]
+pub(super) struct NodeCordonExpiryReconciler {
+    counter: PollTimer,
+}
+impl NodeCordonExpiryReconciler {
+    /// Return a new `Self`.
+    pub(super) fn new() -> Self {
+        Self {
+            counter: PollTimer::from(1),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for NodeCordonExpiryReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let now = Utc::now();
+        let nodes = context
+            .specs()
+            .nodes()
+            .into_iter()
+            .filter(|node| !node.expired_labels(now).is_empty());
+        let mut results = vec![];
+
+        for node in nodes {
+            let mut node_spec = context.registry().specs().guarded_node(node.id()).await?;
+            results.push(remove_expired_labels(context, &mut node_spec).await);
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Remove the cordon and drain labels of the node which have expired, along with the labels they
+/// placed on the node's pools.
+#[tracing::instrument(skip(node_spec, context), level = "trace", fields(node.id = %node_spec.as_ref().id(), request.reconcile = true))]
+async fn remove_expired_labels(
+    context: &PollContext,
+    node_spec: &mut OperationGuardArc<NodeSpec>,
+) -> PollResult {
+    for label in node_spec.as_ref().expired_labels(Utc::now()) {
+        remove_expired_label(context, node_spec, &label).await?;
+        tracing::info!(
+            node.id = node_spec.as_ref().id().as_str(),
+            label = label.as_str(),
+            "Removed expired cordon label"
+        );
+        label_expiry_event(node_spec.as_ref(), &label).generate();
+    }
+    PollResult::Ok(PollerState::Idle)
+}
+
+/// Remove the expired label from the node and from its pools.
+async fn remove_expired_label(
+    context: &PollContext,
+    node_spec: &mut OperationGuardArc<NodeSpec>,
+    label: &str,
+) -> Result<(), SvcError> {
+    node_spec.uncordon_pools(context.registry(), label).await?;
+    node_spec
+        .uncordon(context.registry(), label.to_string())
+        .await?;
+    Ok(())
+}
+
+/// Get the event raised when a cordon or drain label of the node expires.
+fn label_expiry_event(node_spec: &NodeSpec, label: &str) -> EventMessage {
+    let event_source = EventSource::new(node_spec.id().to_string())
+        .with_state_change_data(format!("cordon label {label}"), "expired".to_string());
+    EventMessage {
+        category: EventCategory::Node as i32,
+        action: EventAction::StateChange as i32,
+        target: node_spec.id().to_string(),
+        metadata: Some(EventMeta::from_source(event_source)),
+    }
+}
@@ -0,0 +1,150 @@
+use crate::controller::{
+    resources::{
+        operations_helper::{GuardedOperationsHelper, OperationSequenceGuard},
+        OperationGuardArc,
+    },
+    task_poller::{
+        PollContext, PollEvent, PollResult, PollTimer, PollTriggerEvent, PollerState, TaskPoller,
+    },
+};
+use agents::errors::SvcError;
+use stor_port::types::v0::store::{
+    node::{NodeOperation, NodeSpec},
+    pool::{PoolOperation, PoolSpec},
+    replica::ReplicaOperation,
+};
+
+/// Node decommission reconciler.
+/// Removes the pools of the nodes which are being decommissioned, followed by the nodes
+/// themselves.
+#[derive(Debug)]
+pub(super) struct NodeDecommissionReconciler {
+    counter: PollTimer,
+}
+impl NodeDecommissionReconciler {
+    /// Return a new `Self`.
+    pub(super) fn new() -> Self {
+        Self {
+            counter: PollTimer::from(1),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for NodeDecommissionReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let nodes = context
+            .specs()
+            .nodes()
+            .into_iter()
+            .filter(|node| node.decommissioning());
+        let mut results = vec![];
+
+        for node in nodes {
+            let node_spec = context.registry().specs().guarded_node(node.id()).await?;
+            results.push(decommission_node(context, node_spec).await);
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+
+    async fn poll_event(&mut self, context: &PollContext) -> bool {
+        matches!(
+            context.event(),
+            PollEvent::TimedRun | PollEvent::Triggered(PollTriggerEvent::NodeDecommission)
+        )
+    }
+}
+
+/// Remove the pools of the decommissioning node and then the node itself.
+/// Replicas which are still owned by a volume are left for the volume to replace, and their
+/// pool is only removed once it has none left.
+#[tracing::instrument(skip(node_spec, context), level = "trace", fields(node.id = %node_spec.as_ref().id(), request.reconcile = true))]
+async fn decommission_node(
+    context: &PollContext,
+    node_spec: OperationGuardArc<NodeSpec>,
+) -> PollResult {
+    let registry = context.registry();
+    let node_id = node_spec.as_ref().id().clone();
+
+    // The io-engine has registered again, so its pools are left alone until it's gone.
+    if let Ok(node) = registry.node_wrapper(&node_id).await {
+        if node.read().await.is_online() {
+            tracing::debug!("Node is registered, waiting for it to go away");
+            return PollResult::Ok(PollerState::Idle);
+        }
+    }
+
+    let pools = context
+        .specs()
+        .pools_rsc()
+        .into_iter()
+        .filter(|pool| pool.lock().node == node_id)
+        .collect::<Vec<_>>();
+    let mut remaining = false;
+    for pool in pools {
+        let Ok(pool) = pool.operation_guard() else {
+            remaining = true;
+            continue;
+        };
+        if !remove_pool(context, pool).await? {
+            remaining = true;
+        }
+    }
+    if remaining {
+        return PollResult::Ok(PollerState::Busy);
+    }
+
+    node_spec.delete_spec::<NodeOperation>(registry).await?;
+    if let Some(node) = registry.nodes().write().await.remove(&node_id) {
+        node.write().await.watchdog_mut().disarm();
+    }
+    tracing::info!(node.id = node_id.as_str(), "Node decommissioned");
+    PollResult::Ok(PollerState::Idle)
+}
+
+/// Remove the pool of a decommissioning node along with its stale replicas.
+/// Returns false if the pool still has replicas which are owned by a volume, or which are busy.
+async fn remove_pool(
+    context: &PollContext,
+    pool: OperationGuardArc<PoolSpec>,
+) -> Result<bool, SvcError> {
+    let registry = context.registry();
+    let pool_id = pool.as_ref().id.clone();
+
+    let replicas = context
+        .specs()
+        .replicas()
+        .into_iter()
+        .filter(|replica| replica.lock().pool_name() == &pool_id)
+        .collect::<Vec<_>>();
+    let mut volumes = vec![];
+    let mut busy = false;
+    for replica in replicas {
+        if let Some(volume) = replica.lock().owners.volume() {
+            volumes.push(volume.to_string());
+            continue;
+        }
+        let Ok(replica) = replica.operation_guard() else {
+            busy = true;
+            continue;
+        };
+        replica.delete_spec::<ReplicaOperation>(registry).await?;
+    }
+    if !volumes.is_empty() {
+        tracing::warn!(
+            pool.id = pool_id.as_str(),
+            ?volumes,
+            "Pool of the decommissioning node still has replicas owned by volumes"
+        );
+    }
+    if !volumes.is_empty() || busy {
+        return Ok(false);
+    }
+
+    pool.delete_spec::<PoolOperation>(registry).await?;
+    Ok(true)
+}
@@ -0,0 +1,131 @@
+use crate::controller::{
+    reconciler::PollContext,
+    resources::{
+        operations::ResourceDrain, operations_helper::OperationSequenceGuard, OperationGuardArc,
+    },
+};
+use agents::errors::SvcError;
+use std::collections::{HashMap, HashSet};
+use stor_port::types::v0::{
+    store::node::{
+        CordonDrainState, DrainingVolumes, EvacuatedPool, NodeSpec, VolumeDrainProgress,
+    },
+    transport::{PoolId, VolumeId},
+};
+
+/// Move the volume replicas off the pools of an evacuating node, by draining each of its pools
+/// with the node's drain label. The pool reconciler then moves the replicas off the pools,
+/// placing the new replicas according to the volume topology and affinity group.
+/// The pools are recorded with the node before being drained, so that only their cordons are
+/// removed when the node is uncordoned.
+/// Returns true once all of the node's pools are drained.
+pub(super) async fn evacuate_node_pools(
+    context: &PollContext,
+    node_spec: &mut OperationGuardArc<NodeSpec>,
+) -> Result<bool, SvcError> {
+    let node_id = node_spec.as_ref().id().clone();
+    let Some(label) = drain_label(node_spec.as_ref()) else {
+        return Ok(false);
+    };
+
+    let pools = context
+        .specs()
+        .pools_rsc()
+        .into_iter()
+        .filter(|pool| pool.lock().node == node_id)
+        .collect::<Vec<_>>();
+    let mut drained = true;
+    for pool in &pools {
+        let spec = pool.lock().clone();
+        if spec.is_drained() {
+            continue;
+        }
+        drained = false;
+        if spec.is_draining() {
+            continue;
+        }
+        let Ok(mut pool) = pool.operation_guard() else {
+            continue;
+        };
+        let recorded = node_spec.as_ref().evacuated_pools().contains_key(&spec.id);
+        if !recorded && !spec.has_cordon_label(&label) {
+            let evacuated_pool = EvacuatedPool::new(spec.id.clone(), label.clone());
+            node_spec
+                .add_evacuated_pool(context.registry(), evacuated_pool)
+                .await?;
+        }
+        match pool.drain(context.registry(), label.clone()).await {
+            Ok(_) => {
+                tracing::info!(
+                    node.id = node_id.as_str(),
+                    pool.id = spec.id.as_str(),
+                    "Draining pool of the evacuating node"
+                );
+            }
+            Err(error) => {
+                tracing::error!(
+                    %error,
+                    node.id = node_id.as_str(),
+                    pool.id = spec.id.as_str(),
+                    "Failed to drain pool of the evacuating node"
+                );
+            }
+        }
+    }
+
+    let pools = pools
+        .iter()
+        .map(|pool| pool.lock().id.clone())
+        .collect::<HashSet<_>>();
+    update_volumes_drain_progress(context, node_spec, &pools).await?;
+    Ok(drained)
+}
+
+/// Update the per-volume progress of the replicas being moved off the given pools.
+async fn update_volumes_drain_progress(
+    context: &PollContext,
+    node_spec: &mut OperationGuardArc<NodeSpec>,
+    pools: &HashSet<PoolId>,
+) -> Result<(), SvcError> {
+    let mut remaining = HashMap::<VolumeId, u64>::new();
+    for replica in context.specs().replicas_cloned() {
+        if !pools.contains(replica.pool_name()) {
+            continue;
+        }
+        if let Some(volume) = replica.owners.volume() {
+            *remaining.entry(volume.clone()).or_default() += 1;
+        }
+    }
+
+    let mut progress = node_spec.as_ref().volumes_drain_progress().clone();
+    for (volume, volume_progress) in progress.iter_mut() {
+        let now_remaining = remaining.remove(volume).unwrap_or_default();
+        volume_progress.moved += volume_progress.remaining.saturating_sub(now_remaining);
+        volume_progress.remaining = now_remaining;
+    }
+    progress.extend(remaining.into_iter().map(|(volume, remaining)| {
+        let volume_progress = VolumeDrainProgress {
+            remaining,
+            moved: 0,
+        };
+        (volume, volume_progress)
+    }));
+
+    if &progress != node_spec.as_ref().volumes_drain_progress() {
+        node_spec
+            .set_volumes_drain_progress(
+                context.registry(),
+                DrainingVolumes::with_progress(progress),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Get the drain label which the node's pools are drained with.
+fn drain_label(node_spec: &NodeSpec) -> Option<String> {
+    match node_spec.cordon_drain_state() {
+        Some(CordonDrainState::Draining(state)) => state.drainlabels.first().cloned(),
+        _ => None,
+    }
+}
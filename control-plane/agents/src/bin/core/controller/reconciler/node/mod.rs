@@ -1,12 +1,19 @@
+mod cordon_expiry;
+mod decommission;
+mod evacuate;
 mod nexus;
 mod snapshot;
 
 use crate::controller::{
-    reconciler::node::{nexus::NodeNexusReconciler, snapshot::NodeSnapshotGarbageCollector},
+    reconciler::node::{
+        cordon_expiry::NodeCordonExpiryReconciler, decommission::NodeDecommissionReconciler,
+        nexus::NodeNexusReconciler, snapshot::NodeSnapshotGarbageCollector,
+    },
     task_poller::{PollContext, PollPeriods, PollResult, PollTimer, TaskPoller},
 };
 
-/// Node reconciler loop which moves nexuses from draining nodes.
+/// Node reconciler loop which moves nexuses, and replicas when evacuating, from draining nodes,
+/// removes the cordon labels which have expired and completes the decommission of nodes.
 #[derive(Debug)]
 pub(crate) struct NodeReconciler {
     counter: PollTimer,
@@ -20,6 +27,8 @@ impl NodeReconciler {
             poll_targets: vec![
                 Box::new(NodeNexusReconciler::new()),
                 Box::new(NodeSnapshotGarbageCollector::new()),
+                Box::new(NodeCordonExpiryReconciler::new()),
+                Box::new(NodeDecommissionReconciler::new()),
             ],
         }
     }
@@ -1,5 +1,5 @@
 use crate::controller::{
-    reconciler::{PollContext, TaskPoller},
+    reconciler::{node::evacuate, PollContext, TaskPoller},
     resources::{
         operations::{ResourceDrain, ResourcePublishing, ResourceShutdownOperations},
         operations_helper::OperationSequenceGuard,
@@ -225,7 +225,19 @@ async fn check_and_drain_node(
         // If that is not the case, the next reconciliation loop will check again.
         find_shutdown_volumes(context, node_spec).await?;
 
+        // Once the targets are moved, the replicas are moved off the pools of evacuating nodes.
+        let pools_drained = match node_spec.as_ref().evacuate() {
+            true => evacuate::evacuate_node_pools(context, node_spec).await?,
+            false => true,
+        };
+
         match node_spec.node_draining_volume_count().await {
+            0 if !pools_drained => {
+                tracing::info!(
+                    node.id = node_spec.as_ref().id().as_str(),
+                    "Volume replicas remain on the node's pools"
+                );
+            }
             // if there are no more shutdown volumes, change the node state to "drained"
             0 => {
                 if let Err(error) = node_spec.set_drained(context.registry()).await {
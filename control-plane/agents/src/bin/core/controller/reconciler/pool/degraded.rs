@@ -0,0 +1,79 @@
+use crate::controller::{
+    pool_errors::{PoolErrorKind, PoolErrors},
+    resources::{
+        operations::{ResourceCordon, ResourceDrain},
+        OperationGuardArc, TraceSpan,
+    },
+    task_poller::{PollContext, PollResult, PollerState},
+};
+use events_api::event::{EventAction, EventCategory, EventMessage, EventMeta, EventSource};
+use std::collections::HashMap;
+use stor_port::types::v0::store::pool::PoolSpec;
+
+/// The cordon label applied to the pools which are marked as degraded.
+pub(crate) const DEGRADED_LABEL: &str = "io-engine/degraded";
+
+/// Mark the pool as degraded once its error signals within the error window reach the threshold.
+/// A degraded pool is cordoned, excluding it from replica placement, or drained if so configured.
+/// The marking is cleared by uncordoning the pool's degraded label.
+#[tracing::instrument(skip(pool, context), level = "trace", fields(pool.id = %pool.id(), request.reconcile = true))]
+pub(super) async fn degraded_pool_reconciler(
+    pool: &mut OperationGuardArc<PoolSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let pool_errors = context.registry().pool_errors();
+    let threshold = pool_errors.threshold();
+    if threshold == 0 || !pool.as_ref().status().created() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let errors = pool_errors.recent(pool.id());
+    if PoolErrors::weight(&errors) < threshold {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    if pool.as_ref().has_cordon_label(DEGRADED_LABEL) {
+        pool_errors.clear(pool.id());
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    let registry = context.registry();
+    let result = match pool_errors.drain() {
+        true => pool.drain(registry, DEGRADED_LABEL.to_string()).await,
+        false => pool.cordon(registry, DEGRADED_LABEL.to_string()).await,
+    };
+    match result {
+        Ok(_) => {
+            pool.as_ref().warn_span(|| {
+                tracing::warn!(
+                    ?errors,
+                    drain = pool_errors.drain(),
+                    "Marked the pool as degraded"
+                )
+            });
+            pool_errors.clear(pool.id());
+            degraded_event(pool.as_ref(), &errors).generate();
+            PollResult::Ok(PollerState::Idle)
+        }
+        Err(error) => {
+            pool.as_ref()
+                .error_span(|| tracing::error!(%error, "Failed to mark the pool as degraded"));
+            Err(error)
+        }
+    }
+}
+
+/// Get the event raised when the pool is marked as degraded.
+fn degraded_event(pool: &PoolSpec, errors: &HashMap<PoolErrorKind, usize>) -> EventMessage {
+    let errors = errors
+        .iter()
+        .map(|(kind, count)| format!("{count} {kind}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let event_source = EventSource::new(pool.node.to_string())
+        .with_state_change_data(format!("errors: {errors}"), DEGRADED_LABEL.to_string());
+    EventMessage {
+        category: EventCategory::Pool as i32,
+        action: EventAction::StateChange as i32,
+        target: pool.id.to_string(),
+        metadata: Some(EventMeta::from_source(event_source)),
+    }
+}
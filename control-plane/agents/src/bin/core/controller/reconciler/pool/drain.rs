@@ -0,0 +1,169 @@
+use crate::controller::{
+    registry::Registry,
+    resources::{
+        operations::{ResourceDrain, ResourceReplicas},
+        operations_helper::OperationSequenceGuard,
+        OperationGuardArc, TraceSpan,
+    },
+    task_poller::{PollContext, PollResult, PollerState},
+};
+use agents::errors::SvcError;
+use itertools::Itertools;
+use stor_port::types::v0::{
+    store::{
+        pool::{DrainingReplica, PoolSpec},
+        volume::VolumeSpec,
+    },
+    transport::{ReplicaId, SetVolumeReplica, VolumeStatus},
+};
+
+/// Move the volume replicas off a draining pool.
+/// Each replica is moved in two steps:
+/// 1. the replica count of its volume is increased, creating a replacement replica elsewhere
+/// 2. once the volume target has rebuilt the replacement, the replica is removed from the volume
+/// The replicas being moved are persisted with the pool spec, so the drain resumes from where it
+/// left off following a restart. Replicas which are not owned by a volume are reported rather
+/// than moved, as are the volumes which are not published and so cannot rebuild a replacement.
+#[tracing::instrument(skip(pool, context), level = "trace", fields(pool.id = %pool.id(), request.reconcile = true))]
+pub(super) async fn drain_pool_reconciler(
+    pool: &mut OperationGuardArc<PoolSpec>,
+    context: &PollContext,
+) -> PollResult {
+    if !pool.as_ref().is_draining() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let registry = context.registry();
+    let pool_id = pool.id().clone();
+    let initial_progress = pool.as_ref().drain_progress.clone().unwrap_or_default();
+    let mut progress = initial_progress.clone();
+
+    let replicas = context
+        .specs()
+        .replicas_cloned()
+        .into_iter()
+        .filter(|replica| replica.pool_name() == &pool_id)
+        .collect::<Vec<_>>();
+
+    // Replicas which are no longer on the pool have been moved.
+    let moved = progress
+        .moving
+        .keys()
+        .filter(|id| !replicas.iter().any(|replica| &replica.uuid == *id))
+        .cloned()
+        .collect::<Vec<_>>();
+    for replica in moved {
+        progress.moving.remove(&replica);
+        progress.moved += 1;
+    }
+    progress.unowned = replicas
+        .iter()
+        .filter(|replica| replica.owners.volume().is_none())
+        .map(|replica| replica.uuid.clone())
+        .collect();
+    progress
+        .moving
+        .retain(|id, _| !progress.unowned.contains(id));
+
+    // Record the replica count of the volumes before moving their replicas, so the count can be
+    // restored should we have to resume the drain.
+    for replica in &replicas {
+        let Some(volume) = replica.owners.volume() else {
+            continue;
+        };
+        if progress.moving.contains_key(&replica.uuid) {
+            continue;
+        }
+        if let Ok(spec) = context.specs().volume_clone(volume) {
+            progress.moving.insert(
+                replica.uuid.clone(),
+                DrainingReplica {
+                    volume: volume.clone(),
+                    replicas: spec.num_replicas,
+                },
+            );
+        }
+    }
+    // The replacement replica can only be rebuilt by the volume target.
+    progress.blocked = progress
+        .moving
+        .values()
+        .map(|moving| &moving.volume)
+        .filter(|volume| {
+            matches!(context.specs().volume_clone(volume), Ok(spec) if spec.target().is_none())
+        })
+        .unique()
+        .sorted_by(|a, b| a.as_str().cmp(b.as_str()))
+        .cloned()
+        .collect();
+    if progress != initial_progress {
+        pool.set_drain_progress(registry, progress.clone()).await?;
+    }
+
+    if progress.moving.is_empty() {
+        pool.as_ref().info_span(|| {
+            tracing::info!(
+                pool.moved = progress.moved,
+                pool.unowned = progress.unowned.len(),
+                "Pool drained"
+            )
+        });
+        pool.set_drained(registry).await?;
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    for (replica, moving) in &progress.moving {
+        if progress.blocked.contains(&moving.volume) {
+            continue;
+        }
+        let Some(volume) = context.specs().volume_rsc(&moving.volume) else {
+            continue;
+        };
+        // The volume may be busy with other operations, in which case we'll retry later.
+        let Ok(mut volume) = volume.operation_guard() else {
+            continue;
+        };
+        if let Err(error) = move_volume_replica(&mut volume, replica, moving, registry).await {
+            pool.as_ref().warn_span(|| {
+                tracing::warn!(
+                    replica.uuid = replica.as_str(),
+                    volume.uuid = moving.volume.as_str(),
+                    %error,
+                    "Failed to move replica off the draining pool"
+                )
+            });
+        }
+    }
+
+    PollResult::Ok(PollerState::Busy)
+}
+
+/// Take the next step in moving the given replica off its pool.
+async fn move_volume_replica(
+    volume: &mut OperationGuardArc<VolumeSpec>,
+    replica: &ReplicaId,
+    moving: &DrainingReplica,
+    registry: &Registry,
+) -> Result<(), SvcError> {
+    // The volume may have been unpublished since, it's reported as blocked on the next poll.
+    if volume.as_ref().target().is_none() {
+        return Ok(());
+    }
+
+    let num_replicas = volume.as_ref().num_replicas;
+    if num_replicas <= moving.replicas {
+        let request = SetVolumeReplica::new(moving.volume.clone(), num_replicas + 1);
+        return volume.set_replica(registry, &request).await;
+    }
+
+    let state = registry.volume_state(&moving.volume).await?;
+    let rebuilt = match &state.target {
+        Some(target) => {
+            state.status == VolumeStatus::Online && target.children.len() == num_replicas as usize
+        }
+        None => false,
+    };
+    if rebuilt {
+        volume.remove_volume_replica(registry, replica).await?;
+    }
+    Ok(())
+}
@@ -1,4 +1,6 @@
 mod capacity;
+mod degraded;
+mod drain;
 
 use crate::controller::{
     reconciler::{GarbageCollect, ReCreate},
@@ -19,6 +21,8 @@ use tracing::Instrument;
 
 /// Pool Reconciler loop which:
 /// 1. recreates pools which are not present following an io-engine restart
+/// 2. moves the volume replicas off draining pools
+/// 3. marks the pools with too many recent errors as degraded
 #[derive(Debug)]
 pub(crate) struct PoolReconciler {
     counter: PollTimer,
@@ -53,6 +57,8 @@ impl TaskPoller for PoolReconciler {
             results.push(Self::squash_results(vec![
                 pool.garbage_collect(context).await,
                 pool.recreate_state(context).await,
+                degraded::degraded_pool_reconciler(&mut pool, context).await,
+                drain::drain_pool_reconciler(&mut pool, context).await,
             ]))
         }
         capacity::remove_larger_replicas(context.registry()).await;
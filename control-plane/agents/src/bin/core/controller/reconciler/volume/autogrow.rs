@@ -0,0 +1,112 @@
+use crate::{
+    controller::{
+        reconciler::{PollContext, TaskPoller},
+        resources::{
+            operations::ResourceResize, operations_helper::OperationSequenceGuard, ResourceMutex,
+            TraceSpan,
+        },
+        task_poller::{PollResult, PollerState},
+    },
+    volume::resizeable_replicas,
+};
+
+use stor_port::types::v0::{
+    store::volume::VolumeSpec,
+    transport::{ResizeVolume, VolumeStatus},
+};
+
+/// Volume AutoGrow reconciler.
+/// Expands thin provisioned volumes which have an automatic expansion policy, once their
+/// allocated size crosses the policy's threshold.
+/// Only the volume target is expanded here: on k8s the csi-controller then updates the PVC, which
+/// gets the node plugin to expand the filesystem.
+#[derive(Debug)]
+pub(super) struct VolumeAutoGrowReconciler {}
+impl VolumeAutoGrowReconciler {
+    /// Return a new `Self`
+    pub(super) fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for VolumeAutoGrowReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        let volumes = context.specs().volumes_rsc();
+        for mut volume in volumes {
+            if volume.lock().autogrow().is_none() {
+                continue;
+            }
+            results.push(volume_autogrow_reconcile(&mut volume, context).await);
+        }
+        Self::squash_results(results)
+    }
+}
+
+#[tracing::instrument(level = "debug", skip(context, volume_spec), fields(volume.uuid = %volume_spec.uuid(), request.reconcile = true))]
+async fn volume_autogrow_reconcile(
+    volume_spec: &mut ResourceMutex<VolumeSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let mut volume = match volume_spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return PollResult::Ok(PollerState::Busy),
+    };
+    let Some(autogrow) = volume.as_ref().autogrow().cloned() else {
+        return PollResult::Ok(PollerState::Idle);
+    };
+    if !volume.as_ref().status.created() || !volume.as_ref().thin {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    // Volumes with snapshots cannot be resized, so there's no point in retrying until the
+    // snapshots are gone.
+    if !context.specs().snapshots_by_vol(volume.uuid()).is_empty() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    let volume_state = context.registry().volume_state(volume.uuid()).await?;
+    if volume_state.status != VolumeStatus::Online {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let Some(usage) = volume_state.usage else {
+        return PollResult::Ok(PollerState::Idle);
+    };
+    let size = volume.as_ref().size;
+    let Some(new_size) = autogrow.next_size(size, usage.allocated_replica()) else {
+        return PollResult::Ok(PollerState::Idle);
+    };
+
+    // Likewise, don't attempt the resize until all replicas' pools have room to grow.
+    if let Err(error) = resizeable_replicas(volume.as_ref(), context.registry(), new_size).await {
+        volume.debug_span(|| {
+            tracing::debug!(
+                error = %error,
+                volume.new_size = new_size,
+                "Volume cannot be expanded at the moment"
+            )
+        });
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    volume.info_span(|| {
+        tracing::info!(
+            volume.size = size,
+            volume.allocated = usage.allocated_replica(),
+            volume.new_size = new_size,
+            "Allocation crossed the autogrow threshold, expanding the volume"
+        )
+    });
+    let request = ResizeVolume::new(volume.uuid().clone(), new_size, None);
+    if let Err(error) = volume.resize(context.registry(), &request).await {
+        volume.warn_span(|| {
+            tracing::warn!(
+                error = %error,
+                volume.new_size = new_size,
+                "Failed to automatically expand the volume"
+            )
+        });
+        return Err(error);
+    }
+    PollResult::Ok(PollerState::Idle)
+}
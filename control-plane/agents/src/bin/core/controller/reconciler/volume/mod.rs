@@ -1,3 +1,4 @@
+mod autogrow;
 mod garbage_collector;
 mod hot_spare;
 mod nexus;
@@ -5,13 +6,14 @@ mod nexus;
 use crate::controller::task_poller::{PollContext, PollPeriods, PollResult, PollTimer, TaskPoller};
 
 use crate::controller::reconciler::volume::{
-    garbage_collector::GarbageCollector, hot_spare::HotSpareReconciler,
-    nexus::VolumeNexusReconciler,
+    autogrow::VolumeAutoGrowReconciler, garbage_collector::GarbageCollector,
+    hot_spare::HotSpareReconciler, nexus::VolumeNexusReconciler,
 };
 
 /// Volume Reconciler loop which:
 /// 1. does the replica replacement
-/// 2. volume garbage collection.
+/// 2. volume garbage collection
+/// 3. automatic volume expansion.
 #[derive(Debug)]
 pub(crate) struct VolumeReconciler {
     counter: PollTimer,
@@ -26,6 +28,7 @@ impl VolumeReconciler {
                 Box::new(HotSpareReconciler::new()),
                 Box::new(GarbageCollector::new()),
                 Box::new(VolumeNexusReconciler::new()),
+                Box::new(VolumeAutoGrowReconciler::new()),
             ],
         }
     }
@@ -16,11 +16,15 @@
 use super::{resources::operations_helper::*, wrapper::NodeWrapper};
 use crate::{
     controller::{
+        nvmf_auth::NvmfAuthSealer,
+        operations::AsyncOperations,
+        pool_errors::PoolErrors,
         reconciler::ReconcilerControl,
         task_poller::{PollEvent, PollTriggerEvent},
+        usage::UsageHistories,
         wrapper::InternalOps,
     },
-    ThinArgs,
+    NvmfAuthArgs, PoolErrorArgs, ThinArgs, UsageArgs,
 };
 use agents::errors::SvcError;
 use std::{
@@ -98,6 +102,14 @@ pub(crate) struct RegistryInner<S: Store> {
     legacy_prefix_present: bool,
     /// Thin provisioning parameters.
     thin_args: ThinArgs,
+    /// Usage history of the volumes and pools.
+    usage: UsageHistories,
+    /// Recent error signals of the pools.
+    pool_errors: PoolErrors,
+    /// Seals the volume targets' secrets before they're persisted.
+    nvmf_auth: NvmfAuthSealer,
+    /// Long-running operations of the REST servers.
+    async_operations: AsyncOperations,
     /// Check if the HA feature is enabled.
     ha_disabled: bool,
     /// Etcd max page size.
@@ -122,6 +134,10 @@ impl Registry {
         create_volume_limit: usize,
         host_acl: Vec<HostAccessControl>,
         thin_args: ThinArgs,
+        usage_args: UsageArgs,
+        pool_error_args: PoolErrorArgs,
+        nvmf_auth_args: &NvmfAuthArgs,
+        async_operation_ttl: std::time::Duration,
         ha_enabled: bool,
         etcd_max_page_size: i64,
     ) -> Result<Self, SvcError> {
@@ -173,6 +189,10 @@ impl Registry {
                 host_acl,
                 legacy_prefix_present,
                 thin_args,
+                usage: UsageHistories::new(usage_args),
+                pool_errors: PoolErrors::new(pool_error_args),
+                nvmf_auth: NvmfAuthSealer::new(nvmf_auth_args)?,
+                async_operations: AsyncOperations::new(async_operation_ttl),
                 ha_disabled: ha_enabled,
                 etcd_max_page_size,
             }),
@@ -257,6 +277,26 @@ impl Registry {
         &self.thin_args
     }
 
+    /// Get the usage history of the volumes and pools.
+    pub(crate) fn usage(&self) -> &UsageHistories {
+        &self.usage
+    }
+
+    /// Get the recent error signals of the pools.
+    pub(crate) fn pool_errors(&self) -> &PoolErrors {
+        &self.pool_errors
+    }
+
+    /// Get the sealer of the volume targets' secrets.
+    pub(crate) fn nvmf_auth(&self) -> &NvmfAuthSealer {
+        &self.nvmf_auth
+    }
+
+    /// Get the long-running operations of the REST servers.
+    pub(crate) fn async_operations(&self) -> &AsyncOperations {
+        &self.async_operations
+    }
+
     /// Get the `CoreRegistryConfig`.
     pub(crate) fn config(&self) -> parking_lot::RwLockReadGuard<CoreRegistryConfig> {
         self.config.read()
@@ -393,6 +433,14 @@ impl Registry {
             registry.poller().await;
         });
         let registry = self.clone();
+        tokio::spawn(async move {
+            registry.usage_sampler().await;
+        });
+        let registry = self.clone();
+        tokio::spawn(async move {
+            registry.async_operations_purger().await;
+        });
+        let registry = self.clone();
         self.reconciler.start(registry).await;
     }
 
@@ -416,6 +464,9 @@ impl Registry {
                 self.etcd_max_page_size,
             )
             .await?;
+        self.async_operations
+            .init(store.deref_mut(), self.etcd_max_page_size)
+            .await?;
         Ok(())
     }
 
@@ -500,15 +551,17 @@ impl Registry {
         nodes: &[String],
     ) -> Vec<InitiatorAC> {
         match self.host_acl.contains(&req) {
-            true => nodes
-                .iter()
-                .map(|nodename| {
-                    InitiatorAC::new(nodename.clone(), HostNqn::from_nodename(nodename))
-                })
-                .collect(),
+            true => Self::host_acl_enforced(nodes),
             false => vec![],
         }
     }
+    /// Get the host access list for the given nodenames, even if host access control is disabled.
+    pub(crate) fn host_acl_enforced(nodes: &[String]) -> Vec<InitiatorAC> {
+        nodes
+            .iter()
+            .map(|nodename| InitiatorAC::new(nodename.clone(), HostNqn::from_nodename(nodename)))
+            .collect()
+    }
 
     /// Register a app node (ex: a csi node) with the control-plane.
     pub(crate) async fn register_app_node(
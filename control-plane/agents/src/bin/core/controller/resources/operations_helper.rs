@@ -96,6 +96,11 @@ pub(crate) trait GuardedOperationsHelper:
         UpdateOp = Self::UpdateOp,
     >;
 
+    /// Check that the spec is at the `expected` revision, if one is specified.
+    fn validate_revision(&self, expected: Option<u64>) -> Result<(), SvcError> {
+        self.lock().validate_revision(expected)
+    }
+
     /// Start a create operation and attempt to log the transaction to the store.
     /// In case of error, the log is undone and an error is returned.
     /// Also updates it's inner value to the operation.
@@ -735,6 +740,21 @@ pub(crate) trait SpecOperationsHelper:
         self.start_update_op(registry, state, operation).await
     }
 
+    /// Check that the object is at the `expected` revision, if one is specified.
+    fn validate_revision(&self, expected: Option<u64>) -> Result<(), SvcError> {
+        match (expected, self.spec_revision()) {
+            (Some(expected), Some(revision)) if expected != revision => {
+                Err(SvcError::RevisionMismatch {
+                    kind: self.kind(),
+                    id: self.uuid_str(),
+                    expected,
+                    revision,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Check if the object is free to be modified or if it's still busy
     fn busy(&self) -> Result<(), SvcError> {
         if self.dirty() {
@@ -770,6 +790,10 @@ pub(crate) trait SpecOperationsHelper:
     fn kind(&self) -> ResourceKind;
     /// Get the UUID as a string (for log messages)
     fn uuid_str(&self) -> String;
+    /// Get the revision of the object, if it's revisioned
+    fn spec_revision(&self) -> Option<u64> {
+        None
+    }
     /// Get the state of the object
     fn status(&self) -> SpecStatus<Self::Status>;
     /// Set the state of the object
@@ -16,9 +16,12 @@ use crate::controller::{
 use std::ops::Deref;
 use stor_port::types::v0::{
     store::{nexus::NexusSpec, nexus_persistence::NexusInfo, volume::VolumeSpec},
-    transport::{NexusId, NodeId, VolumeId},
+    transport::{AppNodeId, NexusId, NodeId, TargetPlacement, VolumeId},
 };
 
+/// The well-known label which holds the topology zone of a node.
+const ZONE_LABEL: &str = "topology.kubernetes.io/zone";
+
 /// Request to retrieve a list of healthy nexus children which is used for nexus creation
 /// used by `CreateVolumeNexus`.
 #[derive(Clone)]
@@ -239,19 +242,49 @@ impl ResourceFilter for CreateVolumeNexus {
 #[derive(Clone)]
 pub(crate) struct GetSuitableNodes {
     spec: VolumeSpec,
+    app_nodes: Vec<String>,
 }
 
 impl From<&VolumeSpec> for GetSuitableNodes {
     fn from(spec: &VolumeSpec) -> Self {
-        Self { spec: spec.clone() }
+        let app_nodes = spec
+            .config()
+            .as_ref()
+            .map(|config| config.frontend().node_names())
+            .unwrap_or_default();
+        Self {
+            spec: spec.clone(),
+            app_nodes,
+        }
+    }
+}
+
+impl GetSuitableNodes {
+    /// Use the given application nodes rather than the frontend nodes of the current target.
+    pub(crate) fn with_app_nodes(mut self, app_nodes: Vec<String>) -> Self {
+        self.app_nodes = app_nodes;
+        self
     }
 }
 
+/// Locality of a node in relation to the application nodes of a volume.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) enum AppNodeLocality {
+    /// The node is an application node.
+    Local,
+    /// The node is in the same topology zone as an application node.
+    Zone,
+    /// The node is neither.
+    Remote,
+}
+
 /// `GetSuitableNodes` context for filtering and sorting.
 #[derive(Clone)]
 pub(crate) struct GetSuitableNodesContext {
     registry: Registry,
     spec: VolumeSpec,
+    app_nodes: Vec<NodeId>,
+    app_zones: Vec<String>,
 }
 
 impl GetSuitableNodesContext {
@@ -259,6 +292,26 @@ impl GetSuitableNodesContext {
     pub(crate) fn registry(&self) -> &Registry {
         &self.registry
     }
+    /// Get the locality of the given node in relation to the application nodes.
+    /// Every node is `AppNodeLocality::Remote` unless the volume prefers the app node placement.
+    pub(crate) fn app_node_locality(&self, node: &NodeId) -> AppNodeLocality {
+        if self.spec.target_placement() != TargetPlacement::AppNode {
+            return AppNodeLocality::Remote;
+        }
+        if self.app_nodes.contains(node) {
+            return AppNodeLocality::Local;
+        }
+        let zone = self
+            .registry
+            .specs()
+            .node(node)
+            .ok()
+            .and_then(|spec| spec.labels().get(ZONE_LABEL).cloned());
+        match zone {
+            Some(zone) if self.app_zones.contains(&zone) => AppNodeLocality::Zone,
+            _ => AppNodeLocality::Remote,
+        }
+    }
 }
 
 impl Deref for GetSuitableNodesContext {
@@ -305,9 +358,22 @@ impl NexusTargetNode {
         preferred_node: &Option<NodeId>,
     ) -> Self {
         let request = request.into();
+        let app_zones = request
+            .app_nodes
+            .iter()
+            .map(|node| AppNodeId::from(node.as_str()))
+            .filter_map(|node| registry.specs().app_node_spec(&node)?.labels)
+            .filter_map(|mut labels| labels.remove(ZONE_LABEL))
+            .collect();
         let request = GetSuitableNodesContext {
             registry: registry.clone(),
-            spec: request.spec.clone(),
+            app_nodes: request
+                .app_nodes
+                .iter()
+                .map(|node| NodeId::from(node.as_str()))
+                .collect(),
+            app_zones,
+            spec: request.spec,
         };
         let mut node_ag_nexus_count_map: Option<HashMap<NodeId, u64>> = None;
         if let Some(affinity_group) = &request.affinity_group {
@@ -340,7 +406,9 @@ impl NexusTargetNode {
 
     /// Get `Self` with a default set of filters for nodes following the criteria (any order):
     /// 1. The target node should be online.
-    /// 2. Give preference to nodes which have lesser number of active nexuses, for
+    /// 2. Give preference to the application node, or a node in its zone, if the volume prefers
+    /// the app node placement.
+    /// 3. Give preference to nodes which have lesser number of active nexuses, for
     /// proper distribution.
     pub(crate) async fn builder_with_defaults(
         request: impl Into<GetSuitableNodes>,
@@ -353,6 +421,6 @@ impl NexusTargetNode {
             .filter(NodeFilters::cordoned)
             .filter(NodeFilters::current_target)
             .filter(NodeFilters::no_targets)
-            .sort(NodeSorters::number_targets)
+            .sort_ctx(NodeSorters::app_node_locality)
     }
 }
@@ -124,4 +124,16 @@ impl NodeSorters {
                     .cmp(&b.node_wrapper().nexus_count())
             })
     }
+    /// Sort nodes by their locality to the application nodes, giving preference to an application
+    /// node and then to a node in the same topology zone, and then as per `Self::number_targets`.
+    pub(crate) fn app_node_locality(
+        request: &GetSuitableNodesContext,
+        a: &NodeItem,
+        b: &NodeItem,
+    ) -> std::cmp::Ordering {
+        request
+            .app_node_locality(a.node_wrapper().id())
+            .cmp(&request.app_node_locality(b.node_wrapper().id()))
+            .then_with(|| Self::number_targets(a, b))
+    }
 }
@@ -70,9 +70,15 @@ impl PoolBaseFilters {
             false => item.pool.free_space() > request.size,
         }
     }
-    /// Should only attempt to use usable (not faulted) pools.
-    pub(crate) fn usable(_: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
-        item.pool.status != PoolStatus::Faulted && item.pool.status != PoolStatus::Unknown
+    /// Should only attempt to use usable (not faulted or cordoned) pools.
+    pub(crate) fn usable(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        let cordoned = match request.registry().specs().pool(&item.pool.id) {
+            Ok(spec) => spec.cordoned(),
+            Err(_) => false,
+        };
+        !cordoned
+            && item.pool.status != PoolStatus::Faulted
+            && item.pool.status != PoolStatus::Unknown
     }
 
     /// Should only attempt to use pools having specific creation label if topology has it.
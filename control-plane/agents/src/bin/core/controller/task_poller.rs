@@ -28,6 +28,8 @@ pub(crate) enum PollTriggerEvent {
     Start,
     /// A node needs to be drained.
     NodeDrain,
+    /// A node is being decommissioned.
+    NodeDecommission,
 }
 
 /// State of a poller.
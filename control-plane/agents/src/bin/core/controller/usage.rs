@@ -0,0 +1,187 @@
+use crate::{controller::registry::Registry, UsageArgs};
+use agents::errors::SvcError;
+use std::collections::{HashMap, HashSet};
+use stor_port::types::v0::{
+    store::{
+        definitions::ObjectKey,
+        usage::{UsageSeries, UsageSeriesBounds, UsageSeriesKey},
+    },
+    transport::{UsageHistory, UsageResource, UsageSample},
+};
+
+/// Bounded usage history of all volumes and pools, sampled periodically by the `Registry`.
+#[derive(Debug)]
+pub(crate) struct UsageHistories {
+    args: UsageArgs,
+    series: parking_lot::Mutex<HashMap<UsageResource, UsageSeries>>,
+}
+
+impl UsageHistories {
+    /// Return a new empty `Self` with the given parameters.
+    pub(crate) fn new(args: UsageArgs) -> Self {
+        Self {
+            args,
+            series: Default::default(),
+        }
+    }
+    /// The period at which the usage is sampled.
+    pub(crate) fn sample_period(&self) -> std::time::Duration {
+        self.args.usage_sample_period.into()
+    }
+    fn bounds(&self) -> UsageSeriesBounds {
+        UsageSeriesBounds {
+            samples: self.args.usage_samples,
+            rollup_samples: self.args.usage_rollup_samples.max(1),
+            rollups: self.args.usage_rollups,
+        }
+    }
+    /// Get the usage history of the given resource, if any.
+    pub(crate) fn history(&self, resource: &UsageResource) -> Option<UsageHistory> {
+        self.series.lock().get(resource).map(UsageSeries::history)
+    }
+    /// Check if the given resource is already tracked.
+    fn tracked(&self, resource: &UsageResource) -> bool {
+        self.series.lock().contains_key(resource)
+    }
+    /// Start tracking the given series, unless it's already tracked.
+    fn track(&self, series: UsageSeries) {
+        self.series
+            .lock()
+            .entry(series.resource().clone())
+            .or_insert(series);
+    }
+    /// Record the given samples, returning the series which completed a new rollup.
+    fn record(&self, samples: Vec<(UsageResource, UsageSample)>) -> Vec<UsageSeries> {
+        let bounds = self.bounds();
+        let mut series = self.series.lock();
+        samples
+            .into_iter()
+            .filter_map(|(resource, sample)| {
+                let entry = series
+                    .entry(resource.clone())
+                    .or_insert_with(|| UsageSeries::new(resource));
+                entry.add(sample, &bounds).then(|| entry.clone())
+            })
+            .collect()
+    }
+    /// Stop tracking resources which no longer exist, returning them.
+    fn retain(&self, existing: &HashSet<UsageResource>) -> Vec<UsageResource> {
+        let mut series = self.series.lock();
+        let removed = series
+            .keys()
+            .filter(|resource| !existing.contains(resource))
+            .cloned()
+            .collect::<Vec<_>>();
+        for resource in &removed {
+            series.remove(resource);
+        }
+        removed
+    }
+}
+
+impl Registry {
+    /// Sample the usage of all volumes and pools every sample period.
+    pub(crate) async fn usage_sampler(&self) {
+        loop {
+            self.sample_usage().await;
+            tokio::time::sleep(self.usage().sample_period()).await;
+        }
+    }
+
+    /// Get the usage history of the given resource.
+    pub(crate) async fn usage_history(
+        &self,
+        resource: &UsageResource,
+    ) -> Result<UsageHistory, SvcError> {
+        if let Some(history) = self.usage().history(resource) {
+            return Ok(history);
+        }
+        // it may simply not have been sampled yet
+        match resource {
+            UsageResource::Volume(id) => {
+                self.specs()
+                    .volume_rsc(id)
+                    .ok_or(SvcError::VolumeNotFound {
+                        vol_id: id.to_string(),
+                    })?;
+            }
+            UsageResource::Pool(id) => {
+                if self.specs().pool_rsc(id).is_none() && !self.has_pool_state(id).await {
+                    return Err(SvcError::PoolNotFound {
+                        pool_id: id.clone(),
+                    });
+                }
+            }
+        }
+        Ok(UsageHistory::default())
+    }
+
+    async fn sample_usage(&self) {
+        let mut existing = HashSet::new();
+        let mut samples = vec![];
+
+        for volume in self.specs().volumes() {
+            existing.insert(UsageResource::Volume(volume.uuid));
+        }
+        for pool in self.specs().pools() {
+            existing.insert(UsageResource::Pool(pool.id));
+        }
+        for volume in self.volumes().await {
+            if let Some(usage) = volume.state().usage {
+                samples.push((
+                    UsageResource::Volume(volume.uuid().clone()),
+                    UsageSample::now(usage.capacity(), usage.allocated()),
+                ));
+            }
+        }
+        for pool in self.ctrl_pool_states().await {
+            let pool = pool.state();
+            let resource = UsageResource::Pool(pool.id.clone());
+            existing.insert(resource.clone());
+            samples.push((resource, UsageSample::now(pool.capacity, pool.used)));
+        }
+
+        let persist = self.usage().args.usage_persist;
+        if persist {
+            for (resource, _) in &samples {
+                self.load_usage_series(resource).await;
+            }
+        }
+        let rolled_up = self.usage().record(samples);
+        let removed = self.usage().retain(&existing);
+
+        if persist {
+            for series in rolled_up {
+                if let Err(error) = self.store_obj(&series).await {
+                    tracing::warn!(
+                        resource = %series.resource(),
+                        %error,
+                        "Failed to persist the usage history"
+                    );
+                }
+            }
+            for resource in removed {
+                let key = UsageSeriesKey::from(&resource);
+                if let Err(error) = self.delete_kv(&key.key()).await {
+                    tracing::warn!(%resource, %error, "Failed to delete the usage history");
+                }
+            }
+        }
+    }
+
+    /// Load the persisted usage series of the given resource, if it's not yet tracked.
+    async fn load_usage_series(&self, resource: &UsageResource) {
+        if self.usage().tracked(resource) {
+            return;
+        }
+        let series = match self.load_obj(&UsageSeriesKey::from(resource)).await {
+            Ok(series) => series,
+            Err(SvcError::StoreMissingEntry { .. }) => UsageSeries::new(resource.clone()),
+            Err(error) => {
+                tracing::warn!(%resource, %error, "Failed to load the usage history");
+                UsageSeries::new(resource.clone())
+            }
+        };
+        self.usage().track(series);
+    }
+}
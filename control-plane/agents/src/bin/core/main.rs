@@ -102,6 +102,20 @@ pub(crate) struct CliArgs {
     #[clap(flatten)]
     thin_args: ThinArgs,
 
+    #[clap(flatten)]
+    usage_args: UsageArgs,
+
+    #[clap(flatten)]
+    pool_error_args: PoolErrorArgs,
+
+    #[clap(flatten)]
+    nvmf_auth_args: NvmfAuthArgs,
+
+    /// The time for which the long-running operations of the REST servers are kept once
+    /// they've finished. Running operations whose REST server stops refreshing them are failed.
+    #[clap(long, default_value = "15m")]
+    async_operation_ttl: humantime::Duration,
+
     /// Events message-bus endpoint url.
     #[clap(long, short)]
     events_url: Option<url::Url>,
@@ -147,6 +161,75 @@ pub(crate) struct ThinArgs {
     volume_commitment_initial: u64,
 }
 
+/// Volume and pool usage history parameters.
+#[derive(Debug, clap::Parser, Clone)]
+pub(crate) struct UsageArgs {
+    /// The period at which the usage of volumes and pools is sampled.
+    #[clap(long, default_value = "5m")]
+    usage_sample_period: humantime::Duration,
+    /// The maximum number of usage samples to keep for each resource.
+    /// With the default sample period this covers a full day.
+    #[clap(long, default_value = "288")]
+    usage_samples: usize,
+    /// The number of usage samples which are aggregated into a rollup.
+    /// With the default sample period this gives us hourly rollups.
+    #[clap(long, default_value = "12")]
+    usage_rollup_samples: u32,
+    /// The maximum number of usage rollups to keep for each resource.
+    /// With the default parameters this covers 30 days.
+    #[clap(long, default_value = "720")]
+    usage_rollups: usize,
+    /// Persist the usage history in the persistent store, allowing it to survive restarts.
+    /// The history is written out whenever a new rollup is completed.
+    #[clap(long)]
+    usage_persist: bool,
+}
+
+/// Pool error accounting parameters.
+#[derive(Debug, clap::Parser, Clone)]
+pub(crate) struct PoolErrorArgs {
+    /// The number of device error signals (replica creates failed with device errors,
+    /// children faulted with IO errors and repeated children running out of space) within the
+    /// error window after which a pool is marked as degraded.
+    /// A degraded pool is cordoned, excluding it from replica placement.
+    /// The marking is disabled by default, with a value of 0.
+    #[clap(long, default_value = "0")]
+    pool_error_threshold: usize,
+    /// The window within which the error signals of a pool are counted.
+    #[clap(long, default_value = "10m")]
+    pool_error_window: humantime::Duration,
+    /// Drain the degraded pools rather than only cordoning them, moving their volume replicas
+    /// onto other pools.
+    #[clap(long)]
+    pool_error_drain: bool,
+}
+
+/// NVMe-oF in-band authentication parameters.
+#[derive(clap::Parser, Clone)]
+pub(crate) struct NvmfAuthArgs {
+    /// The key used to seal the DH-HMAC-CHAP secrets of the volume targets before they're
+    /// persisted, as 32 base64 encoded bytes.
+    /// Volume target authentication is disabled when no key is supplied.
+    #[clap(long, env = "NVMF_AUTH_SEAL_KEY", hide_env_values = true)]
+    nvmf_auth_seal_key: Option<String>,
+}
+impl NvmfAuthArgs {
+    /// Get the sealing key, if any.
+    pub(crate) fn seal_key(&self) -> Option<&str> {
+        self.nvmf_auth_seal_key.as_deref()
+    }
+}
+impl std::fmt::Debug for NvmfAuthArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NvmfAuthArgs")
+            .field(
+                "nvmf_auth_seal_key",
+                &self.nvmf_auth_seal_key.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
 fn value_parse_percent(value: &str) -> Result<u64, ParseIntError> {
     value.replace('%', "").parse()
 }
@@ -183,6 +266,10 @@ async fn server(cli_args: CliArgs) -> anyhow::Result<()> {
             cli_args.hosts_acl.clone()
         },
         cli_args.thin_args,
+        cli_args.usage_args,
+        cli_args.pool_error_args,
+        &cli_args.nvmf_auth_args,
+        cli_args.async_operation_ttl.into(),
         cli_args.disable_ha,
         cli_args.etcd_page_limit as i64,
     )
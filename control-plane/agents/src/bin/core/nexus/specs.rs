@@ -51,11 +51,17 @@ impl SpecOperationsHelper for NexusSpec {
         op: Self::UpdateOp,
     ) -> Result<(), SvcError> {
         match &op {
-            NexusOperation::Share(_, _) if state.share.shared() => Err(SvcError::AlreadyShared {
-                kind: ResourceKind::Nexus,
-                id: self.uuid_str(),
-                share: state.share.to_string(),
-            }),
+            // a shared nexus may only be shared again to update its allowed hosts
+            NexusOperation::Share(protocol, allowed_hosts)
+                if state.share.shared()
+                    && (*protocol != state.share || allowed_hosts == &state.allowed_hosts) =>
+            {
+                Err(SvcError::AlreadyShared {
+                    kind: ResourceKind::Nexus,
+                    id: self.uuid_str(),
+                    share: state.share.to_string(),
+                })
+            }
             NexusOperation::Share(_, _) => Ok(()),
             NexusOperation::Unshare if !state.share.shared() => Err(SvcError::NotShared {
                 kind: ResourceKind::Nexus,
@@ -1,15 +1,16 @@
 use agents::errors::SvcError;
 
-use stor_port::types::v0::store::node::{DrainingVolumes, NodeOperation, NodeSpec};
+use stor_port::types::v0::store::node::{DrainingVolumes, EvacuatedPool, NodeOperation, NodeSpec};
 
 use crate::controller::{
     registry::Registry,
     resources::{
         operations::{ResourceCordon, ResourceDrain, ResourceLabel},
-        operations_helper::GuardedOperationsHelper,
+        operations_helper::{GuardedOperationsHelper, OperationSequenceGuard},
         OperationGuardArc,
     },
 };
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
 /// Resource Cordon Operations.
@@ -24,13 +25,7 @@ impl ResourceCordon for OperationGuardArc<NodeSpec> {
         registry: &Registry,
         label: String,
     ) -> Result<Self::CordonOutput, SvcError> {
-        let cloned_node_spec = self.lock().clone();
-        let spec_clone = self
-            .start_update(registry, &cloned_node_spec, NodeOperation::Cordon(label))
-            .await?;
-
-        self.complete_update(registry, Ok(()), spec_clone).await?;
-        Ok(self.as_ref().clone())
+        self.cordon_with_expiry(registry, label, None).await
     }
 
     /// Uncordon a node via operation guard functions.
@@ -106,13 +101,7 @@ impl ResourceDrain for OperationGuardArc<NodeSpec> {
         registry: &Registry,
         label: String,
     ) -> Result<Self::DrainOutput, SvcError> {
-        let cloned_node_spec = self.lock().clone();
-        let spec_clone = self
-            .start_update(registry, &cloned_node_spec, NodeOperation::Drain(label))
-            .await?;
-
-        self.complete_update(registry, Ok(()), spec_clone).await?;
-        Ok(self.as_ref().clone())
+        self.drain_with_expiry(registry, label, None).await
     }
 
     /// Mark a node as drained via operation guard functions.
@@ -129,6 +118,140 @@ impl ResourceDrain for OperationGuardArc<NodeSpec> {
 
 /// Node drain Operations.
 impl OperationGuardArc<NodeSpec> {
+    /// Cordon a node via operation guard functions, with the cordon label expiring at the
+    /// given time, if specified.
+    pub(crate) async fn cordon_with_expiry(
+        &mut self,
+        registry: &Registry,
+        label: String,
+        expiry: Option<DateTime<Utc>>,
+    ) -> Result<NodeSpec, SvcError> {
+        let cloned_node_spec = self.lock().clone();
+        let spec_clone = self
+            .start_update(
+                registry,
+                &cloned_node_spec,
+                NodeOperation::Cordon(label, expiry),
+            )
+            .await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(self.as_ref().clone())
+    }
+
+    /// Drain a node via operation guard functions, with the drain label expiring at the
+    /// given time, if specified.
+    pub(crate) async fn drain_with_expiry(
+        &mut self,
+        registry: &Registry,
+        label: String,
+        expiry: Option<DateTime<Utc>>,
+    ) -> Result<NodeSpec, SvcError> {
+        let cloned_node_spec = self.lock().clone();
+        let spec_clone = self
+            .start_update(
+                registry,
+                &cloned_node_spec,
+                NodeOperation::Drain(label, expiry),
+            )
+            .await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(self.as_ref().clone())
+    }
+
+    /// Drain a node via operation guard functions, moving the volume replicas off the node's
+    /// pools as well as the volume targets.
+    /// The drain label expires at the given time, if specified.
+    pub(crate) async fn evacuate(
+        &mut self,
+        registry: &Registry,
+        label: String,
+        expiry: Option<DateTime<Utc>>,
+    ) -> Result<NodeSpec, SvcError> {
+        let cloned_node_spec = self.lock().clone();
+        let spec_clone = self
+            .start_update(
+                registry,
+                &cloned_node_spec,
+                NodeOperation::Evacuate(label, expiry),
+            )
+            .await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(self.as_ref().clone())
+    }
+
+    /// Uncordon the node's pools which were drained by its evacuation with the given label.
+    /// Cordons which were applied to the pools by other means are left untouched.
+    pub(crate) async fn uncordon_pools(
+        &self,
+        registry: &Registry,
+        label: &str,
+    ) -> Result<(), SvcError> {
+        for pool_id in self.as_ref().evacuated_pools_with_label(label) {
+            let Some(pool) = registry.specs().pool_rsc(&pool_id) else {
+                continue;
+            };
+            if !pool.lock().has_cordon_label(label) {
+                continue;
+            }
+            let mut pool = pool.operation_guard_wait().await?;
+            pool.uncordon(registry, label.to_string()).await?;
+        }
+        Ok(())
+    }
+
+    /// Record a pool which is drained by the evacuation of the node, so that its cordon can be
+    /// removed along with the node's.
+    pub(crate) async fn add_evacuated_pool(
+        &mut self,
+        registry: &Registry,
+        evacuated_pool: EvacuatedPool,
+    ) -> Result<NodeSpec, SvcError> {
+        let cloned_node_spec = self.lock().clone();
+        let spec_clone = self
+            .start_update(
+                registry,
+                &cloned_node_spec,
+                NodeOperation::AddEvacuatedPool(evacuated_pool),
+            )
+            .await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(self.as_ref().clone())
+    }
+
+    /// Mark the node as being decommissioned, the reconciler then removes its pools and spec.
+    pub(crate) async fn decommission(&mut self, registry: &Registry) -> Result<NodeSpec, SvcError> {
+        let cloned_node_spec = self.lock().clone();
+        let spec_clone = self
+            .start_update(registry, &cloned_node_spec, NodeOperation::Decommission())
+            .await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(self.as_ref().clone())
+    }
+
+    /// Set the progress of the replicas being moved off the node's pools.
+    pub(crate) async fn set_volumes_drain_progress(
+        &mut self,
+        registry: &Registry,
+        volumes: DrainingVolumes,
+    ) -> Result<NodeSpec, SvcError> {
+        let cloned_node_spec = self.lock().clone();
+        let spec_clone = self
+            .start_update(
+                registry,
+                &cloned_node_spec,
+                NodeOperation::SetVolumesDrainProgress(volumes),
+            )
+            .await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        Ok(self.as_ref().clone())
+    }
+
     /// Drain the set of draining volumes to the stored set.
     pub(crate) async fn add_draining_volumes(
         &mut self,
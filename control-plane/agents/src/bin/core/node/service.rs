@@ -3,12 +3,13 @@ use crate::controller::{
     reconciler::PollTriggerEvent,
     registry::Registry,
     resources::{
-        operations::{ResourceCordon, ResourceDrain, ResourceLabel},
-        operations_helper::ResourceSpecsLocked,
+        operations::{ResourceCordon, ResourceLabel},
+        operations_helper::{GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked},
     },
     wrapper::NodeWrapper,
 };
 use agents::errors::SvcError;
+use chrono::{DateTime, Utc};
 use stor_port::types::v0::transport::{
     Deregister, Filter, Node, NodeId, NodeState, NodeStatus, Register,
 };
@@ -21,7 +22,7 @@ use grpc::{
         registration::traits::{DeregisterInfo, RegisterInfo, RegistrationOperations},
     },
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 /// Node's Service
 #[derive(Debug, Clone)]
@@ -103,20 +104,40 @@ impl NodeOperations for Service {
     }
 
     /// Cordon the specified node.
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
-        let node = self.cordon(id, label).await?;
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
+        let node = self.cordon(id, label, ttl, expected_revision).await?;
         Ok(node)
     }
 
     /// Uncordon the specified node.
-    async fn uncordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
-        let node = self.uncordon(id, label).await?;
+    async fn uncordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
+        let node = self.uncordon(id, label, expected_revision).await?;
         Ok(node)
     }
 
     /// Apply a drain label to the specified node. The reconciler will perform the drain.
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
-        let node = self.drain(id, label).await?;
+    async fn drain(
+        &self,
+        id: NodeId,
+        label: String,
+        evacuate: bool,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
+        let node = self
+            .drain(id, label, evacuate, ttl, expected_revision)
+            .await?;
         Ok(node)
     }
 
@@ -126,12 +147,18 @@ impl NodeOperations for Service {
         id: NodeId,
         label: HashMap<String, String>,
         overwrite: bool,
+        expected_revision: Option<u64>,
     ) -> Result<Node, ReplyError> {
-        let node = self.label(id, label, overwrite).await?;
+        let node = self.label(id, label, overwrite, expected_revision).await?;
         Ok(node)
     }
     /// Remove the specified label key from the node.
-    async fn unlabel(&self, id: NodeId, label_key: String) -> Result<Node, ReplyError> {
+    async fn unlabel(
+        &self,
+        id: NodeId,
+        label_key: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
         if label_key.is_empty() {
             return Err(SvcError::InvalidLabel {
                 labels: label_key,
@@ -139,9 +166,15 @@ impl NodeOperations for Service {
             }
             .into());
         }
-        let node = self.unlabel(id, label_key).await?;
+        let node = self.unlabel(id, label_key, expected_revision).await?;
         Ok(node)
     }
+
+    /// Decommission the specified node.
+    async fn decommission(&self, id: NodeId, force: bool) -> Result<(), ReplyError> {
+        self.decommission(id, force).await?;
+        Ok(())
+    }
 }
 
 #[tonic::async_trait]
@@ -301,32 +334,35 @@ impl Service {
         }
     }
 
+    /// Get all nodes, from both their specs and states.
+    async fn nodes(&self) -> Vec<Node> {
+        let node_states = self.registry.node_states().await;
+        let node_specs = self.specs().nodes();
+        let mut nodes = HashMap::new();
+
+        node_states.into_iter().for_each(|state| {
+            let spec = node_specs.iter().find(|s| s.id() == &state.id);
+            nodes.insert(
+                state.id.clone(),
+                Node::new(state.id.clone(), spec.cloned(), Some(state)),
+            );
+        });
+        node_specs.into_iter().for_each(|spec| {
+            if nodes.get(spec.id()).is_none() {
+                nodes.insert(
+                    spec.id().clone(),
+                    Node::new(spec.id().clone(), Some(spec), None),
+                );
+            }
+        });
+
+        nodes.values().cloned().collect()
+    }
+
     /// Get nodes by filter.
     pub(crate) async fn get_nodes(&self, request: &GetNodes) -> Result<Nodes, SvcError> {
         match request.filter() {
-            Filter::None => {
-                let node_states = self.registry.node_states().await;
-                let node_specs = self.specs().nodes();
-                let mut nodes = HashMap::new();
-
-                node_states.into_iter().for_each(|state| {
-                    let spec = node_specs.iter().find(|s| s.id() == &state.id);
-                    nodes.insert(
-                        state.id.clone(),
-                        Node::new(state.id.clone(), spec.cloned(), Some(state)),
-                    );
-                });
-                node_specs.into_iter().for_each(|spec| {
-                    if nodes.get(spec.id()).is_none() {
-                        nodes.insert(
-                            spec.id().clone(),
-                            Node::new(spec.id().clone(), Some(spec), None),
-                        );
-                    }
-                });
-
-                Ok(Nodes(nodes.values().cloned().collect()))
-            }
+            Filter::None => Ok(Nodes(self.nodes().await)),
             Filter::Node(node_id) => {
                 let node_state = self.registry.node_state(node_id).await.ok();
                 let node_spec = self.specs().node(node_id).ok();
@@ -346,6 +382,20 @@ impl Service {
                     )]))
                 }
             }
+            Filter::Labels(selector) => {
+                let mut nodes = self
+                    .nodes()
+                    .await
+                    .into_iter()
+                    .filter(|node| {
+                        let labels = node.spec().map(|spec| spec.labels());
+                        selector.matches(labels)
+                    })
+                    .collect::<Vec<_>>();
+                // the nodes are collected from a map, so sort them for a stable listing
+                nodes.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+                Ok(Nodes(nodes))
+            }
             _ => Err(SvcError::InvalidFilter {
                 filter: request.filter().clone(),
             }),
@@ -365,33 +415,75 @@ impl Service {
     }
 
     /// Cordon the specified node.
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, SvcError> {
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, SvcError> {
         let mut guarded_node = self.specs().guarded_node(&id).await?;
+        guarded_node.validate_revision(expected_revision)?;
 
-        let spec = guarded_node.cordon(&self.registry, label).await?;
+        let spec = guarded_node
+            .cordon_with_expiry(&self.registry, label, ttl.map(label_expiry))
+            .await?;
         let state = self.registry.node_state(&id).await.ok();
         Ok(Node::new(id, Some(spec), state))
     }
 
     /// Uncordon the specified node.
-    async fn uncordon(&self, id: NodeId, label: String) -> Result<Node, SvcError> {
+    async fn uncordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, SvcError> {
         let mut guarded_node = self.specs().guarded_node(&id).await?;
+        guarded_node.validate_revision(expected_revision)?;
+        if !guarded_node.as_ref().has_cordon_label(&label) {
+            return Err(SvcError::UncordonLabel {
+                node_id: id.to_string(),
+                label,
+            });
+        }
+
+        // The pools are uncordoned first, as the node no longer records its evacuated pools
+        // once its label is removed.
+        guarded_node.uncordon_pools(&self.registry, &label).await?;
+        let spec = guarded_node.uncordon(&self.registry, label.clone()).await?;
 
-        let spec = guarded_node.uncordon(&self.registry, label).await?;
         let state = self.registry.node_state(&id).await.ok();
         Ok(Node::new(id, Some(spec), state))
     }
 
     /// Apply a drain label to the specified node. The reconciler will perform the drain.
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, SvcError> {
+    /// When evacuating, the reconciler also moves the volume replicas off the node's pools.
+    async fn drain(
+        &self,
+        id: NodeId,
+        label: String,
+        evacuate: bool,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, SvcError> {
         // Don't allow draining if HA_ENABLED is false. If it is undefined we treat it as true.
         if self.registry.ha_disabled() {
             return Err(SvcError::DrainNotAllowedWhenHAisDisabled {});
         }
 
         let mut guarded_node = self.specs().guarded_node(&id).await?;
-
-        let spec = guarded_node.drain(&self.registry, label.clone()).await?;
+        guarded_node.validate_revision(expected_revision)?;
+
+        let expiry = ttl.map(label_expiry);
+        let spec = match evacuate {
+            true => guarded_node.evacuate(&self.registry, label, expiry).await?,
+            false => {
+                guarded_node
+                    .drain_with_expiry(&self.registry, label, expiry)
+                    .await?
+            }
+        };
         let state = self.registry.node_state(&id).await.ok();
 
         self.registry.notify(PollTriggerEvent::NodeDrain).await;
@@ -405,18 +497,107 @@ impl Service {
         id: NodeId,
         label: HashMap<String, String>,
         overwrite: bool,
+        expected_revision: Option<u64>,
     ) -> Result<Node, SvcError> {
         let mut guarded_node = self.specs().guarded_node(&id).await?;
+        guarded_node.validate_revision(expected_revision)?;
         let spec = guarded_node.label(&self.registry, label, overwrite).await?;
         let state = self.registry.node_state(&id).await.ok();
         Ok(Node::new(id, Some(spec), state))
     }
 
     /// Remove the specified label from  the specified node.
-    async fn unlabel(&self, id: NodeId, label: String) -> Result<Node, SvcError> {
+    async fn unlabel(
+        &self,
+        id: NodeId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, SvcError> {
         let mut guarded_node = self.specs().guarded_node(&id).await?;
+        guarded_node.validate_revision(expected_revision)?;
         let spec = guarded_node.unlabel(&self.registry, label).await?;
         let state = self.registry.node_state(&id).await.ok();
         Ok(Node::new(id, Some(spec), state))
     }
+
+    /// Decommission the specified node, permanently removing it and its pools.
+    /// The node's io-engine must no longer be registered. Unless forced, the node must also have
+    /// been drained and no volume may still depend on it.
+    /// The node is marked as decommissioning and the reconciler then removes its pools and spec,
+    /// so the decommission carries on following a restart.
+    #[tracing::instrument(level = "info", skip(self), err, fields(node.id = %id))]
+    async fn decommission(&self, id: NodeId, force: bool) -> Result<(), SvcError> {
+        let mut guarded_node = self.specs().guarded_node(&id).await?;
+        if guarded_node.as_ref().decommissioning() {
+            return Ok(());
+        }
+
+        if let Ok(node) = self.registry.node_wrapper(&id).await {
+            if node.read().await.is_online() {
+                return Err(SvcError::NodeStillRegistered {
+                    node_id: id.to_string(),
+                });
+            }
+        }
+        if !force {
+            if !guarded_node.as_ref().is_drained() {
+                return Err(SvcError::NodeNotDrained {
+                    node_id: id.to_string(),
+                });
+            }
+            let volumes = self.volumes_using_node(&id);
+            if !volumes.is_empty() {
+                return Err(SvcError::NodeInUseByVolumes {
+                    node_id: id.to_string(),
+                    volumes,
+                });
+            }
+        }
+
+        guarded_node.decommission(&self.registry).await?;
+        self.registry
+            .notify(PollTriggerEvent::NodeDecommission)
+            .await;
+        tracing::info!("Node decommission started");
+        Ok(())
+    }
+
+    /// Get the volumes which still depend on the given node, either through their target or
+    /// through a replica on one of its pools.
+    fn volumes_using_node(&self, id: &NodeId) -> Vec<String> {
+        let targets = self
+            .specs()
+            .volumes()
+            .into_iter()
+            .filter(|volume| volume.target().map(|t| t.node()) == Some(id))
+            .map(|volume| volume.uuid.to_string());
+        let nexuses = self
+            .specs()
+            .nexuses()
+            .into_iter()
+            .map(|nexus| nexus.lock().clone())
+            .filter(|nexus| &nexus.node == id)
+            .filter_map(|nexus| nexus.owner.map(|volume| volume.to_string()));
+        let replicas = self
+            .specs()
+            .replicas_cloned()
+            .into_iter()
+            .filter(|replica| {
+                self.specs()
+                    .pool(replica.pool_name())
+                    .map(|pool| &pool.node == id)
+                    .unwrap_or_default()
+            })
+            .filter_map(|replica| replica.owners.volume().map(|volume| volume.to_string()));
+
+        let mut volumes = targets.chain(nexuses).chain(replicas).collect::<Vec<_>>();
+        volumes.sort();
+        volumes.dedup();
+        volumes
+    }
+}
+
+/// Get the expiry of a cordon or drain label with the given TTL.
+fn label_expiry(ttl: Duration) -> DateTime<Utc> {
+    (std::time::SystemTime::now() + ttl).into()
 }
@@ -85,6 +85,12 @@ impl ResourceSpecsLocked {
         node.operation_guard_wait().await
     }
 
+    /// Remove the node `id` from the spec list.
+    fn remove_node(&self, id: &NodeId) {
+        let mut specs = self.write();
+        specs.nodes.remove(id);
+    }
+
     /// Get all locked node specs
     fn nodes_rsc(&self) -> Vec<ResourceMutex<NodeSpec>> {
         self.read().nodes.to_vec()
@@ -124,8 +130,9 @@ impl GuardedOperationsHelper for OperationGuardArc<NodeSpec> {
     type UpdateOp = NodeOperation;
     type Inner = NodeSpec;
 
-    fn remove_spec(&self, _registry: &Registry) {
-        unimplemented!();
+    fn remove_spec(&self, registry: &Registry) {
+        let id = self.lock().id().clone();
+        registry.specs().remove_node(&id);
     }
 }
 
@@ -144,7 +151,9 @@ impl SpecOperationsHelper for NodeSpec {
         op: Self::UpdateOp,
     ) -> Result<(), SvcError> {
         match &op {
-            NodeOperation::Cordon(label) | NodeOperation::Drain(label) => {
+            NodeOperation::Cordon(label, _)
+            | NodeOperation::Drain(label, _)
+            | NodeOperation::Evacuate(label, _) => {
                 // Do not allow the same label to be applied more than once.
                 if self.has_cordon_label(label) {
                     Err(SvcError::CordonLabel {
@@ -215,6 +224,9 @@ impl SpecOperationsHelper for NodeSpec {
     fn uuid_str(&self) -> String {
         self.id().to_string()
     }
+    fn spec_revision(&self) -> Option<u64> {
+        Some(self.revision())
+    }
     fn status(&self) -> SpecStatus<Self::Status> {
         SpecStatus::Created(())
     }
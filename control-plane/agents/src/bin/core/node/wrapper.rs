@@ -25,13 +25,13 @@ use stor_port::{
         transport::{
             AddNexusChild, ApiVersion, Child, CreateNexus, CreatePool, CreateReplica,
             CreateReplicaSnapshot, DestroyNexus, DestroyPool, DestroyReplica,
-            DestroyReplicaSnapshot, FaultNexusChild, ImportPool, IoEngCreateSnapshotClone,
-            ListRebuildRecord, ListReplicaSnapshots, ListSnapshotClones, MessageIdVs, Nexus,
-            NexusChildAction, NexusChildActionContext, NexusChildActionKind, NexusId, NodeId,
-            NodeState, NodeStatus, PoolId, PoolState, RebuildHistory, Register, RemoveNexusChild,
-            Replica, ReplicaId, ReplicaName, ReplicaSnapshot, ResizeNexus, ResizeReplica,
-            ShareNexus, ShareReplica, ShutdownNexus, SnapshotId, UnshareNexus, UnshareReplica,
-            VolumeId,
+            DestroyReplicaSnapshot, ExpandPool, FaultNexusChild, ImportPool,
+            IoEngCreateSnapshotClone, ListRebuildRecord, ListReplicaSnapshots, ListSnapshotClones,
+            MessageIdVs, Nexus, NexusChildAction, NexusChildActionContext, NexusChildActionKind,
+            NexusId, NodeId, NodeState, NodeStatus, PoolId, PoolState, RebuildHistory, Register,
+            RemoveNexusChild, Replica, ReplicaId, ReplicaName, ReplicaSnapshot, ResizeNexus,
+            ResizeReplica, ShareNexus, ShareReplica, ShutdownNexus, SnapshotId, UnshareNexus,
+            UnshareReplica, VolumeId,
         },
     },
 };
@@ -1258,6 +1258,15 @@ impl PoolApi for Arc<tokio::sync::RwLock<NodeWrapper>> {
             }
         }
     }
+
+    /// Expand a pool with more disks on the node via gRPC.
+    async fn expand_pool(&self, request: &ExpandPool) -> Result<PoolState, SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let pool = dataplane.expand_pool(request).await?;
+        // update the capacity straight away so the scheduler can make use of it
+        self.update_pool_state(Either::Insert(pool.clone())).await;
+        Ok(pool)
+    }
 }
 
 #[async_trait]
@@ -1,18 +1,21 @@
-use crate::controller::{
-    io_engine::PoolApi,
-    registry::Registry,
-    resources::{
-        operations::ResourceLifecycle,
-        operations_helper::{GuardedOperationsHelper, OnCreateFail, OperationSequenceGuard},
-        OperationGuardArc,
+use crate::{
+    controller::{
+        io_engine::PoolApi,
+        registry::Registry,
+        resources::{
+            operations::{ResourceCordon, ResourceDrain, ResourceLifecycle, ResourceResize},
+            operations_helper::{GuardedOperationsHelper, OnCreateFail, OperationSequenceGuard},
+            OperationGuardArc,
+        },
     },
+    node::wrapper::GetterOps,
 };
 use agents::errors::{SvcError, SvcError::CordonedNode};
 use stor_port::{
     transport_api::ResourceKind,
     types::v0::{
-        store::pool::PoolSpec,
-        transport::{CreatePool, CtrlPoolState, DestroyPool, Pool},
+        store::pool::{PoolDrainProgress, PoolOperation, PoolSpec},
+        transport::{CreatePool, CtrlPoolState, DestroyPool, ExpandPool, Pool, PoolState},
     },
 };
 
@@ -33,7 +36,8 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
                 node_id: request.node.to_string(),
             });
         }
-        if request.disks.len() != 1 {
+        // expanded pools are imported with all of their disks
+        if request.disks.is_empty() {
             return Err(SvcError::InvalidPoolDeviceNum {
                 disks: request.disks.clone(),
             });
@@ -107,6 +111,123 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
     }
 }
 
+#[async_trait::async_trait]
+impl ResourceResize for OperationGuardArc<PoolSpec> {
+    type Resize = ExpandPool;
+    type ResizeOutput = Pool;
+
+    /// Expand the pool by adding the given disks to it.
+    async fn resize(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resize,
+    ) -> Result<Self::ResizeOutput, SvcError> {
+        let node = registry.node_wrapper(&request.node).await?;
+        if !node.read().await.is_online() {
+            return Err(SvcError::NodeNotOnline {
+                node: request.node.clone(),
+            });
+        }
+
+        let Some(pool) = node.pool(&request.id).await else {
+            return Err(SvcError::PoolNotLoaded {
+                pool_id: request.id.clone(),
+            });
+        };
+        let spec_clone = self
+            .start_update(
+                registry,
+                &pool,
+                PoolOperation::Expand(request.disks.clone()),
+            )
+            .await?;
+
+        let result = node.expand_pool(request).await;
+        let state = self.complete_update(registry, result, spec_clone).await?;
+        let spec = self.lock().clone();
+        Ok(Pool::new(spec, CtrlPoolState::new(state)))
+    }
+}
+
+/// Resource Cordon Operations.
+#[async_trait::async_trait]
+impl ResourceCordon for OperationGuardArc<PoolSpec> {
+    type CordonOutput = Pool;
+    type UncordonOutput = Pool;
+
+    /// Cordon a pool via operation guard functions.
+    async fn cordon(
+        &mut self,
+        registry: &Registry,
+        label: String,
+    ) -> Result<Self::CordonOutput, SvcError> {
+        self.update_cordon_drain(registry, PoolOperation::Cordon(label))
+            .await
+    }
+
+    /// Uncordon a pool via operation guard functions.
+    async fn uncordon(
+        &mut self,
+        registry: &Registry,
+        label: String,
+    ) -> Result<Self::UncordonOutput, SvcError> {
+        self.update_cordon_drain(registry, PoolOperation::Uncordon(label))
+            .await
+    }
+}
+
+/// Resource Drain Operations.
+#[async_trait::async_trait]
+impl ResourceDrain for OperationGuardArc<PoolSpec> {
+    type DrainOutput = Pool;
+
+    /// Drain a pool via operation guard functions.
+    /// The pool replicas are moved by the pool reconciler.
+    async fn drain(
+        &mut self,
+        registry: &Registry,
+        label: String,
+    ) -> Result<Self::DrainOutput, SvcError> {
+        self.update_cordon_drain(registry, PoolOperation::Drain(label))
+            .await
+    }
+
+    /// Mark a pool as drained via operation guard functions.
+    async fn set_drained(&mut self, registry: &Registry) -> Result<Self::DrainOutput, SvcError> {
+        self.update_cordon_drain(registry, PoolOperation::SetDrained)
+            .await
+    }
+}
+
+impl OperationGuardArc<PoolSpec> {
+    /// Persist the progress of the pool drain.
+    pub(crate) async fn set_drain_progress(
+        &mut self,
+        registry: &Registry,
+        progress: PoolDrainProgress,
+    ) -> Result<Pool, SvcError> {
+        self.update_cordon_drain(registry, PoolOperation::DrainProgress(progress))
+            .await
+    }
+
+    /// Apply the cordon or drain operation to the pool spec, which doesn't involve the io-engine.
+    async fn update_cordon_drain(
+        &mut self,
+        registry: &Registry,
+        operation: PoolOperation,
+    ) -> Result<Pool, SvcError> {
+        let state = PoolState::from(self.as_ref());
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+
+        self.complete_update(registry, Ok(()), spec_clone).await?;
+        let spec = self.lock().clone();
+        Ok(match registry.ctrl_pool_state(&spec.id).await {
+            Ok(state) => Pool::new(spec, state),
+            Err(_) => Pool::from_spec(spec),
+        })
+    }
+}
+
 #[async_trait::async_trait]
 impl ResourceLifecycle for Option<OperationGuardArc<PoolSpec>> {
     type Create = CreatePool;
@@ -1,5 +1,6 @@
 use crate::controller::{
     io_engine::{ReplicaApi, ReplicaSnapshotApi},
+    pool_errors::PoolErrorKind,
     registry::Registry,
     resources::{
         operations::{
@@ -49,6 +50,13 @@ impl ResourceLifecycle for OperationGuardArc<ReplicaSpec> {
         let _ = replica.start_create(registry, request).await?;
 
         let result = node.create_replica(request).await;
+        if let Err(error) = &result {
+            if PoolErrorKind::is_device_error(error) {
+                registry
+                    .pool_errors()
+                    .record(&request.pool_id, PoolErrorKind::ReplicaCreate);
+            }
+        }
         let on_fail = OnCreateFail::eeinval_delete(&result);
 
         replica.complete_create(result, registry, on_fail).await
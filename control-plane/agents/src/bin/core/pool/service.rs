@@ -1,8 +1,10 @@
 use crate::controller::{
     registry::Registry,
     resources::{
-        operations::{ResourceLifecycle, ResourceSharing},
-        operations_helper::{OperationSequenceGuard, ResourceSpecsLocked},
+        operations::{
+            ResourceCordon, ResourceDrain, ResourceLifecycle, ResourceResize, ResourceSharing,
+        },
+        operations_helper::{GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked},
         OperationGuardArc, ResourceMutex,
     },
     wrapper::GetterOps,
@@ -11,7 +13,7 @@ use agents::errors::{PoolNotFound, ReplicaNotFound, SvcError};
 use grpc::{
     context::Context,
     operations::{
-        pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+        pool::traits::{CreatePoolInfo, DestroyPoolInfo, ExpandPoolInfo, PoolOperations},
         replica::traits::{
             CreateReplicaInfo, DestroyReplicaInfo, ReplicaOperations, ShareReplicaInfo,
             UnshareReplicaInfo,
@@ -26,8 +28,8 @@ use stor_port::{
     types::v0::{
         store::{pool::PoolSpec, replica::ReplicaSpec},
         transport::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetPools, GetReplicas,
-            NodeId, Pool, PoolId, Replica, ShareReplica, UnshareReplica,
+            CreatePool, CreateReplica, DestroyPool, DestroyReplica, ExpandPool, Filter, GetPools,
+            GetReplicas, NodeId, Pool, PoolId, Replica, ShareReplica, UnshareReplica,
         },
     },
 };
@@ -63,6 +65,61 @@ impl PoolOperations for Service {
         Ok(())
     }
 
+    async fn expand(
+        &self,
+        pool: &dyn ExpandPoolInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = pool.into();
+        let service = self.clone();
+        let pool = Context::spawn(async move { service.expand_pool(&req).await }).await??;
+        Ok(pool)
+    }
+
+    async fn cordon(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let service = self.clone();
+        let pool =
+            Context::spawn(async move { service.cordon_pool(&id, label, expected_revision).await })
+                .await??;
+        Ok(pool)
+    }
+
+    async fn uncordon(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let service = self.clone();
+        let pool =
+            Context::spawn(
+                async move { service.uncordon_pool(&id, label, expected_revision).await },
+            )
+            .await??;
+        Ok(pool)
+    }
+
+    async fn drain(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let service = self.clone();
+        let pool =
+            Context::spawn(async move { service.drain_pool(&id, label, expected_revision).await })
+                .await??;
+        Ok(pool)
+    }
+
     async fn get(&self, filter: Filter, _ctx: Option<Context>) -> Result<Pools, ReplyError> {
         let req = GetPools { filter };
         let pools = self.get_pools(&req).await?;
@@ -148,6 +205,19 @@ impl Service {
                 tracing::Span::current().record("pool.id", pool_id.as_str());
                 self.node_pools(None, Some(pool_id)).await
             }
+            Filter::Labels(selector) => {
+                let pools = self.node_pools(None, None).await?;
+                Ok(Pools(
+                    pools
+                        .into_inner()
+                        .into_iter()
+                        .filter(|pool| {
+                            let labels = pool.spec().and_then(|spec| spec.labels);
+                            selector.matches(labels.as_ref())
+                        })
+                        .collect(),
+                ))
+            }
             _ => Err(SvcError::InvalidFilter { filter }),
         }
     }
@@ -272,6 +342,61 @@ impl Service {
         pool.destroy(&self.registry, request).await
     }
 
+    /// Expand a pool with the given disks.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %request.id))]
+    pub(super) async fn expand_pool(&self, request: &ExpandPool) -> Result<Pool, SvcError> {
+        let mut pool = self.pool_opt(&request.id).await?.context(PoolNotFound {
+            pool_id: request.id.clone(),
+        })?;
+        pool.validate_revision(request.expected_revision)?;
+        pool.resize(&self.registry, request).await
+    }
+
+    /// Cordon a pool, preventing new replicas, snapshots and clones from being placed on it.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %id))]
+    pub(super) async fn cordon_pool(
+        &self,
+        id: &PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Pool, SvcError> {
+        let mut pool = self.pool_opt(id).await?.context(PoolNotFound {
+            pool_id: id.clone(),
+        })?;
+        pool.validate_revision(expected_revision)?;
+        pool.cordon(&self.registry, label).await
+    }
+
+    /// Remove the given cordon label from a pool.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %id))]
+    pub(super) async fn uncordon_pool(
+        &self,
+        id: &PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Pool, SvcError> {
+        let mut pool = self.pool_opt(id).await?.context(PoolNotFound {
+            pool_id: id.clone(),
+        })?;
+        pool.validate_revision(expected_revision)?;
+        pool.uncordon(&self.registry, label).await
+    }
+
+    /// Drain a pool, moving all of its volume replicas to other pools.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %id))]
+    pub(super) async fn drain_pool(
+        &self,
+        id: &PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Pool, SvcError> {
+        let mut pool = self.pool_opt(id).await?.context(PoolNotFound {
+            pool_id: id.clone(),
+        })?;
+        pool.validate_revision(expected_revision)?;
+        pool.drain(&self.registry, label).await
+    }
+
     /// Create a replica using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(replica.uuid = %request.uuid))]
     pub(super) async fn create_replica(
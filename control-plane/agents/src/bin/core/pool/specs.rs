@@ -30,7 +30,7 @@ impl GuardedOperationsHelper for OperationGuardArc<PoolSpec> {
     type Owners = ();
     type Status = PoolStatus;
     type State = PoolState;
-    type UpdateOp = ();
+    type UpdateOp = PoolOperation;
     type Inner = PoolSpec;
 
     fn validate_destroy(&self, registry: &Registry) -> Result<(), SvcError> {
@@ -60,8 +60,47 @@ impl SpecOperationsHelper for PoolSpec {
     type Owners = ();
     type Status = PoolStatus;
     type State = PoolState;
-    type UpdateOp = ();
+    type UpdateOp = PoolOperation;
 
+    async fn start_update_op(
+        &mut self,
+        _: &Registry,
+        _state: &Self::State,
+        op: Self::UpdateOp,
+    ) -> Result<(), SvcError> {
+        match &op {
+            PoolOperation::Expand(disks)
+                if disks.is_empty() || disks.iter().any(|disk| self.disks.contains(disk)) =>
+            {
+                Err(SvcError::InvalidPoolExpansion {
+                    pool_id: self.id.clone(),
+                    disks: disks.clone(),
+                })
+            }
+            PoolOperation::Expand(_) => crate::controller::io_engine::expand_pool_capable(),
+            // Do not allow the same label to be applied more than once.
+            PoolOperation::Cordon(label) | PoolOperation::Drain(label)
+                if self.has_cordon_label(label) =>
+            {
+                Err(SvcError::PoolCordonLabel {
+                    pool_id: self.id.clone(),
+                    label: label.clone(),
+                })
+            }
+            PoolOperation::Cordon(_) | PoolOperation::Drain(_) => Ok(()),
+            PoolOperation::Uncordon(label) if !self.has_cordon_label(label) => {
+                Err(SvcError::PoolUncordonLabel {
+                    pool_id: self.id.clone(),
+                    label: label.clone(),
+                })
+            }
+            PoolOperation::Uncordon(_) => Ok(()),
+            PoolOperation::SetDrained | PoolOperation::DrainProgress(_) => Ok(()),
+            _ => Err(SvcError::InvalidArguments {}),
+        }?;
+        self.start_op(op);
+        Ok(())
+    }
     fn start_create_op(&mut self, _request: &Self::Create) {
         self.start_op(PoolOperation::Create);
     }
@@ -80,6 +119,9 @@ impl SpecOperationsHelper for PoolSpec {
     fn uuid_str(&self) -> String {
         self.id.to_string()
     }
+    fn spec_revision(&self) -> Option<u64> {
+        Some(self.revision)
+    }
     fn status(&self) -> SpecStatus<Self::Status> {
         self.status.clone()
     }
@@ -2,11 +2,16 @@ use crate::{controller, controller::resources::operations_helper::ResourceSpecsL
 use agents::errors::SvcError;
 use grpc::{
     context::Context,
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
+    operations::registry::traits::{
+        GetSpecsInfo, GetStatesInfo, GetUsageHistoryInfo, RegistryOperations,
+    },
 };
 use stor_port::{
     transport_api::ReplyError,
-    types::v0::transport::{GetSpecs, GetStates, Specs, States},
+    types::v0::transport::{
+        AsyncOperation, GetAsyncOperations, GetSpecs, GetStates, GetUsageHistory, Specs, States,
+        UsageHistory,
+    },
 };
 
 /// Registry Service
@@ -36,6 +41,35 @@ impl RegistryOperations for Service {
         let states = self.get_states(&req).await?;
         Ok(states)
     }
+
+    async fn get_usage_history(
+        &self,
+        get_usage: &dyn GetUsageHistoryInfo,
+        _ctx: Option<Context>,
+    ) -> Result<UsageHistory, ReplyError> {
+        let req = get_usage.into();
+        let history = self.get_usage_history(&req).await?;
+        Ok(history)
+    }
+
+    async fn put_async_operation(
+        &self,
+        operation: &AsyncOperation,
+        _ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        self.registry.put_async_operation(operation).await?;
+        Ok(())
+    }
+
+    async fn get_async_operations(
+        &self,
+        get_operations: &GetAsyncOperations,
+        _ctx: Option<Context>,
+    ) -> Result<Vec<AsyncOperation>, ReplyError> {
+        Ok(self
+            .registry
+            .get_async_operations(get_operations.id.as_ref()))
+    }
 }
 
 impl Service {
@@ -81,4 +115,12 @@ impl Service {
             replicas,
         })
     }
+
+    /// Get the usage history of a resource.
+    pub(crate) async fn get_usage_history(
+        &self,
+        request: &GetUsageHistory,
+    ) -> Result<UsageHistory, SvcError> {
+        self.registry.usage_history(request.resource()).await
+    }
 }
@@ -149,8 +149,7 @@ fn test_deserialization_v1_to_v2() {
                     );
                     Some(labels)
                 },
-                sequencer: Default::default(),
-                operation: None,
+                ..Default::default()
             }),
         },
         TestEntry {
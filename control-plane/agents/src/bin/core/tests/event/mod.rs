@@ -86,6 +86,8 @@ async fn events() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -144,7 +146,7 @@ async fn events() {
 
     nexus_deletion_event_test(&mut events_mbus_subscription).await;
     vol_client
-        .destroy(&DestroyVolume { uuid: volid }, None)
+        .destroy(&DestroyVolume::new(&volid), None)
         .await
         .expect("Should be able to destroy the volume");
 
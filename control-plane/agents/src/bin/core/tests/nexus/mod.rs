@@ -85,6 +85,7 @@ async fn nexus() {
                 key: None,
                 protocol: NexusShareProtocol::Nvmf,
                 allowed_hosts: vec![],
+                dhchap: None,
             },
             None,
         )
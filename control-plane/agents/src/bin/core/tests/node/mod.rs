@@ -1,9 +1,19 @@
-use deployer_cluster::ClusterBuilder;
-use grpc::operations::node::traits::NodeOperations;
+use crate::pool::helpers::{create_placed_volume, get_node, get_pool, poll_until};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    node::traits::NodeOperations, pool::traits::PoolOperations,
+    registry::traits::RegistryOperations, volume::traits::VolumeOperations,
+};
 use std::time::Duration;
-use stor_port::types::v0::{
-    store::node::{NodeLabels, NodeSpec},
-    transport::{ApiVersion, Filter, HostNqn, Node, NodeId, NodeState, NodeStatus},
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::node::{NodeLabels, NodeSpec, VolumeDrainProgress},
+        transport::{
+            ApiVersion, DestroyVolume, Filter, GetSpecs, HostNqn, Node, NodeId, NodeState,
+            NodeStatus,
+        },
+    },
 };
 
 /// Get new `Node` from the given parameters
@@ -158,3 +168,371 @@ async fn large_cluster() {
     tracing::info!("Nodes: {:?}", nodes);
     assert_eq!(nodes.0.len(), expected_nodes);
 }
+
+#[tokio::test]
+async fn drain_evacuate() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .with_pool(1, "malloc:///disk1?size_mb=100")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let volume_client = cluster.grpc_client().volume();
+    let drain_node = cluster.node(1);
+
+    // Place the volume replica on the node which is then drained, and its target elsewhere.
+    let volume = create_placed_volume(&cluster, &cluster.pool(0, 0), Some(cluster.node(0))).await;
+
+    let node = node_client
+        .drain(drain_node.clone(), "decommission".into(), true, None, None)
+        .await
+        .unwrap();
+    assert!(node.spec().unwrap().evacuate());
+
+    let node = poll_until(
+        reconcile_period,
+        || get_node(&cluster, &drain_node),
+        |node| node.spec().unwrap().is_drained(),
+    )
+    .await;
+    let spec = node.spec().unwrap();
+    assert!(spec.is_drained(), "Node should be drained: {spec:?}");
+    assert_eq!(
+        spec.volumes_drain_progress().get(volume.uuid()),
+        Some(&VolumeDrainProgress {
+            remaining: 0,
+            moved: 1,
+        })
+    );
+
+    let volume = volume_client
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap()
+        .entries
+        .pop()
+        .unwrap();
+    let replica_pools = volume
+        .state()
+        .replica_topology
+        .values()
+        .map(|topology| topology.pool().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(replica_pools, vec![Some(cluster.pool(0, 0))]);
+
+    // The node's pools are uncordoned along with the node.
+    node_client
+        .uncordon(drain_node, "decommission".into(), None)
+        .await
+        .unwrap();
+    let pool = get_pool(&cluster, &cluster.pool(1, 0)).await;
+    assert!(!pool.spec().unwrap().cordoned());
+}
+
+/// A pool which was drained before the node is evacuated keeps its drain label when the node is
+/// uncordoned, as the evacuation did not apply it.
+#[tokio::test]
+async fn drain_evacuate_drained_pool() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .with_pool(1, "malloc:///disk1?size_mb=100")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let pool_client = cluster.grpc_client().pool();
+    let drain_node = cluster.node(1);
+    let pool_id = cluster.pool(1, 0);
+
+    create_placed_volume(&cluster, &cluster.pool(0, 0), Some(cluster.node(0))).await;
+
+    pool_client
+        .drain(pool_id.clone(), "decommission".into(), None, None)
+        .await
+        .unwrap();
+    node_client
+        .drain(drain_node.clone(), "decommission".into(), true, None, None)
+        .await
+        .unwrap();
+    let node = poll_until(
+        reconcile_period,
+        || get_node(&cluster, &drain_node),
+        |node| node.spec().unwrap().is_drained(),
+    )
+    .await;
+    let spec = node.spec().unwrap();
+    assert!(spec.is_drained(), "Node should be drained: {spec:?}");
+    assert!(spec.evacuated_pools().is_empty());
+
+    node_client
+        .uncordon(drain_node, "decommission".into(), None)
+        .await
+        .unwrap();
+    let pool = get_pool(&cluster, &pool_id).await;
+    let spec = pool.spec().unwrap();
+    assert!(spec.has_cordon_label("decommission"), "{spec:?}");
+    assert!(spec.is_drained());
+}
+
+#[tokio::test]
+async fn cordon_expiry() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let node_id = cluster.node(0);
+
+    let node = node_client
+        .cordon(
+            node_id.clone(),
+            "expiring".into(),
+            Some(Duration::from_secs(2)),
+            None,
+        )
+        .await
+        .unwrap();
+    let spec = node.spec().unwrap();
+    assert!(spec.has_cordon_label("expiring"));
+    assert!(spec.label_expiry().contains_key("expiring"));
+
+    let mut node = node_client
+        .cordon(node_id.clone(), "maintenance".into(), None, None)
+        .await
+        .unwrap();
+    let spec = node.spec().unwrap();
+    assert!(!spec.label_expiry().contains_key("maintenance"));
+
+    for _ in 0 .. 50 {
+        if !node.spec().unwrap().has_cordon_label("expiring") {
+            break;
+        }
+        tokio::time::sleep(reconcile_period).await;
+        node = node_client
+            .get(Filter::Node(node_id.clone()), false, None)
+            .await
+            .unwrap()
+            .0
+            .first()
+            .cloned()
+            .unwrap();
+    }
+    let spec = node.spec().unwrap();
+    assert!(
+        !spec.has_cordon_label("expiring"),
+        "Label should expire: {spec:?}"
+    );
+    assert!(spec.label_expiry().is_empty());
+    // Labels without a TTL do not expire.
+    assert!(spec.has_cordon_label("maintenance"));
+}
+
+#[tokio::test]
+async fn node_revision() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let node_id = cluster.node(0);
+
+    let revision = get_node(&cluster, &node_id)
+        .await
+        .spec()
+        .unwrap()
+        .revision();
+
+    let error = node_client
+        .cordon(
+            node_id.clone(),
+            "maintenance".into(),
+            None,
+            Some(revision + 1),
+        )
+        .await
+        .expect_err("The spec is not at this revision");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let node = node_client
+        .cordon(node_id.clone(), "maintenance".into(), None, Some(revision))
+        .await
+        .unwrap();
+    let spec = node.spec().unwrap();
+    assert!(spec.has_cordon_label("maintenance"));
+    assert_eq!(spec.revision(), revision + 1);
+
+    let error = node_client
+        .label(
+            node_id.clone(),
+            [("zone".to_string(), "a".to_string())].into(),
+            false,
+            Some(revision),
+        )
+        .await
+        .expect_err("The spec revision has moved on");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let node = node_client
+        .uncordon(node_id, "maintenance".into(), Some(revision + 1))
+        .await
+        .unwrap();
+    assert_eq!(node.spec().unwrap().revision(), revision + 2);
+}
+
+#[tokio::test]
+async fn decommission() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .with_pool(1, "malloc:///disk1?size_mb=100")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let volume_client = cluster.grpc_client().volume();
+    let node = cluster.node(1);
+
+    // Place the volume replica on the node which is then decommissioned.
+    let volume = create_placed_volume(&cluster, &cluster.pool(0, 0), None).await;
+
+    let error = node_client
+        .decommission(node.clone(), false)
+        .await
+        .expect_err("Node is still registered");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    stop_io_engine(&cluster, &node, reconcile_period).await;
+    let error = node_client
+        .decommission(node.clone(), false)
+        .await
+        .expect_err("Node is not drained");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    node_client
+        .drain(node.clone(), "decommission".into(), false, None, None)
+        .await
+        .unwrap();
+    poll_until(
+        reconcile_period,
+        || get_node(&cluster, &node),
+        |node| node.spec().unwrap().is_drained(),
+    )
+    .await;
+
+    let error = node_client
+        .decommission(node.clone(), false)
+        .await
+        .expect_err("The volume replica is still on the node");
+    assert_eq!(error.kind, ReplyErrorKind::InUse);
+
+    volume_client
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
+        .await
+        .unwrap();
+    node_client.decommission(node.clone(), false).await.unwrap();
+
+    wait_node_decommissioned(&cluster, &node, reconcile_period).await;
+}
+
+/// A forced decommission leaves the replicas which a volume still owns, and completes once the
+/// volume no longer owns them.
+#[tokio::test]
+async fn decommission_force_owned_replica() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .with_pool(1, "malloc:///disk1?size_mb=100")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let volume_client = cluster.grpc_client().volume();
+    let registry_client = cluster.grpc_client().registry();
+    let node = cluster.node(1);
+
+    let volume = create_placed_volume(&cluster, &cluster.pool(0, 0), None).await;
+
+    stop_io_engine(&cluster, &node, reconcile_period).await;
+    node_client.decommission(node.clone(), true).await.unwrap();
+    tokio::time::sleep(reconcile_period * 5).await;
+
+    // The replica and its pool are kept for the volume.
+    let specs = registry_client.get_specs(&GetSpecs {}, None).await.unwrap();
+    let owned = specs
+        .replicas
+        .iter()
+        .filter(|replica| replica.owners.owned_by(volume.uuid()))
+        .map(|replica| replica.pool_name().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(owned, vec![cluster.pool(1, 0)]);
+    assert!(specs.pools.iter().any(|pool| pool.id == cluster.pool(1, 0)));
+    let spec = get_node(&cluster, &node).await.spec().cloned().unwrap();
+    assert!(spec.decommissioning());
+
+    volume_client
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
+        .await
+        .unwrap();
+
+    wait_node_decommissioned(&cluster, &node, reconcile_period).await;
+}
+
+/// Stop the io-engine of the given node and wait for the node to no longer be online.
+async fn stop_io_engine(cluster: &Cluster, node: &NodeId, period: Duration) {
+    cluster.composer().stop(node.as_str()).await.unwrap();
+    let online =
+        |node: &Node| matches!(node.state(), Some(state) if state.status == NodeStatus::Online);
+    let node = poll_until(period, || get_node(cluster, node), |node| !online(node)).await;
+    assert!(!online(&node), "{node:?}");
+}
+
+/// Wait for the decommissioned node and its pools to be removed.
+async fn wait_node_decommissioned(cluster: &Cluster, node: &NodeId, period: Duration) {
+    let node_client = cluster.grpc_client().node();
+    let pool_client = cluster.grpc_client().pool();
+    let nodes = poll_until(
+        period,
+        || async {
+            node_client
+                .get(Filter::Node(node.clone()), true, None)
+                .await
+                .unwrap()
+                .0
+        },
+        |nodes| nodes.is_empty(),
+    )
+    .await;
+    assert!(nodes.is_empty(), "{nodes:?}");
+
+    let pools = pool_client
+        .get(Filter::Node(node.clone()), None)
+        .await
+        .map(|pools| pools.into_inner())
+        .unwrap_or_default();
+    assert!(pools.is_empty(), "{pools:?}");
+}
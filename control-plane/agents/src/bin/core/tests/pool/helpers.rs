@@ -0,0 +1,111 @@
+use deployer_cluster::Cluster;
+use grpc::operations::{
+    node::traits::NodeOperations, pool::traits::PoolOperations, volume::traits::VolumeOperations,
+};
+use std::{collections::HashMap, future::Future, time::Duration};
+use stor_port::types::v0::transport::{
+    CreateVolume, Filter, Node, NodeId, Pool, PoolId, PublishVolume, Volume, VolumeId,
+};
+
+/// Number of times a drain is polled before giving up on it.
+const DRAIN_POLLS: u32 = 50;
+
+/// Create a single replica volume whose replica is placed away from the `avoid` pool, by
+/// cordoning that pool while the volume is created.
+/// The volume is then published on the `target_node`, if specified.
+pub(crate) async fn create_placed_volume(
+    cluster: &Cluster,
+    avoid: &PoolId,
+    target_node: Option<NodeId>,
+) -> Volume {
+    let pool_client = cluster.grpc_client().pool();
+    let volume_client = cluster.grpc_client().volume();
+
+    pool_client
+        .cordon(avoid.clone(), "placement".into(), None, None)
+        .await
+        .unwrap();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 10 * 1024 * 1024,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    pool_client
+        .uncordon(avoid.clone(), "placement".into(), None, None)
+        .await
+        .unwrap();
+
+    let Some(target_node) = target_node else {
+        return volume;
+    };
+    volume_client
+        .publish(
+            &PublishVolume::new(
+                volume.uuid().clone(),
+                Some(target_node),
+                None,
+                HashMap::new(),
+                vec![],
+            ),
+            None,
+        )
+        .await
+        .unwrap()
+}
+
+/// Fetch the resource with `get` every `period` until `done` holds for it, giving up after
+/// `DRAIN_POLLS` attempts. The last fetched resource is returned either way.
+pub(crate) async fn poll_until<T, F, Fut>(
+    period: Duration,
+    mut get: F,
+    done: impl Fn(&T) -> bool,
+) -> T
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut resource = get().await;
+    for _ in 0 .. DRAIN_POLLS {
+        if done(&resource) {
+            break;
+        }
+        tokio::time::sleep(period).await;
+        resource = get().await;
+    }
+    resource
+}
+
+/// Get the specified pool.
+pub(crate) async fn get_pool(cluster: &Cluster, pool: &PoolId) -> Pool {
+    cluster
+        .grpc_client()
+        .pool()
+        .get(Filter::Pool(pool.clone()), None)
+        .await
+        .unwrap()
+        .into_inner()
+        .first()
+        .cloned()
+        .unwrap()
+}
+
+/// Get the specified node.
+pub(crate) async fn get_node(cluster: &Cluster, node: &NodeId) -> Node {
+    cluster
+        .grpc_client()
+        .node()
+        .get(Filter::Node(node.clone()), false, None)
+        .await
+        .unwrap()
+        .0
+        .first()
+        .cloned()
+        .unwrap()
+}
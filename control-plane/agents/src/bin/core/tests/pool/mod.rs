@@ -1,11 +1,15 @@
+pub(crate) mod helpers;
+
 use deployer_cluster::{Cluster, ClusterBuilder};
 use grpc::{
     context::Context,
     operations::{
         node::traits::NodeOperations, pool::traits::PoolOperations,
         registry::traits::RegistryOperations, replica::traits::ReplicaOperations,
+        volume::traits::VolumeOperations,
     },
 };
+use helpers::{create_placed_volume, get_pool, poll_until};
 use itertools::Itertools;
 use std::{collections::HashMap, convert::TryFrom, thread::sleep, time::Duration};
 use stor_port::{
@@ -23,9 +27,10 @@ use stor_port::{
         },
         store::replica::{ReplicaSpec, ReplicaSpecKey},
         transport::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetSpecs, NexusId,
-            NodeId, Protocol, Replica, ReplicaId, ReplicaName, ReplicaOwners, ReplicaShareProtocol,
-            ReplicaStatus, ShareReplica, UnshareReplica, VolumeId,
+            CreatePool, CreateReplica, CreateVolume, DestroyPool, DestroyReplica, ExpandPool,
+            Filter, GetSpecs, NexusId, NodeId, Protocol, PublishVolume, Replica, ReplicaId,
+            ReplicaName, ReplicaOwners, ReplicaShareProtocol, ReplicaStatus, ShareReplica,
+            UnshareReplica, VolumeId,
         },
     },
 };
@@ -882,6 +887,8 @@ async fn disown_unused_replicas() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -1033,3 +1040,405 @@ async fn destroy_after_restart() {
 
     assert_eq!(pool.state().unwrap().id, create.id);
 }
+
+#[tokio::test]
+async fn expand_pool() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .build()
+        .await
+        .unwrap();
+    let pool_client = cluster.grpc_client().pool();
+
+    let disk = "malloc:///disk0?size_mb=100";
+    let pool = pool_client
+        .create(
+            &CreatePool::new(&cluster.node(0), &"p0".into(), &[disk.into()], &None),
+            None,
+        )
+        .await
+        .unwrap();
+    let capacity = pool.state().unwrap().capacity;
+
+    // Disks which the pool already has or no disks at all are rejected.
+    for disks in [vec![disk.into()], vec![]] {
+        let error = pool_client
+            .expand(&ExpandPool::new(&cluster.node(0), pool.id(), &disks), None)
+            .await
+            .expect_err("No new disks");
+        assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    }
+
+    // The pinned io-engine api cannot add disks to a pool, so the expansion is rejected up front.
+    let expand = ExpandPool::new(
+        &cluster.node(0),
+        pool.id(),
+        &["malloc:///disk1?size_mb=100".into()],
+    );
+    let error = pool_client
+        .expand(&expand, None)
+        .await
+        .expect_err("Not supported by the io-engine");
+    assert_eq!(error.kind, ReplyErrorKind::Unimplemented);
+    let pools = pool_client
+        .get(Filter::Pool(pool.id().clone()), None)
+        .await
+        .unwrap();
+    let expanded = pools.into_inner().first().cloned().unwrap();
+    assert_eq!(expanded.spec().unwrap().disks.len(), 1);
+    assert_eq!(expanded.state().unwrap().capacity, capacity);
+    // no pool operation was started for it
+    assert_eq!(
+        expanded.spec().unwrap().revision,
+        pool.spec().unwrap().revision
+    );
+}
+
+/// Expands a pool and restarts the io-engine, which makes the pool reconciler import the pool
+/// again with all of its disks.
+#[tokio::test]
+#[ignore = "requires an io-engine api which can add disks to a pool"]
+async fn reimport_expanded_pool() {
+    let disk = deployer_cluster::TmpDiskFile::new("expand-disk0.img", POOL_SIZE_BYTES);
+    let new_disk = deployer_cluster::TmpDiskFile::new("expand-disk1.img", POOL_SIZE_BYTES);
+
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, disk.uri())
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_millis(1))
+        .build()
+        .await
+        .unwrap();
+    let pool_client = cluster.grpc_client().pool();
+    let pool_id = cluster.pool(0, 0);
+
+    let pools = pool_client
+        .get(Filter::Pool(pool_id.clone()), None)
+        .await
+        .unwrap();
+    let capacity = pools.into_inner()[0].state().unwrap().capacity;
+
+    let expand = ExpandPool::new(&cluster.node(0), &pool_id, &[new_disk.uri().into()]);
+    let pool = pool_client.expand(&expand, None).await.unwrap();
+    assert_eq!(pool.spec().unwrap().disks.len(), 2);
+    let expanded = pool.state().unwrap().capacity;
+    assert!(expanded > capacity);
+
+    // The scheduler's view of the pool is updated straight away.
+    let pools = pool_client
+        .get(Filter::Pool(pool_id.clone()), None)
+        .await
+        .unwrap();
+    assert_eq!(pools.into_inner()[0].state().unwrap().capacity, expanded);
+
+    let maya = cluster.node(0);
+    let timeout = Duration::from_secs(RECONCILE_TIMEOUT_SECS);
+    cluster.composer().stop(maya.as_str()).await.unwrap();
+    wait_till_pool_state(&cluster, (0, 0), false, timeout).await;
+
+    // The pool is imported with all of the disks from its spec.
+    cluster.composer().restart(maya.as_str()).await.unwrap();
+    let pool = wait_till_pool_state(&cluster, (0, 0), true, timeout).await;
+    assert_eq!(pool.spec.unwrap().disks.len(), 2);
+    let state = pool.state.unwrap();
+    assert_eq!(state.disks.len(), 2);
+    assert_eq!(state.capacity, expanded);
+
+    // An explicit re-create of the pool with all of its disks is also accepted.
+    let create = CreatePool::new(
+        &cluster.node(0),
+        &pool_id,
+        &[disk.uri().into(), new_disk.uri().into()],
+        &None,
+    );
+    let error = pool_client
+        .create(&create, None)
+        .await
+        .expect_err("The pool already exists");
+    assert_eq!(error.kind, ReplyErrorKind::AlreadyExists);
+}
+
+#[tokio::test]
+async fn cordon_pool() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .build()
+        .await
+        .unwrap();
+    let pool_client = cluster.grpc_client().pool();
+    let volume_client = cluster.grpc_client().volume();
+    let pool_id = cluster.pool(0, 0);
+
+    let pool = pool_client
+        .cordon(pool_id.clone(), "maintenance".into(), None, None)
+        .await
+        .unwrap();
+    assert!(pool.spec().unwrap().cordoned());
+    let error = pool_client
+        .cordon(pool_id.clone(), "maintenance".into(), None, None)
+        .await
+        .expect_err("Already cordoned with the label");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // No replicas may be placed on a cordoned pool.
+    let create_volume = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 10 * 1024 * 1024,
+        replicas: 1,
+        ..Default::default()
+    };
+    volume_client
+        .create(&create_volume, None)
+        .await
+        .expect_err("The only pool is cordoned");
+
+    let pool = pool_client
+        .uncordon(pool_id.clone(), "maintenance".into(), None, None)
+        .await
+        .unwrap();
+    assert!(!pool.spec().unwrap().cordoned());
+    let error = pool_client
+        .uncordon(pool_id, "maintenance".into(), None, None)
+        .await
+        .expect_err("Not cordoned with the label");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    volume_client.create(&create_volume, None).await.unwrap();
+}
+
+#[tokio::test]
+async fn pool_revision() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .build()
+        .await
+        .unwrap();
+    let pool_client = cluster.grpc_client().pool();
+    let pool_id = cluster.pool(0, 0);
+
+    let revision = get_pool(&cluster, &pool_id).await.spec().unwrap().revision;
+
+    let error = pool_client
+        .cordon(
+            pool_id.clone(),
+            "maintenance".into(),
+            Some(revision + 1),
+            None,
+        )
+        .await
+        .expect_err("The spec is not at this revision");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let pool = pool_client
+        .cordon(pool_id.clone(), "maintenance".into(), Some(revision), None)
+        .await
+        .unwrap();
+    let spec = pool.spec().unwrap();
+    assert!(spec.cordoned());
+    assert_eq!(spec.revision, revision + 1);
+
+    let error = pool_client
+        .uncordon(pool_id.clone(), "maintenance".into(), Some(revision), None)
+        .await
+        .expect_err("The spec revision has moved on");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let pool = pool_client
+        .uncordon(pool_id, "maintenance".into(), Some(revision + 1), None)
+        .await
+        .unwrap();
+    assert_eq!(pool.spec().unwrap().revision, revision + 2);
+}
+
+#[tokio::test]
+async fn drain_pool() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .with_pool(1, "malloc:///disk1?size_mb=100")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let pool_client = cluster.grpc_client().pool();
+    let volume_client = cluster.grpc_client().volume();
+    let pool_id = cluster.pool(0, 0);
+    let other_pool_id = cluster.pool(1, 0);
+
+    // Place the volume replica on the pool which is then drained.
+    let volume = create_placed_volume(&cluster, &other_pool_id, Some(cluster.node(0))).await;
+
+    let pool = pool_client
+        .drain(pool_id.clone(), "maintenance".into(), None, None)
+        .await
+        .unwrap();
+    assert!(pool.spec().unwrap().is_draining());
+
+    let pool = poll_until(
+        reconcile_period,
+        || get_pool(&cluster, &pool_id),
+        |pool| pool.spec().unwrap().is_drained(),
+    )
+    .await;
+    let spec = pool.spec().unwrap();
+    assert!(spec.is_drained(), "Pool should be drained: {spec:?}");
+    let progress = spec.drain_progress.unwrap();
+    assert_eq!(progress.moved, 1);
+    assert!(progress.moving.is_empty());
+
+    let volume = volume_client
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap()
+        .entries
+        .pop()
+        .unwrap();
+    assert_eq!(volume.spec().num_replicas, 1);
+    let replica_pools = volume
+        .state()
+        .replica_topology
+        .values()
+        .map(|topology| topology.pool().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(replica_pools, vec![Some(other_pool_id)]);
+}
+
+/// The replicas of an unpublished volume cannot be rebuilt, so the drain reports the volume as
+/// blocked until it's published.
+#[tokio::test]
+async fn drain_pool_unpublished_volume() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .with_pool(1, "malloc:///disk1?size_mb=100")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let pool_client = cluster.grpc_client().pool();
+    let volume_client = cluster.grpc_client().volume();
+    let pool_id = cluster.pool(0, 0);
+
+    // Place the volume replica on the pool which is then drained.
+    let volume = create_placed_volume(&cluster, &cluster.pool(1, 0), None).await;
+
+    pool_client
+        .drain(pool_id.clone(), "maintenance".into(), None, None)
+        .await
+        .unwrap();
+    let pool = poll_until(
+        reconcile_period,
+        || get_pool(&cluster, &pool_id),
+        |pool| {
+            let progress = pool.spec().unwrap().drain_progress.unwrap_or_default();
+            !progress.blocked.is_empty()
+        },
+    )
+    .await;
+    let progress = pool.spec().unwrap().drain_progress.unwrap();
+    assert_eq!(progress.blocked, vec![volume.uuid().clone()]);
+
+    // The drain carries on only once the volume is published.
+    tokio::time::sleep(reconcile_period * 5).await;
+    let spec = get_pool(&cluster, &pool_id).await.spec().unwrap();
+    assert!(
+        spec.is_draining(),
+        "Pool should still be draining: {spec:?}"
+    );
+    assert_eq!(spec.drain_progress.unwrap().moved, 0);
+
+    volume_client
+        .publish(
+            &PublishVolume::new(
+                volume.uuid().clone(),
+                Some(cluster.node(0)),
+                None,
+                HashMap::new(),
+                vec![],
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let pool = poll_until(
+        reconcile_period,
+        || get_pool(&cluster, &pool_id),
+        |pool| pool.spec().unwrap().is_drained(),
+    )
+    .await;
+    let spec = pool.spec().unwrap();
+    assert!(spec.is_drained(), "Pool should be drained: {spec:?}");
+    let progress = spec.drain_progress.unwrap();
+    assert_eq!(progress.moved, 1);
+    assert!(progress.blocked.is_empty());
+}
+
+#[tokio::test]
+async fn degraded_pool_out_of_space() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///disk0?size_mb=100")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_pool_error_threshold(2)
+        .build()
+        .await
+        .unwrap();
+    let replica_client = cluster.grpc_client().replica();
+    let volume_client = cluster.grpc_client().volume();
+    let pool_id = cluster.pool(0, 0);
+
+    // Replicas larger than the pool fail to be created as the pool is out of space, which says
+    // nothing about the health of its device.
+    for _ in 0 .. 2 {
+        replica_client
+            .create(
+                &CreateReplica {
+                    node: cluster.node(0),
+                    uuid: ReplicaId::new(),
+                    pool_id: pool_id.clone(),
+                    size: 200 * 1024 * 1024,
+                    thin: false,
+                    share: Protocol::None,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .expect_err("The replica doesn't fit the pool");
+    }
+
+    // Give the reconciler a few chances to mark the pool as degraded.
+    tokio::time::sleep(reconcile_period * 5).await;
+    let pool = get_pool(&cluster, &pool_id).await;
+    assert!(
+        !pool.spec().unwrap().has_cordon_label("io-engine/degraded"),
+        "The pool should not be marked as degraded: {pool:?}"
+    );
+
+    let create_volume = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 10 * 1024 * 1024,
+        replicas: 1,
+        ..Default::default()
+    };
+    volume_client.create(&create_volume, None).await.unwrap();
+}
@@ -64,6 +64,8 @@ async fn rebuild_history_for_full_rebuild() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -195,6 +197,8 @@ async fn rebuild_history_for_partial_rebuild() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
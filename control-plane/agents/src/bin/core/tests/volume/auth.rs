@@ -0,0 +1,365 @@
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use stor_port::{
+    pstor::{etcd::Etcd, StoreObj},
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::auth::{NvmfAuthRef, NvmfAuthSecret},
+        transport::{
+            CreateVolume, DestroyVolume, DhChapSecret, Filter, GetVolumeAuth, PublishVolume,
+            RotateVolumeAuth, UnpublishVolume, VolumeAuth, VolumeId, VolumeShareProtocol,
+        },
+    },
+};
+
+use uuid::Uuid;
+
+const SIZE: u64 = 50 * 1024 * 1024; // 50MiB
+const SEAL_KEY: &str = "gkIL2GGCzmhW8TUmAlGs7Io4ZK7Q0mRfNoBO3/3a9Ss=";
+
+#[tokio::test]
+async fn volume_auth_unsupported() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .with_nvmf_auth_seal_key(SEAL_KEY)
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+
+    // The io-engine api can't carry the secrets, so a volume which could never be published with
+    // them is not created.
+    let uuid: VolumeId = Uuid::new_v4().try_into().unwrap();
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: uuid.clone(),
+                size: SIZE,
+                replicas: 1,
+                auth: Some(VolumeAuth::default()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("DH-HMAC-CHAP is not supported by the io-engine");
+    assert_eq!(error.kind, ReplyErrorKind::Unimplemented);
+    let error = vol_cli
+        .get(Filter::Volume(uuid), false, None, None)
+        .await
+        .expect_err("Volume should not have been created");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    // Malformed secrets are still reported as such.
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                auth: Some(VolumeAuth::new(Some("DHHC-1:00:secret:".into()), None)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("Malformed host secret");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let error = vol_cli
+        .rotate_auth(
+            &RotateVolumeAuth::new(volume.uuid(), VolumeAuth::default()),
+            None,
+        )
+        .await
+        .expect_err("DH-HMAC-CHAP is not supported by the io-engine");
+    assert_eq!(error.kind, ReplyErrorKind::Unimplemented);
+    let volume = vol_cli
+        .get(Filter::Volume(volume.uuid().clone()), false, None, None)
+        .await
+        .unwrap();
+    assert_eq!(volume.entries[0].spec().auth, None);
+}
+
+#[tokio::test]
+#[ignore = "requires an io-engine api which carries the DH-HMAC-CHAP secrets"]
+async fn volume_auth() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .with_nvmf_auth_seal_key(SEAL_KEY)
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+    let mut etcd = Etcd::new("0.0.0.0:2379").await.unwrap();
+
+    // A controller secret can't be supplied without its host secret.
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                auth: Some(VolumeAuth::new(None, Some(DhChapSecret::generate().into()))),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("Controller secret without a host secret");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                auth: Some(VolumeAuth::new(Some("DHHC-1:00:secret:".into()), None)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("Malformed host secret");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                auth: Some(VolumeAuth::default()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let auth = volume.spec().auth.expect("Volume should be authenticated");
+    assert_eq!(auth.generation(), 0);
+
+    // The secrets are generated when not supplied.
+    let keys = vol_cli
+        .get_auth(&GetVolumeAuth::new(volume.uuid()), None)
+        .await
+        .unwrap();
+    assert!(keys.ctrl.is_some());
+
+    // The secrets are never stored in the clear.
+    let secret: NvmfAuthSecret = etcd.get_obj(&auth.key()).await.unwrap();
+    assert!(!secret.sealed().contains(keys.host.as_str()));
+
+    let host = DhChapSecret::generate();
+    let volume = vol_cli
+        .rotate_auth(
+            &RotateVolumeAuth::new(
+                volume.uuid(),
+                VolumeAuth::new(Some(host.clone().into()), None),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().auth.map(|a| a.generation()), Some(1));
+
+    let rotated = vol_cli
+        .get_auth(&GetVolumeAuth::new(volume.uuid()), None)
+        .await
+        .unwrap();
+    assert_ne!(rotated, keys);
+    assert_eq!(rotated.host, host);
+    assert_eq!(rotated.ctrl, None);
+    etcd.get_obj::<NvmfAuthSecret>(&auth.key())
+        .await
+        .expect_err("Previous secrets are not in use by the target");
+
+    vol_cli
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
+        .await
+        .unwrap();
+
+    // Volumes without authentication have no secrets.
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let error = vol_cli
+        .get_auth(&GetVolumeAuth::new(volume.uuid()), None)
+        .await
+        .expect_err("Volume has no secrets");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+}
+
+#[tokio::test]
+#[ignore = "requires an io-engine api which carries the DH-HMAC-CHAP secrets"]
+async fn volume_auth_disabled() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+
+    // Without a sealing key the secrets can't be stored.
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                auth: Some(VolumeAuth::default()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("Volume target authentication is disabled");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let error = vol_cli
+        .rotate_auth(
+            &RotateVolumeAuth::new(volume.uuid(), VolumeAuth::default()),
+            None,
+        )
+        .await
+        .expect_err("Volume target authentication is disabled");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+}
+
+#[tokio::test]
+#[ignore = "requires an io-engine api which carries the DH-HMAC-CHAP secrets"]
+async fn volume_auth_rotation() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .with_nvmf_auth_seal_key(SEAL_KEY)
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+    let mut etcd = Etcd::new("0.0.0.0:2379").await.unwrap();
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                auth: Some(VolumeAuth::default()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let publish = PublishVolume {
+        uuid: volume.spec().uuid,
+        target_node: Some(cluster.node(0)),
+        share: Some(VolumeShareProtocol::Nvmf),
+        ..Default::default()
+    };
+    vol_cli.publish(&publish, None).await.unwrap();
+    let keys = vol_cli
+        .get_auth(&GetVolumeAuth::new(volume.uuid()), None)
+        .await
+        .unwrap();
+
+    // The published target keeps using the previous secrets, which are kept alongside the new.
+    let host = DhChapSecret::generate();
+    vol_cli
+        .rotate_auth(
+            &RotateVolumeAuth::new(
+                volume.uuid(),
+                VolumeAuth::new(Some(host.clone().into()), None),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let target_keys = vol_cli
+        .get_auth(&GetVolumeAuth::new(volume.uuid()), None)
+        .await
+        .unwrap();
+    assert_eq!(target_keys, keys);
+    let previous = NvmfAuthRef::new(volume.uuid(), 0);
+    let rotated = NvmfAuthRef::new(volume.uuid(), 1);
+    etcd.get_obj::<NvmfAuthSecret>(&previous.key())
+        .await
+        .unwrap();
+    etcd.get_obj::<NvmfAuthSecret>(&rotated.key())
+        .await
+        .unwrap();
+
+    // Once the target is no longer shared with the previous secrets, they're deleted.
+    vol_cli
+        .unpublish(&UnpublishVolume::new(volume.uuid(), false), None)
+        .await
+        .unwrap();
+    etcd.get_obj::<NvmfAuthSecret>(&previous.key())
+        .await
+        .expect_err("Previous secrets should be deleted");
+    vol_cli.publish(&publish, None).await.unwrap();
+    let target_keys = vol_cli
+        .get_auth(&GetVolumeAuth::new(volume.uuid()), None)
+        .await
+        .unwrap();
+    assert_eq!(target_keys.host, host);
+    assert_eq!(target_keys.ctrl, None);
+
+    vol_cli
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
+        .await
+        .unwrap();
+    etcd.get_obj::<NvmfAuthSecret>(&rotated.key())
+        .await
+        .expect_err("Secrets should be deleted with the volume");
+}
@@ -1,12 +1,20 @@
 #![cfg(test)]
 
-use crate::volume::RECONCILE_TIMEOUT_SECS;
+use crate::{
+    pool::helpers::{get_pool, poll_until},
+    volume::RECONCILE_TIMEOUT_SECS,
+};
 use deployer_cluster::{Cluster, ClusterBuilder, FindVolumeRequest};
+use events_api::{
+    event::{EventAction, EventCategory, EventMessage},
+    mbus_nats::message_bus_init,
+    Bus,
+};
 use grpc::operations::volume::traits::VolumeOperations;
 use std::{collections::HashMap, convert::TryFrom, time::Duration};
 use stor_port::types::v0::{
     openapi::{models, models::PublishVolumeBody},
-    transport::{ChildState, Filter, VolumeStatus},
+    transport::{ChildState, CreateVolume, Filter, VolumeId, VolumeStatus},
 };
 
 #[tokio::test]
@@ -58,14 +66,14 @@ async fn log_thin(cluster: &Cluster) {
     let pools_api = api_client.pools_api();
     let replica_api = api_client.replicas_api();
 
-    let volumes = volumes_api.get_volumes(0, None, None).await.unwrap();
+    let volumes = volumes_api.get_volumes(0, None, None, None).await.unwrap();
 
     for volume in volumes.entries {
         let target = volume.state.target.as_ref().unwrap();
         tracing::info!("VolumeStatus: {} => {target:#?}", volume.spec.uuid);
     }
 
-    let pools = pools_api.get_pools().await.unwrap();
+    let pools = pools_api.get_pools(None).await.unwrap();
     let pools = pools
         .into_iter()
         .map(|p| p.state.unwrap())
@@ -162,6 +170,74 @@ async fn faulted_nexus_enospc_child() {
     assert_eq!(volume_1.state.status, models::VolumeStatus::Online);
 }
 
+/// Children of two volumes running out of space on the same pool are repeated ENOSPC signals,
+/// which mark the pool as degraded.
+#[tokio::test]
+async fn degraded_pool_repeated_enospc() {
+    let cache_period = Duration::from_millis(250);
+    let reconcile_period = Duration::from_millis(3000);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_io_engines(2)
+        .with_pool(0, "malloc:///p1?size_mb=300")
+        .with_pool(1, "malloc:///p1?size_mb=100")
+        .with_csi(false, true)
+        .with_options(|o| o.with_isolated_io_engine(true))
+        .with_cache_period(&humantime::Duration::from(cache_period).to_string())
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_pool_error_threshold(1)
+        .with_eventing(true)
+        .build()
+        .await
+        .unwrap();
+    let mut bus = message_bus_init("localhost:4222", Some(1)).await;
+    let mut events = bus.subscribe::<EventMessage>().await.unwrap();
+
+    common_enospc_builder(&cluster, cache_period, reconcile_period, 2).await;
+
+    // Both volumes have a replica on the smaller pool, which ran out of space twice.
+    let pool_id = cluster.pool(1, 0);
+    let pool = poll_until(
+        reconcile_period,
+        || get_pool(&cluster, &pool_id),
+        |pool| pool.spec().unwrap().has_cordon_label("io-engine/degraded"),
+    )
+    .await;
+    assert!(
+        pool.spec().unwrap().has_cordon_label("io-engine/degraded"),
+        "The pool should be marked as degraded: {pool:?}"
+    );
+
+    // The degraded pool is excluded from replica placement, leaving a single usable pool.
+    let create_volume = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 10 * 1024 * 1024,
+        replicas: 2,
+        thin: true,
+        ..Default::default()
+    };
+    let volume_client = cluster.grpc_client().volume();
+    volume_client
+        .create(&create_volume, None)
+        .await
+        .expect_err("The degraded pool is cordoned");
+
+    let event = tokio::time::timeout(Duration::from_secs(RECONCILE_TIMEOUT_SECS), async {
+        while let Some(event) = events.next().await {
+            if event.category() == EventCategory::Pool
+                && event.action() == EventAction::StateChange
+                && event.target == pool_id.to_string()
+            {
+                return Some(event);
+            }
+        }
+        None
+    })
+    .await
+    .expect("Timeout waiting for the pool degraded event");
+    assert!(event.is_some(), "The pool degraded event should be raised");
+}
+
 async fn common_enospc_builder(
     cluster: &Cluster,
     cache_period: Duration,
@@ -196,6 +272,8 @@ async fn common_enospc_builder(
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -225,6 +303,8 @@ async fn common_enospc_builder(
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -232,7 +312,7 @@ async fn common_enospc_builder(
     let uri = volume_2.state.target.as_ref().unwrap().device_uri.as_str();
     let _drop_target2 = DeviceDisconnect(nvmeadm::NvmeTarget::try_from(uri).unwrap());
 
-    let pools = pools_api.get_pools().await.unwrap();
+    let pools = pools_api.get_pools(None).await.unwrap();
     tracing::info!(?pools, "Here's the pools");
 
     let replicas = replica_api.get_replicas().await.unwrap();
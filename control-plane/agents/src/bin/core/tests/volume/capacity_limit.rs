@@ -70,12 +70,7 @@ async fn grpc_create_volume_with_limit(
     match volume {
         Ok(_) => {
             volume_client
-                .destroy(
-                    &DestroyVolume {
-                        uuid: vol_uuid.try_into().unwrap(),
-                    },
-                    None,
-                )
+                .destroy(&DestroyVolume::new(&vol_uuid.try_into().unwrap()), None)
                 .await
                 .unwrap();
             assert!(expected_error.is_none());
@@ -0,0 +1,226 @@
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use std::collections::HashMap;
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        AddVolumeFrontendNode, CreateVolume, NexusNvmePreemption, NvmeReservation, PublishVolume,
+        RemoveVolumeFrontendNode, TargetPlacement, Volume, VolumeShareProtocol,
+    },
+};
+
+use uuid::Uuid;
+
+const SIZE: u64 = 50 * 1024 * 1024; // 50MiB
+
+#[tokio::test]
+async fn multi_node_frontend() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+
+    let create = |uuid: Uuid| CreateVolume {
+        uuid: uuid.try_into().unwrap(),
+        size: SIZE,
+        replicas: 1,
+        ..Default::default()
+    };
+    let publish = |volume: &Volume, multi_node: bool| {
+        PublishVolume::new(
+            volume.uuid().clone(),
+            Some(cluster.node(0)),
+            Some(VolumeShareProtocol::Nvmf),
+            HashMap::new(),
+            vec!["app-a".to_string()],
+        )
+        .with_multi_node(multi_node)
+    };
+    let frontend_nodes = |volume: &Volume| {
+        volume
+            .spec()
+            .active_config()
+            .map(|cfg| cfg.frontend().node_names())
+            .unwrap_or_default()
+    };
+
+    // Single node volumes can't gain other frontend nodes.
+    let volume = vol_cli.create(&create(Uuid::new_v4()), None).await.unwrap();
+    let volume = vol_cli
+        .publish(&publish(&volume, false), None)
+        .await
+        .unwrap();
+    let error = vol_cli
+        .add_frontend_node(
+            &AddVolumeFrontendNode::new(volume.uuid().clone(), "app-b".into()),
+            None,
+        )
+        .await
+        .expect_err("Volume is not multi-node");
+    assert_eq!(error.kind, ReplyErrorKind::PermissionDenied);
+
+    let volume = vol_cli.create(&create(Uuid::new_v4()), None).await.unwrap();
+    let volume = vol_cli
+        .publish(&publish(&volume, true), None)
+        .await
+        .unwrap();
+    assert_eq!(frontend_nodes(&volume), vec!["app-a".to_string()]);
+    let target_cfg = volume.spec().active_config().cloned().unwrap();
+    assert_eq!(
+        target_cfg.config().resv_type(),
+        NvmeReservation::ExclusiveAccessAllRegs
+    );
+
+    let add_b = AddVolumeFrontendNode::new(volume.uuid().clone(), "app-b".into());
+    let volume = vol_cli.add_frontend_node(&add_b, None).await.unwrap();
+    assert_eq!(
+        frontend_nodes(&volume),
+        vec!["app-a".to_string(), "app-b".to_string()]
+    );
+    // Adding the same node again is a no-op.
+    let volume = vol_cli.add_frontend_node(&add_b, None).await.unwrap();
+    assert_eq!(frontend_nodes(&volume).len(), 2);
+
+    let volume = vol_cli
+        .remove_frontend_node(
+            &RemoveVolumeFrontendNode::new(volume.uuid().clone(), "app-a".into()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(frontend_nodes(&volume), vec!["app-b".to_string()]);
+    // The target is recreated, preempting the registration of the removed node.
+    let removed = target_cfg.frontend().nodes_info()[0].resv_key();
+    let fenced_cfg = volume.spec().active_config().cloned().unwrap();
+    assert_ne!(fenced_cfg.uuid(), target_cfg.uuid());
+    assert_eq!(fenced_cfg.target().node(), target_cfg.target().node());
+    assert_eq!(
+        fenced_cfg.config().preempt_policy(),
+        NexusNvmePreemption::ArgKey(Some(removed))
+    );
+    assert_eq!(
+        volume.state().target.map(|nexus| nexus.uuid),
+        Some(fenced_cfg.uuid().clone())
+    );
+
+    // The last frontend node may only go away by unpublishing the volume.
+    let error = vol_cli
+        .remove_frontend_node(
+            &RemoveVolumeFrontendNode::new(volume.uuid().clone(), "app-b".into()),
+            None,
+        )
+        .await
+        .expect_err("Last frontend node");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+}
+
+#[tokio::test]
+async fn read_only_frontend() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = vol_cli
+        .publish(
+            &PublishVolume::new(
+                volume.uuid().clone(),
+                Some(cluster.node(0)),
+                Some(VolumeShareProtocol::Nvmf),
+                HashMap::new(),
+                vec!["app-a".to_string()],
+            )
+            .with_multi_node(true)
+            .with_read_only(true),
+            None,
+        )
+        .await
+        .unwrap();
+    let frontend = volume.spec().active_config().unwrap().frontend().clone();
+    assert!(frontend.read_only());
+
+    // Further readers keep the volume read-only.
+    let volume = vol_cli
+        .add_frontend_node(
+            &AddVolumeFrontendNode::new(volume.uuid().clone(), "app-b".into()),
+            None,
+        )
+        .await
+        .unwrap();
+    let frontend = volume.spec().active_config().unwrap().frontend().clone();
+    assert!(frontend.read_only());
+    assert_eq!(frontend.node_names().len(), 2);
+}
+
+#[tokio::test]
+async fn app_node_target_placement() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+
+    let create = |placement: TargetPlacement| CreateVolume {
+        uuid: Uuid::new_v4().try_into().unwrap(),
+        size: SIZE,
+        replicas: 1,
+        target_placement: Some(placement),
+        ..Default::default()
+    };
+    let publish = |volume: &Volume| {
+        PublishVolume::new(
+            volume.uuid().clone(),
+            None,
+            Some(VolumeShareProtocol::Nvmf),
+            HashMap::new(),
+            vec![cluster.node(2).to_string()],
+        )
+    };
+    let target_node = |volume: &Volume| volume.spec().target().map(|t| t.node().clone());
+
+    // By default, the target of a single replica volume is pinned to the replica node.
+    let volume = vol_cli
+        .create(&create(TargetPlacement::Any), None)
+        .await
+        .unwrap();
+    let volume = vol_cli.publish(&publish(&volume), None).await.unwrap();
+    assert_eq!(target_node(&volume), Some(cluster.node(0)));
+
+    // Otherwise, the target is placed on the application node.
+    let volume = vol_cli
+        .create(&create(TargetPlacement::AppNode), None)
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().target_placement(), TargetPlacement::AppNode);
+    let volume = vol_cli.publish(&publish(&volume), None).await.unwrap();
+    assert_eq!(target_node(&volume), Some(cluster.node(2)));
+}
@@ -68,6 +68,7 @@ async fn deleting_volume_reconcile(cluster: &Cluster) {
                 target_node: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -79,12 +80,7 @@ async fn deleting_volume_reconcile(cluster: &Cluster) {
 
     // 2. Attempt to delete the volume
     client
-        .destroy(
-            &DestroyVolume {
-                uuid: volume.uuid().to_owned(),
-            },
-            None,
-        )
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
         .await
         .expect_err("ETCD is paused...");
 
@@ -134,7 +130,7 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
         .await
         .unwrap();
 
-    let nodes = rest_api.nodes_api().get_nodes(None).await.unwrap();
+    let nodes = rest_api.nodes_api().get_nodes(None, None).await.unwrap();
     let replica_nodes = rest_api.replicas_api().get_replicas().await.unwrap();
     let replica_nodes = replica_nodes
         .into_iter()
@@ -162,6 +158,8 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -230,6 +228,8 @@ async fn unused_nexus_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -283,7 +283,7 @@ async fn unused_reconcile(cluster: &Cluster) {
         .values()
         .map(|r| r.node.clone().unwrap())
         .collect::<Vec<_>>();
-    let nodes = rest_api.nodes_api().get_nodes(None).await.unwrap();
+    let nodes = rest_api.nodes_api().get_nodes(None, None).await.unwrap();
     let unused_node = nodes
         .iter()
         .find(|r| !data_replicas_nodes.contains(&r.id))
@@ -317,6 +317,8 @@ async fn unused_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -341,6 +343,8 @@ async fn unused_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -429,6 +433,8 @@ async fn missing_nexus_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -1,12 +1,15 @@
 #![cfg(test)]
 
 mod affinity_group;
+mod auth;
 mod capacity;
 mod capacity_limit;
+mod frontend;
 mod garbage_collection;
 mod helpers;
 mod hotspare;
 mod resize;
+mod revision;
 mod snapshot;
 mod snapshot_clone;
 mod switchover;
@@ -130,6 +133,7 @@ async fn nexus_persistence_test_iteration(
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -208,6 +212,7 @@ async fn nexus_persistence_test_iteration(
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -243,12 +248,7 @@ async fn nexus_persistence_test_iteration(
     }
 
     volume_client
-        .destroy(
-            &DestroyVolume {
-                uuid: volume_state.uuid,
-            },
-            None,
-        )
+        .destroy(&DestroyVolume::new(&volume_state.uuid), None)
         .await
         .expect("Should be able to destroy the volume");
 
@@ -305,6 +305,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -324,6 +325,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume_state.uuid.clone(),
                 protocol: Default::default(),
                 frontend_hosts: vec![],
+                expected_revision: None,
             },
             None,
         )
@@ -338,6 +340,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume_state.uuid.clone(),
                 protocol: Default::default(),
                 frontend_hosts: vec![],
+                expected_revision: None,
             },
             None,
         )
@@ -348,6 +351,7 @@ async fn publishing_test(cluster: &Cluster) {
         .unshare(
             &UnshareVolume {
                 uuid: volume_state.uuid.clone(),
+                expected_revision: None,
             },
             None,
         )
@@ -358,6 +362,7 @@ async fn publishing_test(cluster: &Cluster) {
         .unshare(
             &UnshareVolume {
                 uuid: volume_state.uuid.clone(),
+                expected_revision: None,
             },
             None,
         )
@@ -372,6 +377,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -391,6 +397,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Nvmf),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -426,6 +433,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Iscsi),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -440,6 +448,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Nvmf),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -459,6 +468,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -509,12 +519,7 @@ async fn publishing_test(cluster: &Cluster) {
     tokio::time::sleep(std::time::Duration::from_secs(1)).await;
 
     volume_client
-        .destroy(
-            &DestroyVolume {
-                uuid: volume_state.uuid,
-            },
-            None,
-        )
+        .destroy(&DestroyVolume::new(&volume_state.uuid), None)
         .await
         .expect("Should be able to destroy the volume");
 
@@ -623,26 +628,14 @@ async fn replica_count_test(cluster: &Cluster) {
         .unwrap();
 
     let volume = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume.spec().uuid.clone(),
-                replicas: 3,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume.spec().uuid.clone(), 3), None)
         .await
         .expect("Should have enough nodes/pools to increase replica count");
     tracing::info!("Volume: {:?}", volume);
 
     let volume_state = volume.state();
     let error = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume_state.uuid.clone(),
-                replicas: 4,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume_state.uuid.clone(), 4), None)
         .await
         .expect_err("The volume is degraded (rebuild in progress)");
     tracing::error!("error: {:?}", error);
@@ -660,13 +653,7 @@ async fn replica_count_test(cluster: &Cluster) {
         .unwrap();
 
     let error = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume.uuid.clone(),
-                replicas: 4,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume.uuid.clone(), 4), None)
         .await
         .expect_err("Not enough pools available");
     tracing::error!("error: {:?}", error);
@@ -681,26 +668,14 @@ async fn replica_count_test(cluster: &Cluster) {
     ));
 
     let volume = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume.uuid.clone(),
-                replicas: 2,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume.uuid.clone(), 2), None)
         .await
         .expect("Should be able to bring the replica count back down");
     tracing::info!("Volume: {:?}", volume);
 
     let volume_state = volume.state();
     let volume = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume_state.uuid.clone(),
-                replicas: 1,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume_state.uuid.clone(), 1), None)
         .await
         .expect("Should be able to bring the replica to 1");
     tracing::info!("Volume: {:?}", volume);
@@ -713,13 +688,7 @@ async fn replica_count_test(cluster: &Cluster) {
         .any(|n| n.children.iter().any(|c| c.state != ChildState::Online)));
 
     let error = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume_state.uuid.clone(),
-                replicas: 0,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume_state.uuid.clone(), 0), None)
         .await
         .expect_err("Can't bring the replica count down to 0");
     tracing::error!("error: {:?}", error);
@@ -734,13 +703,7 @@ async fn replica_count_test(cluster: &Cluster) {
     ));
 
     let volume = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume_state.uuid.clone(),
-                replicas: 2,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume_state.uuid.clone(), 2), None)
         .await
         .expect("Should be able to bring the replica count back to 2");
     tracing::info!("Volume: {:?}", volume);
@@ -752,24 +715,13 @@ async fn replica_count_test(cluster: &Cluster) {
         .unwrap();
 
     let volume = volume_client
-        .set_replica(
-            &SetVolumeReplica {
-                uuid: volume_state.uuid.clone(),
-                replicas: 3,
-            },
-            None,
-        )
+        .set_replica(&SetVolumeReplica::new(volume_state.uuid.clone(), 3), None)
         .await
         .expect("Should be able to bring the replica count back to 3");
     tracing::info!("Volume: {:?}", volume);
 
     volume_client
-        .destroy(
-            &DestroyVolume {
-                uuid: volume.spec().uuid,
-            },
-            None,
-        )
+        .destroy(&DestroyVolume::new(&volume.spec().uuid), None)
         .await
         .expect("Should be able to destroy the volume");
 
@@ -815,12 +767,7 @@ async fn smoke_test(cluster: &Cluster) {
     assert_eq!(Some(&volume), volumes.first());
 
     volume_client
-        .destroy(
-            &DestroyVolume {
-                uuid: volume.spec().uuid,
-            },
-            None,
-        )
+        .destroy(&DestroyVolume::new(&volume.spec().uuid), None)
         .await
         .expect("Should be able to destroy the volume");
 
@@ -1,14 +1,17 @@
-use deployer_cluster::{Cluster, ClusterBuilder};
+use deployer_cluster::{Cluster, ClusterBuilder, FindVolumeRequest};
 use grpc::operations::{
     nexus::traits::NexusOperations, replica::traits::ReplicaOperations,
     volume::traits::VolumeOperations,
 };
-use std::time::Duration;
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
 use stor_port::{
     transport_api::{ReplyError, ReplyErrorKind},
-    types::v0::transport::{
-        CreateVolume, DestroyVolume, Filter, PublishVolume, ResizeVolume, Volume, VolumeId,
-        VolumeShareProtocol,
+    types::v0::{
+        openapi::models,
+        transport::{
+            CreateVolume, DestroyVolume, Filter, PublishVolume, ResizeVolume, SetVolumeProperty,
+            Volume, VolumeAutoGrow, VolumeId, VolumeProperty, VolumeShareProtocol,
+        },
     },
 };
 
@@ -93,11 +96,7 @@ async fn resize_unpublished_and_published() {
 
     let resized_volume = vol_cli
         .resize(
-            &ResizeVolume {
-                uuid: volume.uuid().clone(),
-                requested_size: EXPANDED_SIZE,
-                cluster_capacity_limit: None,
-            },
+            &ResizeVolume::new(volume.uuid().clone(), EXPANDED_SIZE, None),
             None,
         )
         .await
@@ -114,12 +113,7 @@ async fn resize_unpublished_and_published() {
     .await;
 
     let _ = vol_cli
-        .destroy(
-            &DestroyVolume {
-                uuid: volume.uuid().clone(),
-            },
-            None,
-        )
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
         .await;
 
     // Test resizing a published volume.
@@ -160,11 +154,7 @@ async fn resize_published(cluster: &Cluster) {
 
     let resized_volume = vol_cli
         .resize(
-            &ResizeVolume {
-                uuid: volume.uuid().clone(),
-                requested_size: EXPANDED_SIZE,
-                cluster_capacity_limit: None,
-            },
+            &ResizeVolume::new(volume.uuid().clone(), EXPANDED_SIZE, None),
             None,
         )
         .await
@@ -218,11 +208,7 @@ async fn resize_on_no_capacity_pool() {
 
     let resized_volume = vol_cli
         .resize(
-            &ResizeVolume {
-                uuid: volume.uuid().clone(),
-                requested_size: EXPANDED_SIZE,
-                cluster_capacity_limit: None,
-            },
+            &ResizeVolume::new(volume.uuid().clone(), EXPANDED_SIZE, None),
             None,
         )
         .await
@@ -240,11 +226,11 @@ async fn resize_on_no_capacity_pool() {
     // try a resize again, this time setting cluster capacity limit.
     let _ = vol_cli
         .resize(
-            &ResizeVolume {
-                uuid: volume.uuid().clone(),
-                requested_size: EXPANDED_SIZE,
-                cluster_capacity_limit: Some(EXPANDED_SIZE + CAPACITY_LIMIT_DIFF),
-            },
+            &ResizeVolume::new(
+                volume.uuid().clone(),
+                EXPANDED_SIZE,
+                Some(EXPANDED_SIZE + CAPACITY_LIMIT_DIFF),
+            ),
             None,
         )
         .await
@@ -335,11 +321,11 @@ async fn resize_with_cluster_capacity_limit_concurrent() {
         tokio::spawn(async move {
             let hdl = refp_vol_cli
                 .resize(
-                    &ResizeVolume {
-                        uuid: volume_id.try_into().unwrap(),
-                        requested_size: EXPANDED_SIZE,
-                        cluster_capacity_limit: Some(4 * EXPANDED_SIZE),
-                    },
+                    &ResizeVolume::new(
+                        volume_id.try_into().unwrap(),
+                        EXPANDED_SIZE,
+                        Some(4 * EXPANDED_SIZE),
+                    ),
                     None,
                 )
                 .await;
@@ -387,11 +373,7 @@ async fn grpc_resize_volume_with_limit(
 
     let result = volume_client
         .resize(
-            &ResizeVolume {
-                uuid: volume.uuid().clone(),
-                requested_size: EXPANDED_SIZE,
-                cluster_capacity_limit: capacity,
-            },
+            &ResizeVolume::new(volume.uuid().clone(), EXPANDED_SIZE, capacity),
             None,
         )
         .await;
@@ -402,9 +384,7 @@ async fn grpc_resize_volume_with_limit(
             assert!(resized_volume.spec().size == EXPANDED_SIZE);
             volume_client
                 .destroy(
-                    &DestroyVolume {
-                        uuid: resized_volume.uuid().try_into().unwrap(),
-                    },
+                    &DestroyVolume::new(&resized_volume.uuid().try_into().unwrap()),
                     None,
                 )
                 .await
@@ -416,9 +396,7 @@ async fn grpc_resize_volume_with_limit(
                                                       // Volume not needed anymore.
             volume_client
                 .destroy(
-                    &DestroyVolume {
-                        uuid: volume.uuid().try_into().unwrap(),
-                    },
+                    &DestroyVolume::new(&volume.uuid().try_into().unwrap()),
                     None,
                 )
                 .await
@@ -427,6 +405,274 @@ async fn grpc_resize_volume_with_limit(
     }
 }
 
+#[tokio::test]
+async fn autogrow_policy() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+
+    // The max size can't be smaller than the volume.
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                thin: true,
+                autogrow: Some(VolumeAutoGrow::new(80, SIZE, SIZE / 2)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("Max size is smaller than the volume");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let autogrow = VolumeAutoGrow::new(80, SIZE, EXPANDED_SIZE);
+
+    // Thick provisioned volumes are fully allocated, and so can't grow automatically.
+    let error = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                thin: false,
+                autogrow: Some(autogrow.clone()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("Thick volumes can't have an autogrow policy");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    let thick = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                thin: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let error = vol_cli
+        .set_property(
+            &SetVolumeProperty::new(
+                thick.uuid().clone(),
+                VolumeProperty::AutoGrow(Some(autogrow.clone())),
+            ),
+            None,
+        )
+        .await
+        .expect_err("Thick volumes can't have an autogrow policy");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    vol_cli
+        .destroy(&DestroyVolume::new(thick.uuid()), None)
+        .await
+        .unwrap();
+
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: SIZE,
+                replicas: 1,
+                thin: true,
+                autogrow: Some(autogrow.clone()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().autogrow, Some(autogrow));
+
+    let error = vol_cli
+        .set_property(
+            &SetVolumeProperty::new(
+                volume.uuid().clone(),
+                VolumeProperty::AutoGrow(Some(VolumeAutoGrow::new(0, SIZE, EXPANDED_SIZE))),
+            ),
+            None,
+        )
+        .await
+        .expect_err("Threshold must be a valid percentage");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let volume = vol_cli
+        .set_property(
+            &SetVolumeProperty::new(volume.uuid().clone(), VolumeProperty::AutoGrow(None)),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().autogrow, None);
+
+    vol_cli
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
+        .await
+        .unwrap();
+}
+
+struct DeviceDisconnect(nvmeadm::NvmeTarget);
+impl Drop for DeviceDisconnect {
+    fn drop(&mut self) {
+        if self.0.disconnect().is_err() {
+            std::process::Command::new("sudo")
+                .args(["nvme", "disconnect-all"])
+                .status()
+                .unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn autogrow_expansion() {
+    const MIB: u64 = 1024 * 1024;
+    let cache_period = Duration::from_millis(250);
+    let reconcile_period = Duration::from_millis(1000);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_io_engines(1)
+        .with_pool(0, "malloc:///p1?size_mb=200")
+        .with_csi(false, true)
+        .with_options(|o| o.with_isolated_io_engine(true))
+        .with_cache_period(&humantime::Duration::from(cache_period).to_string())
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let vol_cli = cluster.grpc_client().volume();
+    let api_client = cluster.rest_v00();
+    let volumes_api = api_client.volumes_api();
+
+    // Grow by 8MiB once half of the volume is allocated, up to 32MiB.
+    let volume = vol_cli
+        .create(
+            &CreateVolume {
+                uuid: Uuid::new_v4().try_into().unwrap(),
+                size: 20 * MIB,
+                replicas: 1,
+                thin: true,
+                autogrow: Some(VolumeAutoGrow::new(50, 8 * MIB, 32 * MIB)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volumes_api
+        .put_volume_target(
+            volume.uuid(),
+            models::PublishVolumeBody::new_all(
+                HashMap::new(),
+                None,
+                cluster.node(0).to_string(),
+                models::VolumeShareProtocol::Nvmf,
+                None,
+                cluster.csi_node(0),
+                None,
+                None,
+            ),
+        )
+        .await
+        .unwrap();
+    let uri = volume.state.target.as_ref().unwrap().device_uri.as_str();
+    let _drop_target = DeviceDisconnect(nvmeadm::NvmeTarget::try_from(uri).unwrap());
+
+    let mut node = cluster.csi_node_client(0).await.unwrap();
+    node.node_stage_volume(&volume, HashMap::new())
+        .await
+        .unwrap();
+    let device_path = node
+        .internal()
+        .find_volume(FindVolumeRequest {
+            volume_id: volume.spec.uuid.to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .device_path;
+    // 12MiB of 20MiB crosses the threshold, the volume is grown by a single step.
+    write_mib(&cluster, &device_path, 0, 12).await;
+    wait_volume_size(&cluster, volume.spec.uuid, 28 * MIB).await;
+    tokio::time::sleep(reconcile_period * 5).await;
+    let volume = volumes_api.get_volume(&volume.spec.uuid).await.unwrap();
+    assert_eq!(
+        volume.spec.size,
+        28 * MIB,
+        "12MiB of 28MiB is below the threshold"
+    );
+
+    // 24MiB of 28MiB crosses the threshold again, but the step is capped by the max size.
+    write_mib(&cluster, &device_path, 12, 12).await;
+    wait_volume_size(&cluster, volume.spec.uuid, 32 * MIB).await;
+
+    // Once at the max size, the volume is never grown again.
+    write_mib(&cluster, &device_path, 24, 6).await;
+    tokio::time::sleep(reconcile_period * 5).await;
+    let volume = volumes_api.get_volume(&volume.spec.uuid).await.unwrap();
+    assert_eq!(volume.spec.size, 32 * MIB);
+    assert!(volume.state.size >= 32 * MIB);
+
+    node.node_unstage_volume(&volume).await.unwrap();
+}
+
+/// Write `count` MiB of random data to the device, starting at the `seek` MiB offset.
+async fn write_mib(cluster: &Cluster, device_path: &str, seek: u64, count: u64) {
+    let of = format!("of={device_path}");
+    let seek = format!("seek={seek}");
+    let count = format!("count={count}");
+    let output = cluster
+        .composer()
+        .exec(
+            cluster.csi_container(0).as_str(),
+            vec![
+                "dd",
+                "if=/dev/urandom",
+                of.as_str(),
+                "bs=1M",
+                seek.as_str(),
+                count.as_str(),
+                "oflag=direct",
+            ],
+        )
+        .await;
+    tracing::info!("\n{:?}", output);
+}
+
+/// Wait until the volume has been grown to the expected size.
+async fn wait_volume_size(cluster: &Cluster, uuid: Uuid, expected_size: u64) {
+    let volumes_api = cluster.rest_v00().volumes_api();
+    let timeout = Duration::from_secs(30);
+    let start = std::time::Instant::now();
+    loop {
+        let volume = volumes_api.get_volume(&uuid).await.unwrap();
+        if volume.spec.size == expected_size {
+            return;
+        }
+        assert!(
+            volume.spec.size < expected_size,
+            "Volume grown beyond {expected_size}: {volume:?}"
+        );
+        if std::time::Instant::now() > (start + timeout) {
+            panic!("Timeout waiting for the volume to grow to {expected_size}: {volume:?}");
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
+
 // Creates count number of volumes, and return the uuid of volume to be resized.
 async fn create_volumes(volume_client: &dyn VolumeOperations, count: u64) -> Vec<Uuid> {
     let mut volumes = Vec::with_capacity(count as usize);
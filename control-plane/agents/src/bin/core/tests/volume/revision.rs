@@ -0,0 +1,114 @@
+#![cfg(test)]
+
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use std::time::Duration;
+use stor_port::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, DestroyVolume, PublishVolume, SetVolumeReplica, UnpublishVolume, VolumeId,
+    },
+};
+
+#[tokio::test]
+async fn volume_revision() {
+    let cluster = ClusterBuilder::builder()
+        .with_io_engines(2)
+        .with_tmpfs_pool(100 * 1024 * 1024)
+        .with_cache_period("1s")
+        // don't let the reconcile change the spec revision
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+    let client = cluster.grpc_client().volume();
+
+    let volume = client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let revision = volume.spec().revision;
+    assert_eq!(revision, 1);
+
+    let error = client
+        .set_replica(
+            &SetVolumeReplica::new(volume.uuid().clone(), 2)
+                .with_expected_revision(Some(revision - 1)),
+            None,
+        )
+        .await
+        .expect_err("The spec revision has moved on");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    let volume = client
+        .set_replica(
+            &SetVolumeReplica::new(volume.uuid().clone(), 2).with_expected_revision(Some(revision)),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().num_replicas, 2);
+    assert_eq!(volume.spec().revision, revision + 1);
+
+    let publish = PublishVolume::new(
+        volume.uuid().clone(),
+        Some(cluster.node(0)),
+        None,
+        Default::default(),
+        vec![],
+    );
+    let error = client
+        .publish(
+            &publish.clone().with_expected_revision(Some(revision)),
+            None,
+        )
+        .await
+        .expect_err("The spec revision has moved on");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let volume = client
+        .publish(&publish.with_expected_revision(Some(revision + 1)), None)
+        .await
+        .unwrap();
+    assert!(volume.spec().target().is_some());
+    let revision = volume.spec().revision;
+
+    let unpublish = UnpublishVolume::new(volume.uuid(), false);
+    let error = client
+        .unpublish(
+            &unpublish.clone().with_expected_revision(Some(revision - 1)),
+            None,
+        )
+        .await
+        .expect_err("The spec revision has moved on");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let volume = client
+        .unpublish(&unpublish.with_expected_revision(Some(revision)), None)
+        .await
+        .unwrap();
+    let revision = volume.spec().revision;
+
+    let error = client
+        .destroy(
+            &DestroyVolume::new(volume.uuid()).with_expected_revision(Some(revision - 1)),
+            None,
+        )
+        .await
+        .expect_err("The spec revision has moved on");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    client
+        .destroy(
+            &DestroyVolume::new(volume.uuid()).with_expected_revision(Some(revision)),
+            None,
+        )
+        .await
+        .unwrap();
+}
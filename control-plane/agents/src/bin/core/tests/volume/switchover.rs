@@ -67,6 +67,7 @@ async fn old_nexus_delete_after_vol_destroy() {
                 target_node: Some(cluster.node(0)),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
             },
             None,
         )
@@ -85,6 +86,7 @@ async fn old_nexus_delete_after_vol_destroy() {
                 reuse_existing: false,
                 frontend_node: cluster.node(1),
                 reuse_existing_fallback: false,
+                expected_revision: None,
             },
             None,
         )
@@ -136,12 +138,7 @@ async fn old_nexus_delete_after_vol_destroy() {
     assert_eq!(nexus_spec.len(), 2, "spec should contain 2 nexuses");
 
     vol_client
-        .destroy(
-            &DestroyVolume {
-                uuid: vol.uuid().clone(),
-            },
-            None,
-        )
+        .destroy(&DestroyVolume::new(vol.uuid()), None)
         .await
         .expect("failed to delete volume");
 
@@ -235,6 +232,7 @@ async fn lazy_delete_shutdown_targets() {
                 target_node: Some(cluster.node(0)),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
             },
             None,
         )
@@ -257,6 +255,7 @@ async fn lazy_delete_shutdown_targets() {
                 reuse_existing: true,
                 frontend_node: cluster.node(0),
                 reuse_existing_fallback: false,
+                expected_revision: None,
             },
             None,
         )
@@ -272,6 +271,7 @@ async fn lazy_delete_shutdown_targets() {
                 reuse_existing: true,
                 frontend_node: cluster.node(1),
                 reuse_existing_fallback: false,
+                expected_revision: None,
             },
             None,
         )
@@ -377,6 +377,7 @@ async fn volume_republish_nexus_recreation() {
                 target_node: Some(replica_node.into()),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
             },
             None,
         )
@@ -420,6 +421,7 @@ async fn volume_republish_nexus_recreation() {
                 reuse_existing: true,
                 frontend_node: cluster.node(1),
                 reuse_existing_fallback: false,
+                expected_revision: None,
             },
             None,
         )
@@ -485,6 +487,7 @@ async fn node_exhaustion() {
                 target_node: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
             },
             None,
         )
@@ -501,6 +504,7 @@ async fn node_exhaustion() {
                 reuse_existing: false,
                 frontend_node: cluster.node(1),
                 reuse_existing_fallback: false,
+                expected_revision: None,
             },
             None,
         )
@@ -517,6 +521,7 @@ async fn node_exhaustion() {
                 reuse_existing: false,
                 frontend_node: cluster.node(1),
                 reuse_existing_fallback: false,
+                expected_revision: None,
             },
             None,
         )
@@ -533,6 +538,7 @@ async fn node_exhaustion() {
                 reuse_existing: false,
                 frontend_node: cluster.node(1),
                 reuse_existing_fallback: false,
+                expected_revision: None,
             },
             None,
         )
@@ -542,3 +548,72 @@ async fn node_exhaustion() {
     assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
     assert_eq!(error.resource, ResourceKind::Node);
 }
+
+#[tokio::test]
+async fn single_replica_target_move() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_tmpfs_pool(POOL_SIZE_BYTES)
+        .build()
+        .await
+        .unwrap();
+
+    let client = cluster.grpc_client().volume();
+    let volume = client
+        .create(
+            &CreateVolume {
+                uuid: VOLUME_UUID.try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let replica_node = volume
+        .state()
+        .replica_topology
+        .values()
+        .next()
+        .and_then(|replica| replica.node().clone())
+        .expect("Should have one replica");
+
+    let volume = client
+        .publish(
+            &PublishVolume {
+                uuid: VOLUME_UUID.try_into().unwrap(),
+                share: Some(VolumeShareProtocol::Nvmf),
+                target_node: None,
+                publish_context: HashMap::new(),
+                frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    // 1 replica volumes are pinned to the replica node...
+    assert_eq!(volume.state().target.unwrap().node, replica_node);
+
+    let republish = RepublishVolume {
+        uuid: VOLUME_UUID.try_into().unwrap(),
+        share: VolumeShareProtocol::Nvmf,
+        target_node: None,
+        reuse_existing: false,
+        frontend_node: cluster.node(1),
+        reuse_existing_fallback: false,
+        expected_revision: None,
+    };
+    // ...but a target move must still place the target elsewhere.
+    let volume = client.republish(&republish, None).await.unwrap();
+    assert_ne!(volume.state().target.unwrap().node, replica_node);
+
+    let error = client
+        .republish(&republish, None)
+        .await
+        .expect_err("No other node to move the target to");
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+}
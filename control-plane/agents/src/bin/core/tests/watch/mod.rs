@@ -1,5 +1,6 @@
 use deployer_cluster::ClusterBuilder;
-use grpc::operations::volume::traits::VolumeOperations;
+use futures::StreamExt;
+use grpc::operations::{volume::traits::VolumeOperations, watch::traits::WatchOperations};
 use http::{Request, Response};
 use once_cell::sync::OnceCell;
 use std::{convert::Infallible, net::SocketAddr, str::FromStr, time::Duration};
@@ -13,7 +14,10 @@ use stor_port::{
             },
             models,
         },
-        transport::{CreateVolume, Volume, VolumeId, WatchResourceId},
+        transport::{
+            CreateVolume, DestroyVolume, EventAction, EventResource, Volume, VolumeId, WatchEvents,
+            WatchResourceId, WatchType,
+        },
     },
 };
 use tokio::net::TcpStream;
@@ -138,3 +142,82 @@ async fn watch() {
     let watches = client.get_watch_volume(&volume.spec().uuid).await.unwrap();
     assert!(watches.is_empty());
 }
+
+#[tokio::test]
+async fn watch_events() {
+    let cluster = ClusterBuilder::builder().with_pools(1).build().await;
+    let cluster = cluster.unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let watch_client = cluster.grpc_client().watch();
+
+    let labels = std::collections::HashMap::from([("tier".to_string(), "gold".to_string())]);
+    let mut events = watch_client
+        .events(
+            &WatchEvents {
+                resources: vec![EventResource::Volume],
+                labels: labels.clone(),
+                watch_type: WatchType::Desired,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    // not labelled, so it should be filtered out
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 10 * 1024 * 1024,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 10 * 1024 * 1024,
+                replicas: 1,
+                labels: Some(labels.clone()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(2), events.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.resource, EventResource::Volume);
+    assert_eq!(event.id, volume.uuid().to_string());
+    assert_eq!(event.watch_type, WatchType::Desired);
+    assert_eq!(event.action, EventAction::Updated);
+    assert_eq!(event.labels, labels);
+
+    volume_client
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
+        .await
+        .unwrap();
+
+    let deleted = loop {
+        let event = tokio::time::timeout(Duration::from_secs(2), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.id, volume.uuid().to_string());
+        if event.action == EventAction::Deleted {
+            break event;
+        }
+    };
+    assert_eq!(deleted.labels, labels);
+    assert!(deleted.value.is_none());
+}
@@ -0,0 +1,123 @@
+use crate::controller::{
+    registry::Registry,
+    resources::{operations_helper::GuardedOperationsHelper, OperationGuardArc},
+};
+use agents::errors::SvcError;
+use stor_port::types::v0::{
+    store::{
+        auth::{NvmfAuthRef, NvmfAuthSecret},
+        definitions::ObjectKey,
+        volume::{VolumeOperation, VolumeSpec},
+    },
+    transport::{DhChapKeys, RotateVolumeAuth, VolumeAuth},
+};
+
+impl OperationGuardArc<VolumeSpec> {
+    /// Rotate the DH-HMAC-CHAP secrets of the volume target, enabling authentication if the
+    /// volume had none.
+    /// The new secrets are used by the target from its next publish or republish onwards, and
+    /// until then the target keeps using the previous ones.
+    pub(super) async fn rotate_auth(
+        &mut self,
+        registry: &Registry,
+        request: &RotateVolumeAuth,
+    ) -> Result<(), SvcError> {
+        snafu::ensure!(request.auth.valid(), agents::errors::InvalidArguments {});
+        crate::controller::io_engine::volume_auth_capable("rotate_volume_auth")?;
+        registry.nvmf_auth().enabled()?;
+
+        let previous = self.as_ref().auth().cloned();
+        let generation = previous.as_ref().map_or(0, |auth| auth.generation() + 1);
+        let auth_ref = NvmfAuthRef::new(self.uuid(), generation);
+
+        let state = registry.volume_state(&request.uuid).await?;
+        let operation = VolumeOperation::RotateAuth(auth_ref.clone());
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+
+        let result = registry.store_volume_auth(&auth_ref, &request.auth).await;
+        self.complete_update(registry, result, spec_clone).await?;
+
+        // the previous secrets are only kept if the target is still shared with them
+        registry
+            .delete_stale_volume_auth(self.as_ref(), previous.as_ref())
+            .await;
+        Ok(())
+    }
+}
+
+impl Registry {
+    /// Store the DH-HMAC-CHAP secrets of a volume target, generating those which were not supplied.
+    pub(crate) async fn store_volume_auth(
+        &self,
+        auth_ref: &NvmfAuthRef,
+        auth: &VolumeAuth,
+    ) -> Result<(), SvcError> {
+        let keys = auth.keys().map_err(|_| SvcError::InvalidArguments {})?;
+        let secret = self.nvmf_auth().seal(auth_ref, &keys)?;
+        self.store_obj(&secret).await
+    }
+
+    /// Get the DH-HMAC-CHAP secrets of the volume target, if it has any.
+    /// These are the latest secrets, which the target is shared with on its next publish.
+    pub(crate) async fn volume_auth(
+        &self,
+        volume: &VolumeSpec,
+    ) -> Result<Option<DhChapKeys>, SvcError> {
+        self.load_volume_auth(volume.auth()).await
+    }
+
+    /// Get the DH-HMAC-CHAP secrets which the active volume target is shared with, if any.
+    /// After a rotation, these are the previous secrets until the target is republished.
+    pub(crate) async fn volume_target_auth(
+        &self,
+        volume: &VolumeSpec,
+    ) -> Result<Option<DhChapKeys>, SvcError> {
+        self.load_volume_auth(volume.target_auth()).await
+    }
+
+    async fn load_volume_auth(
+        &self,
+        auth_ref: Option<&NvmfAuthRef>,
+    ) -> Result<Option<DhChapKeys>, SvcError> {
+        match auth_ref {
+            None => Ok(None),
+            Some(auth_ref) => {
+                let secret: NvmfAuthSecret = self.load_obj(&auth_ref.key()).await?;
+                self.nvmf_auth().open(&secret).map(Some)
+            }
+        }
+    }
+
+    /// Delete the `previous` DH-HMAC-CHAP secrets of the volume target, unless they're still the
+    /// latest secrets or those which the active target is shared with.
+    pub(crate) async fn delete_stale_volume_auth(
+        &self,
+        volume: &VolumeSpec,
+        previous: Option<&NvmfAuthRef>,
+    ) {
+        let Some(previous) = previous else {
+            return;
+        };
+        if volume.auth() == Some(previous) || volume.target_auth() == Some(previous) {
+            return;
+        }
+        if let Err(error) = self.delete_kv(&previous.key().key()).await {
+            tracing::warn!(volume.uuid=%volume.uuid, auth.generation=previous.generation(), error=%error,
+                "Failed to delete the stale volume target secrets"
+            );
+        }
+    }
+
+    /// Delete the DH-HMAC-CHAP secrets of the volume target, if it has any.
+    pub(crate) async fn delete_volume_auth(&self, volume: &VolumeSpec) -> Result<(), SvcError> {
+        let target_auth = volume
+            .target_config
+            .as_ref()
+            .and_then(|config| config.auth())
+            .filter(|auth| volume.auth() != Some(auth));
+        for auth_ref in volume.auth().into_iter().chain(target_auth) {
+            self.delete_kv(&auth_ref.key().key()).await?;
+        }
+        Ok(())
+    }
+}
@@ -110,6 +110,13 @@ impl CreateVolumeExeVal for SnapshotCloneOp<'_> {
                 || new_volume.allowed_nodes().len() >= new_volume.replicas as usize,
             errors::InvalidArguments {}
         );
+        snafu::ensure!(
+            new_volume.auth.as_ref().map_or(true, |a| a.valid()),
+            errors::InvalidArguments {}
+        );
+        if new_volume.auth.is_some() {
+            crate::controller::io_engine::volume_auth_capable("create_snapshot_volume")?;
+        }
         snafu::ensure!(new_volume.thin, errors::ClonedSnapshotVolumeThin {});
         snafu::ensure!(snapshot.status().created(), errors::SnapshotNotCreated {});
         snafu::ensure!(
@@ -0,0 +1,200 @@
+use crate::controller::{
+    registry::Registry,
+    resources::{
+        operations::{ResourceLifecycle, ResourceSharing, ResourceShutdownOperations},
+        operations_helper::GuardedOperationsHelper,
+        OperationGuardArc,
+    },
+};
+use agents::errors::SvcError;
+use stor_port::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::volume::{
+            FrontendConfig, InitiatorAC, RepublishOperation, VolumeOperation, VolumeSpec,
+        },
+        transport::{
+            AddVolumeFrontendNode, DestroyNexus, HostNqn, RemoveVolumeFrontendNode, ShareNexus,
+            ShutdownNexus, VolumeId,
+        },
+    },
+};
+
+impl OperationGuardArc<VolumeSpec> {
+    /// Allow another frontend node to attach to the target of a multi-node volume.
+    pub(super) async fn add_frontend_node(
+        &mut self,
+        registry: &Registry,
+        request: &AddVolumeFrontendNode,
+    ) -> Result<(), SvcError> {
+        let node = request.frontend_node.to_string();
+        let frontend = self.multi_node_frontend(&node)?;
+        if frontend.nodename_allowed(&node) {
+            return Ok(());
+        }
+
+        let nqn = HostNqn::from_nodename(&node);
+        let frontend = frontend.with_node(InitiatorAC::new(node, nqn));
+        self.set_frontend(registry, &request.uuid, frontend).await
+    }
+
+    /// Fence a frontend node off the target of a multi-node volume.
+    /// The last frontend node cannot be removed, the volume should be unpublished instead.
+    pub(super) async fn remove_frontend_node(
+        &mut self,
+        registry: &Registry,
+        request: &RemoveVolumeFrontendNode,
+    ) -> Result<(), SvcError> {
+        let node = request.frontend_node.as_str();
+        let frontend = self.multi_node_frontend(node)?;
+        let Some(initiator) = frontend
+            .nodes_info()
+            .iter()
+            .find(|info| info.node_name() == node)
+            .cloned()
+        else {
+            return Ok(());
+        };
+        snafu::ensure!(
+            frontend.nodes_info().len() > 1,
+            agents::errors::InvalidArguments {}
+        );
+
+        let frontend = frontend.without_node(node);
+        self.fence_frontend_node(registry, &request.uuid, frontend, &initiator)
+            .await
+    }
+
+    /// Get the frontend configuration of the published volume, which must allow multiple nodes.
+    fn multi_node_frontend(&self, node: &str) -> Result<FrontendConfig, SvcError> {
+        let Some(config) = self.as_ref().active_config() else {
+            return Err(SvcError::VolumeNotPublished {
+                vol_id: self.uuid().to_string(),
+            });
+        };
+        if !config.frontend().multi_node() {
+            return Err(SvcError::FrontendNodeNotAllowed {
+                node: node.to_string(),
+                vol_id: self.uuid().to_string(),
+            });
+        }
+        Ok(config.frontend().clone())
+    }
+
+    /// Recreate the target on its current node for the remaining `frontend` nodes, preempting the
+    /// reservation registration of the removed `initiator`.
+    /// Shutting down the current target also drops the connections of the removed node, which
+    /// can't connect to the new target as it's no longer in the host access list.
+    async fn fence_frontend_node(
+        &mut self,
+        registry: &Registry,
+        volume_id: &VolumeId,
+        frontend: FrontendConfig,
+        initiator: &InitiatorAC,
+    ) -> Result<(), SvcError> {
+        let spec = self.as_ref().clone();
+        let state = registry.volume_state(volume_id).await?;
+        let (target, share) = match spec.active_config() {
+            Some(config) => match config.target().protocol() {
+                Some(share) => Ok((config.target().clone(), *share)),
+                None => Err(SvcError::NotShared {
+                    kind: ResourceKind::Volume,
+                    id: volume_id.to_string(),
+                }),
+            },
+            None => Err(SvcError::VolumeNotPublished {
+                vol_id: volume_id.to_string(),
+            }),
+        }?;
+
+        let mut older_nexus = registry.specs().nexus(target.nexus()).await?;
+        let target_cfg = self
+            .next_target_config(
+                registry,
+                target.node(),
+                &Some(share),
+                &frontend.node_names(),
+                true,
+                frontend.read_only(),
+            )
+            .await
+            .with_preempt_key(initiator.resv_key());
+        let dhchap = registry.volume_auth(self.as_ref()).await?;
+        let previous_auth = spec.target_auth().cloned();
+        let operation = VolumeOperation::Republish(RepublishOperation::new(target_cfg.clone()));
+
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+
+        let older_nexus_id = older_nexus.uuid().clone();
+        let result = older_nexus
+            .shutdown(registry, &ShutdownNexus::new(older_nexus_id, true))
+            .await;
+        self.validate_update_step(registry, result, &spec_clone)
+            .await?;
+
+        let result = self.create_nexus(registry, &target_cfg).await;
+        let (mut nexus, nexus_state) = self
+            .validate_update_step(registry, result, &spec_clone)
+            .await?;
+        let allowed_hosts = target_cfg.frontend().node_nqns();
+        let result = match nexus
+            .share(
+                registry,
+                &ShareNexus::new(&nexus_state, share, allowed_hosts).with_dhchap(dhchap),
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                // the reconcilers will pick up the slack if we can't revert inline
+                nexus
+                    .destroy(registry, &DestroyNexus::from(nexus_state).with_disown_all())
+                    .await
+                    .ok();
+                Err(error)
+            }
+        };
+
+        self.complete_update(registry, result, spec_clone).await?;
+        registry
+            .delete_stale_volume_auth(self.as_ref(), previous_auth.as_ref())
+            .await;
+        Ok(())
+    }
+
+    /// Update the frontend nodes of the volume and share the target again so its host ACL
+    /// matches them.
+    /// If the target is not currently available, the nexus reconciler applies the ACL later.
+    async fn set_frontend(
+        &mut self,
+        registry: &Registry,
+        volume_id: &VolumeId,
+        frontend: FrontendConfig,
+    ) -> Result<(), SvcError> {
+        let state = registry.volume_state(volume_id).await?;
+        let dhchap = registry.volume_target_auth(self.as_ref()).await?;
+
+        let operation = VolumeOperation::SetFrontendNodes(frontend.clone());
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+
+        let protocol = state.target_protocol();
+        let result = match (state.target, protocol) {
+            (Some(nexus_state), Some(protocol)) => {
+                match registry.specs().nexus(&nexus_state.uuid).await {
+                    Ok(mut nexus) => nexus
+                        .share(
+                            registry,
+                            &ShareNexus::new(&nexus_state, protocol, frontend.node_nqns())
+                                .with_dhchap(dhchap),
+                        )
+                        .await
+                        .map(|_| ()),
+                    Err(error) => Err(error),
+                }
+            }
+            _ => Ok(()),
+        };
+
+        self.complete_update(registry, result, spec_clone).await
+    }
+}
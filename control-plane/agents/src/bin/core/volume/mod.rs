@@ -2,7 +2,9 @@ use crate::controller::registry::Registry;
 use grpc::operations::volume::server::VolumeServer;
 use std::sync::Arc;
 
+mod auth;
 mod clone_operations;
+mod frontend;
 mod operations;
 mod operations_helper;
 mod registry;
@@ -14,6 +16,7 @@ mod specs;
 
 pub(crate) use operations::MoveReplicaRequest;
 pub(crate) use snapshot_operations::DestroyVolumeSnapshotRequest;
+pub(crate) use specs::resizeable_replicas;
 
 /// Configure the Service and return the builder.
 pub(crate) fn configure(builder: agents::Service) -> agents::Service {
@@ -30,15 +30,17 @@ use stor_port::{
     transport_api::ErrorChain,
     types::v0::{
         store::{
+            definitions::ObjectKey,
             nexus_persistence::NexusInfoKey,
             replica::ReplicaSpec,
+            switchover::SwitchOverHistoryKey,
             volume::{PublishOperation, RepublishOperation, VolumeOperation, VolumeSpec},
         },
         transport::{
             CreateVolume, DestroyNexus, DestroyReplica, DestroyShutdownTargets, DestroyVolume,
             Protocol, PublishVolume, Replica, ReplicaId, ReplicaOwners, RepublishVolume,
             ResizeVolume, SetVolumeProperty, SetVolumeReplica, ShareNexus, ShareVolume,
-            ShutdownNexus, UnpublishVolume, UnshareNexus, UnshareVolume, Volume,
+            ShutdownNexus, UnpublishVolume, UnshareNexus, UnshareVolume, Volume, VolumeAuth,
         },
     },
 };
@@ -174,6 +176,20 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
             }
         }
 
+        if let Err(error) = registry.delete_volume_auth(self.as_ref()).await {
+            tracing::warn!(volume.uuid=%self.uuid(), error=%error,
+                "Failed to delete the volume target secrets"
+            );
+        }
+
+        // Delete the switchover history recorded by the HA cluster agent.
+        let history = SwitchOverHistoryKey::new(self.uuid().clone());
+        if let Err(error) = registry.delete_kv(&history.key()).await {
+            tracing::warn!(volume.uuid=%self.uuid(), error=%error,
+                "Failed to delete the volume switchover history"
+            );
+        }
+
         self.complete_destroy(Ok(()), registry).await
     }
 }
@@ -269,6 +285,7 @@ impl ResourceSharing for OperationGuardArc<VolumeSpec> {
     ) -> Result<String, SvcError> {
         let specs = registry.specs();
         let state = registry.volume_state(&request.uuid).await?;
+        let dhchap = registry.volume_target_auth(self.as_ref()).await?;
 
         let spec_clone = self
             .start_update(registry, &state, VolumeOperation::Share(request.protocol))
@@ -289,7 +306,8 @@ impl ResourceSharing for OperationGuardArc<VolumeSpec> {
                                 .into_iter()
                                 .map(TryInto::try_into)
                                 .collect::<Result<_, _>>()?,
-                        ),
+                        )
+                        .with_dhchap(dhchap),
                     )
                     .await
             }
@@ -346,9 +364,19 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
                 nexus_node.candidate(),
                 &request.share,
                 frontend_nodes,
+                request.multi_node,
+                request.read_only,
             )
             .await;
 
+        let dhchap = registry.volume_auth(self.as_ref()).await?;
+        let previous_auth = self
+            .as_ref()
+            .target_config
+            .as_ref()
+            .and_then(|config| config.auth())
+            .cloned();
+
         let operation = VolumeOperation::Publish(PublishOperation::new(
             target_cfg.clone(),
             request.publish_context.clone(),
@@ -369,7 +397,7 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
             result = match nexus
                 .share(
                     registry,
-                    &ShareNexus::new(&nexus_state, share, allowed_hosts),
+                    &ShareNexus::new(&nexus_state, share, allowed_hosts).with_dhchap(dhchap),
                 )
                 .await
             {
@@ -387,6 +415,9 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         }
 
         self.complete_update(registry, result, spec_clone).await?;
+        registry
+            .delete_stale_volume_auth(self.as_ref(), previous_auth.as_ref())
+            .await;
 
         // If there was a previous nexus we should delete the persisted NexusInfo structure.
         if let Some(nexus_id) = last_target {
@@ -412,6 +443,7 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         let specs = registry.specs();
 
         let state = registry.volume_state(&request.uuid).await?;
+        let previous_auth = self.as_ref().target_auth().cloned();
 
         let spec_clone = self
             .start_update(registry, &state, VolumeOperation::Unpublish)
@@ -448,7 +480,11 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
             }
         };
 
-        self.complete_update(registry, result, spec_clone).await
+        self.complete_update(registry, result, spec_clone).await?;
+        registry
+            .delete_stale_volume_auth(self.as_ref(), previous_auth.as_ref())
+            .await;
+        Ok(())
     }
 
     async fn republish(
@@ -536,8 +572,12 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
                 nexus_node.candidate(),
                 &Some(request.share),
                 &nodes,
+                target_cfg.frontend().multi_node(),
+                target_cfg.frontend().read_only(),
             )
             .await;
+        let dhchap = registry.volume_auth(self.as_ref()).await?;
+        let previous_auth = spec.target_auth().cloned();
         let operation = VolumeOperation::Republish(RepublishOperation::new(target_cfg.clone()));
 
         let spec_clone = self.start_update(registry, &state, operation).await?;
@@ -561,7 +601,7 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         let result = match nexus
             .share(
                 registry,
-                &ShareNexus::new(&nexus_state, request.share, allowed_host),
+                &ShareNexus::new(&nexus_state, request.share, allowed_host).with_dhchap(dhchap),
             )
             .await
         {
@@ -578,6 +618,9 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         };
 
         self.complete_update(registry, result, spec_clone).await?;
+        registry
+            .delete_stale_volume_auth(self.as_ref(), previous_auth.as_ref())
+            .await;
 
         let volume = registry.volume(&request.uuid).await?;
         registry
@@ -877,13 +920,20 @@ impl ResourceLifecycleExt<CreateVolumeSource<'_>> for OperationGuardArc<VolumeSp
         // If the volume is a part of the ag, create or update accordingly.
         registry.specs().get_or_create_affinity_group(&volume_clone);
 
+        let result = match (volume_clone.auth(), &request_src.source().auth) {
+            (Some(auth_ref), Some(auth)) => registry.store_volume_auth(auth_ref, auth).await,
+            _ => Ok(()),
+        };
         let context = Context {
             registry,
             volume: &mut volume,
         };
-        let result = match request_src {
-            CreateVolumeSource::None(params) => params.run(context).await,
-            CreateVolumeSource::Snapshot(params) => params.run(context).await,
+        let result = match result {
+            Err(error) => Err(error),
+            Ok(()) => match request_src {
+                CreateVolumeSource::None(params) => params.run(context).await,
+                CreateVolumeSource::Snapshot(params) => params.run(context).await,
+            },
         };
 
         // we can destroy volume on error because there's no volume resource created on the nodes,
@@ -963,6 +1013,22 @@ impl CreateVolumeExeVal for CreateVolume {
             self.allowed_nodes().is_empty() || self.allowed_nodes().len() >= self.replicas as usize,
             agents::errors::InvalidArguments {}
         );
+        snafu::ensure!(
+            self.autogrow.as_ref().map_or(true, |a| a.valid(self.size)),
+            agents::errors::InvalidArguments {}
+        );
+        // Only thin provisioned volumes can grow automatically.
+        snafu::ensure!(
+            self.autogrow.is_none() || self.thin,
+            agents::errors::InvalidArguments {}
+        );
+        snafu::ensure!(
+            self.auth.as_ref().map_or(true, VolumeAuth::valid),
+            agents::errors::InvalidArguments {}
+        );
+        if self.auth.is_some() {
+            crate::controller::io_engine::volume_auth_capable("create_volume")?;
+        }
         Ok(())
     }
 }
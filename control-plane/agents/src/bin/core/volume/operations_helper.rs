@@ -8,7 +8,10 @@ use crate::{
             },
             OperationGuardArc, ResourceUid, TraceSpan, TraceStrLog,
         },
-        scheduling::resources::{HealthyChildItems, ReplicaItem},
+        scheduling::{
+            nexus::GetSuitableNodes,
+            resources::{HealthyChildItems, ReplicaItem},
+        },
     },
     volume::{
         scheduling::target_node_candidate,
@@ -50,13 +53,15 @@ impl OperationGuardArc<VolumeSpec> {
     /// Make the next target config.
     /// This essentially bumps up the controller id by 1 as otherwise the initiator cannot tell
     /// this target apart from others.
-    /// Also sets the reservation key based off the nexus uuid.
+    /// Also sets the reservation key based off the nexus uuid and the current target secrets.
     pub(super) async fn next_target_config(
         &self,
         registry: &Registry,
         node: &NodeId,
         share: &Option<VolumeShareProtocol>,
         frontend_nodes: &[String],
+        multi_node: bool,
+        read_only: bool,
     ) -> TargetConfig {
         let nexus = NexusId::new();
         let resv_key = nexus.as_u128() as u64;
@@ -75,18 +80,25 @@ impl OperationGuardArc<VolumeSpec> {
                 }
             }
         };
-        let host_acl = registry.host_acl_nodename(HostAccessControl::Nexuses, frontend_nodes);
-        let frontend = FrontendConfig::from_acls(host_acl);
+        // multi-node targets always enforce the host access list, so detached nodes are fenced off
+        let host_acl = match multi_node {
+            true => Registry::host_acl_enforced(frontend_nodes),
+            false => registry.host_acl_nodename(HostAccessControl::Nexuses, frontend_nodes),
+        };
+        let frontend = FrontendConfig::from_acls(host_acl)
+            .with_multi_node(multi_node)
+            .with_read_only(read_only);
+        // every frontend node of a multi-node target is a registrant and must keep its access
+        let resv_type = match multi_node {
+            true => NvmeReservation::ExclusiveAccessAllRegs,
+            false => NvmeReservation::ExclusiveAccess,
+        };
         TargetConfig::new(
             VolumeTarget::new(node.clone(), nexus, *share),
-            NexusNvmfConfig::new(
-                range,
-                resv_key,
-                NvmeReservation::ExclusiveAccess,
-                NexusNvmePreemption::Holder,
-            ),
+            NexusNvmfConfig::new(range, resv_key, resv_type, NexusNvmePreemption::Holder),
             frontend,
         )
+        .with_auth(self.as_ref().auth.clone())
     }
 
     /// Remove the given NexusChild Replica but make sure we're not removing the last healthy
@@ -238,6 +250,40 @@ impl OperationGuardArc<VolumeSpec> {
         registry.volume(&state.uuid).await
     }
 
+    /// Decrement the replica count of the given volume by 1, removing the given replica rather
+    /// than the least preferred one.
+    /// This is used to move a replica off a draining pool, once its replacement is rebuilt.
+    pub(crate) async fn remove_volume_replica(
+        &mut self,
+        registry: &Registry,
+        replica_id: &ReplicaId,
+    ) -> Result<(), SvcError> {
+        let state = registry.volume_state(self.uuid()).await?;
+        let replicas = self.as_ref().num_replicas.saturating_sub(1);
+        let spec_clone = self
+            .start_update(registry, &state, VolumeOperation::SetReplica(replicas))
+            .await?;
+
+        let replica = registry
+            .specs()
+            .replica_rsc(replica_id)
+            .map(|replica| replica.lock().clone())
+            .ok_or(SvcError::ReplicaNotFound {
+                replica_id: replica_id.clone(),
+            });
+        let replica = self
+            .validate_update_step(registry, replica, &spec_clone)
+            .await?;
+
+        // Remove the replica from its nexus (where it exists as a child)
+        let result = self.remove_volume_child(registry, replica_id).await;
+        self.validate_update_step(registry, result, &spec_clone)
+            .await?;
+
+        let result = self.destroy_replica(registry, &replica).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+
     /// Remove unused replicas from the volume
     /// (that is, replicas which are not used by a nexus and are in excess to the
     /// volume's replica count).
@@ -594,6 +640,16 @@ impl OperationGuardArc<VolumeSpec> {
         &self,
         registry: &Registry,
         remove: &ReplicaItem,
+    ) -> Result<(), SvcError> {
+        self.remove_volume_child(registry, &remove.spec().uuid)
+            .await
+    }
+
+    /// Remove the given replica from all nexuses for the given volume.
+    async fn remove_volume_child(
+        &self,
+        registry: &Registry,
+        replica_id: &ReplicaId,
     ) -> Result<(), SvcError> {
         // if the nexus is up, first remove the child from the nexus before deleting the replica
         let mut nexus = registry.specs().volume_target_nexus(self.as_ref()).await?;
@@ -603,7 +659,7 @@ impl OperationGuardArc<VolumeSpec> {
                 .children
                 .iter()
                 .flat_map(|c| c.as_replica())
-                .find(|r| r.uuid() == &remove.spec().uuid);
+                .find(|r| r.uuid() == replica_id);
             found.map(|r| (n, r))
         });
         match nexus {
@@ -730,8 +786,14 @@ impl OperationGuardArc<VolumeSpec> {
                 // In case there is no target node specified, let the control-plane scheduling logic
                 // determine a suitable node for the same and in case of affinity group, let
                 // control-plane decide with specified node as the preferred node.
+                // On publish, the target is placed close to the requested frontend nodes, whereas on
+                // republish the frontend nodes of the current target are kept.
+                let mut suitable_nodes = GetSuitableNodes::from(self.as_ref());
+                if !republish {
+                    suitable_nodes = suitable_nodes.with_app_nodes(request.frontend_nodes());
+                }
                 let candidate =
-                    target_node_candidate(self.as_ref(), registry, state, &request.target_node())
+                    target_node_candidate(suitable_nodes, registry, state, &request.target_node())
                         .await?;
                 tracing::debug!(node.id=%candidate.id(), "Node selected for volume publish by the core-agent");
                 Ok(NexusNodeCandidate::new(candidate.id().clone(), ag_guard))
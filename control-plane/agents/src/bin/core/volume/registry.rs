@@ -21,8 +21,8 @@ use stor_port::{
             volume::VolumeSpec,
         },
         transport::{
-            uri_with_hostnqn, Nexus, NexusStatus, ReplicaSnapshot, ReplicaStatus, ReplicaTopology,
-            SnapshotId, Volume, VolumeId, VolumeState, VolumeStatus, VolumeUsage,
+            uri_with_hostnqn, LabelSelector, Nexus, NexusStatus, ReplicaSnapshot, ReplicaStatus,
+            ReplicaTopology, SnapshotId, Volume, VolumeId, VolumeState, VolumeStatus, VolumeUsage,
         },
     },
     IntoOption,
@@ -199,7 +199,11 @@ impl Registry {
 
     /// Get all volumes.
     pub(crate) async fn volumes(&self) -> Vec<Volume> {
-        let volume_specs = self.specs().volumes();
+        self.specs_volumes(self.specs().volumes()).await
+    }
+
+    /// Get the volumes of the given specs.
+    async fn specs_volumes(&self, volume_specs: Vec<VolumeSpec>) -> Vec<Volume> {
         let replicas = self.specs().replicas_cloned();
         let mut volumes = Vec::with_capacity(volume_specs.len());
         for spec in volume_specs {
@@ -210,6 +214,17 @@ impl Registry {
         volumes
     }
 
+    /// Get the volumes whose labels match the `selector`.
+    pub(super) async fn labelled_volumes(&self, selector: &LabelSelector) -> Vec<Volume> {
+        let volume_specs = self
+            .specs()
+            .volumes()
+            .into_iter()
+            .filter(|spec| selector.matches(spec.labels.as_ref()))
+            .collect();
+        self.specs_volumes(volume_specs).await
+    }
+
     /// Get a paginated subset of volumes.
     pub(super) async fn paginated_volumes(
         &self,
@@ -343,6 +358,29 @@ impl Registry {
         snapshots
     }
 
+    /// Get the volume snapshots whose source volume labels match the `selector`, as the
+    /// snapshots have no labels of their own.
+    pub(super) async fn labelled_snapshots(
+        &self,
+        selector: &LabelSelector,
+    ) -> Vec<grpc_mod::VolumeSnapshot> {
+        let labels = self
+            .specs()
+            .volumes()
+            .into_iter()
+            .map(|spec| (spec.uuid, spec.labels))
+            .collect::<HashMap<_, _>>();
+        let mut snapshots = Vec::new();
+        for spec in self.specs().snapshots().into_iter().filter(|snapshot| {
+            let labels = labels.get(snapshot.spec().source_id());
+            selector.matches(labels.and_then(Option::as_ref))
+        }) {
+            let state = self.snapshot_state(&spec).await;
+            snapshots.push(grpc_mod::VolumeSnapshot::new(&spec, state));
+        }
+        snapshots
+    }
+
     /// Get a paginated subset of volume snapshots.
     pub(super) async fn paginated_snapshots(
         &self,
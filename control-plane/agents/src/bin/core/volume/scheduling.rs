@@ -17,7 +17,7 @@ use crate::{
 use agents::errors::{NotEnough, SvcError};
 use stor_port::types::v0::{
     store::{nexus::NexusSpec, volume::VolumeSpec},
-    transport::{NodeId, Replica, VolumeState},
+    transport::{NodeId, Replica, TargetPlacement, VolumeState},
 };
 
 /// Return a list of pre sorted pools to be used by a volume.
@@ -110,9 +110,17 @@ pub(crate) async fn target_node_candidate(
 ) -> Result<NodeWrapper, SvcError> {
     let request = request.into();
     let replicas = request.num_replicas;
-    let candidates = target_node_candidates(request, registry, preferred_node).await;
-    if replicas == 1 {
-        // For 1replica volumes, pin the volume target to the replica node.
+    let placement = request.target_placement();
+    // the target is always moved away from its current node, eg: on a switchover
+    let current_target = state.target.as_ref().map(|nexus| &nexus.node);
+    let candidates = target_node_candidates(request, registry, preferred_node)
+        .await
+        .into_iter()
+        .filter(|node| Some(node.id()) != current_target)
+        .collect::<Vec<_>>();
+    if replicas == 1 && placement == TargetPlacement::Any {
+        // For 1replica volumes, pin the volume target to the replica node, unless the target
+        // should rather be placed close to the application.
         if let Some(Some(node)) = state.replica_topology.values().last().map(|r| r.node()) {
             if let Some(node) = candidates.iter().find(|n| n.id() == node) {
                 return Ok(node.clone());
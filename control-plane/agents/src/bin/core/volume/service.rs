@@ -7,7 +7,9 @@ use crate::{
                 ResourceProperty, ResourcePublishing, ResourceReplicas, ResourceResize,
                 ResourceSharing, ResourceShutdownOperations, ResourceSnapshotting,
             },
-            operations_helper::{OperationSequenceGuard, ResourceSpecsLocked},
+            operations_helper::{
+                GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked,
+            },
             OperationGuardArc,
         },
     },
@@ -20,12 +22,12 @@ use grpc::{
         volume::traits::{
             CreateSnapshotVolumeInfo, CreateVolumeInfo, CreateVolumeSnapshot,
             CreateVolumeSnapshotInfo, DestroyShutdownTargetsInfo, DestroyVolumeInfo,
-            DestroyVolumeSnapshot, DestroyVolumeSnapshotInfo, PublishVolumeInfo,
-            RepublishVolumeInfo, ResizeVolumeInfo, SetVolumePropertyInfo, SetVolumeReplicaInfo,
-            ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
-            VolumeSnapshot, VolumeSnapshots,
+            DestroyVolumeSnapshot, DestroyVolumeSnapshotInfo, GetVolumeAuthInfo, PublishVolumeInfo,
+            RepublishVolumeInfo, ResizeVolumeInfo, RotateVolumeAuthInfo, SetVolumePropertyInfo,
+            SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo,
+            VolumeFrontendNodeInfo, VolumeOperations, VolumeSnapshot, VolumeSnapshots,
         },
-        Pagination,
+        PaginatedResult, Pagination,
     },
 };
 use stor_port::{
@@ -36,9 +38,11 @@ use stor_port::{
             volume::{VolumeContentSource, VolumeSpec},
         },
         transport::{
-            CreateSnapshotVolume, CreateVolume, DestroyShutdownTargets, DestroyVolume, Filter,
-            PublishVolume, RepublishVolume, ResizeVolume, SetVolumeProperty, SetVolumeReplica,
-            ShareVolume, UnpublishVolume, UnshareVolume, Volume,
+            AddVolumeFrontendNode, CreateSnapshotVolume, CreateVolume, DestroyShutdownTargets,
+            DestroyVolume, DhChapKeys, Filter, GetVolumeAuth, PublishVolume,
+            RemoveVolumeFrontendNode, RepublishVolume, ResizeVolume, RotateVolumeAuth,
+            SetVolumeProperty, SetVolumeReplica, ShareVolume, UnpublishVolume, UnshareVolume,
+            Volume,
         },
     },
 };
@@ -177,6 +181,56 @@ impl VolumeOperations for Service {
         Ok(volume)
     }
 
+    async fn get_auth(
+        &self,
+        req: &dyn GetVolumeAuthInfo,
+        _ctx: Option<Context>,
+    ) -> Result<DhChapKeys, ReplyError> {
+        let get_auth = req.into();
+        let service = self.clone();
+        let keys =
+            Context::spawn(async move { service.get_volume_auth(&get_auth).await }).await??;
+        Ok(keys)
+    }
+
+    async fn rotate_auth(
+        &self,
+        req: &dyn RotateVolumeAuthInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let rotate_auth = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.rotate_volume_auth(&rotate_auth).await }).await??;
+        Ok(volume)
+    }
+
+    async fn add_frontend_node(
+        &self,
+        req: &dyn VolumeFrontendNodeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let add_node = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.add_volume_frontend_node(&add_node).await })
+                .await??;
+        Ok(volume)
+    }
+
+    async fn remove_frontend_node(
+        &self,
+        req: &dyn VolumeFrontendNodeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let remove_node = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.remove_volume_frontend_node(&remove_node).await })
+                .await??;
+        Ok(volume)
+    }
+
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         return Ok(true);
     }
@@ -306,6 +360,17 @@ impl Service {
                     Err(error) => Err(error),
                 }?
             }
+            Filter::Labels(selector) => {
+                let volumes = self.registry.labelled_volumes(&selector).await;
+                match &pagination {
+                    Some(p) => {
+                        let paginated_volumes = PaginatedResult::paginate(volumes, p);
+                        last_result = paginated_volumes.last();
+                        paginated_volumes.result()
+                    }
+                    None => volumes,
+                }
+            }
             filter => return Err(SvcError::InvalidFilter { filter }),
         };
         Ok(Volumes {
@@ -329,6 +394,7 @@ impl Service {
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn destroy_volume(&self, request: &DestroyVolume) -> Result<(), SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         let content_source = volume.as_ref().content_source.as_ref();
         let snap_guard = match content_source {
             None => None,
@@ -358,6 +424,7 @@ impl Service {
         request: &DestroyShutdownTargets,
     ) -> Result<(), SvcError> {
         let mut volume = self.specs().volume(request.uuid()).await?;
+        volume.validate_revision(request.expected_revision())?;
         volume
             .remove_shutdown_targets(&self.registry, request)
             .await
@@ -367,6 +434,7 @@ impl Service {
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn share_volume(&self, request: &ShareVolume) -> Result<String, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         volume.share(&self.registry, request).await
     }
 
@@ -374,6 +442,7 @@ impl Service {
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn unshare_volume(&self, request: &UnshareVolume) -> Result<(), SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         volume.unshare(&self.registry, request).await
     }
 
@@ -381,6 +450,7 @@ impl Service {
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn publish_volume(&self, request: &PublishVolume) -> Result<Volume, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         volume.publish(&self.registry, request).await
     }
 
@@ -395,6 +465,7 @@ impl Service {
             return Err(SvcError::SwitchoverNotAllowedWhenHAisDisabled {});
         }
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         volume.republish(&self.registry, request).await
     }
 
@@ -405,6 +476,7 @@ impl Service {
         request: &UnpublishVolume,
     ) -> Result<Volume, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         volume.unpublish(&self.registry, request).await?;
         self.registry.volume(&request.uuid).await
     }
@@ -416,6 +488,7 @@ impl Service {
         request: &SetVolumeReplica,
     ) -> Result<Volume, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         volume.set_replica(&self.registry, request).await?;
         self.registry.volume(&request.uuid).await
     }
@@ -426,9 +499,58 @@ impl Service {
         request: &SetVolumeProperty,
     ) -> Result<Volume, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
         volume.set_property(&self.registry, request).await?;
         self.registry.volume(&request.uuid).await
     }
+    /// Get the volume target DH-HMAC-CHAP secrets.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn get_volume_auth(
+        &self,
+        request: &GetVolumeAuth,
+    ) -> Result<DhChapKeys, SvcError> {
+        let volume = self.specs().volume_clone(&request.uuid)?;
+        match self.registry.volume_target_auth(&volume).await? {
+            Some(keys) => Ok(keys),
+            None => Err(SvcError::NotFound {
+                kind: ResourceKind::Volume,
+                id: format!("{}/auth", request.uuid),
+            }),
+        }
+    }
+    /// Rotate the volume target DH-HMAC-CHAP secrets.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn rotate_volume_auth(
+        &self,
+        request: &RotateVolumeAuth,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
+        volume.rotate_auth(&self.registry, request).await?;
+        self.registry.volume(&request.uuid).await
+    }
+    /// Attach another frontend node to a multi-node volume.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn add_volume_frontend_node(
+        &self,
+        request: &AddVolumeFrontendNode,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
+        volume.add_frontend_node(&self.registry, request).await?;
+        self.registry.volume(&request.uuid).await
+    }
+    /// Detach a frontend node from a multi-node volume.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn remove_volume_frontend_node(
+        &self,
+        request: &RemoveVolumeFrontendNode,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
+        volume.remove_frontend_node(&self.registry, request).await?;
+        self.registry.volume(&request.uuid).await
+    }
     /// Create a volume snapshot.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.source_id, snapshot.source_uuid = %request.source_id, snapshot.uuid = %request.snap_id))]
     async fn create_snapshot(
@@ -576,6 +698,17 @@ impl Service {
                     Err(error) => Err(error),
                 }?
             }
+            Filter::Labels(selector) => {
+                let snapshots = self.registry.labelled_snapshots(&selector).await;
+                match &pagination {
+                    Some(p) => {
+                        let paginated_snaps = PaginatedResult::paginate(snapshots, p);
+                        last_result = paginated_snaps.last();
+                        paginated_snaps.result()
+                    }
+                    None => snapshots,
+                }
+            }
             filter => return Err(SvcError::InvalidFilter { filter }),
         };
 
@@ -605,6 +738,7 @@ impl Service {
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn resize_volume(&self, request: &ResizeVolume) -> Result<Volume, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.validate_revision(request.expected_revision)?;
 
         let Some(limit) = request.cluster_capacity_limit() else {
             return volume.resize(&self.registry, request).await;
@@ -42,7 +42,8 @@ use stor_port::{
         },
         transport::{
             CreateReplica, CreateVolume, NodeId, PoolId, Protocol, Replica, ReplicaId, ReplicaName,
-            ReplicaOwners, SnapshotId, VolumeId, VolumeShareProtocol, VolumeState, VolumeStatus,
+            ReplicaOwners, SnapshotId, VolumeId, VolumeProperty, VolumeShareProtocol, VolumeState,
+            VolumeStatus,
         },
     },
 };
@@ -1119,7 +1120,22 @@ impl SpecOperationsHelper for VolumeSpec {
             VolumeOperation::CreateSnapshot(_) => Ok(()),
             VolumeOperation::DestroySnapshot(_) => Ok(()),
             VolumeOperation::Resize(_) => Ok(()),
+            VolumeOperation::SetVolumeProperty(VolumeProperty::AutoGrow(Some(autogrow)))
+                if !self.thin || !autogrow.valid(self.size) =>
+            {
+                Err(SvcError::InvalidSetProperty {
+                    property_name: "autogrow".to_string(),
+                    id: self.uuid_str(),
+                })
+            }
             VolumeOperation::SetVolumeProperty(_) => Ok(()),
+            VolumeOperation::RotateAuth(_) => Ok(()),
+            VolumeOperation::SetFrontendNodes(_) if self.active_config().is_none() => {
+                Err(SvcError::VolumeNotPublished {
+                    vol_id: self.uuid_str(),
+                })
+            }
+            VolumeOperation::SetFrontendNodes(_) => Ok(()),
         }?;
         self.start_op(operation);
         Ok(())
@@ -1139,6 +1155,9 @@ impl SpecOperationsHelper for VolumeSpec {
     fn uuid_str(&self) -> String {
         self.uuid.to_string()
     }
+    fn spec_revision(&self) -> Option<u64> {
+        Some(self.revision)
+    }
     fn status(&self) -> SpecStatus<Self::Status> {
         self.status.clone()
     }
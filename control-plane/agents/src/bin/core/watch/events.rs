@@ -0,0 +1,339 @@
+use crate::controller::registry::Registry;
+use grpc::operations::watch::traits::ResourceEventStream;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use stor_port::{
+    pstor::{key_prefix, StorableObjectType, StoreKv, StoreWatchReceiver, WatchEvent, API_VERSION},
+    types::v0::{
+        openapi::models,
+        store::{
+            nexus::NexusSpec, node::NodeSpec, pool::PoolSpec, snapshots::volume::VolumeSnapshot,
+            volume::VolumeSpec,
+        },
+        transport::{EventAction, EventResource, ResourceEvent, WatchEvents, WatchType},
+    },
+};
+use tokio::{
+    sync::{broadcast, Mutex},
+    task::JoinHandle,
+};
+
+/// Period at which the resource states are compared against the previously seen ones.
+const STATE_POLL_PERIOD: Duration = Duration::from_secs(2);
+/// Number of events which a subscriber may fall behind on before it starts missing them.
+const EVENTS_CAPACITY: usize = 1024;
+
+/// Resource identified by its type and id.
+type ResourceKey = (EventResource, String);
+/// Labels of a resource.
+type Labels = HashMap<String, String>;
+
+/// Publishes the spec and state change events of the resources to all subscribers.
+/// The spec changes are received from a prefix watch on the persistent store, whereas the state
+/// changes are found by periodically comparing the registry states against the previous ones.
+/// The worker which generates the events only runs whilst there are subscribers.
+#[derive(Debug, Clone)]
+pub(super) struct ResourceEvents {
+    registry: Registry,
+    sender: broadcast::Sender<ResourceEvent>,
+    worker: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl ResourceEvents {
+    /// Create a new `Self` which generates events from the given `registry`.
+    pub(super) fn new(registry: Registry) -> Self {
+        let (sender, _) = broadcast::channel(EVENTS_CAPACITY);
+        Self {
+            registry,
+            sender,
+            worker: Default::default(),
+        }
+    }
+
+    /// Subscribe to the events which match the `filter`.
+    pub(super) async fn subscribe(&self, filter: WatchEvents) -> ResourceEventStream {
+        let mut worker = self.worker.lock().await;
+        let receiver = self.sender.subscribe();
+        if worker.as_ref().map_or(true, |worker| worker.is_finished()) {
+            // watch before returning, so that no spec changes are missed by the subscriber
+            let specs = self.watch_specs().await;
+            let events = self.clone();
+            *worker = Some(tokio::spawn(async move { events.worker(specs).await }));
+        }
+
+        Box::pin(futures::stream::unfold(
+            (receiver, filter),
+            |(mut receiver, filter)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) if filter.matches(&event) => {
+                            return Some((Ok(event), (receiver, filter)))
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(missed)) => {
+                            tracing::warn!(missed, "Subscriber is lagging behind the events");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        ))
+    }
+
+    /// Generate the events until there are no subscribers left.
+    async fn worker(&self, mut specs: Option<StoreWatchReceiver>) {
+        let prefix = Self::specs_prefix();
+        let mut labels = self.spec_labels();
+        let mut states = self.states().await;
+        let mut poll = tokio::time::interval(STATE_POLL_PERIOD);
+
+        loop {
+            tokio::select! {
+                event = Self::next_spec(&mut specs) => match event {
+                    Some(Ok(event)) => {
+                        if let Some(event) = Self::spec_event(&prefix, event, &mut labels) {
+                            let _ = self.sender.send(event);
+                        }
+                    }
+                    Some(Err(error)) => {
+                        tracing::error!(%error, "Error watching the resource specs");
+                    }
+                    None => {
+                        // the watch is lost, it'll be reissued on the next poll
+                        specs = None;
+                    }
+                },
+                _ = poll.tick() => {
+                    {
+                        let mut worker = self.worker.lock().await;
+                        if self.sender.receiver_count() == 0 {
+                            *worker = None;
+                            return;
+                        }
+                    }
+                    if specs.is_none() {
+                        specs = self.watch_specs().await;
+                    }
+                    let new_states = self.states().await;
+                    for event in Self::state_events(&states, &new_states, &labels) {
+                        let _ = self.sender.send(event);
+                    }
+                    states = new_states;
+                }
+            }
+        }
+    }
+
+    /// Prefix of all entries of the persistent store, as the specs are stored under different keys.
+    fn specs_prefix() -> String {
+        format!("{}/", key_prefix(API_VERSION))
+    }
+
+    /// Watch the specs on the persistent store.
+    async fn watch_specs(&self) -> Option<StoreWatchReceiver> {
+        let mut store = self.registry.store().lock().await;
+        match store.watch_prefix(&Self::specs_prefix()).await {
+            Ok(channel) => Some(channel),
+            Err(error) => {
+                tracing::error!(%error, "Failed to watch the resource specs");
+                None
+            }
+        }
+    }
+
+    /// Receive the next spec watch event, if the watch is still alive.
+    async fn next_spec(
+        specs: &mut Option<StoreWatchReceiver>,
+    ) -> Option<Result<WatchEvent, stor_port::pstor::Error>> {
+        match specs {
+            Some(channel) => channel.recv().await,
+            None => futures::future::pending().await,
+        }
+    }
+
+    /// Get the labels of all resource specs.
+    fn spec_labels(&self) -> HashMap<ResourceKey, Labels> {
+        let specs = self.registry.specs();
+        let volumes = specs.volumes().into_iter().map(|volume| {
+            let labels = volume.labels.clone().unwrap_or_default();
+            ((EventResource::Volume, volume.uuid.to_string()), labels)
+        });
+        let pools = specs.pools().into_iter().map(|pool| {
+            let labels = pool.labels.clone().unwrap_or_default();
+            ((EventResource::Pool, pool.id.to_string()), labels)
+        });
+        let nodes = specs.nodes().into_iter().map(|node| {
+            let labels = node.labels().clone();
+            ((EventResource::Node, node.id().to_string()), labels)
+        });
+        volumes.chain(pools).chain(nodes).collect()
+    }
+
+    /// Map a persistent store event into a spec event, if it's the spec of an event resource.
+    /// The `labels` are kept up to date with the spec changes.
+    fn spec_event(
+        prefix: &str,
+        event: WatchEvent,
+        labels: &mut HashMap<ResourceKey, Labels>,
+    ) -> Option<ResourceEvent> {
+        let (key, value) = match event {
+            WatchEvent::Put(key, value) => (key, Some(value)),
+            WatchEvent::Delete(key) => (key, None),
+        };
+        let (key_type, id) = key.strip_prefix(prefix)?.split_once('/')?;
+        let resource = match key_type {
+            t if t == StorableObjectType::VolumeSpec.as_ref() => EventResource::Volume,
+            t if t == StorableObjectType::NexusSpec.as_ref() => EventResource::Nexus,
+            t if t == StorableObjectType::PoolSpec.as_ref() => EventResource::Pool,
+            t if t == StorableObjectType::NodeSpec.as_ref() => EventResource::Node,
+            t if t == StorableObjectType::VolumeSnapshot.as_ref() => EventResource::Snapshot,
+            _ => return None,
+        };
+        let resource_key = (resource, id.to_string());
+
+        let (action, value, spec_labels) = match value {
+            Some(value) => match Self::spec_value(resource, value) {
+                Ok((value, spec_labels)) => {
+                    labels.insert(resource_key.clone(), spec_labels.clone());
+                    (EventAction::Updated, Some(value), spec_labels)
+                }
+                Err(error) => {
+                    tracing::error!(%error, %key, "Failed to deserialize the resource spec");
+                    return None;
+                }
+            },
+            None => {
+                let spec_labels = labels.remove(&resource_key).unwrap_or_default();
+                (EventAction::Deleted, None, spec_labels)
+            }
+        };
+
+        Some(ResourceEvent {
+            resource,
+            id: resource_key.1,
+            watch_type: WatchType::Desired,
+            action,
+            labels: spec_labels,
+            value,
+        })
+    }
+
+    /// Convert the persisted spec into its REST representation, along with its labels.
+    fn spec_value(
+        resource: EventResource,
+        value: serde_json::Value,
+    ) -> Result<(serde_json::Value, Labels), serde_json::Error> {
+        Ok(match resource {
+            EventResource::Volume => {
+                let spec: VolumeSpec = serde_json::from_value(value)?;
+                let labels = spec.labels.clone().unwrap_or_default();
+                (
+                    serde_json::to_value(models::VolumeSpec::from(spec))?,
+                    labels,
+                )
+            }
+            EventResource::Nexus => {
+                let spec: NexusSpec = serde_json::from_value(value)?;
+                (
+                    serde_json::to_value(models::NexusSpec::from(spec))?,
+                    Labels::new(),
+                )
+            }
+            EventResource::Pool => {
+                let spec: PoolSpec = serde_json::from_value(value)?;
+                let labels = spec.labels.clone().unwrap_or_default();
+                (serde_json::to_value(models::PoolSpec::from(spec))?, labels)
+            }
+            EventResource::Node => {
+                let spec: NodeSpec = serde_json::from_value(value)?;
+                let labels = spec.labels().clone();
+                (serde_json::to_value(models::NodeSpec::from(spec))?, labels)
+            }
+            EventResource::Snapshot => {
+                // there's no REST representation of the snapshot spec on its own
+                let _: VolumeSnapshot = serde_json::from_value(value.clone())?;
+                (value, Labels::new())
+            }
+        })
+    }
+
+    /// Get the REST representation of the states of all resources.
+    async fn states(&self) -> HashMap<ResourceKey, serde_json::Value> {
+        let mut states = HashMap::new();
+        let mut insert = |resource: EventResource, id: String, state: serde_json::Value| {
+            states.insert((resource, id), state);
+        };
+        let registry = &self.registry;
+
+        for volume in registry.volumes().await {
+            let state = serde_json::json!(models::VolumeState::from(volume.state()));
+            insert(EventResource::Volume, volume.spec().uuid.to_string(), state);
+        }
+        for nexus in registry.nexuses().await {
+            let id = nexus.uuid.to_string();
+            insert(
+                EventResource::Nexus,
+                id,
+                serde_json::json!(models::Nexus::from(nexus)),
+            );
+        }
+        for pool in registry.ctrl_pool_states().await {
+            let id = pool.id.to_string();
+            insert(
+                EventResource::Pool,
+                id,
+                serde_json::json!(models::PoolState::from(pool)),
+            );
+        }
+        for node in registry.node_states().await {
+            let id = node.id.to_string();
+            insert(
+                EventResource::Node,
+                id,
+                serde_json::json!(models::NodeState::from(node)),
+            );
+        }
+        for snapshot in registry.specs().snapshots() {
+            let state = registry.snapshot_state(&snapshot).await;
+            let state = serde_json::json!({
+                "uuid": state.uuid(),
+                "source_id": state.source_id(),
+                "allocated_size": state.allocated_size().unwrap_or_default(),
+                "timestamp": state.timestamp().map(|t| t.to_string()),
+                "ready_as_source": state.ready_as_source(),
+            });
+            insert(
+                EventResource::Snapshot,
+                snapshot.spec().uuid().to_string(),
+                state,
+            );
+        }
+        states
+    }
+
+    /// Compare the `new` states against the `old` ones, generating the events for the changes.
+    fn state_events(
+        old: &HashMap<ResourceKey, serde_json::Value>,
+        new: &HashMap<ResourceKey, serde_json::Value>,
+        labels: &HashMap<ResourceKey, Labels>,
+    ) -> Vec<ResourceEvent> {
+        let event = |key: &ResourceKey, action: EventAction, value: Option<&serde_json::Value>| {
+            ResourceEvent {
+                resource: key.0,
+                id: key.1.clone(),
+                watch_type: WatchType::Actual,
+                action,
+                labels: labels.get(key).cloned().unwrap_or_default(),
+                value: value.cloned(),
+            }
+        };
+        let updated = new
+            .iter()
+            .filter(|(key, state)| old.get(*key) != Some(*state))
+            .map(|(key, state)| event(key, EventAction::Updated, Some(state)));
+        let deleted = old
+            .keys()
+            .filter(|key| !new.contains_key(*key))
+            .map(|key| event(key, EventAction::Deleted, None));
+        updated.chain(deleted).collect()
+    }
+}
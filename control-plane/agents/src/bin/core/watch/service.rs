@@ -1,12 +1,17 @@
 use crate::{
     controller::registry::Registry,
-    watch::watches::{StoreWatch, WatchCfgId},
+    watch::{
+        events::ResourceEvents,
+        watches::{StoreWatch, WatchCfgId},
+    },
 };
 pub(crate) use agents::errors::SvcError;
 
 use grpc::{
     context::Context,
-    operations::watch::traits::{GetWatchInfo, WatchInfo, WatchOperations},
+    operations::watch::traits::{
+        GetWatchInfo, ResourceEventStream, WatchEventsInfo, WatchInfo, WatchOperations,
+    },
 };
 use stor_port::{
     transport_api::{v0::Watches, ReplyError},
@@ -19,6 +24,7 @@ use tokio::sync::Mutex;
 #[derive(Clone, Debug)]
 pub(super) struct Service {
     watch: Arc<Mutex<StoreWatch>>,
+    events: ResourceEvents,
 }
 
 #[tonic::async_trait]
@@ -46,13 +52,22 @@ impl WatchOperations for Service {
         Context::spawn(async move { service.delete_watch(&destroy_watch).await }).await??;
         Ok(())
     }
+
+    async fn events(
+        &self,
+        req: &dyn WatchEventsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<ResourceEventStream, ReplyError> {
+        Ok(self.events.subscribe(req.into()).await)
+    }
 }
 
 /// Watch Agent's Service
 impl Service {
     pub(super) fn new(registry: Registry) -> Self {
         Self {
-            watch: Arc::new(Mutex::new(StoreWatch::new(registry))),
+            watch: Arc::new(Mutex::new(StoreWatch::new(registry.clone()))),
+            events: ResourceEvents::new(registry),
         }
     }
 
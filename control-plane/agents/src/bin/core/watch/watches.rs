@@ -261,7 +261,7 @@ impl WatchCfg {
                                 WatchEvent::Put(_, v) => {
                                     last_seen = Some(v.clone());
                                 }
-                                WatchEvent::Delete => {
+                                WatchEvent::Delete(_) => {
                                     // resource deleted so we don't need to keep on watching
                                     return;
                                 }
@@ -4,7 +4,12 @@ use http::Uri;
 use std::{sync::Arc, time::Duration};
 use stor_port::{
     pstor::{etcd::Etcd, *},
-    types::v0::store::switchover::SwitchOverSpec,
+    types::v0::{
+        store::switchover::{
+            SwitchOverHistoryKey, SwitchOverHistorySpec, SwitchOverSpec, SwitchOverSpecKey,
+        },
+        transport::{SwitchOverHistory, SwitchOverRecord, VolumeId},
+    },
 };
 use tracing::{debug, error};
 
@@ -56,6 +61,26 @@ impl EtcdStore {
         }
     }
 
+    /// Get the object from the persistent store, if it exists.
+    async fn get_obj<O: StorableObject>(&self, key: &O::Key) -> Result<Option<O>, anyhow::Error> {
+        let mut store = self.store.lock().await;
+        match tokio::time::timeout(self.timeout, async move { store.get_obj(key).await }).await {
+            Ok(result) => match result {
+                Ok(object) => Ok(Some(object)),
+                Err(Error::MissingEntry { .. }) => Ok(None),
+                Err(error) => Err(error.into()),
+            },
+            Err(error) => {
+                error!(%error, "Failed to read from persistent store");
+                Err(Error::Timeout {
+                    operation: "Get".to_string(),
+                    timeout: self.timeout,
+                }
+                .into())
+            }
+        }
+    }
+
     /// Delete the object from the persistent store.
     pub(crate) async fn delete_obj<O: StorableObject>(
         &self,
@@ -102,4 +127,42 @@ impl EtcdStore {
 
         Ok(entries.iter().map(Into::into).collect())
     }
+
+    /// Add the record of a completed switchover to the history of the volume, keeping at most
+    /// `max_records` records.
+    pub(crate) async fn add_switchover_history(
+        &self,
+        record: SwitchOverRecord,
+        volume: &VolumeId,
+        max_records: usize,
+    ) -> Result<(), anyhow::Error> {
+        let key = SwitchOverHistoryKey::new(volume.clone());
+        let mut history = self
+            .get_obj::<SwitchOverHistorySpec>(&key)
+            .await?
+            .unwrap_or_else(|| SwitchOverHistorySpec::new(volume.clone()));
+        history.add(record, max_records);
+        self.store_obj(&history).await
+    }
+
+    /// Get the switchover history of the volume, including the switchover in progress, if any.
+    pub(crate) async fn switchover_history(
+        &self,
+        volume: &VolumeId,
+    ) -> Result<SwitchOverHistory, anyhow::Error> {
+        let key = SwitchOverHistoryKey::new(volume.clone());
+        let mut history = self
+            .get_obj::<SwitchOverHistorySpec>(&key)
+            .await?
+            .unwrap_or_else(|| SwitchOverHistorySpec::new(volume.clone()))
+            .history();
+
+        let key = SwitchOverSpecKey::new(volume.clone());
+        if let Some(spec) = self.get_obj::<SwitchOverSpec>(&key).await? {
+            history
+                .records
+                .push(SwitchOverRequest::from(&spec).record());
+        }
+        Ok(history)
+    }
 }
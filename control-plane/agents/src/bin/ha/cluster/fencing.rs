@@ -0,0 +1,295 @@
+use k8s_openapi::api::core::v1::{Node, Taint};
+use kube::api::{Api, Patch, PatchParams};
+use serde::Serialize;
+use std::{path::PathBuf, time::Duration};
+use stor_port::types::v0::transport::{NodeId, VolumeId};
+
+/// Kubernetes taint which marks a node as out-of-service, allowing the workloads and their
+/// volume attachments to be forcefully removed from the node.
+const OUT_OF_SERVICE_TAINT: &str = "node.kubernetes.io/out-of-service";
+
+/// What to do when the fencer does not confirm the fencing in time.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum FenceTimeoutPolicy {
+    /// Carry on with the switchover, even though the node may not be fenced.
+    Proceed,
+    /// Retry fencing the node later.
+    Retry,
+    /// Give up on the switchover.
+    Abort,
+}
+
+/// Fencing error.
+#[derive(Debug)]
+pub(crate) enum FenceError {
+    /// The fencer did not confirm the fencing in time.
+    Timeout(Duration),
+    /// The fencer failed to fence the node.
+    Failed(String),
+}
+impl std::fmt::Display for FenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout(timeout) => {
+                let timeout = humantime::Duration::from(*timeout);
+                write!(f, "Fencing was not confirmed within {timeout}")
+            }
+            Self::Failed(error) => write!(f, "Fencing failed: {error}"),
+        }
+    }
+}
+impl std::error::Error for FenceError {}
+
+/// Request to fence the node where a volume target resides.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct FenceRequest<'a> {
+    /// The node to be fenced.
+    node: &'a NodeId,
+    /// The volume whose target is being switched over.
+    volume: &'a VolumeId,
+}
+
+/// A pluggable fencer, which stops a node from writing to the volume replicas.
+#[derive(Debug, Clone)]
+pub(crate) enum Fencer {
+    /// POST the fence request to the webhook, which confirms with a success status code.
+    Webhook(url::Url),
+    /// Run the command with the node and volume as its arguments, which confirms by exiting
+    /// successfully.
+    Command(PathBuf),
+    /// Taint the Kubernetes node as out-of-service.
+    NodeTaint,
+}
+
+impl Fencer {
+    async fn fence(&self, request: &FenceRequest<'_>) -> Result<(), String> {
+        match self {
+            Self::Webhook(url) => {
+                let body = serde_json::to_vec(request).map_err(|error| error.to_string())?;
+                let response = reqwest::Client::new()
+                    .post(url.clone())
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body)
+                    .send()
+                    .await
+                    .map_err(|error| error.to_string())?;
+                match response.status() {
+                    status if status.is_success() => Ok(()),
+                    status => Err(format!("Webhook {url} responded with {status}")),
+                }
+            }
+            Self::Command(command) => {
+                let output = tokio::process::Command::new(command)
+                    .arg(request.node.as_str())
+                    .arg(request.volume.to_string())
+                    .kill_on_drop(true)
+                    .output()
+                    .await
+                    .map_err(|error| error.to_string())?;
+                match output.status.success() {
+                    true => Ok(()),
+                    false => Err(format!(
+                        "Command {} exited with {}: {}",
+                        command.display(),
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    )),
+                }
+            }
+            Self::NodeTaint => taint_node_out_of_service(request.node)
+                .await
+                .map_err(|error| error.to_string()),
+        }
+    }
+    /// Undo the fencing of a node which has recovered.
+    /// Only the out-of-service taint is undone here, other fencers own their recovery.
+    async fn recover(&self, node: &NodeId) -> Result<(), String> {
+        match self {
+            Self::Webhook(_) | Self::Command(_) => Ok(()),
+            Self::NodeTaint => untaint_node_out_of_service(node)
+                .await
+                .map_err(|error| error.to_string()),
+        }
+    }
+}
+
+/// Fencing configuration of the cluster.
+#[derive(Debug, Clone)]
+pub(crate) struct Fencing {
+    fencer: Fencer,
+    timeout: Duration,
+    timeout_policy: FenceTimeoutPolicy,
+}
+
+impl Fencing {
+    /// Create a new `Self`.
+    pub(crate) fn new(
+        fencer: Fencer,
+        timeout: Duration,
+        timeout_policy: FenceTimeoutPolicy,
+    ) -> Self {
+        Self {
+            fencer,
+            timeout,
+            timeout_policy,
+        }
+    }
+    /// Get the policy to apply when the fencing is not confirmed in time.
+    pub(crate) fn timeout_policy(&self) -> FenceTimeoutPolicy {
+        self.timeout_policy
+    }
+    /// Fence the given node, waiting for the fencer to confirm it.
+    pub(crate) async fn fence(&self, node: &NodeId, volume: &VolumeId) -> Result<(), FenceError> {
+        let request = FenceRequest { node, volume };
+        match tokio::time::timeout(self.timeout, self.fencer.fence(&request)).await {
+            Ok(result) => result.map_err(FenceError::Failed),
+            Err(_) => Err(FenceError::Timeout(self.timeout)),
+        }
+    }
+    /// Undo the fencing of the given node, which has recovered.
+    pub(crate) async fn recover(&self, node: &NodeId) -> Result<(), FenceError> {
+        match tokio::time::timeout(self.timeout, self.fencer.recover(node)).await {
+            Ok(result) => result.map_err(FenceError::Failed),
+            Err(_) => Err(FenceError::Timeout(self.timeout)),
+        }
+    }
+}
+
+/// Taint the node as out-of-service through the Kubernetes API.
+async fn taint_node_out_of_service(node: &NodeId) -> Result<(), anyhow::Error> {
+    patch_node(node, taint_patch).await
+}
+
+/// Remove the out-of-service taint of the node through the Kubernetes API.
+async fn untaint_node_out_of_service(node: &NodeId) -> Result<(), anyhow::Error> {
+    patch_node(node, untaint_patch).await
+}
+
+/// Patch the Kubernetes node with the JSON patch built from its current state, if any.
+async fn patch_node(
+    node: &NodeId,
+    patch: fn(&Node) -> Option<json_patch::Patch>,
+) -> Result<(), anyhow::Error> {
+    let nodes = Api::<Node>::all(kube::Client::try_default().await?);
+    let k8s_node = nodes.get(node.as_str()).await?;
+    if let Some(patch) = patch(&k8s_node) {
+        nodes
+            .patch(
+                node.as_str(),
+                &PatchParams::default(),
+                &Patch::Json::<()>(patch),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// The taints of the node.
+fn node_taints(node: &Node) -> &[Taint] {
+    node.spec
+        .as_ref()
+        .and_then(|spec| spec.taints.as_deref())
+        .unwrap_or_default()
+}
+
+/// JSON patch which adds the out-of-service taint, unless the node already has it.
+fn taint_patch(node: &Node) -> Option<json_patch::Patch> {
+    let taints = node_taints(node);
+    if taints.iter().any(|taint| taint.key == OUT_OF_SERVICE_TAINT) {
+        return None;
+    }
+    let taint = Taint {
+        key: OUT_OF_SERVICE_TAINT.to_string(),
+        value: Some("nodeshutdown".to_string()),
+        effect: "NoExecute".to_string(),
+        time_added: None,
+    };
+    let patch = match taints.is_empty() {
+        true => serde_json::json!([{ "op": "add", "path": "/spec/taints", "value": [taint] }]),
+        false => serde_json::json!([{ "op": "add", "path": "/spec/taints/-", "value": taint }]),
+    };
+    serde_json::from_value(patch).ok()
+}
+
+/// JSON patch which removes the out-of-service taint, if the node has it.
+/// The patch tests the taint is still at the same index, so it fails rather than removing
+/// another taint if the taints are modified concurrently.
+fn untaint_patch(node: &Node) -> Option<json_patch::Patch> {
+    let index = node_taints(node)
+        .iter()
+        .position(|taint| taint.key == OUT_OF_SERVICE_TAINT)?;
+    let path = format!("/spec/taints/{index}");
+    let patch = serde_json::json!([
+        { "op": "test", "path": format!("{path}/key"), "value": OUT_OF_SERVICE_TAINT },
+        { "op": "remove", "path": path },
+    ]);
+    serde_json::from_value(patch).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::NodeSpec;
+
+    fn node(taints: &[&str]) -> Node {
+        let taints = taints
+            .iter()
+            .map(|key| Taint {
+                key: key.to_string(),
+                effect: "NoSchedule".to_string(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        Node {
+            spec: Some(NodeSpec {
+                taints: (!taints.is_empty()).then_some(taints),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn patched(node: &Node, patch: json_patch::Patch) -> Vec<String> {
+        let mut value = serde_json::to_value(node).unwrap();
+        json_patch::patch(&mut value, &patch).unwrap();
+        let node: Node = serde_json::from_value(value).unwrap();
+        node_taints(&node)
+            .iter()
+            .map(|taint| taint.key.clone())
+            .collect()
+    }
+
+    #[test]
+    fn taint_out_of_service() {
+        let untainted = node(&[]);
+        let patch = taint_patch(&untainted).expect("Node is not tainted");
+        assert_eq!(patched(&untainted, patch), vec![OUT_OF_SERVICE_TAINT]);
+
+        let other = node(&["other"]);
+        let patch = taint_patch(&other).expect("Node is not tainted");
+        assert_eq!(patched(&other, patch), vec!["other", OUT_OF_SERVICE_TAINT]);
+
+        let tainted = node(&["other", OUT_OF_SERVICE_TAINT]);
+        assert!(taint_patch(&tainted).is_none());
+    }
+
+    #[test]
+    fn untaint_out_of_service() {
+        let tainted = node(&["other", OUT_OF_SERVICE_TAINT, "another"]);
+        let patch = untaint_patch(&tainted).expect("Node is tainted");
+        assert_eq!(patched(&tainted, patch), vec!["other", "another"]);
+
+        let tainted = node(&[OUT_OF_SERVICE_TAINT]);
+        let patch = untaint_patch(&tainted).expect("Node is tainted");
+        assert!(patched(&tainted, patch).is_empty());
+
+        assert!(untaint_patch(&node(&[])).is_none());
+        assert!(untaint_patch(&node(&["other"])).is_none());
+
+        // The taint moved, so the patch must not remove another taint.
+        let patch = untaint_patch(&node(&[OUT_OF_SERVICE_TAINT, "other"])).unwrap();
+        let mut moved = serde_json::to_value(node(&["other", OUT_OF_SERVICE_TAINT])).unwrap();
+        assert!(json_patch::patch(&mut moved, &patch).is_err());
+    }
+}
@@ -9,7 +9,9 @@ use utils::{
     DEFAULT_CLUSTER_AGENT_SERVER_ADDR, DEFAULT_GRPC_CLIENT_ADDR,
 };
 mod etcd;
+mod fencing;
 mod nodes;
+mod queue;
 mod server;
 mod switchover;
 mod volume;
@@ -45,6 +47,37 @@ struct Cli {
     #[clap(long)]
     fast_requeue: Option<humantime::Duration>,
 
+    /// Maximum number of completed switchovers kept in the history of each volume.
+    #[clap(long, default_value = "10")]
+    switchover_history: usize,
+
+    /// Maximum number of volumes being switched over concurrently.
+    #[clap(long, default_value = "4", value_parser = clap::value_parser!(u16).range(1 ..))]
+    switchover_concurrency: u16,
+
+    /// Fence the node of a failed volume target by POSTing to this webhook before the volume
+    /// is republished on another node.
+    #[clap(long, conflicts_with_all = ["fence_command", "fence_node_taint"])]
+    fence_webhook: Option<url::Url>,
+
+    /// Fence the node of a failed volume target by running this command, with the node and
+    /// the volume as its arguments, before the volume is republished on another node.
+    #[clap(long, conflicts_with = "fence_node_taint")]
+    fence_command: Option<std::path::PathBuf>,
+
+    /// Fence the node of a failed volume target by tainting the Kubernetes node as
+    /// out-of-service before the volume is republished on another node.
+    #[clap(long)]
+    fence_node_taint: bool,
+
+    /// Time to wait for the fencing to be confirmed.
+    #[clap(long, default_value = "30s")]
+    fence_timeout: humantime::Duration,
+
+    /// What to do when the fencing is not confirmed within the fence timeout.
+    #[clap(long, value_enum, default_value_t = fencing::FenceTimeoutPolicy::Retry)]
+    fence_timeout_policy: fencing::FenceTimeoutPolicy,
+
     /// Events message-bus endpoint url.
     #[clap(long, short)]
     events_url: Option<url::Url>,
@@ -54,6 +87,23 @@ impl Cli {
     fn args() -> Self {
         Cli::parse()
     }
+    /// Get the fencing configuration, if a fencer has been specified.
+    fn fencing(&self) -> Option<fencing::Fencing> {
+        let fencer = if let Some(url) = &self.fence_webhook {
+            fencing::Fencer::Webhook(url.clone())
+        } else if let Some(command) = &self.fence_command {
+            fencing::Fencer::Command(command.clone())
+        } else if self.fence_node_taint {
+            fencing::Fencer::NodeTaint
+        } else {
+            return None;
+        };
+        Some(fencing::Fencing::new(
+            fencer,
+            self.fence_timeout.into(),
+            self.fence_timeout_policy,
+        ))
+    }
 }
 
 /// Once cell static variable to store the grpc client and initialize once at startup.
@@ -94,7 +144,14 @@ async fn main() -> anyhow::Result<()> {
     let entries = store.fetch_incomplete_requests().await?;
 
     // Node list has ref counted list internally.
-    let mover = volume::VolumeMover::new(store, cli.fast_requeue, node_list.clone());
+    let mover = volume::VolumeMover::new(
+        store,
+        cli.fast_requeue,
+        cli.switchover_history,
+        cli.fencing(),
+        cli.switchover_concurrency.into(),
+        node_list.clone(),
+    );
     mover.send_switchover_req(entries).await?;
 
     info!("Starting cluster-agent server");
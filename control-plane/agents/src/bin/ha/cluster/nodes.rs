@@ -4,8 +4,9 @@ use crate::{
 };
 use stor_port::{
     transport_api::{ReplyError, ResourceKind},
-    types::v0::transport::NodeId,
+    types::v0::transport::{NodeId, VolumeId},
 };
+use utils::NVME_TARGET_NQN_PREFIX;
 
 use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 use tokio::sync::Mutex;
@@ -43,6 +44,11 @@ impl NodeList {
         list.insert(name, endpoint);
     }
 
+    /// Get the endpoint of the node agent registered on the given node.
+    pub(crate) async fn node_endpoint(&self, node: &NodeId) -> Option<SocketAddr> {
+        self.list.lock().await.get(node).cloned()
+    }
+
     /// Remove path from failed_path list.
     pub(crate) async fn remove_failed_path(&self, path: &str) {
         let mut failed_path = self.failed_path.lock().await;
@@ -77,23 +83,7 @@ impl NodeList {
             .or_insert(endpoint);
 
         let mut failed_path = self.failed_path.lock().await;
-
-        if let Some(record) = failed_path.get(&path) {
-            return match record.stage() {
-                Stage::ReplacePath | Stage::DeleteTarget | Stage::Successful | Stage::Errored => {
-                    Err(ReplyError::failed_precondition(
-                        ResourceKind::NvmePath,
-                        path,
-                        "Path is already reported for switchover".to_owned(),
-                    ))
-                }
-                Stage::Init | Stage::RepublishVolume => Err(ReplyError::already_exist(
-                    ResourceKind::NvmePath,
-                    path,
-                    "Path is already reported for switchover".to_owned(),
-                )),
-            };
-        }
+        Self::check_in_progress(&failed_path, &path)?;
 
         info!(node.id=%node, %path, "Sending switchover");
 
@@ -105,4 +95,62 @@ impl NodeList {
         failed_path.insert(path, record);
         Ok(())
     }
+
+    /// Send a planned switchover request to the switchover engine, moving the target of the
+    /// given volume away from its current node, which is used by the given frontend node.
+    pub(crate) async fn planned_switchover(
+        &self,
+        node: NodeId,
+        volume: VolumeId,
+        target_node: Option<NodeId>,
+        mover: &VolumeMover,
+    ) -> Result<(), ReplyError> {
+        let Some(endpoint) = self.node_endpoint(&node).await else {
+            return Err(ReplyError::failed_precondition(
+                ResourceKind::Node,
+                node.to_string(),
+                "No HA node agent is registered for the frontend node".to_owned(),
+            ));
+        };
+        let path = format!("{NVME_TARGET_NQN_PREFIX}{volume}");
+
+        let mut failed_path = self.failed_path.lock().await;
+        Self::check_in_progress(&failed_path, &path)?;
+
+        info!(node.id=%node, %path, target.node=?target_node, "Sending planned switchover");
+
+        let stage = mover
+            .planned_switchover(node, endpoint, volume, target_node)
+            .await?;
+        let record = PathRecord {
+            _socket: endpoint,
+            stage,
+        };
+        failed_path.insert(path, record);
+        Ok(())
+    }
+
+    /// Fail if a switchover is already in progress for the given path.
+    fn check_in_progress(
+        failed_path: &HashMap<String, PathRecord>,
+        path: &str,
+    ) -> Result<(), ReplyError> {
+        match failed_path.get(path).map(PathRecord::stage) {
+            None => Ok(()),
+            Some(Stage::ReplacePath | Stage::DeleteTarget | Stage::Successful | Stage::Errored) => {
+                Err(ReplyError::failed_precondition(
+                    ResourceKind::NvmePath,
+                    path.to_owned(),
+                    "Path is already reported for switchover".to_owned(),
+                ))
+            }
+            Some(Stage::Init | Stage::FenceTarget | Stage::RepublishVolume) => {
+                Err(ReplyError::already_exist(
+                    ResourceKind::NvmePath,
+                    path.to_owned(),
+                    "Path is already reported for switchover".to_owned(),
+                ))
+            }
+        }
+    }
 }
@@ -0,0 +1,202 @@
+use crate::switchover::SwitchOverRequest;
+use events_api::event::{EventAction, EventCategory, EventMessage, EventMeta, EventSource};
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use stor_port::types::v0::{
+    store::switchover::SwitchOverTime,
+    transport::{NodeId, VolumeId},
+};
+use tokio::{sync::Notify, time::Instant};
+use tracing::{info, warn};
+
+/// Initial back-off of the workers when the core agent fails a request.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+/// Maximum back-off of the workers while the core agent keeps failing requests.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Queue of the switchover requests waiting to be picked up by a worker.
+/// Requests are batched by the node where the volume target was, as a node failure triggers
+/// the switchover of every volume targeted there at the same time. Batches are worked on in the
+/// order they came in and, within a batch, volumes with an active IO path go first.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SwitchOverQueue {
+    pending: Arc<Mutex<Pending>>,
+    backoff: Arc<Mutex<Backoff>>,
+    notify: Arc<Notify>,
+}
+
+impl SwitchOverQueue {
+    /// Get a new `Self`.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the request to the queue, in the batch of its failed node.
+    pub(crate) fn push(&self, request: SwitchOverRequest) {
+        self.pending.lock().push(request);
+        self.notify.notify_one();
+    }
+
+    /// Wait for the next request to work on, after the back-off if the core agent is failing.
+    pub(crate) async fn pop(&self) -> SwitchOverRequest {
+        loop {
+            let until = self.backoff.lock().until;
+            if let Some(until) = until {
+                tokio::time::sleep_until(until).await;
+            }
+            if let Some(request) = self.pending.lock().pop() {
+                return request;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Account for the completion of the request in the progress of its batch.
+    pub(crate) fn complete(&self, request: &SwitchOverRequest, successful: bool) {
+        self.pending.lock().complete(request, successful);
+    }
+
+    /// Back off all workers, for longer each time the core agent fails a request in a row.
+    pub(crate) fn back_off(&self) {
+        let mut backoff = self.backoff.lock();
+        backoff.delay = match backoff.delay {
+            delay if delay.is_zero() => MIN_BACKOFF,
+            delay => (delay * 2).min(MAX_BACKOFF),
+        };
+        backoff.until = Some(Instant::now() + backoff.delay);
+        let delay = humantime::Duration::from(backoff.delay);
+        warn!(%delay, "Core agent is failing switchover requests, backing off");
+    }
+
+    /// Stop backing off, as the core agent is handling requests again.
+    pub(crate) fn reset_back_off(&self) {
+        let mut backoff = self.backoff.lock();
+        if backoff.until.take().is_some() {
+            backoff.delay = Duration::ZERO;
+            info!("Core agent is handling switchover requests again");
+        }
+    }
+}
+
+/// Back-off of the workers while the core agent is failing requests.
+#[derive(Debug, Default)]
+struct Backoff {
+    delay: Duration,
+    until: Option<Instant>,
+}
+
+/// The queued requests and the progress of their batches.
+#[derive(Debug, Default)]
+struct Pending {
+    requests: Vec<SwitchOverRequest>,
+    batches: HashMap<NodeId, Batch>,
+    batch_seq: u64,
+}
+
+impl Pending {
+    fn push(&mut self, request: SwitchOverRequest) {
+        if let Some(node) = request.failed_node() {
+            let seq = &mut self.batch_seq;
+            let batch = self.batches.entry(node.clone()).or_insert_with(|| {
+                *seq += 1;
+                Batch::new(node.clone(), *seq)
+            });
+            if batch.add(request.volume_id()) {
+                batch.update_progress(self.requests.len() + 1);
+            }
+        }
+        self.requests.push(request);
+    }
+
+    fn pop(&mut self) -> Option<SwitchOverRequest> {
+        let (index, _) = self
+            .requests
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, request)| self.priority(request))?;
+        Some(self.requests.swap_remove(index))
+    }
+
+    fn complete(&mut self, request: &SwitchOverRequest, successful: bool) {
+        let Some(node) = request.failed_node() else {
+            return;
+        };
+        let queued = self.requests.len();
+        if let Some(batch) = self.batches.get_mut(node) {
+            batch.complete(request.volume_id(), successful);
+            batch.update_progress(queued);
+            if batch.is_complete() {
+                self.batches.remove(node);
+            }
+        }
+    }
+
+    /// Requests which are not batched yet go first, as they're only being initialized.
+    /// Then batches in the order they came in and within a batch, volumes with an active IO
+    /// path before the others, failed paths before planned switchovers and oldest first.
+    fn priority(&self, request: &SwitchOverRequest) -> (u64, bool, bool, SwitchOverTime) {
+        let batch = request
+            .failed_node()
+            .and_then(|node| self.batches.get(node))
+            .map_or(0, |batch| batch.seq);
+        (
+            batch,
+            !request.io_active(),
+            request.planned(),
+            request.timestamp(),
+        )
+    }
+}
+
+/// The switchovers of the volumes whose target was on the same node.
+#[derive(Debug)]
+struct Batch {
+    node: NodeId,
+    seq: u64,
+    /// Whether the switchover of each volume succeeded, once completed.
+    volumes: HashMap<VolumeId, Option<bool>>,
+    progress: String,
+}
+
+impl Batch {
+    fn new(node: NodeId, seq: u64) -> Self {
+        Self {
+            node,
+            seq,
+            volumes: HashMap::new(),
+            progress: String::new(),
+        }
+    }
+    /// Add the volume to the batch, returning true if it wasn't already being switched over.
+    fn add(&mut self, volume: &VolumeId) -> bool {
+        !matches!(self.volumes.insert(volume.clone(), None), Some(None))
+    }
+    fn complete(&mut self, volume: &VolumeId, successful: bool) {
+        self.volumes.insert(volume.clone(), Some(successful));
+    }
+    fn is_complete(&self) -> bool {
+        self.volumes.values().all(Option::is_some)
+    }
+    /// Update the progress of the batch, raising an event with the previous and new progress.
+    fn update_progress(&mut self, queued: usize) {
+        let successful = self.volumes.values().filter(|v| **v == Some(true)).count();
+        let errored = self.volumes.values().filter(|v| **v == Some(false)).count();
+        let total = self.volumes.len();
+        let progress = format!(
+            "queued: {queued}, successful: {successful}, errored: {errored}, total: {total}"
+        );
+        info!(node.id=%self.node, %progress, "Switchover batch progress");
+        let previous = std::mem::replace(&mut self.progress, progress);
+        self.event(EventAction::StateChange, previous).generate();
+    }
+    fn event(&self, action: EventAction, previous: String) -> EventMessage {
+        let event_source = EventSource::new(self.node.to_string())
+            .with_state_change_data(previous, self.progress.clone());
+        EventMessage {
+            category: EventCategory::HighAvailability as i32,
+            action: action as i32,
+            target: self.node.to_string(),
+            metadata: Some(EventMeta::from_source(event_source)),
+        }
+    }
+}
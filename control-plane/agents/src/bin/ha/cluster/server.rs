@@ -1,15 +1,24 @@
-use crate::{nodes::NodeList, volume::VolumeMover};
+use crate::{core_grpc, nodes::NodeList, volume::VolumeMover};
 use grpc::{
     context::Context,
-    operations::ha_node::{
-        server::ClusterAgentServer,
-        traits::{ClusterAgentOperations, NodeInfo, ReportFailedPathsInfo},
+    operations::{
+        ha_node::{
+            server::ClusterAgentServer,
+            traits::{
+                ClusterAgentOperations, GetSwitchOverHistoryInfo, NodeInfo, ReportFailedPathsInfo,
+                SwitchOverVolumeInfo,
+            },
+        },
+        node::traits::NodeOperations,
+        volume::traits::VolumeOperations,
     },
 };
 use std::{net::SocketAddr, sync::Arc};
 use stor_port::{
     transport_api::{ReplyError, ResourceKind},
-    types::v0::transport::FailedPathsResponse,
+    types::v0::transport::{
+        FailedPathsResponse, Filter, NodeId, NodeStatus, SwitchOverHistory, VolumeId,
+    },
 };
 
 /// High-level object that represents HA Cluster agent gRPC server.
@@ -46,6 +55,35 @@ struct ClusterAgentSvc {
     mover: VolumeMover,
 }
 
+impl ClusterAgentSvc {
+    /// Check that the volume target can be moved away from its current node: either onto the
+    /// requested node or onto any other node which may host it.
+    async fn check_target_candidates(
+        volume_id: &VolumeId,
+        current: &NodeId,
+        requested: Option<&NodeId>,
+    ) -> Result<(), ReplyError> {
+        let nodes = core_grpc().node().get(Filter::None, false, None).await?;
+        let candidate = nodes.into_inner().iter().any(|node| {
+            node.id() != current
+                && requested.map_or(true, |requested| node.id() == requested)
+                && node.state().map(|state| state.status()) == Some(&NodeStatus::Online)
+                && node.spec().map_or(true, |spec| !spec.cordoned())
+        });
+        match candidate {
+            true => Ok(()),
+            false => Err(ReplyError::failed_precondition(
+                ResourceKind::Volume,
+                volume_id.to_string(),
+                match requested {
+                    Some(node) => format!("Requested target node '{node}' can't host the target"),
+                    None => "No other node can host the volume target".to_owned(),
+                },
+            )),
+        }
+    }
+}
+
 #[tonic::async_trait]
 impl ClusterAgentOperations for ClusterAgentSvc {
     #[tracing::instrument(level = "info", skip(self), err, fields(node.id = %request.node(), node.endpoint = %request.endpoint()))]
@@ -63,6 +101,7 @@ impl ClusterAgentOperations for ClusterAgentSvc {
         self.nodes
             .register_node(request.node().into(), request.endpoint())
             .await;
+        self.mover.node_recovered(request.node().into());
         tracing::trace!(agent = request.node(), "node successfully registered");
         Ok(())
     }
@@ -102,4 +141,71 @@ impl ClusterAgentOperations for ClusterAgentSvc {
 
         Ok(report)
     }
+
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.volume()))]
+    async fn switchover_volume(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        _context: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let volume_id = request.volume();
+        let volume = core_grpc()
+            .volume()
+            .get(Filter::Volume(volume_id.clone()), false, None, None)
+            .await?
+            .entries
+            .pop()
+            .ok_or_else(|| {
+                ReplyError::not_found(
+                    ResourceKind::Volume,
+                    volume_id.to_string(),
+                    "Volume not found".to_owned(),
+                )
+            })?;
+
+        let spec = volume.spec();
+        let Some(config) = spec.active_config() else {
+            return Err(ReplyError::failed_precondition(
+                ResourceKind::Volume,
+                volume_id.to_string(),
+                "Volume is not published".to_owned(),
+            ));
+        };
+        let frontend_node = match config.frontend().node_names().as_slice() {
+            [node] => node.as_str().into(),
+            _ => {
+                return Err(ReplyError::failed_precondition(
+                    ResourceKind::Volume,
+                    volume_id.to_string(),
+                    "Switchover requires a volume published to a single frontend node".to_owned(),
+                ))
+            }
+        };
+        if request.target_node().as_ref() == Some(config.target().node()) {
+            return Err(ReplyError::failed_precondition(
+                ResourceKind::Volume,
+                volume_id.to_string(),
+                "Volume target already resides on the requested node".to_owned(),
+            ));
+        }
+        ClusterAgentSvc::check_target_candidates(
+            &volume_id,
+            config.target().node(),
+            request.target_node().as_ref(),
+        )
+        .await?;
+
+        self.nodes
+            .planned_switchover(frontend_node, volume_id, request.target_node(), &self.mover)
+            .await
+    }
+
+    #[tracing::instrument(level = "debug", skip(self), err, fields(volume.uuid = %request.volume()))]
+    async fn get_switchover_history(
+        &self,
+        request: &dyn GetSwitchOverHistoryInfo,
+        _context: Option<Context>,
+    ) -> Result<SwitchOverHistory, ReplyError> {
+        self.mover.switchover_history(&request.volume()).await
+    }
 }
@@ -1,4 +1,10 @@
-use crate::{core_grpc, etcd::EtcdStore, nodes::NodeList};
+use crate::{
+    core_grpc,
+    etcd::EtcdStore,
+    fencing::{FenceError, FenceTimeoutPolicy, Fencing},
+    nodes::NodeList,
+    queue::SwitchOverQueue,
+};
 use agents::eventing::Event;
 use anyhow::anyhow;
 use chrono::Utc;
@@ -19,15 +25,12 @@ use stor_port::{
             SpecTransaction,
         },
         transport::{
-            DestroyShutdownTargets, GetController, NodeId, NvmeSubsystem, ReplacePath,
-            RepublishVolume, Volume, VolumeId, VolumeShareProtocol,
+            DestroyShutdownTargets, Filter, GetController, NodeId, NvmeSubsystem, ReplacePath,
+            RepublishVolume, SwitchOverRecord, SwitchOverStageRecord,
+            SwitchOverStatus as RecordStatus, Volume, VolumeId, VolumeShareProtocol,
         },
     },
 };
-use tokio::sync::{
-    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
-    Mutex,
-};
 use tonic::transport::Uri;
 use tracing::{error, info, warn};
 
@@ -40,6 +43,8 @@ fn client() -> impl VolumeOperations {
 pub(crate) enum Stage {
     /// Initialize switchover request.
     Init,
+    /// Fence the node of the original/old volume target, if fencing is configured.
+    FenceTarget,
     /// Shutdown original/old volume target. Create new nexus for existing vol obj.
     RepublishVolume,
     /// Send updated path of volume to node-agent.
@@ -95,6 +100,18 @@ pub(crate) struct SwitchOverRequest {
     publish_context: Option<HashMap<String, String>>,
     /// The first time we handle exhaustion, retry right away.
     fast_exhaustion_retry: bool,
+    /// Node where the new target should be placed, if requested by the operator.
+    target_node: Option<NodeId>,
+    /// The switchover was requested by the operator, rather than by a path failure.
+    planned: bool,
+    /// Stages reached so far, in order.
+    stages: Vec<SwitchOverStageRecord>,
+    /// The last error encountered.
+    error: Option<String>,
+    /// Node of the volume target when the switchover was initialized.
+    failed_node: Option<NodeId>,
+    /// The volume is in use by the frontend node, so its application IO depends on the path.
+    io_active: bool,
 }
 
 impl Ord for SwitchOverRequest {
@@ -132,9 +149,32 @@ impl SwitchOverRequest {
             reuse_existing: true,
             publish_context: None,
             fast_exhaustion_retry: true,
+            target_node: None,
+            planned: false,
+            stages: vec![],
+            error: None,
+            failed_node: None,
+            io_active: false,
         }
     }
 
+    /// Create a new switchover request, triggered by the operator, for a healthy Nvme path.
+    /// The existing target is never reused, so the volume target is moved away from its node,
+    /// and onto the `target_node` if one is given.
+    pub(crate) fn new_planned(
+        callback_uri: SocketAddr,
+        volume: VolumeId,
+        node_name: NodeId,
+        existing_path: String,
+        target_node: Option<NodeId>,
+    ) -> SwitchOverRequest {
+        let mut request = Self::new(callback_uri, volume, node_name, existing_path);
+        request.reuse_existing = false;
+        request.target_node = target_node;
+        request.planned = true;
+        request
+    }
+
     /// Get a ref-counted switchover stage.
     pub(crate) fn stage_arc(&self) -> SwitchOverStage {
         self.stage.clone()
@@ -153,12 +193,69 @@ impl SwitchOverRequest {
     pub(crate) fn nqn(&self) -> &str {
         &self.existing_nqn
     }
+    /// Get the volume being switched over.
+    pub(crate) fn volume_id(&self) -> &VolumeId {
+        &self.volume_id
+    }
+    /// Get the time when the switchover was requested.
+    pub(crate) fn timestamp(&self) -> SwitchOverTime {
+        self.timestamp
+    }
+    /// Check if the switchover was requested by the operator.
+    pub(crate) fn planned(&self) -> bool {
+        self.planned
+    }
+    /// Get the node of the volume target being switched over, once initialized.
+    pub(crate) fn failed_node(&self) -> Option<&NodeId> {
+        self.failed_node.as_ref()
+    }
+    /// Check if the volume is in use by the frontend node, once initialized.
+    pub(crate) fn io_active(&self) -> bool {
+        self.io_active
+    }
+
+    /// Add the current stage to the stages reached so far, unless it was the last one reached.
+    fn record_stage(&mut self) {
+        let stage = format!("{:?}", self.stage());
+        if self.stages.last().map(|record| &record.stage) != Some(&stage) {
+            self.stages.push(SwitchOverStageRecord {
+                stage,
+                timestamp: std::time::SystemTime::now(),
+            });
+        }
+    }
+
+    /// Get the history record of this switchover.
+    pub(crate) fn record(&self) -> SwitchOverRecord {
+        let status = match self.stage() {
+            Stage::Successful => RecordStatus::Successful,
+            Stage::Errored => RecordStatus::Errored,
+            _ => RecordStatus::InProgress,
+        };
+        let end_time = match status {
+            RecordStatus::InProgress => None,
+            _ => self.stages.last().map(|record| record.timestamp),
+        };
+        SwitchOverRecord {
+            frontend_node: self.node_name.clone(),
+            nqn: self.existing_nqn.clone(),
+            planned: self.planned,
+            target_node: self.target_node.clone(),
+            new_path: self.new_path.clone(),
+            status,
+            start_time: self.timestamp.into(),
+            end_time,
+            stages: self.stages.clone(),
+            error: self.error.clone(),
+        }
+    }
 
     /// Update stage with next stage.
     /// If a stage is PublishPath or Errored then it will not be updated.
     pub(crate) fn update_next_stage(&mut self) {
         self.set_stage(match self.stage() {
-            Stage::Init => Stage::RepublishVolume,
+            Stage::Init => Stage::FenceTarget,
+            Stage::FenceTarget => Stage::RepublishVolume,
             Stage::RepublishVolume => Stage::ReplacePath,
             Stage::ReplacePath => Stage::DeleteTarget,
             Stage::DeleteTarget => Stage::Successful,
@@ -233,6 +330,91 @@ impl SwitchOverRequest {
     async fn initialize(&mut self, etcd: &EtcdStore) -> Result<(), anyhow::Error> {
         self.start_op(etcd).await?;
         info!(volume.uuid=%self.volume_id, "Initializing");
+        self.classify().await?;
+        self.complete_op(true, "".to_string(), etcd).await?;
+        self.update_next_stage();
+        Ok(())
+    }
+
+    /// Find the node of the volume target and whether the volume is in use by the frontend node,
+    /// which decide how the request is batched and prioritised.
+    async fn classify(&mut self) -> Result<(), ReplyError> {
+        let volume = match client()
+            .get(Filter::Volume(self.volume_id.clone()), false, None, None)
+            .await
+        {
+            Ok(volumes) => volumes.entries.into_iter().next(),
+            // the republish cancels the switchover of a deleted volume
+            Err(error) if error.kind == ReplyErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        };
+        let spec = volume.map(|volume| volume.spec());
+        let config = spec.as_ref().and_then(|spec| spec.active_config());
+        self.failed_node = config.map(|config| config.target().node().clone());
+        // an empty host ACL allows any node, so the frontend node must be a published app node
+        let published = config.map_or(false, |config| {
+            config
+                .frontend()
+                .node_names()
+                .iter()
+                .any(|node| node == self.node_name.as_str())
+        });
+        self.io_active = published && self.frontend_connected().await;
+        Ok(())
+    }
+
+    /// Check whether the frontend node still has nvme controllers for the volume subsystem, as
+    /// reported by its HA node agent, meaning the application IO goes through the volume target.
+    async fn frontend_connected(&self) -> bool {
+        let Ok(uri) = self.node_uri() else {
+            return false;
+        };
+        // the node agent only looks up the subsystem nqn of the path
+        let path = format!("nvmf://{}/{}", self.node_name, self.existing_nqn);
+        let node_client = NodeAgentClient::new(uri, None).await;
+        match node_client
+            .get_nvme_controller(&GetController::new(path), None)
+            .await
+        {
+            Ok(subsystems) => !subsystems.into_inner().is_empty(),
+            Err(error) if error.kind == ReplyErrorKind::NotFound => false,
+            Err(error) => {
+                warn!(volume.uuid=%self.volume_id, %error, "Failed to get the Nvme controllers of the frontend node");
+                false
+            }
+        }
+    }
+
+    /// Fence the node of the current volume target, so it can no longer write to the replicas
+    /// once the volume is republished elsewhere, eg: when the node is only partitioned away.
+    /// Planned switchovers move the target away from a healthy node, which is not fenced.
+    #[tracing::instrument(level = "info", skip(self, etcd, fencing), fields(volume.uuid = %self.volume_id), err)]
+    async fn fence_target(
+        &mut self,
+        etcd: &EtcdStore,
+        fencing: Option<&Fencing>,
+    ) -> Result<(), anyhow::Error> {
+        self.start_op(etcd).await?;
+        if let Some(fencing) = fencing.filter(|_| !self.planned) {
+            if let Some(node) = &self.failed_node {
+                info!(volume.uuid=%self.volume_id, node.id=%node, "Fencing the volume target node");
+                match fencing.fence(node, &self.volume_id).await {
+                    Ok(()) => {}
+                    Err(error @ FenceError::Timeout(_)) => match fencing.timeout_policy() {
+                        FenceTimeoutPolicy::Proceed => {
+                            warn!(volume.uuid=%self.volume_id, %error, "Proceeding without fencing");
+                        }
+                        FenceTimeoutPolicy::Retry => return Err(error.into()),
+                        FenceTimeoutPolicy::Abort => {
+                            error!(volume.uuid=%self.volume_id, %error, "Cancelling switchover");
+                            self.set_stage(Stage::Errored);
+                            return Err(error.into());
+                        }
+                    },
+                    Err(error) => return Err(error.into()),
+                }
+            }
+        }
         self.complete_op(true, "".to_string(), etcd).await?;
         self.update_next_stage();
         Ok(())
@@ -244,11 +426,12 @@ impl SwitchOverRequest {
     ) -> Result<Volume, ReplyError> {
         let republish_req = RepublishVolume {
             uuid: self.volume_id.clone(),
-            target_node: None,
+            target_node: self.target_node.clone(),
             share: VolumeShareProtocol::Nvmf,
             reuse_existing: self.reuse_existing,
             frontend_node: self.node_name.clone(),
             reuse_existing_fallback,
+            expected_revision: None,
         };
         client().republish(&republish_req, None).await
     }
@@ -458,13 +641,12 @@ impl SwitchOverRequest {
 pub(crate) struct SwitchOverEngine {
     etcd: EtcdStore,
     fast_requeue: Option<humantime::Duration>,
+    history_size: usize,
+    fencing: Option<Arc<Fencing>>,
     nodes: NodeList,
-    channel: UnboundedSender<SwitchOverRequest>,
+    queue: SwitchOverQueue,
 }
 
-/// Number of Switchover worker tasks to be spawned.
-const WORKER_NUM: u8 = 4;
-
 enum ReQueue {
     /// Sleep duration during Fast requeue phase.
     Fast = 10,
@@ -479,41 +661,56 @@ impl SwitchOverEngine {
     pub(crate) fn new(
         etcd: EtcdStore,
         fast_requeue: Option<humantime::Duration>,
+        history_size: usize,
+        fencing: Option<Fencing>,
+        workers: usize,
         nodes: NodeList,
     ) -> Self {
-        let (rq_tx, rq_rx) = unbounded_channel();
-
         let sw = SwitchOverEngine {
-            channel: rq_tx,
+            queue: SwitchOverQueue::new(),
             etcd,
             nodes,
             fast_requeue,
+            history_size,
+            fencing: fencing.map(Arc::new),
         };
 
-        sw.init_worker(Arc::new(Mutex::new(rq_rx)));
+        sw.init_worker(workers);
         sw
     }
     pub(crate) fn nodes(&self) -> &NodeList {
         &self.nodes
     }
 
-    /// Instantiates worker task to asynchronously process Switchover request.
-    pub(crate) fn init_worker(&self, recv: Arc<Mutex<UnboundedReceiver<SwitchOverRequest>>>) {
-        for i in 0 .. WORKER_NUM {
+    /// Undo the fencing of the given node, which has recovered, in the background.
+    pub(crate) fn recover_node(&self, node: NodeId) {
+        let Some(fencing) = self.fencing.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            match fencing.recover(&node).await {
+                Ok(()) => info!(node.id=%node, "Node has recovered from fencing"),
+                Err(error) => {
+                    warn!(node.id=%node, %error, "Failed to recover the node from fencing")
+                }
+            }
+        });
+    }
+
+    /// Instantiates worker tasks to asynchronously process Switchover requests.
+    /// The number of workers limits how many switchovers are worked on concurrently.
+    pub(crate) fn init_worker(&self, workers: usize) {
+        for i in 0 .. workers {
             info!(worker = i, "Spawning Switchover Engine worker");
             let cloned_self = self.clone();
-            let cloned_channel = recv.clone();
-            tokio::spawn(async move { cloned_self.worker(cloned_channel, i).await });
+            tokio::spawn(async move { cloned_self.worker(i).await });
         }
     }
 
     /// Switchover request to be handled synchronously in each worker task.
-    async fn worker(self, recv: Arc<Mutex<UnboundedReceiver<SwitchOverRequest>>>, worker_num: u8) {
+    async fn worker(self, worker_num: usize) {
         loop {
-            let request = match recv.lock().await.recv().await {
-                None => break,
-                Some(request) => request,
-            };
+            let request = self.queue.pop().await;
 
             info!(
                 volume.uuid = %request.volume_id,
@@ -522,18 +719,24 @@ impl SwitchOverEngine {
             );
             self.work_request(request).await;
         }
-        info!(worker = worker_num, "Switchover Engine worker stopped");
     }
 
     /// Handle Switchover request synchronously as long as it's succeeding on each stage.
     /// Failed requests are sent back to the work queue to be picked up later.
     async fn work_request(&self, mut request: SwitchOverRequest) {
         loop {
-            let result = match request.stage() {
+            request.record_stage();
+            let stage = request.stage();
+            let result = match stage {
                 Stage::Init => {
                     request.event(EventAction::SwitchOver).generate();
                     request.initialize(&self.etcd).await
                 }
+                Stage::FenceTarget => {
+                    request
+                        .fence_target(&self.etcd, self.fencing.as_deref())
+                        .await
+                }
                 Stage::RepublishVolume => request.republish_volume(&self.etcd).await,
                 Stage::ReplacePath => request.replace_path(&self.etcd, &self.nodes).await,
                 Stage::DeleteTarget => request.delete_target(&self.etcd).await,
@@ -542,6 +745,8 @@ impl SwitchOverEngine {
                     match request.errored_switchover(&self.etcd, &self.nodes).await {
                         Ok(_) => {
                             event.generate();
+                            self.queue.complete(&request, false);
+                            self.add_history(&request).await;
                             break;
                         }
                         Err(e) => Err(e),
@@ -550,6 +755,8 @@ impl SwitchOverEngine {
                 Stage::Successful => match request.delete_switchover(&self.etcd).await {
                     Ok(_) => {
                         request.event(EventAction::SwitchOver).generate();
+                        self.queue.complete(&request, true);
+                        self.add_history(&request).await;
                         break;
                     }
                     Err(e) => Err(e),
@@ -559,13 +766,23 @@ impl SwitchOverEngine {
                 Ok(_) => {
                     // reset retry count back to the start after successfully completing a stage.
                     request.retry_count = 0;
+                    self.queue.reset_back_off();
+                    if stage == Stage::Init {
+                        // now that we know its failed node, queue it up in the right batch.
+                        self.queue.push(request);
+                        break;
+                    }
                 }
                 Err(error) => {
+                    if core_unavailable(&error) {
+                        self.queue.back_off();
+                    }
                     info!(
                         volume.uuid = %request.volume_id,
                         %error,
                         "Sending failed Switchover request back to the work queue"
                     );
+                    request.error = Some(error.to_string());
                     request.retry_count += 1;
                     self.enqueue(request);
                     break;
@@ -574,9 +791,28 @@ impl SwitchOverEngine {
         }
     }
 
-    /// Sends Switchover request to the channel after sleeping for sometime (if necessary).
+    /// Add the completed switchover request to the switchover history of its volume.
+    /// The history is informational only, so failing to update it does not fail the request.
+    /// The history is deleted along with the volume, so it's not recreated for deleted volumes.
+    async fn add_history(&self, request: &SwitchOverRequest) {
+        let volume = client()
+            .get(Filter::Volume(request.volume_id.clone()), false, None, None)
+            .await;
+        if matches!(volume, Err(ref error) if error.kind == ReplyErrorKind::NotFound) {
+            return;
+        }
+        if let Err(error) = self
+            .etcd
+            .add_switchover_history(request.record(), &request.volume_id, self.history_size)
+            .await
+        {
+            warn!(volume.uuid=%request.volume_id, %error, "Failed to update the switchover history");
+        }
+    }
+
+    /// Sends Switchover request to the queue after sleeping for sometime (if necessary).
     pub(crate) fn enqueue(&self, req: SwitchOverRequest) {
-        let tx_clone = self.channel.clone();
+        let queue = self.queue.clone();
         let fast_requeue = self.fast_requeue;
         tokio::spawn(async move {
             let errored_request = req.retry_count > 0;
@@ -591,11 +827,24 @@ impl SwitchOverEngine {
             if errored_request {
                 tokio::time::sleep(Duration::from_secs(retry_delay)).await;
             }
-            tx_clone.send(req)
+            queue.push(req)
         });
     }
 }
 
+/// Check if the error is the core agent failing to handle requests, rather than the request
+/// itself failing.
+fn core_unavailable(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<ReplyError>().map_or(false, |error| {
+        matches!(
+            error.kind,
+            ReplyErrorKind::Unavailable
+                | ReplyErrorKind::Timeout
+                | ReplyErrorKind::DeadlineExceeded
+        )
+    })
+}
+
 impl From<&SwitchOverRequest> for SwitchOverSpec {
     fn from(req: &SwitchOverRequest) -> Self {
         let op = OperationState::new(req.stage().into(), None);
@@ -610,6 +859,12 @@ impl From<&SwitchOverRequest> for SwitchOverSpec {
             retry_count: req.retry_count,
             reuse_existing: req.reuse_existing,
             publish_context: req.publish_context.clone(),
+            target_node: req.target_node.clone(),
+            planned: req.planned,
+            stages: req.stages.clone(),
+            error: req.error.clone(),
+            failed_node: req.failed_node.clone(),
+            io_active: req.io_active,
         }
     }
 }
@@ -633,6 +888,12 @@ impl From<&SwitchOverSpec> for SwitchOverRequest {
             reuse_existing: req.reuse_existing,
             publish_context: req.publish_context.clone(),
             fast_exhaustion_retry: true,
+            target_node: req.target_node.clone(),
+            planned: req.planned,
+            stages: req.stages.clone(),
+            error: req.error.clone(),
+            failed_node: req.failed_node.clone(),
+            io_active: req.io_active,
         }
     }
 }
@@ -641,6 +902,7 @@ impl From<Stage> for Operation {
     fn from(stage: Stage) -> Self {
         match stage {
             Stage::Init => Operation::Init,
+            Stage::FenceTarget => Operation::FenceTarget,
             Stage::RepublishVolume => Operation::RepublishVolume,
             Stage::ReplacePath => Operation::ReplacePath,
             Stage::DeleteTarget => Operation::DeleteTarget,
@@ -654,6 +916,7 @@ impl From<Operation> for Stage {
     fn from(op: Operation) -> Self {
         match op {
             Operation::Init => Stage::Init,
+            Operation::FenceTarget => Stage::FenceTarget,
             Operation::RepublishVolume => Stage::RepublishVolume,
             Operation::ReplacePath => Stage::ReplacePath,
             Operation::DeleteTarget => Stage::DeleteTarget,
@@ -723,3 +986,61 @@ fn switchover_ord() {
     test_vec.sort();
     assert_eq!(test_vec, vec![sw1, sw4, sw5, sw3, sw2]);
 }
+
+#[test]
+fn switchover_record() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    let sock: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+    let vol = VolumeId::try_from("ec4e66fd-3b33-4439-b504-d49aba53da26").unwrap();
+    let mut sw = SwitchOverRequest::new_planned(sock, vol, "nn".into(), "nw".to_string(), None);
+
+    sw.record_stage();
+    sw.update_next_stage();
+    sw.record_stage();
+    // Retrying the same stage is recorded only once.
+    sw.record_stage();
+    let record = sw.record();
+    assert!(record.planned);
+    assert_eq!(record.status, RecordStatus::InProgress);
+    assert_eq!(record.end_time, None);
+    let stages = record.stages.iter().map(|s| s.stage.as_str());
+    assert_eq!(stages.collect::<Vec<_>>(), vec!["Init", "FenceTarget"]);
+
+    sw.set_stage(Stage::Errored);
+    sw.record_stage();
+    let record = sw.record();
+    assert_eq!(record.status, RecordStatus::Errored);
+    assert_eq!(record.end_time, record.stages.last().map(|s| s.timestamp));
+
+    // The record survives a round trip through the persistent store spec.
+    let spec = SwitchOverSpec::from(&sw);
+    assert_eq!(SwitchOverRequest::from(&spec).record(), record);
+}
+
+#[tokio::test]
+async fn switchover_queue_batches() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    let sock: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+    let request = |volume: &str, failed_node: Option<&str>, io_active: bool| {
+        let volume = VolumeId::try_from(volume).unwrap();
+        let mut sw = SwitchOverRequest::new(sock, volume, "nn".into(), "nw".to_string());
+        sw.failed_node = failed_node.map(Into::into);
+        sw.io_active = io_active;
+        sw
+    };
+    let sw1 = request("ec4e66fd-3b33-4439-b504-d49aba53da26", Some("n1"), false);
+    let sw2 = request("b3e1dfc6-4f4d-4a47-a1fb-6d38fc8a7d90", Some("n2"), true);
+    let sw3 = request("2c40f3d3-8b52-4d8a-a0d8-cf8c0ec8ed3a", Some("n1"), true);
+    let sw4 = request("8f1b7c0e-79f7-4e6c-9b2b-4c8d5e16a3a5", None, false);
+
+    let queue = SwitchOverQueue::new();
+    for sw in [&sw1, &sw2, &sw3, &sw4] {
+        queue.push(sw.clone());
+    }
+    // Requests which are not batched yet, then the batches in order with active IO first.
+    let mut popped = vec![];
+    for _ in 0 .. 4 {
+        popped.push(queue.pop().await);
+    }
+    assert_eq!(popped, vec![sw4, sw3, sw1, sw2]);
+}
@@ -1,12 +1,13 @@
 use crate::{
     etcd::EtcdStore,
+    fencing::Fencing,
     nodes::NodeList,
     switchover::{SwitchOverEngine, SwitchOverRequest, SwitchOverStage},
 };
 use std::{convert::TryFrom, net::SocketAddr};
 use stor_port::{
     transport_api::{ReplyError, ResourceKind},
-    types::v0::transport::{NodeId, VolumeId},
+    types::v0::transport::{NodeId, SwitchOverHistory, VolumeId},
 };
 use utils::NVME_TARGET_NQN_PREFIX;
 
@@ -22,12 +23,42 @@ impl VolumeMover {
     pub(crate) fn new(
         etcd: EtcdStore,
         fast_requeue: Option<humantime::Duration>,
+        history_size: usize,
+        fencing: Option<Fencing>,
+        workers: usize,
         nodes: NodeList,
     ) -> Self {
-        let engine = SwitchOverEngine::new(etcd.clone(), fast_requeue, nodes);
+        let engine = SwitchOverEngine::new(
+            etcd.clone(),
+            fast_requeue,
+            history_size,
+            fencing,
+            workers,
+            nodes,
+        );
         Self { engine, etcd }
     }
 
+    /// The HA node agent of the given node has (re)started, so the node has recovered and any
+    /// fencing of the node is undone.
+    pub(crate) fn node_recovered(&self, node: NodeId) {
+        self.engine.recover_node(node);
+    }
+
+    /// Get the switchover history of the given volume.
+    pub(crate) async fn switchover_history(
+        &self,
+        volume: &VolumeId,
+    ) -> Result<SwitchOverHistory, ReplyError> {
+        self.etcd.switchover_history(volume).await.map_err(|error| {
+            ReplyError::failed_persist(
+                ResourceKind::Volume,
+                error.to_string(),
+                "Failed to get the switchover history".into(),
+            )
+        })
+    }
+
     /// Switchover build the switchover request for the given nqn and send it to SwitchOverEngine.
     #[tracing::instrument(level = "info", skip(self), err)]
     pub(crate) async fn switchover(
@@ -44,7 +75,27 @@ impl VolumeMover {
             ReplyError::invalid_argument(ResourceKind::Volume, "volume", error.to_string())
         })?;
 
-        let req = SwitchOverRequest::new(uri, volume_uuid, node, nqn);
+        self.enqueue(SwitchOverRequest::new(uri, volume_uuid, node, nqn))
+            .await
+    }
+
+    /// Build a planned switchover request which moves the target of the given volume, optionally
+    /// onto the given node, and send it to SwitchOverEngine.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn planned_switchover(
+        &self,
+        node: NodeId,
+        uri: SocketAddr,
+        volume: VolumeId,
+        target_node: Option<NodeId>,
+    ) -> Result<SwitchOverStage, ReplyError> {
+        let nqn = format!("{NVME_TARGET_NQN_PREFIX}{volume}");
+        let req = SwitchOverRequest::new_planned(uri, volume, node, nqn, target_node);
+        self.enqueue(req).await
+    }
+
+    /// Store the switchover request in etcd and send it to SwitchOverEngine.
+    async fn enqueue(&self, req: SwitchOverRequest) -> Result<SwitchOverStage, ReplyError> {
         let stage_arc = req.stage_arc();
 
         // calling start_op here to store the request in etcd
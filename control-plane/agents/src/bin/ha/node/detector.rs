@@ -1,4 +1,5 @@
 use crate::{
+    io_stats::IoStallDetector,
     path_provider::{CachedNvmePathProvider, NvmePath, NvmePathNameCollection},
     reporter::PathReporter,
     Cli,
@@ -211,6 +212,8 @@ type NvmeCacheMessage = (NvmePathCacheCommand, Sender<NvmePathCacheCommandRespon
 ///  Good - path is fully functional.
 ///  Suspected - path experiences connectivity problems for the first time.
 ///  Failed - path has experienced connectivity problems two times in a row.
+/// With IO stall detection, a live path whose IO has stalled is treated as having connectivity
+/// problems as well.
 /// Once a path is classified as Failed, it's reported to PathReporter and gets sent to
 /// HA Cluster agent.
 #[derive(Debug)]
@@ -221,6 +224,7 @@ pub struct PathFailureDetector {
     reporter: Rc<PathReporter>,
     cache_channel_rx: Receiver<NvmeCacheMessage>,
     cache_channel_tx: Arc<Sender<NvmeCacheMessage>>,
+    io_stall_detector: Option<IoStallDetector>,
 }
 
 impl PathFailureDetector {
@@ -242,6 +246,9 @@ impl PathFailureDetector {
             reporter: Rc::new(reporter),
             cache_channel_tx: Arc::new(tx),
             cache_channel_rx: rx,
+            io_stall_detector: args
+                .io_stall_detection
+                .then(|| IoStallDetector::new(&args.sysfs_root)),
         }
     }
 
@@ -257,7 +264,18 @@ impl PathFailureDetector {
             // as we might see lots of false-positive errors when removing a failed path
             // from a multi-pathed NVMe subsystem.
             if let Ok(subsystem) = Subsystem::new(ctrlr.path()) {
-                match subsystem.state.as_str() {
+                let state = match subsystem.state.as_str() {
+                    "live" if self.io_stalled(ctrlr) => {
+                        tracing::warn!(
+                            target = subsystem.nqn,
+                            path = %ctrlr.path().display(),
+                            "Live target path has stalled IO"
+                        );
+                        "connecting"
+                    }
+                    state => state,
+                };
+                match state {
                     "connecting" => {
                         // Add a new record in case no record exists for target NQN.
                         let rec = self
@@ -298,6 +316,17 @@ impl PathFailureDetector {
         // ReviewQuestion: If the paths are now good, we don't need to keep them right?
         self.suspected_paths
             .retain(|_nqn, recs| recs.has_broken_paths());
+        if let Some(detector) = &mut self.io_stall_detector {
+            detector.retain_epoch(self.epoch);
+        }
+    }
+
+    /// Check if the IO of the path has stalled, when IO stall detection is enabled.
+    fn io_stalled(&mut self, ctrlr: &NvmePath) -> bool {
+        let epoch = self.epoch;
+        self.io_stall_detector
+            .as_mut()
+            .map_or(false, |detector| detector.is_stalled(ctrlr.path(), epoch))
     }
 
     /// Handle command for NVMe cache.
@@ -0,0 +1,166 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// Default root of the sysfs tree.
+pub(crate) const SYSFS_ROOT: &str = "/sys";
+
+/// IO statistics of an NVMe controller, summed over all of its namespaces.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+struct IoStats {
+    /// Number of completed requests.
+    completed: u64,
+    /// Number of requests which have been issued but not completed yet.
+    in_flight: u64,
+}
+
+impl IoStats {
+    /// Parse the content of a block device `stat` file.
+    /// See https://www.kernel.org/doc/Documentation/block/stat.txt.
+    fn parse(stat: &str) -> Option<Self> {
+        let fields = stat
+            .split_whitespace()
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        if fields.len() < 11 {
+            return None;
+        }
+        let field = |index: usize| fields.get(index).copied().unwrap_or_default();
+        Some(Self {
+            // reads, writes, discards and flushes, the latter only present in newer kernels.
+            completed: field(0) + field(4) + field(11) + field(15),
+            in_flight: field(8),
+        })
+    }
+    fn add(self, other: Self) -> Self {
+        Self {
+            completed: self.completed + other.completed,
+            in_flight: self.in_flight + other.in_flight,
+        }
+    }
+}
+
+/// Detects NVMe controllers with stalled IO, ie: with outstanding requests but no requests
+/// completed since the previous check, which happens when the target is still connected but
+/// no longer processing IO.
+#[derive(Debug)]
+pub(crate) struct IoStallDetector {
+    sysfs_root: PathBuf,
+    /// The IO statistics of each controller from the previous check and its epoch.
+    samples: HashMap<String, (u64, IoStats)>,
+}
+
+impl IoStallDetector {
+    /// Get a new `Self` which reads the IO statistics from the given sysfs root.
+    pub(crate) fn new(sysfs_root: impl Into<PathBuf>) -> Self {
+        Self {
+            sysfs_root: sysfs_root.into(),
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Read the IO statistics of the controller at the given sysfs path, eg: `nvme0` from
+    /// `<sysfs_root>/class/nvme/nvme0/<namespace>/stat`.
+    fn read(&self, controller: &str) -> std::io::Result<IoStats> {
+        let dir = self.sysfs_root.join("class/nvme").join(controller);
+        let mut stats = IoStats::default();
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            // namespaces are named after the controller, eg: nvme0n1 or nvme0c0n1 for multipath.
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == controller || !name.starts_with(controller) {
+                continue;
+            }
+            let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+                continue;
+            };
+            if let Some(ns_stats) = IoStats::parse(&stat) {
+                stats = stats.add(ns_stats);
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Check if the IO of the controller at the given path has stalled since the previous check.
+    /// The IO statistics are sampled for the given epoch, so the samples of the controllers
+    /// which are no longer checked can be removed with `retain_epoch`.
+    pub(crate) fn is_stalled(&mut self, path: &Path, epoch: u64) -> bool {
+        let Some(controller) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            return false;
+        };
+        let stats = match self.read(&controller) {
+            Ok(stats) => stats,
+            Err(error) => {
+                tracing::debug!(%error, controller, "Failed to read NVMe controller IO stats");
+                self.samples.remove(&controller);
+                return false;
+            }
+        };
+        let previous = self.samples.insert(controller, (epoch, stats));
+        matches!(previous, Some((_, previous)) if stats.in_flight > 0
+            && stats.completed == previous.completed)
+    }
+
+    /// Remove the samples of the controllers which were not checked in the given epoch.
+    pub(crate) fn retain_epoch(&mut self, epoch: u64) {
+        self.samples
+            .retain(|_, (sample_epoch, _)| *sample_epoch == epoch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fake sysfs tree with a single controller and namespace.
+    struct FakeSysfs {
+        root: PathBuf,
+    }
+    impl FakeSysfs {
+        fn new() -> Self {
+            let root = std::env::temp_dir().join(format!("sysfs-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(root.join("class/nvme/nvme0/nvme0c0n1")).unwrap();
+            Self { root }
+        }
+        fn set_stat(&self, completed_reads: u64, in_flight: u64) {
+            let stat = format!("{completed_reads} 0 0 0 0 0 0 0 {in_flight} 0 0 0 0 0 0 0 0");
+            let path = self.root.join("class/nvme/nvme0/nvme0c0n1/stat");
+            std::fs::write(path, stat).unwrap();
+        }
+    }
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            std::fs::remove_dir_all(&self.root).ok();
+        }
+    }
+
+    #[test]
+    fn io_stall() {
+        let sysfs = FakeSysfs::new();
+        let mut detector = IoStallDetector::new(&sysfs.root);
+        let path = Path::new("/sys/devices/virtual/nvme-fabrics/ctl/nvme0");
+
+        sysfs.set_stat(10, 2);
+        // No previous sample to compare with.
+        assert!(!detector.is_stalled(path, 1));
+        sysfs.set_stat(12, 2);
+        assert!(!detector.is_stalled(path, 2));
+        // Outstanding requests but no progress.
+        assert!(detector.is_stalled(path, 3));
+        // No progress but nothing outstanding either, ie: idle.
+        sysfs.set_stat(12, 0);
+        assert!(!detector.is_stalled(path, 4));
+
+        detector.retain_epoch(5);
+        sysfs.set_stat(12, 2);
+        assert!(!detector.is_stalled(path, 5));
+        // Unknown controllers never stall.
+        let unknown = Path::new("/sys/devices/virtual/nvme-fabrics/ctl/nvme1");
+        assert!(!detector.is_stalled(unknown, 5));
+    }
+}
@@ -19,6 +19,7 @@ use utils::{
     NVME_PATH_RETRANSMISSION_PERIOD, NVME_SUBSYS_REFRESH_PERIOD,
 };
 mod detector;
+mod io_stats;
 mod path_provider;
 mod reporter;
 mod server;
@@ -58,6 +59,15 @@ struct Cli {
     #[clap(short, long, env = "AGGREGATION_PERIOD", default_value = NVME_PATH_AGGREGATION_PERIOD)]
     aggregation_period: humantime::Duration,
 
+    /// Also detect paths whose IO has stalled, ie: paths which are live but have outstanding
+    /// requests and didn't complete any request since the previous detection.
+    #[clap(long, env = "IO_STALL_DETECTION")]
+    io_stall_detection: bool,
+
+    /// Root of the sysfs tree from which the IO statistics of the NVMe controllers are read.
+    #[clap(long, env = "SYSFS_ROOT", default_value = io_stats::SYSFS_ROOT)]
+    sysfs_root: std::path::PathBuf,
+
     /// Connection timeout for path replacement operation.
     #[clap(short, long, env = "PATH_CONNECTION_TIMEOUT", default_value = NVME_PATH_CONNECTION_PERIOD)]
     path_connection_timeout: humantime::Duration,
@@ -43,6 +43,17 @@ pub enum SvcError {
     CordonLabel { node_id: String, label: String },
     #[snafu(display("Node {node_id} does not have a cordon label '{label}'"))]
     UncordonLabel { node_id: String, label: String },
+    #[snafu(display("Node {node_id} must be drained before it can be decommissioned"))]
+    NodeNotDrained { node_id: String },
+    #[snafu(display(
+        "Node {node_id} is still registered, its io-engine must be stopped before it can be decommissioned"
+    ))]
+    NodeStillRegistered { node_id: String },
+    #[snafu(display("Node {node_id} is still in use by volumes {volumes:?}"))]
+    NodeInUseByVolumes {
+        node_id: String,
+        volumes: Vec<String>,
+    },
     #[snafu(display(
         "Timed out after '{:?}' attempting to connect to node '{}' via gRPC endpoint '{}'",
         timeout,
@@ -92,8 +103,21 @@ pub enum SvcError {
     PoolNotLoaded { pool_id: PoolId },
     #[snafu(display("Pool '{}' not found", pool_id))]
     PoolNotFound { pool_id: PoolId },
-    #[snafu(display("Disk list should have only 1 device. Received :{:?}", disks))]
+    #[snafu(display("Disk list should have at least 1 device. Received :{:?}", disks))]
     InvalidPoolDeviceNum { disks: Vec<PoolDeviceUri> },
+    #[snafu(display(
+        "Pool '{}' can only be expanded with disks it doesn't already have. Received: {:?}",
+        pool_id,
+        disks
+    ))]
+    InvalidPoolExpansion {
+        pool_id: PoolId,
+        disks: Vec<PoolDeviceUri>,
+    },
+    #[snafu(display("Pool {pool_id} is already cordoned with label '{label}'"))]
+    PoolCordonLabel { pool_id: PoolId, label: String },
+    #[snafu(display("Pool {pool_id} does not have a cordon label '{label}'"))]
+    PoolUncordonLabel { pool_id: PoolId, label: String },
     #[snafu(display("Nexus '{}' not found", nexus_id))]
     NexusNotFound { nexus_id: String },
     #[snafu(display(
@@ -394,6 +418,20 @@ pub enum SvcError {
     },
     #[snafu(display("Invalid property name '{property_name}' for the volume '{id}'"))]
     InvalidSetProperty { property_name: String, id: String },
+    #[snafu(display(
+        "{} Resource id {id} is at revision {revision} but revision {expected} was expected",
+        kind.to_string()
+    ))]
+    RevisionMismatch {
+        kind: ResourceKind,
+        id: String,
+        expected: u64,
+        revision: u64,
+    },
+    #[snafu(display("Volume target authentication is disabled: {details}"))]
+    NvmfAuthDisabled { details: String },
+    #[snafu(display("Failed to seal or open the secrets of volume '{id}'"))]
+    NvmfAuthSeal { id: String },
 }
 
 impl SvcError {
@@ -602,6 +640,27 @@ impl From<SvcError> for ReplyError {
                 extra,
             },
 
+            SvcError::NodeNotDrained { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Node,
+                source,
+                extra,
+            },
+
+            SvcError::NodeStillRegistered { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Node,
+                source,
+                extra,
+            },
+
+            SvcError::NodeInUseByVolumes { .. } => ReplyError {
+                kind: ReplyErrorKind::InUse,
+                resource: ResourceKind::Node,
+                source,
+                extra,
+            },
+
             SvcError::GrpcConnectTimeout { .. } => ReplyError {
                 kind: ReplyErrorKind::Timeout,
                 resource: ResourceKind::Node,
@@ -683,6 +742,24 @@ impl From<SvcError> for ReplyError {
                 source,
                 extra,
             },
+            SvcError::InvalidPoolExpansion { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Pool,
+                source,
+                extra,
+            },
+            SvcError::PoolCordonLabel { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source,
+                extra,
+            },
+            SvcError::PoolUncordonLabel { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source,
+                extra,
+            },
             SvcError::ReplicaNotFound { .. } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: ResourceKind::Replica,
@@ -1062,6 +1139,24 @@ impl From<SvcError> for ReplyError {
                 source,
                 extra,
             },
+            SvcError::RevisionMismatch { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: kind.clone(),
+                source,
+                extra,
+            },
+            SvcError::NvmfAuthDisabled { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
+            SvcError::NvmfAuthSeal { .. } => ReplyError {
+                kind: ReplyErrorKind::Internal,
+                resource: ResourceKind::Volume,
+                source,
+                extra,
+            },
         }
     }
 }
@@ -5,8 +5,8 @@ use stor_port::types::v0::openapi::{
     models,
     models::{
         AffinityGroup, AppNode, CreateVolumeBody, Node, NodeTopology, Pool, PoolTopology,
-        PublishVolumeBody, ResizeVolumeBody, RestJsonError, Topology, Volume, VolumePolicy,
-        VolumeShareProtocol, Volumes,
+        PublishVolumeBody, ResizeVolumeBody, RestJsonError, TargetPlacement, Topology, Volume,
+        VolumeAuth, VolumeAutoGrow, VolumePolicy, VolumeShareProtocol, Volumes,
     },
 };
 
@@ -161,7 +161,7 @@ pub(crate) enum ListToken {
 impl RestApiClient {
     /// List all nodes available in IoEngine cluster.
     pub(crate) async fn list_nodes(&self) -> Result<Vec<Node>, ApiClientError> {
-        let response = self.rest_client.nodes_api().get_nodes(None).await?;
+        let response = self.rest_client.nodes_api().get_nodes(None, None).await?;
         Ok(response.into_body())
     }
 
@@ -170,7 +170,7 @@ impl RestApiClient {
         let response = self
             .rest_client
             .nodes_api()
-            .get_nodes(Some(node_id))
+            .get_nodes(Some(node_id), None)
             .await?;
         match response.into_body().pop() {
             Some(node) => Ok(node),
@@ -180,7 +180,7 @@ impl RestApiClient {
 
     /// List all pools available in IoEngine cluster.
     pub(crate) async fn list_pools(&self) -> Result<Vec<Pool>, ApiClientError> {
-        let response = self.rest_client.pools_api().get_pools().await?;
+        let response = self.rest_client.pools_api().get_pools(None).await?;
         Ok(response.into_body())
     }
 
@@ -204,7 +204,7 @@ impl RestApiClient {
         let response = self
             .rest_client
             .volumes_api()
-            .get_volumes(max_entries, None, Some(starting_token))
+            .get_volumes(max_entries, None, Some(starting_token), None)
             .await?;
         Ok(response.into_body())
     }
@@ -229,6 +229,9 @@ impl RestApiClient {
         thin: bool,
         affinity_group: Option<AffinityGroup>,
         max_snapshots: Option<u32>,
+        autogrow: Option<VolumeAutoGrow>,
+        auth: Option<VolumeAuth>,
+        target_placement: Option<TargetPlacement>,
     ) -> Result<Volume, ApiClientError> {
         let topology =
             Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
@@ -242,6 +245,9 @@ impl RestApiClient {
             labels: None,
             affinity_group,
             max_snapshots,
+            autogrow,
+            auth,
+            target_placement,
         };
 
         let result = self
@@ -267,6 +273,9 @@ impl RestApiClient {
         thin: bool,
         affinity_group: Option<AffinityGroup>,
         max_snapshots: Option<u32>,
+        autogrow: Option<VolumeAutoGrow>,
+        auth: Option<VolumeAuth>,
+        target_placement: Option<TargetPlacement>,
     ) -> Result<Volume, ApiClientError> {
         let topology =
             Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
@@ -280,6 +289,9 @@ impl RestApiClient {
             labels: None,
             affinity_group,
             max_snapshots,
+            autogrow,
+            auth,
+            target_placement,
         };
         let result = self
             .rest_client
@@ -347,7 +359,7 @@ impl RestApiClient {
         let response = self
             .rest_client
             .volumes_api()
-            .get_volumes(1, Some(volume_id), None)
+            .get_volumes(1, Some(volume_id), None, None)
             .await?;
         let mut entries = response.into_body().entries;
         match entries.pop() {
@@ -375,6 +387,7 @@ impl RestApiClient {
     }
 
     /// Publish volume (i.e. make it accessible via specified protocol by creating a target).
+    #[allow(clippy::too_many_arguments)]
     #[instrument(fields(volume.uuid = %volume_id), skip(self, volume_id))]
     pub(crate) async fn publish_volume(
         &self,
@@ -382,6 +395,8 @@ impl RestApiClient {
         node: Option<&str>,
         protocol: VolumeShareProtocol,
         frontend_node: String,
+        multi_node: bool,
+        read_only: bool,
         publish_context: &HashMap<String, String>,
     ) -> Result<Volume, ApiClientError> {
         let publish_volume_body = PublishVolumeBody::new_all(
@@ -391,6 +406,8 @@ impl RestApiClient {
             protocol,
             None,
             frontend_node,
+            multi_node,
+            read_only,
         );
         let volume = self
             .rest_client
@@ -400,6 +417,36 @@ impl RestApiClient {
         Ok(volume.into_body())
     }
 
+    /// Allow another frontend node to attach to the target of a multi-node volume.
+    #[instrument(fields(volume.uuid = %volume_id), skip(self, volume_id))]
+    pub(crate) async fn add_volume_frontend_node(
+        &self,
+        volume_id: &uuid::Uuid,
+        node_id: &str,
+    ) -> Result<Volume, ApiClientError> {
+        let volume = self
+            .rest_client
+            .volumes_api()
+            .put_volume_frontend_node(volume_id, node_id)
+            .await?;
+        Ok(volume.into_body())
+    }
+
+    /// Fence a frontend node off the target of a multi-node volume.
+    #[instrument(fields(volume.uuid = %volume_id), skip(self, volume_id))]
+    pub(crate) async fn remove_volume_frontend_node(
+        &self,
+        volume_id: &uuid::Uuid,
+        node_id: &str,
+    ) -> Result<Volume, ApiClientError> {
+        let volume = self
+            .rest_client
+            .volumes_api()
+            .del_volume_frontend_node(volume_id, node_id)
+            .await?;
+        Ok(volume.into_body())
+    }
+
     /// Create a volume snapshot.
     #[instrument(fields(volume.uuid = %volume_id, snapshot.source_uuid = %volume_id, snapshot.uuid = %snapshot_id), skip(self, volume_id, snapshot_id))]
     pub(crate) async fn create_volume_snapshot(
@@ -461,6 +508,7 @@ impl RestApiClient {
                 snapshot_id.as_ref(),
                 volume_id.as_ref(),
                 Some(starting_token),
+                None,
             )
             .await?;
 
@@ -3,7 +3,7 @@ use crate::{
     CsiControllerConfig,
 };
 use csi_driver::{
-    context::{CreateParams, CreateSnapshotParams, PublishParams, QuiesceFsCandidate},
+    context::{CreateParams, CreateSnapshotParams, Parameters, PublishParams, QuiesceFsCandidate},
     node::internal::{node_plugin_client::NodePluginClient, FreezeFsRequest, UnfreezeFsRequest},
 };
 use rpc::csi::{volume_content_source::Type, Topology as CsiTopology, *};
@@ -11,7 +11,7 @@ use stor_port::types::v0::openapi::{
     models,
     models::{
         AffinityGroup, LabelledTopology, NodeSpec, NodeStatus, Pool, PoolStatus, PoolTopology,
-        SpecStatus, Volume, VolumeShareProtocol,
+        SpecStatus, Volume, VolumeAuth, VolumeShareProtocol,
     },
 };
 use utils::{CREATED_BY_KEY, DSP_OPERATOR};
@@ -53,12 +53,52 @@ impl CsiControllerSvc {
     }
 }
 
-/// Check whether target volume capabilities are valid. As of now, only
-/// SingleNodeWriter capability is supported.
+/// Get the access mode of the volume capability.
+fn access_mode(capability: &VolumeCapability) -> Option<volume_capability::access_mode::Mode> {
+    capability.access_mode.as_ref().and_then(|access_mode| {
+        volume_capability::access_mode::Mode::try_from(access_mode.mode).ok()
+    })
+}
+
+/// Check whether the volume capability is supported. As of now, SingleNodeWriter and the
+/// reader-only modes are supported, and MultiNodeMultiWriter for raw block volumes.
+fn supported_capability(capability: &VolumeCapability) -> bool {
+    use volume_capability::access_mode::Mode;
+    match access_mode(capability) {
+        Some(Mode::SingleNodeWriter | Mode::SingleNodeReaderOnly | Mode::MultiNodeReaderOnly) => {
+            true
+        }
+        Some(Mode::MultiNodeMultiWriter) => {
+            matches!(capability.access_type, Some(AccessType::Block(_)))
+        }
+        _ => false,
+    }
+}
+
+/// Check whether the volume capability allows multiple nodes to attach the volume.
+fn multi_node_capability(capability: &VolumeCapability) -> bool {
+    use volume_capability::access_mode::Mode;
+    supported_capability(capability)
+        && matches!(
+            access_mode(capability),
+            Some(Mode::MultiNodeMultiWriter | Mode::MultiNodeReaderOnly)
+        )
+}
+
+/// Check whether the volume capability only allows reading from the volume.
+fn reader_only_capability(capability: &VolumeCapability) -> bool {
+    use volume_capability::access_mode::Mode;
+    matches!(
+        access_mode(capability),
+        Some(Mode::SingleNodeReaderOnly | Mode::MultiNodeReaderOnly)
+    )
+}
+
+/// Check whether target volume capabilities are valid.
 fn check_volume_capabilities(capabilities: &[VolumeCapability]) -> Result<(), tonic::Status> {
     for c in capabilities {
         if let Some(access_mode) = c.access_mode.as_ref() {
-            if access_mode.mode != volume_capability::access_mode::Mode::SingleNodeWriter as i32 {
+            if !supported_capability(c) {
                 return Err(Status::invalid_argument(format!(
                     "Invalid volume access mode: {:?}",
                     access_mode.mode
@@ -231,6 +271,31 @@ fn check_existing_volume(
     Ok(())
 }
 
+/// Get the DH-HMAC-CHAP authentication of the volume target, if requested by the storage class.
+/// The initial secrets must be supplied by the provisioner secret. The node stage secret marks the
+/// volume as authenticated on the node, which then gets the current secrets from the control-plane,
+/// as they may have been rotated since.
+fn volume_auth(
+    context: &CreateParams,
+    secrets: &HashMap<String, String>,
+) -> Result<Option<VolumeAuth>, Status> {
+    if !context.nvmf_auth() {
+        return Ok(None);
+    }
+    let host_secret = secrets
+        .get(Parameters::DhChapHostSecret.as_ref())
+        .cloned()
+        .ok_or_else(|| {
+            Status::invalid_argument(format!(
+                "`{}` requires the `{}` provisioner secret",
+                Parameters::NvmfAuth.as_ref(),
+                Parameters::DhChapHostSecret.as_ref()
+            ))
+        })?;
+    let ctrl_secret = secrets.get(Parameters::DhChapCtrlSecret.as_ref()).cloned();
+    Ok(Some(VolumeAuth::new_all(Some(host_secret), ctrl_secret)))
+}
+
 struct VolumeTopologyMapper {}
 
 impl VolumeTopologyMapper {
@@ -353,6 +418,9 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
                 let sts_affinity_group_name = context.sts_affinity_group();
                 let max_snapshots = context.max_snapshots();
+                let autogrow = context.autogrow();
+                let auth = volume_auth(&context, &args.secrets)?;
+                let target_placement = context.target_placement();
 
                 let volume = match volume_content_source {
                     Some(snapshot_uuid) => {
@@ -366,6 +434,9 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                                 thin,
                                 sts_affinity_group_name.clone().map(AffinityGroup::new),
                                 max_snapshots,
+                                autogrow,
+                                auth.clone(),
+                                target_placement,
                             )
                             .await?
                     }
@@ -379,6 +450,9 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                                 thin,
                                 sts_affinity_group_name.clone().map(AffinityGroup::new),
                                 max_snapshots,
+                                autogrow,
+                                auth.clone(),
+                                target_placement,
                             )
                             .await?
                     }
@@ -448,12 +522,6 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
     ) -> Result<tonic::Response<ControllerPublishVolumeResponse>, tonic::Status> {
         let args = request.into_inner();
         tracing::trace!(volume.uuid = %args.volume_id, request = ?args);
-        if args.readonly {
-            return Err(Status::invalid_argument(
-                "Read-only volumes are not supported",
-            ));
-        }
-
         let protocol = parse_protocol(args.volume_context.get("protocol"))?;
 
         if args.node_id.is_empty() {
@@ -469,6 +537,17 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         })?;
         let _guard = csi_driver::limiter::VolumeOpGuard::new(volume_id)?;
 
+        let multi_node = args
+            .volume_capability
+            .as_ref()
+            .map(multi_node_capability)
+            .unwrap_or_default();
+        let read_only = args.readonly
+            || args
+                .volume_capability
+                .as_ref()
+                .map(reader_only_capability)
+                .unwrap_or_default();
         match args.volume_capability {
             Some(c) => check_volume_capabilities(&[c])?,
             None => {
@@ -479,7 +558,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         // Check if the volume is already published.
         let volume = RestApiClient::get_client().get_volume(&volume_id).await?;
 
-        let params = PublishParams::try_from(&args.volume_context)?;
+        let params = PublishParams::try_from(&args.volume_context)?.with_read_only(read_only);
 
         // Prepare the context for the csi-node plugin.
         let mut publish_context = params.into_context();
@@ -496,15 +575,30 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                         error!("{}", m);
                         return Err(Status::failed_precondition(m));
                     }
+                    if target.read_only == Some(true) && !read_only {
+                        let m = format!("Volume {volume_id} is published read-only");
+                        error!("{m}");
+                        return Err(Status::failed_precondition(m));
+                    }
 
                     if let Some((node, uri)) = get_volume_share_location(&volume) {
-                        // Make sure volume is accessible from the same app node.
-                        if let Err(allowed) = frontend_nodes_allowed(target, &node_id) {
-                            let m = format!(
-                                "Volume {volume_id} is only accessible to nodes: {allowed:?}, and not to {node_id}"
-                            );
-                            error!("{m}");
-                            return Err(Status::failed_precondition(m));
+                        // Make sure volume is accessible from the same app node, or let another
+                        // node attach to a multi-node volume.
+                        match frontend_nodes_allowed(target, &node_id) {
+                            Ok(()) => {}
+                            Err(_) if multi_node && target.multi_node == Some(true) => {
+                                RestApiClient::get_client()
+                                    .add_volume_frontend_node(&volume_id, &node_id)
+                                    .await?;
+                                debug!("Volume {volume_id} now also accessible to {node_id}");
+                            }
+                            Err(allowed) => {
+                                let m = format!(
+                                    "Volume {volume_id} is only accessible to nodes: {allowed:?}, and not to {node_id}"
+                                );
+                                error!("{m}");
+                                return Err(Status::failed_precondition(m));
+                            }
                         }
 
                         debug!("Volume {volume_id} already published for {node_id} on {node} => {uri}");
@@ -545,7 +639,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
                     // Volume is not published.
                     let v = RestApiClient::get_client()
-                        .publish_volume(&volume_id, target_node, protocol, args.node_id.clone(), &publish_context)
+                        .publish_volume(&volume_id, target_node, protocol, args.node_id.clone(), multi_node, read_only, &publish_context)
                         .await?;
 
                     if let Some((node, uri)) = get_volume_share_location(&v) {
@@ -606,6 +700,25 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             return Ok(Response::new(ControllerUnpublishVolumeResponse {}));
         }
 
+        // Other nodes may still be attached to a multi-node volume, so only fence this one off.
+        if let Some(target) = volume.spec.target.as_ref() {
+            let others = target
+                .frontend_nodes
+                .iter()
+                .flatten()
+                .any(|node| node.name != args.node_id);
+            if target.multi_node == Some(true) && others {
+                RestApiClient::get_client()
+                    .remove_volume_frontend_node(&volume_uuid, &args.node_id)
+                    .await?;
+                debug!(
+                    "Volume {} no longer accessible to {}",
+                    args.volume_id, args.node_id
+                );
+                return Ok(Response::new(ControllerUnpublishVolumeResponse {}));
+            }
+        }
+
         // Do forced volume upublish as Kubernetes already detached the volume.
         RestApiClient::get_client()
             .unpublish_volume(&volume_uuid, true)
@@ -642,16 +755,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         let caps: Vec<VolumeCapability> = args
             .volume_capabilities
             .into_iter()
-            .filter(|cap| {
-                if let Some(access_mode) = cap.access_mode.as_ref() {
-                    if access_mode.mode
-                        == volume_capability::access_mode::Mode::SingleNodeWriter as i32
-                    {
-                        return true;
-                    }
-                }
-                false
-            })
+            .filter(supported_capability)
             .collect();
 
         let response = if !caps.is_empty() {
@@ -666,7 +770,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         } else {
             ValidateVolumeCapabilitiesResponse {
                 confirmed: None,
-                message: "The only supported capability is SINGLE_NODE_WRITER".to_string(),
+                message: "The only supported capabilities are SINGLE_NODE_WRITER, SINGLE_NODE_READER_ONLY, MULTI_NODE_READER_ONLY and MULTI_NODE_MULTI_WRITER for raw block volumes".to_string(),
             }
         };
 
@@ -5,6 +5,7 @@ mod client;
 mod config;
 mod controller;
 mod identity;
+mod pvcresizer;
 mod pvwatcher;
 mod server;
 
@@ -103,6 +104,15 @@ async fn main() -> anyhow::Result<()> {
                         An orphan volume is a volume with no corresponding PV",
                 )
         )
+        .arg(
+            Arg::new("autogrow-pvc-sync-period")
+                .long("autogrow-pvc-sync-period")
+                .default_value("1m")
+                .help(
+                    "How often to update the PVCs of volumes which were automatically grown. \n\
+                        This requires permission to patch PVCs",
+                )
+        )
         .get_matches();
 
     utils::print_package_info!();
@@ -120,6 +130,10 @@ async fn main() -> anyhow::Result<()> {
         .get_one::<String>("orphan-vol-gc-period")
         .map(|p| p.parse::<humantime::Duration>())
         .transpose()?;
+    let autogrow_pvc_sync_period = args
+        .get_one::<String>("autogrow-pvc-sync-period")
+        .expect("AutoGrow PVC sync period must be specified")
+        .parse::<humantime::Duration>()?;
     let csi_socket = args
         .get_one::<String>("socket")
         .expect("CSI socket must be specified");
@@ -134,10 +148,12 @@ async fn main() -> anyhow::Result<()> {
     // Try to detect REST API endpoint to debug the accessibility status.
     ping_rest_api().await;
 
-    // Starts PV Garbage Collector if platform type is k8s
+    // Starts PV Garbage Collector and AutoGrow PVC Resizer if platform type is k8s
     if stor_port::platform::current_platform_type() == stor_port::platform::PlatformType::K8s {
         let gc_instance = pvwatcher::PvGarbageCollector::new(orphan_period).await?;
         tokio::spawn(async move { gc_instance.run_watcher().await });
+        let pvc_resizer = pvcresizer::AutoGrowPvcResizer::new(autogrow_pvc_sync_period).await?;
+        tokio::spawn(pvc_resizer.run());
     }
 
     let result = server::CsiServer::run(csi_socket).await;
@@ -0,0 +1,179 @@
+use crate::client::ListToken;
+use k8s_openapi::{
+    api::core::v1::{PersistentVolume, PersistentVolumeClaim},
+    apimachinery::pkg::api::resource::Quantity,
+};
+use kube::{
+    api::{Api, Patch, PatchParams},
+    Client,
+};
+use stor_port::types::v0::openapi::models::Volume;
+use tracing::{debug, error, info};
+
+/// Syncs the storage requested by the PVCs of volumes which were automatically grown by the
+/// control-plane.
+/// The autogrow reconciler only expands the volume target, and so the PVC is updated to the new
+/// size, which makes the external-resizer and the kubelet complete the expansion, ie: the no-op
+/// ControllerExpandVolume followed by the NodeExpandVolume which grows the filesystem.
+/// This requires the PVC's storage class to allow volume expansion.
+#[derive(Clone)]
+pub(crate) struct AutoGrowPvcResizer {
+    client: Client,
+    pv_handle: Api<PersistentVolume>,
+    period: std::time::Duration,
+    rest_client: &'static crate::RestApiClient,
+}
+
+impl AutoGrowPvcResizer {
+    /// Returns an instance of the AutoGrow PVC resizer.
+    pub(crate) async fn new(period: humantime::Duration) -> anyhow::Result<Self> {
+        let client = Client::try_default().await?;
+        Ok(Self {
+            pv_handle: Api::<PersistentVolume>::all(client.clone()),
+            client,
+            period: period.into(),
+            rest_client: crate::RestApiClient::get_client(),
+        })
+    }
+    /// Periodically syncs the PVCs of the volumes with an autogrow policy.
+    pub(crate) async fn run(self) {
+        info!("Starting AutoGrow PVC Resizer");
+        let mut ticker = tokio::time::interval(self.period);
+        loop {
+            ticker.tick().await;
+            self.resize_pvcs().await;
+        }
+    }
+
+    async fn resize_pvcs(&self) {
+        let max_entries = 200;
+        let mut starting_token = Some(0);
+        while let Some(token) = starting_token {
+            match self
+                .rest_client
+                .list_volumes(max_entries, ListToken::Number(token))
+                .await
+            {
+                Ok(volumes) => {
+                    starting_token = volumes.next_token;
+                    for volume in volumes.entries {
+                        if volume.spec.autogrow.is_some() {
+                            self.resize_pvc(&volume).await;
+                        }
+                    }
+                }
+                Err(error) => {
+                    error!(?error, "Unable to list volumes");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Updates the PVC of the volume if it requests less storage than the volume's size.
+    #[tracing::instrument(level = "debug", skip(self, volume), fields(volume.uuid = %volume.spec.uuid))]
+    async fn resize_pvc(&self, volume: &Volume) -> Option<()> {
+        let pv_name = format!("pvc-{}", volume.spec.uuid);
+        let pv = match self.pv_handle.get_opt(&pv_name).await {
+            Ok(pv) => pv?,
+            Err(error) => {
+                error!(%error, pv.name = pv_name, "Error while fetching the PV");
+                return None;
+            }
+        };
+        let claim = pv.spec?.claim_ref?;
+        let (namespace, name) = (claim.namespace?, claim.name?);
+
+        let pvc_handle = Api::<PersistentVolumeClaim>::namespaced(self.client.clone(), &namespace);
+        let pvc = match pvc_handle.get_opt(&name).await {
+            Ok(pvc) => pvc?,
+            Err(error) => {
+                error!(
+                    %error,
+                    pvc.name = name,
+                    pvc.namespace = namespace,
+                    "Error while fetching the PVC"
+                );
+                return None;
+            }
+        };
+        let requested = pvc
+            .spec?
+            .resources?
+            .requests?
+            .get("storage")
+            .and_then(quantity_bytes)?;
+        if requested >= volume.spec.size {
+            debug!(
+                pvc.name = name,
+                pvc.requested = requested,
+                "PVC is up to date"
+            );
+            return Some(());
+        }
+
+        let patch = serde_json::json!({
+            "spec": { "resources": { "requests": { "storage": volume.spec.size.to_string() } } }
+        });
+        match pvc_handle
+            .patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+        {
+            Ok(_) => info!(
+                pvc.name = name,
+                pvc.namespace = namespace,
+                pvc.requested = requested,
+                volume.size = volume.spec.size,
+                "Updated the PVC of the automatically grown volume"
+            ),
+            Err(error) => error!(
+                %error,
+                pvc.name = name,
+                pvc.namespace = namespace,
+                "Failed to update the PVC of the automatically grown volume"
+            ),
+        }
+        Some(())
+    }
+}
+
+/// Get the number of bytes of a k8s quantity, eg: "10Gi" or "1073741824".
+/// Fractional and exponent quantities are not supported.
+fn quantity_bytes(quantity: &Quantity) -> Option<u64> {
+    let quantity = quantity.0.trim();
+    let index = quantity
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(quantity.len());
+    let (number, suffix) = quantity.split_at(index);
+    let multiplier: u64 = match suffix {
+        "" => 1,
+        "k" => 1000,
+        "M" => 1000u64.pow(2),
+        "G" => 1000u64.pow(3),
+        "T" => 1000u64.pow(4),
+        "P" => 1000u64.pow(5),
+        "E" => 1000u64.pow(6),
+        "Ki" => 1 << 10,
+        "Mi" => 1 << 20,
+        "Gi" => 1 << 30,
+        "Ti" => 1 << 40,
+        "Pi" => 1 << 50,
+        "Ei" => 1 << 60,
+        _ => return None,
+    };
+    number.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+#[test]
+fn quantity() {
+    let bytes = |quantity: &str| quantity_bytes(&Quantity(quantity.to_string()));
+    assert_eq!(bytes("1073741824"), Some(1073741824));
+    assert_eq!(bytes("10Gi"), Some(10 * 1024 * 1024 * 1024));
+    assert_eq!(bytes("512Mi"), Some(512 * 1024 * 1024));
+    assert_eq!(bytes("2G"), Some(2_000_000_000));
+    assert_eq!(bytes("1k"), Some(1000));
+    assert_eq!(bytes("1.5Gi"), None);
+    assert_eq!(bytes("1e3"), None);
+    assert_eq!(bytes("Gi"), None);
+    assert_eq!(bytes("16Ei"), None);
+}
@@ -1,13 +1,18 @@
 use stor_port::types::v0::openapi::{
-    apis::app_nodes_api::tower::client::AppNodesClient,
+    apis::{
+        app_nodes_api::tower::client::AppNodesClient, volumes_api::tower::client::VolumesClient,
+        Uuid,
+    },
     clients,
     clients::tower::StatusCode,
-    models::{RegisterAppNode, RestJsonError},
+    models::{DhChapKeys, RegisterAppNode, RestJsonError},
 };
 
 use anyhow::{anyhow, Result};
 use std::{collections::HashMap, sync::Arc, time::Duration};
-use stor_port::types::v0::openapi::apis::app_nodes_api::tower::client::direct::AppNodes;
+use stor_port::types::v0::openapi::apis::{
+    app_nodes_api::tower::client::direct::AppNodes, volumes_api::tower::client::direct::Volumes,
+};
 use tonic::Status;
 use tracing::info;
 
@@ -96,8 +101,10 @@ impl From<clients::tower::Error<RestJsonError>> for ApiClientError {
 const DEFAULT_TIMEOUT_FOR_REST_REQUESTS: Duration = Duration::from_secs(5);
 
 /// Wrapper for AppNodes REST API client.
+/// It also gets the DH-HMAC-CHAP secrets of volume targets through the Volumes REST API client.
 pub(crate) struct AppNodesClientWrapper {
     client: AppNodesClient,
+    volumes: VolumesClient,
 }
 
 impl AppNodesClientWrapper {
@@ -127,8 +134,10 @@ impl AppNodesClientWrapper {
             endpoint, DEFAULT_TIMEOUT_FOR_REST_REQUESTS,
         );
 
+        let tower = Arc::new(tower);
         Ok(Some(Self {
-            client: AppNodesClient::new(Arc::new(tower)),
+            client: AppNodesClient::new(tower.clone()),
+            volumes: VolumesClient::new(tower),
         }))
     }
 
@@ -158,4 +167,10 @@ impl AppNodesClientWrapper {
 
         Ok(())
     }
+
+    /// Get the DH-HMAC-CHAP secrets which the volume target is currently shared with.
+    pub(crate) async fn volume_auth(&self, volume_id: &Uuid) -> Result<DhChapKeys, ApiClientError> {
+        let keys = self.volumes.get_volume_auth(volume_id).await?;
+        Ok(keys.into_body())
+    }
 }
@@ -9,7 +9,7 @@ use nvmeadm::{
     nvmf_discovery::{disconnect, ConnectArgsBuilder},
 };
 
-use csi_driver::PublishParams;
+use csi_driver::{Parameters, PublishParams};
 use glob::glob;
 use nvmeadm::nvmf_subsystem::Subsystem;
 use regex::Regex;
@@ -39,6 +39,8 @@ pub(super) struct NvmfAttach {
     ctrl_loss_tmo: Option<u32>,
     keep_alive_tmo: Option<u32>,
     hostnqn: Option<String>,
+    dhchap_secret: Option<String>,
+    dhchap_ctrl_secret: Option<String>,
 }
 
 impl NvmfAttach {
@@ -64,6 +66,8 @@ impl NvmfAttach {
             ctrl_loss_tmo,
             keep_alive_tmo,
             hostnqn,
+            dhchap_secret: None,
+            dhchap_ctrl_secret: None,
         }
     }
 
@@ -145,6 +149,10 @@ impl Attach for NvmfAttach {
         if let Some(keep_alive_tmo) = nvme_config.keep_alive_tmo() {
             self.keep_alive_tmo = Some(keep_alive_tmo);
         }
+
+        // DH-HMAC-CHAP secrets of authenticated volume targets.
+        self.dhchap_secret = context.get(Parameters::DhChapHostSecret.as_ref()).cloned();
+        self.dhchap_ctrl_secret = context.get(Parameters::DhChapCtrlSecret.as_ref()).cloned();
         Ok(())
     }
 
@@ -177,6 +185,8 @@ impl Attach for NvmfAttach {
                     .nr_io_queues(self.nr_io_queues)
                     .hostnqn(self.hostnqn.clone())
                     .keep_alive_tmo(self.keep_alive_tmo)
+                    .dhchap_secret(self.dhchap_secret.clone())
+                    .dhchap_ctrl_secret(self.dhchap_ctrl_secret.clone())
                     .build()?;
                 match ca.connect() {
                     // Should we remove this arm?
@@ -42,6 +42,12 @@ pub(crate) async fn stage_fs_volume(
     let params = PublishParams::try_from(&msg.publish_context)?;
     let fs_id = params.fs_id();
 
+    // Volumes published read-only are staged read-only.
+    let mut stage_flags = mnt.mount_flags.clone();
+    if params.read_only() && !stage_flags.readonly() {
+        stage_flags.push("ro".to_string());
+    }
+
     let fs_staging_path = &msg.staging_target_path;
 
     // One final check for fs volumes, ignore for block volumes.
@@ -106,8 +112,8 @@ pub(crate) async fn stage_fs_volume(
 
         if !continue_stage {
             // todo: validate other flags?
-            if mnt.mount_flags.readonly() != existing.options.readonly() {
-                mount::remount(fs_staging_path, mnt.mount_flags.readonly())?;
+            if stage_flags.readonly() != existing.options.readonly() {
+                mount::remount(fs_staging_path, stage_flags.readonly())?;
             }
 
             return Ok(());
@@ -145,7 +151,7 @@ pub(crate) async fn stage_fs_volume(
                 error
             )
         })?
-        .mount_flags(mnt.mount_flags.clone());
+        .mount_flags(stage_flags);
 
     if let Err(error) =
         prepare_device(fstype, device_path, fs_staging_path, &mount_flags, fs_id).await
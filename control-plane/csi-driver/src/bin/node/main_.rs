@@ -297,7 +297,8 @@ pub(super) async fn main() -> anyhow::Result<()> {
     }
 
     // Initialize the rest api client.
-    let client = AppNodesClientWrapper::initialize(matches.get_one::<String>("rest-endpoint"))?;
+    let client = AppNodesClientWrapper::initialize(matches.get_one::<String>("rest-endpoint"))?
+        .map(Arc::new);
 
     let registration_enabled = matches.get_flag("enable-registration");
 
@@ -311,7 +312,7 @@ pub(super) async fn main() -> anyhow::Result<()> {
     // enabled.
     *crate::config::config().nvme_as_mut() = TryFrom::try_from(&matches)?;
     let (csi, grpc, registration) = tokio::join!(
-        CsiServer::run(csi_socket, &matches)?,
+        CsiServer::run(csi_socket, &matches, client.clone())?,
         NodePluginGrpcServer::run(grpc_sock_addr),
         run_registration_loop(
             node_name.clone(),
@@ -330,6 +331,7 @@ impl CsiServer {
     fn run(
         csi_socket: &str,
         cli_args: &clap::ArgMatches,
+        client: Option<Arc<AppNodesClientWrapper>>,
     ) -> anyhow::Result<impl Future<Output = anyhow::Result<()>>> {
         let node_name = cli_args.get_one::<String>("node-name").expect("required");
         let node_selector = csi_driver::csi_node_selector_parse(
@@ -361,7 +363,7 @@ impl CsiServer {
             }
         };
 
-        let node = Node::new(node_name.into(), node_selector, probe_filesystems());
+        let node = Node::new(node_name.into(), node_selector, probe_filesystems(), client);
         Ok(async move {
             Server::builder()
                 .add_service(NodeServer::new(node))
@@ -1,5 +1,6 @@
 use crate::{
     block_vol::{publish_block_volume, unpublish_block_volume},
+    client::AppNodesClientWrapper,
     dev::{get_size_from_dev_name, Device},
     filesystem_ops::FileSystem,
     filesystem_vol::{publish_fs_volume, stage_fs_volume, unpublish_fs_volume, unstage_fs_volume},
@@ -9,6 +10,7 @@ use csi_driver::{
     csi::volume_capability::{access_mode::Mode, AccessType},
     filesystem::FileSystem as Fs,
     limiter::VolumeOpGuard,
+    Parameters,
 };
 use rpc::{
     csi,
@@ -24,7 +26,7 @@ use rpc::{
 };
 
 use nix::{errno::Errno, sys};
-use std::{collections::HashMap, path::Path, time::Duration, vec::Vec};
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration, vec::Vec};
 use tonic::{Code, Request, Response, Status};
 use tracing::{debug, error, info, trace};
 use uuid::Uuid;
@@ -40,6 +42,7 @@ pub(crate) struct Node {
     node_name: String,
     node_selector: HashMap<String, String>,
     filesystems: Vec<FileSystem>,
+    client: Option<Arc<AppNodesClientWrapper>>,
 }
 
 impl Node {
@@ -48,15 +51,59 @@ impl Node {
         node_name: String,
         node_selector: HashMap<String, String>,
         filesystems: Vec<FileSystem>,
+        client: Option<Arc<AppNodesClientWrapper>>,
     ) -> Node {
         let self_ = Self {
             node_name,
             node_selector,
             filesystems,
+            client,
         };
         info!("Node topology segments: {:?}", self_.segments());
         self_
     }
+    /// Get the DH-HMAC-CHAP secrets to connect to the volume target with.
+    /// Authenticated volumes have a node stage secret, but the control-plane may have rotated the
+    /// secrets since, and so those which the target is currently shared with are taken from the
+    /// control-plane instead. The node stage secret is only used when there's no control-plane
+    /// endpoint, in which case the secrets must only be rotated through the k8s Secret.
+    async fn dhchap_secrets(
+        &self,
+        uuid: &Uuid,
+        secrets: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, Status> {
+        let stage_secrets = secrets
+            .iter()
+            .filter(|(key, _)| {
+                key.as_str() == Parameters::DhChapHostSecret.as_ref()
+                    || key.as_str() == Parameters::DhChapCtrlSecret.as_ref()
+            })
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<HashMap<_, _>>();
+        let Some(client) = self.client.as_ref().filter(|_| !stage_secrets.is_empty()) else {
+            return Ok(stage_secrets);
+        };
+
+        let keys = client.volume_auth(uuid).await.map_err(|error| {
+            failure!(
+                Code::Unavailable,
+                "Failed to stage volume {}: failed to get the DH-HMAC-CHAP secrets: {:?}",
+                uuid,
+                error
+            )
+        })?;
+        let mut secrets = HashMap::from([(
+            Parameters::DhChapHostSecret.as_ref().to_string(),
+            keys.host_secret,
+        )]);
+        if let Some(ctrl_secret) = keys.ctrl_secret {
+            secrets.insert(
+                Parameters::DhChapCtrlSecret.as_ref().to_string(),
+                ctrl_secret,
+            );
+        }
+        Ok(secrets)
+    }
     /// Get the node_name label segment.
     fn node_name_segment(&self) -> (String, String) {
         (
@@ -102,6 +149,12 @@ fn check_access_mode(
             Some(access) => match Mode::try_from(access.mode) {
                 Ok(mode) => match mode {
                     Mode::SingleNodeWriter | Mode::MultiNodeSingleWriter => Ok(()),
+                    // Multiple writers are left to coordinate through the raw block device.
+                    Mode::MultiNodeMultiWriter
+                        if matches!(capability.access_type, Some(AccessType::Block(_))) =>
+                    {
+                        Ok(())
+                    }
                     Mode::SingleNodeReaderOnly | Mode::MultiNodeReaderOnly => {
                         if readonly {
                             return Ok(());
@@ -240,10 +293,24 @@ impl node_server::Node for Node {
         &self,
         request: Request<NodePublishVolumeRequest>,
     ) -> Result<Response<NodePublishVolumeResponse>, Status> {
-        let msg = request.into_inner();
+        let mut msg = request.into_inner();
 
         trace!("node_publish_volume {:?}", msg);
 
+        // Volumes published read-only may only be read from, whatever the CO asks for.
+        let read_only = Parameters::read_only(
+            msg.publish_context.get(Parameters::ReadOnly.as_ref()),
+        )
+        .map_err(|error| {
+            failure!(
+                Code::InvalidArgument,
+                "Failed to publish volume {}: invalid read-only flag: {}",
+                &msg.volume_id,
+                error
+            )
+        })?;
+        msg.readonly |= read_only.unwrap_or_default();
+
         if msg.volume_id.is_empty() {
             return Err(failure!(
                 Code::InvalidArgument,
@@ -674,8 +741,10 @@ impl node_server::Node for Node {
                 error
             )
         })?;
+        let mut parameters = msg.publish_context.clone();
+        parameters.extend(self.dhchap_secrets(&uuid, &msg.secrets).await?);
         device
-            .parse_parameters(&msg.publish_context)
+            .parse_parameters(&parameters)
             .await
             .map_err(|error| {
                 failure!(
@@ -1,6 +1,6 @@
 use crate::{client::AppNodesClientWrapper, shutdown_event::Shutdown};
 use snafu::Snafu;
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::task::JoinError;
 use tracing::error;
 
@@ -18,7 +18,7 @@ pub(crate) async fn run_registration_loop(
     id: String,
     endpoint: String,
     labels: Option<HashMap<String, String>>,
-    client: &Option<AppNodesClientWrapper>,
+    client: &Option<Arc<AppNodesClientWrapper>>,
     registration_enabled: bool,
 ) -> anyhow::Result<()> {
     if !registration_enabled {
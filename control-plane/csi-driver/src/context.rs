@@ -6,7 +6,7 @@ use std::{
     num::ParseIntError,
     str::{FromStr, ParseBoolError},
 };
-use stor_port::types::v0::openapi::models::VolumeShareProtocol;
+use stor_port::types::v0::openapi::models::{TargetPlacement, VolumeAutoGrow, VolumeShareProtocol};
 use strum_macros::{AsRefStr, Display, EnumString};
 use tracing::log::warn;
 use utils::K8S_STS_PVC_NAMING_REGEX;
@@ -64,6 +64,25 @@ pub enum Parameters {
     NodeAffinityTopologyLabel,
     #[strum(serialize = "nodeHasTopologyKey")]
     NodeHasTopologyKey,
+    #[strum(serialize = "autoGrowThreshold")]
+    AutoGrowThreshold,
+    #[strum(serialize = "autoGrowStep")]
+    AutoGrowStep,
+    #[strum(serialize = "autoGrowMaxSize")]
+    AutoGrowMaxSize,
+    #[strum(serialize = "nvmfAuth")]
+    NvmfAuth,
+    #[strum(serialize = "targetPlacement")]
+    TargetPlacement,
+    /// Publish context key, rather than a storage class parameter.
+    #[strum(serialize = "readOnly")]
+    ReadOnly,
+    /// Secret key, rather than a storage class parameter.
+    #[strum(serialize = "dhchapHostSecret")]
+    DhChapHostSecret,
+    /// Secret key, rather than a storage class parameter.
+    #[strum(serialize = "dhchapCtrlSecret")]
+    DhChapCtrlSecret,
 }
 impl Parameters {
     fn parse_human_time(
@@ -120,6 +139,12 @@ impl Parameters {
             None => None,
         })
     }
+    fn parse_u64(value: Option<&String>) -> Result<Option<u64>, ParseIntError> {
+        Ok(match value {
+            Some(value) => value.parse::<u64>().map(Some)?,
+            None => None,
+        })
+    }
     fn parse_bool(value: Option<&String>) -> Result<Option<bool>, ParseBoolError> {
         Ok(match value {
             Some(value) => value.parse::<bool>().map(Some)?,
@@ -196,6 +221,42 @@ impl Parameters {
     pub fn max_snapshots(value: Option<&String>) -> Result<Option<u32>, ParseIntError> {
         Self::parse_u32(value)
     }
+    /// Parse the value for `Self::AutoGrowThreshold`.
+    pub fn autogrow_threshold(value: Option<&String>) -> Result<Option<u8>, ParseIntError> {
+        Ok(match value {
+            Some(value) => value.parse::<u8>().map(Some)?,
+            None => None,
+        })
+    }
+    /// Parse the value for `Self::AutoGrowStep`.
+    pub fn autogrow_step(value: Option<&String>) -> Result<Option<u64>, ParseIntError> {
+        Self::parse_u64(value)
+    }
+    /// Parse the value for `Self::AutoGrowMaxSize`.
+    pub fn autogrow_max_size(value: Option<&String>) -> Result<Option<u64>, ParseIntError> {
+        Self::parse_u64(value)
+    }
+    /// Parse the value for `Self::NvmfAuth`.
+    pub fn nvmf_auth(value: Option<&String>) -> Result<Option<bool>, ParseBoolError> {
+        Self::parse_bool(value)
+    }
+    /// Parse the value for `Self::TargetPlacement`.
+    pub fn target_placement(
+        value: Option<&String>,
+    ) -> Result<Option<TargetPlacement>, tonic::Status> {
+        match value.map(|s| s.as_str()) {
+            None => Ok(None),
+            Some("any") => Ok(Some(TargetPlacement::Any)),
+            Some("appNode") => Ok(Some(TargetPlacement::AppNode)),
+            Some(value) => Err(tonic::Status::invalid_argument(format!(
+                "Invalid `targetPlacement` value: {value}, expected `any` or `appNode`"
+            ))),
+        }
+    }
+    /// Parse the value for `Self::ReadOnly`.
+    pub fn read_only(value: Option<&String>) -> Result<Option<bool>, ParseBoolError> {
+        Self::parse_bool(value)
+    }
 }
 
 /// Volume publish parameters.
@@ -212,6 +273,7 @@ pub struct PublishParams {
     pool_has_topology_key: Option<HashMap<String, String>>,
     node_affinity_topology_label: Option<HashMap<String, String>>,
     node_has_topology_key: Option<HashMap<String, String>>,
+    read_only: Option<bool>,
 }
 impl PublishParams {
     /// Get the `Parameters::IoTimeout` value.
@@ -250,6 +312,15 @@ impl PublishParams {
     pub fn node_has_topology_key(&self) -> &Option<HashMap<String, String>> {
         &self.node_has_topology_key
     }
+    /// Get the `Parameters::ReadOnly` value.
+    pub fn read_only(&self) -> bool {
+        self.read_only.unwrap_or_default()
+    }
+    /// Only allow the volume to be read from.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
     /// Convert `Self` into a publish context.
     pub fn into_context(self) -> HashMap<String, String> {
         let mut publish_context = HashMap::new();
@@ -272,6 +343,9 @@ impl PublishParams {
         if let Some(fs_id) = self.fs_id() {
             publish_context.insert(Parameters::FsId.to_string(), fs_id.to_string());
         }
+        if self.read_only() {
+            publish_context.insert(Parameters::ReadOnly.to_string(), true.to_string());
+        }
 
         publish_context
     }
@@ -314,6 +388,8 @@ impl TryFrom<&HashMap<String, String>> for PublishParams {
         let node_has_topology_key =
             Parameters::node_has_topology_key(args.get(Parameters::NodeHasTopologyKey.as_ref()))
                 .map_err(|_| tonic::Status::invalid_argument("Invalid node_has_topology_key"))?;
+        let read_only = Parameters::read_only(args.get(Parameters::ReadOnly.as_ref()))
+            .map_err(|_| tonic::Status::invalid_argument("Invalid read_only"))?;
         Ok(Self {
             io_timeout,
             nvme_io_timeout,
@@ -325,6 +401,7 @@ impl TryFrom<&HashMap<String, String>> for PublishParams {
             pool_has_topology_key,
             node_affinity_topology_label,
             node_has_topology_key,
+            read_only,
         })
     }
 }
@@ -339,6 +416,9 @@ pub struct CreateParams {
     sts_affinity_group: Option<String>,
     clone_fs_id_as_volume_id: Option<bool>,
     max_snapshots: Option<u32>,
+    autogrow: Option<VolumeAutoGrow>,
+    nvmf_auth: bool,
+    target_placement: Option<TargetPlacement>,
 }
 impl CreateParams {
     /// Get the `Parameters::PublishParams` value.
@@ -366,6 +446,18 @@ impl CreateParams {
     pub fn max_snapshots(&self) -> Option<u32> {
         self.max_snapshots
     }
+    /// Get the automatic expansion policy built from the `Parameters::AutoGrow*` values.
+    pub fn autogrow(&self) -> Option<VolumeAutoGrow> {
+        self.autogrow.clone()
+    }
+    /// Get the `Parameters::NvmfAuth` value.
+    pub fn nvmf_auth(&self) -> bool {
+        self.nvmf_auth
+    }
+    /// Get the `Parameters::TargetPlacement` value.
+    pub fn target_placement(&self) -> Option<TargetPlacement> {
+        self.target_placement
+    }
 }
 impl TryFrom<&HashMap<String, String>> for CreateParams {
     type Error = tonic::Status;
@@ -418,6 +510,17 @@ impl TryFrom<&HashMap<String, String>> for CreateParams {
                 tonic::Status::invalid_argument("Invalid `maxSnapshots` value, expected an u32")
             })?;
 
+        let autogrow = parse_autogrow(args)?;
+
+        let nvmf_auth = Parameters::nvmf_auth(args.get(Parameters::NvmfAuth.as_ref()))
+            .map_err(|_| {
+                tonic::Status::invalid_argument("Invalid `nvmfAuth` value, expected a bool")
+            })?
+            .unwrap_or_default();
+
+        let target_placement =
+            Parameters::target_placement(args.get(Parameters::TargetPlacement.as_ref()))?;
+
         Ok(Self {
             publish_params,
             share_protocol,
@@ -425,10 +528,49 @@ impl TryFrom<&HashMap<String, String>> for CreateParams {
             sts_affinity_group: sts_affinity_group_name,
             clone_fs_id_as_volume_id,
             max_snapshots,
+            autogrow,
+            nvmf_auth,
+            target_placement,
         })
     }
 }
 
+/// Parse the automatic expansion policy from the `Parameters::AutoGrow*` values.
+/// The policy is enabled by the threshold, in which case the step and max size are required.
+fn parse_autogrow(args: &HashMap<String, String>) -> Result<Option<VolumeAutoGrow>, tonic::Status> {
+    let threshold = Parameters::autogrow_threshold(
+        args.get(Parameters::AutoGrowThreshold.as_ref()),
+    )
+    .map_err(|_| {
+        tonic::Status::invalid_argument("Invalid `autoGrowThreshold` value, expected an u8")
+    })?;
+    let Some(threshold) = threshold else {
+        return Ok(None);
+    };
+    if threshold == 0 || threshold > 100 {
+        return Err(tonic::Status::invalid_argument(
+            "Invalid `autoGrowThreshold` value, expected a percentage between 1 and 100",
+        ));
+    }
+    let step = Parameters::autogrow_step(args.get(Parameters::AutoGrowStep.as_ref()))
+        .map_err(|_| {
+            tonic::Status::invalid_argument("Invalid `autoGrowStep` value, expected an u64")
+        })?
+        .ok_or_else(|| {
+            tonic::Status::invalid_argument("`autoGrowStep` is required with `autoGrowThreshold`")
+        })?;
+    let max_size = Parameters::autogrow_max_size(args.get(Parameters::AutoGrowMaxSize.as_ref()))
+        .map_err(|_| {
+            tonic::Status::invalid_argument("Invalid `autoGrowMaxSize` value, expected an u64")
+        })?
+        .ok_or_else(|| {
+            tonic::Status::invalid_argument(
+                "`autoGrowMaxSize` is required with `autoGrowThreshold`",
+            )
+        })?;
+    Ok(Some(VolumeAutoGrow::new(threshold, step, max_size)))
+}
+
 // Generate a affinity group name from the parameters.
 // 1. Both pvc name and ns should be valid.
 // 2. Pvc name should follow the sts pvc naming convention.
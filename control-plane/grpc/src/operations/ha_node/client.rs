@@ -3,14 +3,14 @@ use crate::{
     ha_cluster_agent::ha_cluster_rpc_client::HaClusterRpcClient,
     ha_node_agent::{get_nvme_controller_response::Reply, ha_node_rpc_client::HaNodeRpcClient},
     operations::ha_node::traits::{
-        ClusterAgentOperations, GetControllerInfo, NodeAgentOperations, NodeInfo, ReplacePathInfo,
-        ReportFailedPathsInfo,
+        ClusterAgentOperations, GetControllerInfo, GetSwitchOverHistoryInfo, NodeAgentOperations,
+        NodeInfo, ReplacePathInfo, ReportFailedPathsInfo, SwitchOverVolumeInfo,
     },
 };
 use std::ops::Deref;
 use stor_port::{
     transport_api::{v0::NvmeSubsystems, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{FailedPathsResponse, MessageIdVs},
+    types::v0::transport::{FailedPathsResponse, MessageIdVs, SwitchOverHistory},
 };
 use tonic::transport::Uri;
 
@@ -72,6 +72,42 @@ impl ClusterAgentOperations for ClusterAgentClient {
             .into_inner();
         Ok(report.into())
     }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::switchover_volume",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn switchover_volume(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        context: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, context, MessageIdVs::SwitchOverVolume);
+        self.client().switch_over_volume(req).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::get_switchover_history",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn get_switchover_history(
+        &self,
+        request: &dyn GetSwitchOverHistoryInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOverHistory, ReplyError> {
+        let req = self.request(request, context, MessageIdVs::GetSwitchOverHistory);
+        let history = self
+            .client()
+            .get_switch_over_history(req)
+            .await?
+            .into_inner();
+        history.try_into()
+    }
 }
 
 /// Node agent RPC Client.
@@ -1,14 +1,18 @@
 use crate::{
     ha_cluster_agent::{
         ha_cluster_rpc_server::{HaClusterRpc, HaClusterRpcServer},
-        FailedNvmePathsResponse, HaNodeInfo, ReportFailedNvmePathsRequest,
+        FailedNvmePathsResponse, GetSwitchOverHistoryRequest, HaNodeInfo,
+        ReportFailedNvmePathsRequest, SwitchOverHistory, SwitchOverVolumeRequest,
     },
     ha_node_agent::{
         get_nvme_controller_response,
         ha_node_rpc_server::{HaNodeRpc, HaNodeRpcServer},
         GetNvmeControllerRequest, GetNvmeControllerResponse, ReplacePathRequest,
     },
-    operations::ha_node::traits::{ClusterAgentOperations, NodeAgentOperations, NodeInfoConv},
+    operations::ha_node::traits::{
+        ClusterAgentOperations, GetSwitchOverHistoryConv, NodeAgentOperations, NodeInfoConv,
+        SwitchOverVolumeConv,
+    },
 };
 
 use tonic::{Request, Response, Status};
@@ -111,4 +115,24 @@ impl HaClusterRpc for ClusterAgentServer {
             Err(error) => Err(error.into()),
         }
     }
+    async fn switch_over_volume(
+        &self,
+        request: tonic::Request<SwitchOverVolumeRequest>,
+    ) -> Result<tonic::Response<()>, tonic::Status> {
+        let req = SwitchOverVolumeConv::try_from(request.into_inner())?;
+        match self.service.switchover_volume(&req, None).await {
+            Ok(_) => Ok(Response::new(())),
+            Err(error) => Err(error.into()),
+        }
+    }
+    async fn get_switch_over_history(
+        &self,
+        request: tonic::Request<GetSwitchOverHistoryRequest>,
+    ) -> Result<tonic::Response<SwitchOverHistory>, tonic::Status> {
+        let req = GetSwitchOverHistoryConv::try_from(request.into_inner())?;
+        match self.service.get_switchover_history(&req, None).await {
+            Ok(history) => Ok(Response::new(history.into())),
+            Err(error) => Err(error.into()),
+        }
+    }
 }
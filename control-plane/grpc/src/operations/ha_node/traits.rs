@@ -2,8 +2,9 @@ use crate::{
     common,
     context::Context,
     ha_cluster_agent::{
-        FailedNvmePath, FailedNvmePathResponse, FailedNvmePathsResponse, HaNodeInfo,
-        ReportFailedNvmePathsRequest,
+        self, FailedNvmePath, FailedNvmePathResponse, FailedNvmePathsResponse,
+        GetSwitchOverHistoryRequest, HaNodeInfo, ReportFailedNvmePathsRequest,
+        SwitchOverVolumeRequest,
     },
     ha_node_agent::{GetNvmeControllerRequest, NvmeControllers, ReplacePathRequest},
 };
@@ -11,8 +12,10 @@ use crate::{
 use stor_port::{
     transport_api::{v0::NvmeSubsystems, ReplyError, ResourceKind},
     types::v0::transport::{
-        cluster_agent::NodeAgentInfo, FailedPath, FailedPathsResponse, GetController,
-        NvmeSubsystem, ReplacePath, ReportFailedPaths,
+        cluster_agent::{GetSwitchOverHistory, NodeAgentInfo, SwitchOverVolume},
+        FailedPath, FailedPathsResponse, GetController, NodeId, NvmeSubsystem, ReplacePath,
+        ReportFailedPaths, SwitchOverHistory, SwitchOverRecord, SwitchOverStageRecord,
+        SwitchOverStatus, VolumeId,
     },
     IntoVec,
 };
@@ -152,6 +155,20 @@ pub trait ClusterAgentOperations: Send + Sync {
         request: &dyn ReportFailedPathsInfo,
         context: Option<Context>,
     ) -> Result<FailedPathsResponse, ReplyError>;
+
+    /// Move the target of a published volume.
+    async fn switchover_volume(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        context: Option<Context>,
+    ) -> Result<(), ReplyError>;
+
+    /// Get the switchover history of a volume.
+    async fn get_switchover_history(
+        &self,
+        request: &dyn GetSwitchOverHistoryInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOverHistory, ReplyError>;
 }
 
 /// NodeInfo trait for the node-agent registration to be implemented by entities which want to
@@ -302,3 +319,230 @@ impl From<FailedPathsResponse> for FailedNvmePathsResponse {
         }
     }
 }
+
+/// SwitchOverVolumeInfo trait for the operator-triggered volume target switchover to be
+/// implemented by entities which want to use this operation.
+pub trait SwitchOverVolumeInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the volume whose target is moved.
+    fn volume(&self) -> VolumeId;
+    /// Node where the volume target should be moved to.
+    fn target_node(&self) -> Option<NodeId>;
+}
+
+impl SwitchOverVolumeInfo for SwitchOverVolume {
+    fn volume(&self) -> VolumeId {
+        self.volume().clone()
+    }
+
+    fn target_node(&self) -> Option<NodeId> {
+        self.target_node().cloned()
+    }
+}
+
+/// Intermediate struct to convert grpc to control plane object.
+#[derive(Debug)]
+pub struct SwitchOverVolumeConv {
+    volume: VolumeId,
+    target_node: Option<NodeId>,
+}
+
+impl TryFrom<SwitchOverVolumeRequest> for SwitchOverVolumeConv {
+    type Error = ReplyError;
+
+    fn try_from(value: SwitchOverVolumeRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(value.volume_id).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Volume, "volume_id", error)
+            })?,
+            target_node: value.target_node.map(Into::into),
+        })
+    }
+}
+
+impl SwitchOverVolumeInfo for SwitchOverVolumeConv {
+    fn volume(&self) -> VolumeId {
+        self.volume.clone()
+    }
+
+    fn target_node(&self) -> Option<NodeId> {
+        self.target_node.clone()
+    }
+}
+
+impl From<&dyn SwitchOverVolumeInfo> for SwitchOverVolumeRequest {
+    fn from(src: &dyn SwitchOverVolumeInfo) -> Self {
+        Self {
+            volume_id: src.volume().to_string(),
+            target_node: src.target_node().map(|node| node.to_string()),
+        }
+    }
+}
+
+/// GetSwitchOverHistoryInfo trait for the switchover history retrieval to be implemented by
+/// entities which want to use this operation.
+pub trait GetSwitchOverHistoryInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the volume whose switchover history we want.
+    fn volume(&self) -> VolumeId;
+}
+
+impl GetSwitchOverHistoryInfo for GetSwitchOverHistory {
+    fn volume(&self) -> VolumeId {
+        self.volume().clone()
+    }
+}
+
+/// Intermediate struct to convert grpc to control plane object.
+#[derive(Debug)]
+pub struct GetSwitchOverHistoryConv {
+    volume: VolumeId,
+}
+
+impl TryFrom<GetSwitchOverHistoryRequest> for GetSwitchOverHistoryConv {
+    type Error = ReplyError;
+
+    fn try_from(value: GetSwitchOverHistoryRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(value.volume_id).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Volume, "volume_id", error)
+            })?,
+        })
+    }
+}
+
+impl GetSwitchOverHistoryInfo for GetSwitchOverHistoryConv {
+    fn volume(&self) -> VolumeId {
+        self.volume.clone()
+    }
+}
+
+impl From<&dyn GetSwitchOverHistoryInfo> for GetSwitchOverHistoryRequest {
+    fn from(src: &dyn GetSwitchOverHistoryInfo) -> Self {
+        Self {
+            volume_id: src.volume().to_string(),
+        }
+    }
+}
+
+impl From<SwitchOverStatus> for ha_cluster_agent::SwitchOverStatus {
+    fn from(value: SwitchOverStatus) -> Self {
+        match value {
+            SwitchOverStatus::InProgress => Self::InProgress,
+            SwitchOverStatus::Successful => Self::Successful,
+            SwitchOverStatus::Errored => Self::Errored,
+        }
+    }
+}
+
+impl From<ha_cluster_agent::SwitchOverStatus> for SwitchOverStatus {
+    fn from(value: ha_cluster_agent::SwitchOverStatus) -> Self {
+        match value {
+            ha_cluster_agent::SwitchOverStatus::InProgress => Self::InProgress,
+            ha_cluster_agent::SwitchOverStatus::Successful => Self::Successful,
+            ha_cluster_agent::SwitchOverStatus::Errored => Self::Errored,
+        }
+    }
+}
+
+fn switchover_time(
+    time: Option<prost_types::Timestamp>,
+    arg_name: &str,
+) -> Result<std::time::SystemTime, ReplyError> {
+    time.and_then(|t| std::time::SystemTime::try_from(t).ok())
+        .ok_or(ReplyError::invalid_argument(
+            ResourceKind::Volume,
+            arg_name,
+            "time conversion failure".to_string(),
+        ))
+}
+
+impl From<SwitchOverStageRecord> for ha_cluster_agent::SwitchOverStageRecord {
+    fn from(value: SwitchOverStageRecord) -> Self {
+        Self {
+            stage: value.stage,
+            timestamp: Some(value.timestamp.into()),
+        }
+    }
+}
+
+impl TryFrom<ha_cluster_agent::SwitchOverStageRecord> for SwitchOverStageRecord {
+    type Error = ReplyError;
+
+    fn try_from(value: ha_cluster_agent::SwitchOverStageRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            stage: value.stage,
+            timestamp: switchover_time(value.timestamp, "timestamp")?,
+        })
+    }
+}
+
+impl From<SwitchOverRecord> for ha_cluster_agent::SwitchOverRecord {
+    fn from(value: SwitchOverRecord) -> Self {
+        let status: ha_cluster_agent::SwitchOverStatus = value.status.into();
+        Self {
+            frontend_node: value.frontend_node.to_string(),
+            nqn: value.nqn,
+            planned: value.planned,
+            target_node: value.target_node.map(|node| node.to_string()),
+            new_path: value.new_path,
+            status: status as i32,
+            start_time: Some(value.start_time.into()),
+            end_time: value.end_time.map(Into::into),
+            stages: value.stages.into_iter().map(From::from).collect(),
+            error: value.error,
+        }
+    }
+}
+
+impl TryFrom<ha_cluster_agent::SwitchOverRecord> for SwitchOverRecord {
+    type Error = ReplyError;
+
+    fn try_from(value: ha_cluster_agent::SwitchOverRecord) -> Result<Self, Self::Error> {
+        let status = ha_cluster_agent::SwitchOverStatus::try_from(value.status)
+            .map_err(|error| ReplyError::invalid_argument(ResourceKind::Volume, "status", error))?;
+        Ok(Self {
+            frontend_node: value.frontend_node.into(),
+            nqn: value.nqn,
+            planned: value.planned,
+            target_node: value.target_node.map(Into::into),
+            new_path: value.new_path,
+            status: status.into(),
+            start_time: switchover_time(value.start_time, "start_time")?,
+            end_time: match value.end_time {
+                Some(end_time) => Some(switchover_time(Some(end_time), "end_time")?),
+                None => None,
+            },
+            stages: value
+                .stages
+                .into_iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+            error: value.error,
+        })
+    }
+}
+
+impl From<SwitchOverHistory> for ha_cluster_agent::SwitchOverHistory {
+    fn from(value: SwitchOverHistory) -> Self {
+        Self {
+            volume_id: value.volume.to_string(),
+            records: value.records.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<ha_cluster_agent::SwitchOverHistory> for SwitchOverHistory {
+    type Error = ReplyError;
+
+    fn try_from(value: ha_cluster_agent::SwitchOverHistory) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(value.volume_id).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Volume, "volume_id", error)
+            })?,
+            records: value
+                .records
+                .into_iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
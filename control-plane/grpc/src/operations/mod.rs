@@ -2,7 +2,13 @@
 
 use crate::common;
 use events_api::event::EventMessage;
-use stor_port::types::v0::store::SpecStatus;
+use stor_port::{
+    transport_api::{ReplyError, ResourceKind},
+    types::v0::{
+        store::SpecStatus,
+        transport::{LabelOperator, LabelRequirement, LabelSelector},
+    },
+};
 
 /// Module for all corresponding client, server, traits for nexus rpc transport.
 pub mod nexus;
@@ -63,6 +69,20 @@ impl<T> PaginatedResult<T> {
         }
     }
 
+    /// Create a new `PaginatedResult` instance from a subset of the `entries`, based on the
+    /// pagination argument.
+    pub fn paginate(entries: Vec<T>, pagination: &Pagination) -> Self {
+        let num_entries = entries.len() as u64;
+        let offset = std::cmp::min(pagination.starting_token(), num_entries);
+        let last_result = offset + pagination.max_entries() >= num_entries;
+        let result = entries
+            .into_iter()
+            .skip(offset as usize)
+            .take(pagination.max_entries() as usize)
+            .collect();
+        Self::new(result, last_result)
+    }
+
     /// Returns the result vector.
     pub fn result(self) -> Vec<T> {
         self.result
@@ -142,6 +162,74 @@ impl From<common::SpecStatus> for SpecStatus<()> {
     }
 }
 
+impl From<LabelSelector> for common::LabelSelectorFilter {
+    fn from(selector: LabelSelector) -> Self {
+        Self {
+            requirements: selector
+                .requirements()
+                .iter()
+                .map(|requirement| common::LabelRequirement {
+                    key: requirement.key().to_string(),
+                    operator: common::LabelOperator::from(requirement.operator()) as i32,
+                    values: requirement.values().clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<LabelOperator> for common::LabelOperator {
+    fn from(operator: LabelOperator) -> Self {
+        match operator {
+            LabelOperator::Equals => Self::Equals,
+            LabelOperator::NotEquals => Self::NotEquals,
+            LabelOperator::In => Self::In,
+            LabelOperator::NotIn => Self::NotIn,
+            LabelOperator::Exists => Self::Exists,
+            LabelOperator::DoesNotExist => Self::DoesNotExist,
+        }
+    }
+}
+
+impl From<common::LabelOperator> for LabelOperator {
+    fn from(operator: common::LabelOperator) -> Self {
+        match operator {
+            common::LabelOperator::Equals => Self::Equals,
+            common::LabelOperator::NotEquals => Self::NotEquals,
+            common::LabelOperator::In => Self::In,
+            common::LabelOperator::NotIn => Self::NotIn,
+            common::LabelOperator::Exists => Self::Exists,
+            common::LabelOperator::DoesNotExist => Self::DoesNotExist,
+        }
+    }
+}
+
+/// Convert the label selector `filter` of a request for the resources of the given `kind`.
+pub(crate) fn label_selector(
+    filter: common::LabelSelectorFilter,
+    kind: ResourceKind,
+) -> Result<LabelSelector, ReplyError> {
+    let requirements = filter
+        .requirements
+        .into_iter()
+        .map(|requirement| {
+            let operator = common::LabelOperator::try_from(requirement.operator).map_err(|_| {
+                ReplyError::invalid_argument(
+                    kind.clone(),
+                    "label_selector",
+                    format!("unknown operator {}", requirement.operator),
+                )
+            })?;
+            Ok(LabelRequirement::new(
+                requirement.key,
+                operator.into(),
+                requirement.values,
+            ))
+        })
+        .collect::<Result<Vec<_>, ReplyError>>()?;
+    Ok(LabelSelector::new(requirements))
+}
+
 /// Event trait definition for creating events.
 pub(crate) trait Event {
     /// Create event message.
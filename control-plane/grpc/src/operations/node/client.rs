@@ -5,12 +5,12 @@ use crate::{
     node::{
         cordon_node_reply, drain_node_reply, get_nodes_reply, get_nodes_request, label_node_reply,
         node_grpc_client::NodeGrpcClient, uncordon_node_reply, unlabel_node_reply,
-        CordonNodeRequest, DrainNodeRequest, GetNodesRequest, LabelNodeRequest, ProbeRequest,
-        UncordonNodeRequest, UnlabelNodeRequest,
+        CordonNodeRequest, DecommissionNodeRequest, DrainNodeRequest, GetNodesRequest,
+        LabelNodeRequest, ProbeRequest, UncordonNodeRequest, UnlabelNodeRequest,
     },
-    operations::node::traits::{GetBlockDeviceInfo, NodeOperations},
+    operations::node::traits::{grpc_label_ttl, GetBlockDeviceInfo, NodeOperations},
 };
-use std::{collections::HashMap, convert::TryFrom, ops::Deref};
+use std::{collections::HashMap, convert::TryFrom, ops::Deref, time::Duration};
 use stor_port::{
     transport_api::{
         v0::{BlockDevices, Nodes},
@@ -56,6 +56,10 @@ impl NodeOperations for NodeClient {
                 })),
                 ignore_notfound,
             },
+            Filter::Labels(selector) => GetNodesRequest {
+                filter: Some(get_nodes_request::Filter::Labels(selector.into())),
+                ignore_notfound,
+            },
             _ => GetNodesRequest {
                 filter: None,
                 ignore_notfound,
@@ -97,10 +101,18 @@ impl NodeOperations for NodeClient {
     }
 
     #[tracing::instrument(name = "NodeClient::cordon", level = "debug", skip(self), err)]
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
         let req = CordonNodeRequest {
             node_id: id.to_string(),
             label,
+            ttl: grpc_label_ttl(ttl)?,
+            expected_revision,
         };
         let response = self.client().cordon_node(req).await?.into_inner();
         match response.reply {
@@ -113,10 +125,16 @@ impl NodeOperations for NodeClient {
     }
 
     #[tracing::instrument(name = "NodeClient::uncordon", level = "debug", skip(self), err)]
-    async fn uncordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
+    async fn uncordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
         let req = UncordonNodeRequest {
             node_id: id.to_string(),
             label,
+            expected_revision,
         };
         let response = self.client().uncordon_node(req).await?.into_inner();
         match response.reply {
@@ -128,10 +146,20 @@ impl NodeOperations for NodeClient {
         }
     }
     #[tracing::instrument(name = "NodeClient::drain", level = "debug", skip(self), err)]
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
+    async fn drain(
+        &self,
+        id: NodeId,
+        label: String,
+        evacuate: bool,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
         let req = DrainNodeRequest {
             node_id: id.to_string(),
             label,
+            evacuate,
+            ttl: grpc_label_ttl(ttl)?,
+            expected_revision,
         };
         let response = self.client().drain_node(req).await?.into_inner();
         match response.reply {
@@ -149,11 +177,13 @@ impl NodeOperations for NodeClient {
         id: NodeId,
         label: HashMap<String, String>,
         overwrite: bool,
+        expected_revision: Option<u64>,
     ) -> Result<Node, ReplyError> {
         let req = LabelNodeRequest {
             node_id: id.to_string(),
             label: Some(crate::common::StringMapValue { value: label }),
             overwrite,
+            expected_revision,
         };
         let response = self.client().label_node(req).await?.into_inner();
         match response.reply {
@@ -166,10 +196,16 @@ impl NodeOperations for NodeClient {
     }
 
     #[tracing::instrument(name = "NodeClient::unlabel", level = "debug", skip(self), err)]
-    async fn unlabel(&self, id: NodeId, label_key: String) -> Result<Node, ReplyError> {
+    async fn unlabel(
+        &self,
+        id: NodeId,
+        label_key: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError> {
         let req = UnlabelNodeRequest {
             node_id: id.to_string(),
             label_key,
+            expected_revision,
         };
         let response = self.client().unlabel_node(req).await?.into_inner();
         match response.reply {
@@ -180,4 +216,17 @@ impl NodeOperations for NodeClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Node)),
         }
     }
+
+    #[tracing::instrument(name = "NodeClient::decommission", level = "debug", skip(self), err)]
+    async fn decommission(&self, id: NodeId, force: bool) -> Result<(), ReplyError> {
+        let req = DecommissionNodeRequest {
+            node_id: id.to_string(),
+            force,
+        };
+        let response = self.client().decommission_node(req).await?.into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
 }
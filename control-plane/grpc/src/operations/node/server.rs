@@ -5,13 +5,15 @@ use crate::{
         cordon_node_reply, drain_node_reply, get_nodes_reply, label_node_reply,
         node_grpc_server::{NodeGrpc, NodeGrpcServer},
         uncordon_node_reply, unlabel_node_reply, CordonNodeReply, CordonNodeRequest,
-        DrainNodeReply, DrainNodeRequest, GetNodesReply, GetNodesRequest, LabelNodeReply,
-        LabelNodeRequest, ProbeRequest, ProbeResponse, UncordonNodeReply, UncordonNodeRequest,
-        UnlabelNodeReply, UnlabelNodeRequest,
+        DecommissionNodeReply, DecommissionNodeRequest, DrainNodeReply, DrainNodeRequest,
+        GetNodesReply, GetNodesRequest, LabelNodeReply, LabelNodeRequest, ProbeRequest,
+        ProbeResponse, UncordonNodeReply, UncordonNodeRequest, UnlabelNodeReply,
+        UnlabelNodeRequest,
     },
-    operations::node::traits::NodeOperations,
+    operations::node::traits::{label_ttl, NodeOperations},
 };
-use std::sync::Arc;
+use std::{convert::TryFrom, sync::Arc};
+use stor_port::types::v0::transport::Filter;
 use tonic::{Request, Response};
 
 /// gRPC Node Server
@@ -39,7 +41,17 @@ impl NodeGrpc for NodeServer {
         request: Request<GetNodesRequest>,
     ) -> Result<tonic::Response<node::GetNodesReply>, tonic::Status> {
         let req: GetNodesRequest = request.into_inner();
-        let filter = req.filter.map(Into::into).unwrap_or_default();
+        let filter = match req.filter {
+            Some(filter) => match Filter::try_from(filter) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    return Ok(Response::new(GetNodesReply {
+                        reply: Some(get_nodes_reply::Reply::Error(err.into())),
+                    }))
+                }
+            },
+            None => Filter::None,
+        };
         match self.service.get(filter, false, None).await {
             Ok(nodes) => Ok(Response::new(GetNodesReply {
                 reply: Some(get_nodes_reply::Reply::Nodes(nodes.into())),
@@ -79,7 +91,19 @@ impl NodeGrpc for NodeServer {
         request: tonic::Request<CordonNodeRequest>,
     ) -> Result<tonic::Response<CordonNodeReply>, tonic::Status> {
         let req: CordonNodeRequest = request.into_inner();
-        match self.service.cordon(req.node_id.into(), req.label).await {
+        let ttl = match label_ttl(req.ttl) {
+            Ok(ttl) => ttl,
+            Err(err) => {
+                return Ok(Response::new(CordonNodeReply {
+                    reply: Some(cordon_node_reply::Reply::Error(err.into())),
+                }))
+            }
+        };
+        match self
+            .service
+            .cordon(req.node_id.into(), req.label, ttl, req.expected_revision)
+            .await
+        {
             Ok(node) => Ok(Response::new(CordonNodeReply {
                 reply: Some(cordon_node_reply::Reply::Node(node.into())),
             })),
@@ -94,7 +118,11 @@ impl NodeGrpc for NodeServer {
         request: tonic::Request<UncordonNodeRequest>,
     ) -> Result<tonic::Response<UncordonNodeReply>, tonic::Status> {
         let req: UncordonNodeRequest = request.into_inner();
-        match self.service.uncordon(req.node_id.into(), req.label).await {
+        match self
+            .service
+            .uncordon(req.node_id.into(), req.label, req.expected_revision)
+            .await
+        {
             Ok(node) => Ok(Response::new(UncordonNodeReply {
                 reply: Some(uncordon_node_reply::Reply::Node(node.into())),
             })),
@@ -109,7 +137,25 @@ impl NodeGrpc for NodeServer {
         request: tonic::Request<DrainNodeRequest>,
     ) -> Result<tonic::Response<DrainNodeReply>, tonic::Status> {
         let req: DrainNodeRequest = request.into_inner();
-        match self.service.drain(req.node_id.into(), req.label).await {
+        let ttl = match label_ttl(req.ttl) {
+            Ok(ttl) => ttl,
+            Err(err) => {
+                return Ok(Response::new(DrainNodeReply {
+                    reply: Some(drain_node_reply::Reply::Error(err.into())),
+                }))
+            }
+        };
+        match self
+            .service
+            .drain(
+                req.node_id.into(),
+                req.label,
+                req.evacuate,
+                ttl,
+                req.expected_revision,
+            )
+            .await
+        {
             Ok(node) => Ok(Response::new(DrainNodeReply {
                 reply: Some(drain_node_reply::Reply::Node(node.into())),
             })),
@@ -131,7 +177,12 @@ impl NodeGrpc for NodeServer {
         };
         match self
             .service
-            .label(req.node_id.into(), label_map, req.overwrite)
+            .label(
+                req.node_id.into(),
+                label_map,
+                req.overwrite,
+                req.expected_revision,
+            )
             .await
         {
             Ok(node) => Ok(Response::new(LabelNodeReply {
@@ -150,7 +201,7 @@ impl NodeGrpc for NodeServer {
         let req: UnlabelNodeRequest = request.into_inner();
         match self
             .service
-            .unlabel(req.node_id.into(), req.label_key)
+            .unlabel(req.node_id.into(), req.label_key, req.expected_revision)
             .await
         {
             Ok(node) => Ok(Response::new(UnlabelNodeReply {
@@ -161,4 +212,20 @@ impl NodeGrpc for NodeServer {
             })),
         }
     }
+    async fn decommission_node(
+        &self,
+        request: tonic::Request<DecommissionNodeRequest>,
+    ) -> Result<tonic::Response<DecommissionNodeReply>, tonic::Status> {
+        let req: DecommissionNodeRequest = request.into_inner();
+        match self
+            .service
+            .decommission(req.node_id.into(), req.force)
+            .await
+        {
+            Ok(()) => Ok(Response::new(DecommissionNodeReply { error: None })),
+            Err(err) => Ok(Response::new(DecommissionNodeReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
 }
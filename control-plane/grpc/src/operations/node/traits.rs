@@ -1,18 +1,18 @@
 use crate::{
-    blockdevice, blockdevice::GetBlockDevicesRequest, context::Context, node,
-    node::get_nodes_request,
+    blockdevice, blockdevice::GetBlockDevicesRequest, context::Context, misc::traits::StringValue,
+    node, node::get_nodes_request, operations::label_selector,
 };
-use std::{collections::HashMap, convert::TryFrom, str::FromStr};
+use std::{collections::HashMap, convert::TryFrom, str::FromStr, time::Duration};
 use stor_port::{
     transport_api::{
         v0::{BlockDevices, Nodes},
         ReplyError, ResourceKind,
     },
     types::v0::{
-        store::node::{CordonDrainState, CordonedState, DrainState, NodeSpec},
+        store::node::{CordonDrainState, CordonedState, DrainState, NodeSpec, VolumeDrainProgress},
         transport::{
             BlockDevice, Filesystem, Filter, GetBlockDevices, Node, NodeId, NodeState, NodeStatus,
-            Partition,
+            Partition, VolumeId,
         },
     },
     TryIntoOption,
@@ -37,64 +37,128 @@ pub trait NodeOperations: Send + Sync {
         ctx: Option<Context>,
     ) -> Result<BlockDevices, ReplyError>;
     /// Cordon the node with the given ID and associate the label with the cordoned node.
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
+    /// The label is removed automatically once its TTL, if any, expires.
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError>;
     /// Uncordon the node with the given ID by removing the associated label.
-    async fn uncordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
+    async fn uncordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError>;
     /// Drain the node with the given ID and associate the label with the draining node.
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
+    /// When evacuating, the volume replicas are moved off the node's pools as well.
+    /// The label is removed automatically once its TTL, if any, expires.
+    async fn drain(
+        &self,
+        id: NodeId,
+        label: String,
+        evacuate: bool,
+        ttl: Option<Duration>,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError>;
     /// Associate the labels with the given node.
     async fn label(
         &self,
         id: NodeId,
         label: HashMap<String, String>,
         overwrite: bool,
+        expected_revision: Option<u64>,
     ) -> Result<Node, ReplyError>;
     /// Remove label from the a given node.
-    async fn unlabel(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
+    async fn unlabel(
+        &self,
+        id: NodeId,
+        label: String,
+        expected_revision: Option<u64>,
+    ) -> Result<Node, ReplyError>;
+    /// Decommission the node with the given ID, removing its pools and its spec.
+    /// Unless forced, the node must be drained and not in use by any volume.
+    async fn decommission(&self, id: NodeId, force: bool) -> Result<(), ReplyError>;
 }
 
 impl TryFrom<node::Node> for Node {
     type Error = ReplyError;
     fn try_from(node_grpc_type: node::Node) -> Result<Self, Self::Error> {
         let node_spec = match node_grpc_type.spec {
-            Some(spec) => Some(NodeSpec::new(
-                spec.node_id.into(),
-                std::net::SocketAddr::from_str(&spec.endpoint).map_err(|e| {
-                    Self::Error::invalid_argument(
-                        ResourceKind::Node,
-                        "node.spec.endpoint",
-                        e.to_string(),
-                    )
-                })?,
-                spec.labels.unwrap_or_default().value,
-                match spec.cordon_drain_state {
-                    Some(state) => match state.cordondrainstate {
-                        Some(node::cordon_drain_state::Cordondrainstate::Cordoned(state)) => {
-                            let type_v0_cordoned_state = CordonedState {
-                                cordonlabels: state.cordon_labels,
-                            };
-                            Some(CordonDrainState::Cordoned(type_v0_cordoned_state))
-                        }
-                        Some(node::cordon_drain_state::Cordondrainstate::Draining(state)) => {
-                            let type_v0_draining_state = DrainState {
-                                cordonlabels: state.cordon_labels,
-                                drainlabels: state.drain_labels,
-                            };
-                            Some(CordonDrainState::Draining(type_v0_draining_state))
-                        }
-                        Some(node::cordon_drain_state::Cordondrainstate::Drained(state)) => {
-                            let type_v0_drained_state = DrainState {
-                                cordonlabels: state.cordon_labels,
-                                drainlabels: state.drain_labels,
-                            };
-                            Some(CordonDrainState::Drained(type_v0_drained_state))
-                        }
+            Some(spec) => Some(
+                NodeSpec::new(
+                    spec.node_id.into(),
+                    std::net::SocketAddr::from_str(&spec.endpoint).map_err(|e| {
+                        Self::Error::invalid_argument(
+                            ResourceKind::Node,
+                            "node.spec.endpoint",
+                            e.to_string(),
+                        )
+                    })?,
+                    spec.labels.unwrap_or_default().value,
+                    match spec.cordon_drain_state {
+                        Some(state) => match state.cordondrainstate {
+                            Some(node::cordon_drain_state::Cordondrainstate::Cordoned(state)) => {
+                                let type_v0_cordoned_state = CordonedState {
+                                    cordonlabels: state.cordon_labels,
+                                };
+                                Some(CordonDrainState::Cordoned(type_v0_cordoned_state))
+                            }
+                            Some(node::cordon_drain_state::Cordondrainstate::Draining(state)) => {
+                                let type_v0_draining_state = DrainState {
+                                    cordonlabels: state.cordon_labels,
+                                    drainlabels: state.drain_labels,
+                                };
+                                Some(CordonDrainState::Draining(type_v0_draining_state))
+                            }
+                            Some(node::cordon_drain_state::Cordondrainstate::Drained(state)) => {
+                                let type_v0_drained_state = DrainState {
+                                    cordonlabels: state.cordon_labels,
+                                    drainlabels: state.drain_labels,
+                                };
+                                Some(CordonDrainState::Drained(type_v0_drained_state))
+                            }
+                            None => None,
+                        },
                         None => None,
                     },
-                    None => None,
-                },
-                spec.node_nqn.try_into_opt()?,
-            )),
+                    spec.node_nqn.try_into_opt()?,
+                )
+                .with_evacuation(
+                    spec.evacuate,
+                    spec.draining_volumes
+                        .into_iter()
+                        .map(|volume| {
+                            Ok((
+                                VolumeId::try_from(StringValue(Some(volume.volume_id)))?,
+                                VolumeDrainProgress {
+                                    remaining: volume.remaining,
+                                    moved: volume.moved,
+                                },
+                            ))
+                        })
+                        .collect::<Result<_, ReplyError>>()?,
+                )
+                .with_label_expiry(
+                    spec.label_expiry
+                        .into_iter()
+                        .map(|expiry| {
+                            let time = expiry
+                                .expiry
+                                .and_then(|time| std::time::SystemTime::try_from(time).ok())
+                                .ok_or(ReplyError::invalid_argument(
+                                    ResourceKind::Node,
+                                    "node.spec.label_expiry",
+                                    "time conversion failure".to_string(),
+                                ))?;
+                            Ok((expiry.label, time.into()))
+                        })
+                        .collect::<Result<_, ReplyError>>()?,
+                )
+                .with_revision(spec.revision),
+            ),
             None => None,
         };
         let node_state = match node_grpc_type.state {
@@ -177,6 +241,25 @@ impl From<Node> for node::Node {
                 None => None,
             },
             node_nqn: types_v0_spec.node_nqn().as_ref().map(|nqn| nqn.to_string()),
+            evacuate: types_v0_spec.evacuate(),
+            draining_volumes: types_v0_spec
+                .volumes_drain_progress()
+                .iter()
+                .map(|(volume, progress)| node::DrainingVolume {
+                    volume_id: volume.to_string(),
+                    remaining: progress.remaining,
+                    moved: progress.moved,
+                })
+                .collect(),
+            label_expiry: types_v0_spec
+                .label_expiry()
+                .iter()
+                .map(|(label, expiry)| node::LabelExpiry {
+                    label: label.clone(),
+                    expiry: Some(std::time::SystemTime::from(*expiry).into()),
+                })
+                .collect(),
+            revision: types_v0_spec.revision(),
         });
         let grpc_node_state = match types_v0_node.state() {
             None => None,
@@ -221,13 +304,17 @@ impl From<Nodes> for node::Nodes {
     }
 }
 
-impl From<get_nodes_request::Filter> for Filter {
-    fn from(filter: get_nodes_request::Filter) -> Self {
-        match filter {
+impl TryFrom<get_nodes_request::Filter> for Filter {
+    type Error = ReplyError;
+    fn try_from(filter: get_nodes_request::Filter) -> Result<Self, Self::Error> {
+        Ok(match filter {
             get_nodes_request::Filter::Node(node_filter) => {
                 Filter::Node(node_filter.node_id.into())
             }
-        }
+            get_nodes_request::Filter::Labels(labels) => {
+                Filter::Labels(label_selector(labels, ResourceKind::Node)?)
+            }
+        })
     }
 }
 
@@ -285,6 +372,40 @@ impl From<DrainState> for node::DrainState {
     }
 }
 
+impl From<node::cordon_drain_state::Cordondrainstate> for CordonDrainState {
+    fn from(src: node::cordon_drain_state::Cordondrainstate) -> Self {
+        match src {
+            node::cordon_drain_state::Cordondrainstate::Cordoned(state) => {
+                Self::Cordoned(state.into())
+            }
+            node::cordon_drain_state::Cordondrainstate::Draining(state) => {
+                Self::Draining(state.into())
+            }
+            node::cordon_drain_state::Cordondrainstate::Drained(state) => {
+                Self::Drained(state.into())
+            }
+        }
+    }
+}
+impl From<CordonDrainState> for node::CordonDrainState {
+    fn from(src: CordonDrainState) -> Self {
+        let state = match src {
+            CordonDrainState::Cordoned(state) => {
+                node::cordon_drain_state::Cordondrainstate::Cordoned(state.into())
+            }
+            CordonDrainState::Draining(state) => {
+                node::cordon_drain_state::Cordondrainstate::Draining(state.into())
+            }
+            CordonDrainState::Drained(state) => {
+                node::cordon_drain_state::Cordondrainstate::Drained(state.into())
+            }
+        };
+        Self {
+            cordondrainstate: Some(state),
+        }
+    }
+}
+
 /// GetBlockDeviceInfo trait for the getblockdevices
 /// operation
 pub trait GetBlockDeviceInfo: Send + Sync {
@@ -415,3 +536,27 @@ impl From<BlockDevices> for blockdevice::BlockDevices {
         }
     }
 }
+
+/// Convert the optional grpc TTL of a cordon or drain label.
+pub(crate) fn label_ttl(
+    ttl: Option<prost_types::Duration>,
+) -> Result<Option<Duration>, ReplyError> {
+    ttl.map(|ttl| {
+        Duration::try_from(ttl).map_err(|error| {
+            ReplyError::invalid_argument(ResourceKind::Node, "ttl", error.to_string())
+        })
+    })
+    .transpose()
+}
+
+/// Convert the optional TTL of a cordon or drain label into its grpc type.
+pub(crate) fn grpc_label_ttl(
+    ttl: Option<Duration>,
+) -> Result<Option<prost_types::Duration>, ReplyError> {
+    ttl.map(|ttl| {
+        prost_types::Duration::try_from(ttl).map_err(|error| {
+            ReplyError::invalid_argument(ResourceKind::Node, "ttl", error.to_string())
+        })
+    })
+    .transpose()
+}
@@ -1,16 +1,17 @@
 use crate::{
     common::{NodeFilter, NodePoolFilter, PoolFilter},
     context::{Client, Context, TracedChannel},
-    operations::pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+    operations::pool::traits::{CreatePoolInfo, DestroyPoolInfo, ExpandPoolInfo, PoolOperations},
     pool::{
-        create_pool_reply, get_pools_reply, get_pools_request, pool_grpc_client::PoolGrpcClient,
-        GetPoolsRequest,
+        cordon_pool_reply, create_pool_reply, drain_pool_reply, expand_pool_reply, get_pools_reply,
+        get_pools_request, pool_grpc_client::PoolGrpcClient, uncordon_pool_reply,
+        CordonPoolRequest, DrainPoolRequest, GetPoolsRequest, UncordonPoolRequest,
     },
 };
 use std::{convert::TryFrom, ops::Deref};
 use stor_port::{
     transport_api::{v0::Pools, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{Filter, MessageIdVs, Pool},
+    types::v0::transport::{Filter, MessageIdVs, Pool, PoolId},
 };
 use tonic::transport::Uri;
 
@@ -69,6 +70,95 @@ impl PoolOperations for PoolClient {
         }
     }
 
+    #[tracing::instrument(name = "PoolClient::expand", level = "debug", skip(self), err)]
+    async fn expand(
+        &self,
+        request: &dyn ExpandPoolInfo,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ExpandPool);
+        let response = self.client().expand_pool(req).await?.into_inner();
+        match response.reply {
+            Some(expand_pool_reply) => match expand_pool_reply {
+                expand_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                expand_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
+
+    #[tracing::instrument(name = "PoolClient::cordon", level = "debug", skip(self), err)]
+    async fn cordon(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = CordonPoolRequest {
+            pool_id: id.to_string(),
+            label,
+            expected_revision,
+        };
+        let req = self.request(req, ctx, MessageIdVs::CordonPool);
+        let response = self.client().cordon_pool(req).await?.into_inner();
+        match response.reply {
+            Some(cordon_pool_reply) => match cordon_pool_reply {
+                cordon_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                cordon_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
+
+    #[tracing::instrument(name = "PoolClient::uncordon", level = "debug", skip(self), err)]
+    async fn uncordon(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = UncordonPoolRequest {
+            pool_id: id.to_string(),
+            label,
+            expected_revision,
+        };
+        let req = self.request(req, ctx, MessageIdVs::UncordonPool);
+        let response = self.client().uncordon_pool(req).await?.into_inner();
+        match response.reply {
+            Some(uncordon_pool_reply) => match uncordon_pool_reply {
+                uncordon_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                uncordon_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
+
+    #[tracing::instrument(name = "PoolClient::drain", level = "debug", skip(self), err)]
+    async fn drain(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = DrainPoolRequest {
+            pool_id: id.to_string(),
+            label,
+            expected_revision,
+        };
+        let req = self.request(req, ctx, MessageIdVs::DrainPool);
+        let response = self.client().drain_pool(req).await?.into_inner();
+        match response.reply {
+            Some(drain_pool_reply) => match drain_pool_reply {
+                drain_pool_reply::Reply::Pool(pool) => Ok(Pool::try_from(pool)?),
+                drain_pool_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
+
     #[tracing::instrument(name = "PoolClient::get", level = "debug", skip(self), err)]
     async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError> {
         let req: GetPoolsRequest = match filter {
@@ -88,6 +178,9 @@ impl PoolOperations for PoolClient {
                     pool_id: pool_id.into(),
                 })),
             },
+            Filter::Labels(selector) => GetPoolsRequest {
+                filter: Some(get_pools_request::Filter::Labels(selector.into())),
+            },
             _ => GetPoolsRequest { filter: None },
         };
         let req = self.request(req, ctx, MessageIdVs::GetPools);
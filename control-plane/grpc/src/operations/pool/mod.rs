@@ -100,13 +100,13 @@ mod test {
             context::Context,
             operations::pool::{
                 test::TimeoutTester,
-                traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+                traits::{CreatePoolInfo, DestroyPoolInfo, ExpandPoolInfo, PoolOperations},
             },
         };
         use std::time::Duration;
         use stor_port::{
             transport_api::{v0::Pools, ReplyError},
-            types::v0::transport::{Filter, Pool},
+            types::v0::transport::{Filter, Pool, PoolId},
         };
 
         pub(super) struct Server {}
@@ -126,6 +126,40 @@ mod test {
             ) -> Result<(), ReplyError> {
                 todo!()
             }
+            async fn expand(
+                &self,
+                _pool: &dyn ExpandPoolInfo,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
+            async fn cordon(
+                &self,
+                _id: PoolId,
+                _label: String,
+                _expected_revision: Option<u64>,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
+            async fn uncordon(
+                &self,
+                _id: PoolId,
+                _label: String,
+                _expected_revision: Option<u64>,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
+            async fn drain(
+                &self,
+                _id: PoolId,
+                _label: String,
+                _expected_revision: Option<u64>,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
             async fn get(
                 &self,
                 _filter: Filter,
@@ -2,13 +2,16 @@ use crate::{
     operations::pool::traits::PoolOperations,
     pool,
     pool::{
-        create_pool_reply, get_pools_reply,
+        cordon_pool_reply, create_pool_reply, drain_pool_reply, expand_pool_reply, get_pools_reply,
         pool_grpc_server::{PoolGrpc, PoolGrpcServer},
-        CreatePoolReply, CreatePoolRequest, DestroyPoolReply, DestroyPoolRequest, GetPoolsReply,
-        GetPoolsRequest,
+        uncordon_pool_reply, CordonPoolReply, CordonPoolRequest, CreatePoolReply,
+        CreatePoolRequest, DestroyPoolReply, DestroyPoolRequest, DrainPoolReply, DrainPoolRequest,
+        ExpandPoolReply, ExpandPoolRequest, GetPoolsReply, GetPoolsRequest, UncordonPoolReply,
+        UncordonPoolRequest,
     },
 };
-use std::sync::Arc;
+use std::{convert::TryFrom, sync::Arc};
+use stor_port::types::v0::transport::Filter;
 use tonic::{Request, Response};
 
 /// gRPC Pool Server
@@ -59,12 +62,94 @@ impl PoolGrpc for PoolServer {
         }
     }
 
+    async fn expand_pool(
+        &self,
+        request: Request<ExpandPoolRequest>,
+    ) -> Result<tonic::Response<ExpandPoolReply>, tonic::Status> {
+        let req = request.into_inner();
+        match self.service.expand(&req, None).await {
+            Ok(pool) => Ok(Response::new(ExpandPoolReply {
+                reply: Some(expand_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(ExpandPoolReply {
+                reply: Some(expand_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn cordon_pool(
+        &self,
+        request: Request<CordonPoolRequest>,
+    ) -> Result<tonic::Response<CordonPoolReply>, tonic::Status> {
+        let req = request.into_inner();
+        match self
+            .service
+            .cordon(req.pool_id.into(), req.label, req.expected_revision, None)
+            .await
+        {
+            Ok(pool) => Ok(Response::new(CordonPoolReply {
+                reply: Some(cordon_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(CordonPoolReply {
+                reply: Some(cordon_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn uncordon_pool(
+        &self,
+        request: Request<UncordonPoolRequest>,
+    ) -> Result<tonic::Response<UncordonPoolReply>, tonic::Status> {
+        let req = request.into_inner();
+        match self
+            .service
+            .uncordon(req.pool_id.into(), req.label, req.expected_revision, None)
+            .await
+        {
+            Ok(pool) => Ok(Response::new(UncordonPoolReply {
+                reply: Some(uncordon_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(UncordonPoolReply {
+                reply: Some(uncordon_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn drain_pool(
+        &self,
+        request: Request<DrainPoolRequest>,
+    ) -> Result<tonic::Response<DrainPoolReply>, tonic::Status> {
+        let req = request.into_inner();
+        match self
+            .service
+            .drain(req.pool_id.into(), req.label, req.expected_revision, None)
+            .await
+        {
+            Ok(pool) => Ok(Response::new(DrainPoolReply {
+                reply: Some(drain_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(DrainPoolReply {
+                reply: Some(drain_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
     async fn get_pools(
         &self,
         request: Request<GetPoolsRequest>,
     ) -> Result<tonic::Response<pool::GetPoolsReply>, tonic::Status> {
         let req: GetPoolsRequest = request.into_inner();
-        let filter = req.filter.map(Into::into).unwrap_or_default();
+        let filter = match req.filter {
+            Some(filter) => match Filter::try_from(filter) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    return Ok(Response::new(GetPoolsReply {
+                        reply: Some(get_pools_reply::Reply::Error(err.into())),
+                    }))
+                }
+            },
+            None => Filter::None,
+        };
         match self.service.get(filter, None).await {
             Ok(pools) => Ok(Response::new(GetPoolsReply {
                 reply: Some(get_pools_reply::Reply::Pools(pools.into())),
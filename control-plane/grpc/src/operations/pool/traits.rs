@@ -1,18 +1,20 @@
 use crate::{
     common,
     context::Context,
+    misc::traits::StringValue,
+    operations::label_selector,
     pool,
-    pool::{get_pools_request, CreatePoolRequest, DestroyPoolRequest},
+    pool::{get_pools_request, CreatePoolRequest, DestroyPoolRequest, ExpandPoolRequest},
 };
 use std::convert::TryFrom;
 use stor_port::{
     transport_api::{v0::Pools, ReplyError, ResourceKind},
     types::v0::{
-        store::pool::{PoolLabel, PoolSpec, PoolSpecStatus},
+        store::pool::{DrainingReplica, PoolDrainProgress, PoolLabel, PoolSpec, PoolSpecStatus},
         transport,
         transport::{
-            CreatePool, CtrlPoolState, DestroyPool, Filter, NodeId, Pool, PoolDeviceUri, PoolId,
-            PoolState,
+            CreatePool, CtrlPoolState, DestroyPool, ExpandPool, Filter, NodeId, Pool,
+            PoolDeviceUri, PoolId, PoolState, ReplicaId, VolumeId,
         },
     },
     IntoOption,
@@ -33,6 +35,36 @@ pub trait PoolOperations: Send + Sync {
         pool: &dyn DestroyPoolInfo,
         ctx: Option<Context>,
     ) -> Result<(), ReplyError>;
+    /// Expand a pool with more disks
+    async fn expand(
+        &self,
+        pool: &dyn ExpandPoolInfo,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
+    /// Cordon the pool with the given ID and associate the label with the cordoned pool
+    async fn cordon(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
+    /// Uncordon the pool with the given ID by removing the associated label
+    async fn uncordon(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
+    /// Drain the pool with the given ID, moving its volume replicas to other pools
+    async fn drain(
+        &self,
+        id: PoolId,
+        label: String,
+        expected_revision: Option<u64>,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
     /// Get pools based on the filters
     async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError>;
 }
@@ -78,12 +110,76 @@ impl TryFrom<pool::PoolDefinition> for PoolSpec {
                 Some(labels) => Some(labels.value),
                 None => None,
             },
+            cordon_drain_state: pool_spec
+                .cordon_drain_state
+                .and_then(|state| state.cordondrainstate)
+                .map(Into::into),
+            drain_progress: pool_spec
+                .drain_progress
+                .map(PoolDrainProgress::try_from)
+                .transpose()?,
+            revision: pool_spec.revision,
             sequencer: Default::default(),
             operation: None,
         })
     }
 }
 
+impl TryFrom<pool::PoolDrainProgress> for PoolDrainProgress {
+    type Error = ReplyError;
+
+    fn try_from(progress: pool::PoolDrainProgress) -> Result<Self, Self::Error> {
+        let moving = progress
+            .moving
+            .into_iter()
+            .map(|moving| {
+                Ok((
+                    ReplicaId::try_from(StringValue(Some(moving.replica_id)))?,
+                    DrainingReplica {
+                        volume: VolumeId::try_from(StringValue(Some(moving.volume_id)))?,
+                        replicas: moving.replicas as u8,
+                    },
+                ))
+            })
+            .collect::<Result<_, ReplyError>>()?;
+        let unowned = progress
+            .unowned
+            .into_iter()
+            .map(|id| ReplicaId::try_from(StringValue(Some(id))))
+            .collect::<Result<_, ReplyError>>()?;
+        let blocked = progress
+            .blocked
+            .into_iter()
+            .map(|id| VolumeId::try_from(StringValue(Some(id))))
+            .collect::<Result<_, ReplyError>>()?;
+        Ok(Self {
+            moving,
+            moved: progress.moved,
+            unowned,
+            blocked,
+        })
+    }
+}
+
+impl From<PoolDrainProgress> for pool::PoolDrainProgress {
+    fn from(progress: PoolDrainProgress) -> Self {
+        Self {
+            moving: progress
+                .moving
+                .into_iter()
+                .map(|(replica, moving)| pool::DrainingReplica {
+                    replica_id: replica.to_string(),
+                    volume_id: moving.volume.to_string(),
+                    replicas: moving.replicas as u32,
+                })
+                .collect(),
+            moved: progress.moved,
+            unowned: progress.unowned.iter().map(ToString::to_string).collect(),
+            blocked: progress.blocked.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
 impl TryFrom<pool::PoolState> for PoolState {
     type Error = ReplyError;
 
@@ -145,6 +241,9 @@ impl From<PoolSpec> for pool::PoolDefinition {
                 labels: pool_spec
                     .labels
                     .map(|labels| crate::common::StringMapValue { value: labels }),
+                cordon_drain_state: pool_spec.cordon_drain_state.map(Into::into),
+                drain_progress: pool_spec.drain_progress.map(Into::into),
+                revision: pool_spec.revision,
             }),
             metadata: Some(pool::Metadata {
                 uuid: None,
@@ -202,9 +301,10 @@ impl From<Pools> for pool::Pools {
     }
 }
 
-impl From<get_pools_request::Filter> for Filter {
-    fn from(filter: get_pools_request::Filter) -> Self {
-        match filter {
+impl TryFrom<get_pools_request::Filter> for Filter {
+    type Error = ReplyError;
+    fn try_from(filter: get_pools_request::Filter) -> Result<Self, Self::Error> {
+        Ok(match filter {
             get_pools_request::Filter::Node(node_filter) => {
                 Filter::Node(node_filter.node_id.into())
             }
@@ -215,7 +315,10 @@ impl From<get_pools_request::Filter> for Filter {
             get_pools_request::Filter::Pool(pool_filter) => {
                 Filter::Pool(pool_filter.pool_id.into())
             }
-        }
+            get_pools_request::Filter::Labels(labels) => {
+                Filter::Labels(label_selector(labels, ResourceKind::Pool)?)
+            }
+        })
     }
 }
 
@@ -241,6 +344,19 @@ pub trait DestroyPoolInfo: Sync + Send + std::fmt::Debug {
     fn node_id(&self) -> NodeId;
 }
 
+/// ExpandPoolInfo trait for the pool expansion to be implemented by entities which want to avail
+/// this operation
+pub trait ExpandPoolInfo: Sync + Send + std::fmt::Debug {
+    /// Id of the pool
+    fn pool_id(&self) -> PoolId;
+    /// Id of the IoEngine instance
+    fn node_id(&self) -> NodeId;
+    /// Disk device paths or URIs to be added to the pool
+    fn disks(&self) -> Vec<PoolDeviceUri>;
+    /// Revision which the pool spec must be at
+    fn expected_revision(&self) -> Option<u64>;
+}
+
 impl CreatePoolInfo for CreatePool {
     fn pool_id(&self) -> PoolId {
         self.id.clone()
@@ -342,6 +458,64 @@ impl From<&dyn DestroyPoolInfo> for DestroyPool {
     }
 }
 
+impl ExpandPoolInfo for ExpandPool {
+    fn pool_id(&self) -> PoolId {
+        self.id.clone()
+    }
+
+    fn node_id(&self) -> NodeId {
+        self.node.clone()
+    }
+
+    fn disks(&self) -> Vec<PoolDeviceUri> {
+        self.disks.clone()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
+}
+
+impl ExpandPoolInfo for ExpandPoolRequest {
+    fn pool_id(&self) -> PoolId {
+        self.pool_id.clone().into()
+    }
+
+    fn node_id(&self) -> NodeId {
+        self.node_id.clone().into()
+    }
+
+    fn disks(&self) -> Vec<PoolDeviceUri> {
+        self.disks.iter().map(|disk| disk.into()).collect()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
+}
+
+impl From<&dyn ExpandPoolInfo> for ExpandPoolRequest {
+    fn from(data: &dyn ExpandPoolInfo) -> Self {
+        Self {
+            pool_id: data.pool_id().to_string(),
+            node_id: data.node_id().to_string(),
+            disks: data.disks().iter().map(|disk| disk.to_string()).collect(),
+            expected_revision: data.expected_revision(),
+        }
+    }
+}
+
+impl From<&dyn ExpandPoolInfo> for ExpandPool {
+    fn from(data: &dyn ExpandPoolInfo) -> Self {
+        Self {
+            node: data.node_id(),
+            id: data.pool_id(),
+            disks: data.disks(),
+            expected_revision: data.expected_revision(),
+        }
+    }
+}
+
 impl From<pool::PoolStatus> for transport::PoolStatus {
     fn from(src: pool::PoolStatus) -> Self {
         match src {
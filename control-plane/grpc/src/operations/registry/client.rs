@@ -1,12 +1,20 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
-    registry::{get_specs_reply, get_states_reply, registry_grpc_client::RegistryGrpcClient},
+    operations::registry::traits::{
+        GetSpecsInfo, GetStatesInfo, GetUsageHistoryInfo, RegistryOperations,
+    },
+    registry::{
+        get_async_operations_reply, get_specs_reply, get_states_reply, get_usage_history_reply,
+        registry_grpc_client::RegistryGrpcClient, GetAsyncOperationsRequest,
+        PutAsyncOperationRequest,
+    },
 };
 use std::{convert::TryFrom, ops::Deref};
 use stor_port::{
     transport_api::{ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{MessageIdVs, Specs, States},
+    types::v0::transport::{
+        AsyncOperation, GetAsyncOperations, MessageIdVs, Specs, States, UsageHistory,
+    },
 };
 use tonic::transport::Uri;
 
@@ -64,4 +72,57 @@ impl RegistryOperations for RegistryClient {
             None => Err(ReplyError::invalid_response(ResourceKind::State)),
         }
     }
+
+    async fn get_usage_history(
+        &self,
+        request: &dyn GetUsageHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<UsageHistory, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetUsageHistory);
+        let response = self.client().get_usage_history(req).await?.into_inner();
+        match response.reply {
+            Some(get_usage_history_reply) => match get_usage_history_reply {
+                get_usage_history_reply::Reply::History(history) => {
+                    Ok(UsageHistory::try_from(history)?)
+                }
+                get_usage_history_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
+
+    async fn put_async_operation(
+        &self,
+        operation: &AsyncOperation,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req: tonic::Request<PutAsyncOperationRequest> =
+            self.request(operation, ctx, MessageIdVs::PutAsyncOperation);
+        let response = self.client().put_async_operation(req).await?.into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
+
+    async fn get_async_operations(
+        &self,
+        request: &GetAsyncOperations,
+        ctx: Option<Context>,
+    ) -> Result<Vec<AsyncOperation>, ReplyError> {
+        let req: tonic::Request<GetAsyncOperationsRequest> =
+            self.request(request, ctx, MessageIdVs::GetAsyncOperations);
+        let response = self.client().get_async_operations(req).await?.into_inner();
+        match response.reply {
+            Some(get_async_operations_reply) => match get_async_operations_reply {
+                get_async_operations_reply::Reply::Operations(operations) => Ok(operations
+                    .operations
+                    .into_iter()
+                    .map(AsyncOperation::try_from)
+                    .collect::<Result<Vec<_>, _>>()?),
+                get_async_operations_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
 }
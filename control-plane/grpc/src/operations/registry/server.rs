@@ -1,9 +1,12 @@
 use crate::{
+    misc::traits::ValidateRequestTypes,
     operations::registry::traits::RegistryOperations,
     registry::{
-        get_specs_reply, get_states_reply,
+        get_async_operations_reply, get_specs_reply, get_states_reply, get_usage_history_reply,
         registry_grpc_server::{RegistryGrpc, RegistryGrpcServer},
-        GetSpecsReply, GetSpecsRequest, GetStatesReply, GetStatesRequest,
+        AsyncOperations, GetAsyncOperationsReply, GetAsyncOperationsRequest, GetSpecsReply,
+        GetSpecsRequest, GetStatesReply, GetStatesRequest, GetUsageHistoryReply,
+        GetUsageHistoryRequest, PutAsyncOperationReply, PutAsyncOperationRequest,
     },
 };
 use std::sync::Arc;
@@ -57,4 +60,48 @@ impl RegistryGrpc for RegistryServer {
             })),
         }
     }
+    async fn get_usage_history(
+        &self,
+        request: tonic::Request<GetUsageHistoryRequest>,
+    ) -> Result<tonic::Response<GetUsageHistoryReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_usage_history(&req, None).await {
+            Ok(history) => Ok(Response::new(GetUsageHistoryReply {
+                reply: Some(get_usage_history_reply::Reply::History(history.into())),
+            })),
+            Err(err) => Ok(Response::new(GetUsageHistoryReply {
+                reply: Some(get_usage_history_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn put_async_operation(
+        &self,
+        request: tonic::Request<PutAsyncOperationRequest>,
+    ) -> Result<tonic::Response<PutAsyncOperationReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.put_async_operation(&req, None).await {
+            Ok(()) => Ok(Response::new(PutAsyncOperationReply { error: None })),
+            Err(err) => Ok(Response::new(PutAsyncOperationReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
+    async fn get_async_operations(
+        &self,
+        request: tonic::Request<GetAsyncOperationsRequest>,
+    ) -> Result<tonic::Response<GetAsyncOperationsReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_async_operations(&req, None).await {
+            Ok(operations) => Ok(Response::new(GetAsyncOperationsReply {
+                reply: Some(get_async_operations_reply::Reply::Operations(
+                    AsyncOperations {
+                        operations: operations.into_iter().map(From::from).collect(),
+                    },
+                )),
+            })),
+            Err(err) => Ok(Response::new(GetAsyncOperationsReply {
+                reply: Some(get_async_operations_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
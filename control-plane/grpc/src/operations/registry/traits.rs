@@ -1,11 +1,15 @@
 use crate::{
     context::Context,
+    misc::traits::{StringValue, ValidateRequestTypes},
     registry,
-    registry::{GetSpecsRequest, GetStatesRequest},
+    registry::{
+        get_usage_history_request, GetAsyncOperationsRequest, GetSpecsRequest, GetStatesRequest,
+        GetUsageHistoryRequest, PutAsyncOperationRequest,
+    },
 };
 use std::convert::TryFrom;
 use stor_port::{
-    transport_api::ReplyError,
+    transport_api::{ReplyError, ResourceKind},
     types::v0::{
         store,
         store::{
@@ -15,7 +19,11 @@ use stor_port::{
             volume::{AffinityGroupSpec, VolumeSpec},
         },
         transport,
-        transport::{GetSpecs, GetStates, Specs},
+        transport::{
+            AsyncOperation, AsyncOperationError, AsyncOperationId, GetAsyncOperations, GetSpecs,
+            GetStates, GetUsageHistory, PoolId, Specs, UsageHistory, UsageResource, UsageRollup,
+            UsageSample, VolumeId,
+        },
     },
 };
 
@@ -34,6 +42,24 @@ pub trait RegistryOperations: Send + Sync {
         get_spec: &dyn GetStatesInfo,
         ctx: Option<Context>,
     ) -> Result<transport::States, ReplyError>;
+    /// Get the usage history of a resource
+    async fn get_usage_history(
+        &self,
+        get_usage: &dyn GetUsageHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<UsageHistory, ReplyError>;
+    /// Create or update a long-running operation
+    async fn put_async_operation(
+        &self,
+        operation: &AsyncOperation,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError>;
+    /// Get the long-running operations
+    async fn get_async_operations(
+        &self,
+        get_operations: &GetAsyncOperations,
+        ctx: Option<Context>,
+    ) -> Result<Vec<AsyncOperation>, ReplyError>;
 }
 
 /// GetSpecsInfo trait for the get_specs operation
@@ -50,6 +76,72 @@ impl GetStatesInfo for GetStates {}
 
 impl GetStatesInfo for GetStatesRequest {}
 
+/// GetUsageHistoryInfo trait for the get_usage_history operation
+pub trait GetUsageHistoryInfo: Send + Sync {
+    /// The resource whose usage history is requested
+    fn resource(&self) -> UsageResource;
+}
+
+impl GetUsageHistoryInfo for GetUsageHistory {
+    fn resource(&self) -> UsageResource {
+        self.resource.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetUsageHistoryRequest type
+#[derive(Debug)]
+pub struct ValidatedGetUsageHistoryRequest {
+    resource: UsageResource,
+}
+
+impl GetUsageHistoryInfo for ValidatedGetUsageHistoryRequest {
+    fn resource(&self) -> UsageResource {
+        self.resource.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetUsageHistoryRequest {
+    type Validated = ValidatedGetUsageHistoryRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let resource = match self.resource {
+            Some(get_usage_history_request::Resource::VolumeId(id)) => {
+                UsageResource::Volume(VolumeId::try_from(StringValue(Some(id)))?)
+            }
+            Some(get_usage_history_request::Resource::PoolId(id)) => {
+                UsageResource::Pool(PoolId::from(id))
+            }
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::Unknown,
+                    "resource",
+                ))
+            }
+        };
+        Ok(ValidatedGetUsageHistoryRequest { resource })
+    }
+}
+
+impl From<&dyn GetUsageHistoryInfo> for GetUsageHistory {
+    fn from(data: &dyn GetUsageHistoryInfo) -> Self {
+        Self::new(data.resource())
+    }
+}
+
+impl From<&dyn GetUsageHistoryInfo> for GetUsageHistoryRequest {
+    fn from(data: &dyn GetUsageHistoryInfo) -> Self {
+        Self {
+            resource: Some(match data.resource() {
+                UsageResource::Volume(id) => {
+                    get_usage_history_request::Resource::VolumeId(id.to_string())
+                }
+                UsageResource::Pool(id) => {
+                    get_usage_history_request::Resource::PoolId(id.to_string())
+                }
+            }),
+        }
+    }
+}
+
 impl From<&dyn GetSpecsInfo> for GetSpecsRequest {
     fn from(_: &dyn GetSpecsInfo) -> Self {
         Self {}
@@ -198,3 +290,201 @@ impl From<transport::States> for registry::States {
         }
     }
 }
+
+impl From<UsageSample> for registry::UsageSample {
+    fn from(value: UsageSample) -> Self {
+        Self {
+            timestamp: Some(value.timestamp.into()),
+            capacity: value.capacity,
+            used: value.used,
+        }
+    }
+}
+
+impl From<UsageRollup> for registry::UsageRollup {
+    fn from(value: UsageRollup) -> Self {
+        Self {
+            start: Some(value.start.into()),
+            end: Some(value.end.into()),
+            samples: value.samples,
+            capacity: value.capacity,
+            used_min: value.used_min,
+            used_max: value.used_max,
+            used_avg: value.used_avg,
+        }
+    }
+}
+
+impl From<UsageHistory> for registry::UsageHistory {
+    fn from(value: UsageHistory) -> Self {
+        Self {
+            samples: value.samples.into_iter().map(From::from).collect(),
+            rollups: value.rollups.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+fn usage_time(
+    time: Option<prost_types::Timestamp>,
+    arg_name: &str,
+) -> Result<std::time::SystemTime, ReplyError> {
+    time.and_then(|t| std::time::SystemTime::try_from(t).ok())
+        .ok_or(ReplyError::invalid_argument(
+            ResourceKind::Unknown,
+            arg_name,
+            "time conversion failure".to_string(),
+        ))
+}
+
+impl TryFrom<registry::UsageSample> for UsageSample {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::UsageSample) -> Result<Self, Self::Error> {
+        Ok(Self {
+            timestamp: usage_time(value.timestamp, "timestamp")?,
+            capacity: value.capacity,
+            used: value.used,
+        })
+    }
+}
+
+impl TryFrom<registry::UsageRollup> for UsageRollup {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::UsageRollup) -> Result<Self, Self::Error> {
+        Ok(Self {
+            start: usage_time(value.start, "start")?,
+            end: usage_time(value.end, "end")?,
+            samples: value.samples,
+            capacity: value.capacity,
+            used_min: value.used_min,
+            used_max: value.used_max,
+            used_avg: value.used_avg,
+        })
+    }
+}
+
+impl TryFrom<registry::UsageHistory> for UsageHistory {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::UsageHistory) -> Result<Self, Self::Error> {
+        Ok(Self {
+            samples: value
+                .samples
+                .into_iter()
+                .map(UsageSample::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            rollups: value
+                .rollups
+                .into_iter()
+                .map(UsageRollup::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+fn async_operation_id(id: String) -> Result<AsyncOperationId, ReplyError> {
+    AsyncOperationId::try_from(id).map_err(|error| {
+        ReplyError::invalid_argument(ResourceKind::Unknown, "id", error.to_string())
+    })
+}
+
+impl From<AsyncOperationError> for registry::AsyncOperationError {
+    fn from(value: AsyncOperationError) -> Self {
+        Self {
+            kind: value.kind,
+            details: value.details,
+            message: value.message,
+        }
+    }
+}
+
+impl From<registry::AsyncOperationError> for AsyncOperationError {
+    fn from(value: registry::AsyncOperationError) -> Self {
+        Self {
+            kind: value.kind,
+            details: value.details,
+            message: value.message,
+        }
+    }
+}
+
+impl From<AsyncOperation> for registry::AsyncOperation {
+    fn from(value: AsyncOperation) -> Self {
+        Self {
+            id: value.id.to_string(),
+            method: value.method,
+            uri: value.uri,
+            started: Some(value.started.into()),
+            finished: value.finished.map(Into::into),
+            error: value.error.map(Into::into),
+            heartbeat: value.heartbeat.map(Into::into),
+            cancelled: value.cancelled,
+            result: value.result,
+        }
+    }
+}
+
+impl TryFrom<registry::AsyncOperation> for AsyncOperation {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::AsyncOperation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: async_operation_id(value.id)?,
+            method: value.method,
+            uri: value.uri,
+            started: usage_time(value.started, "started")?,
+            heartbeat: match value.heartbeat {
+                Some(heartbeat) => Some(usage_time(Some(heartbeat), "heartbeat")?),
+                None => None,
+            },
+            finished: match value.finished {
+                Some(finished) => Some(usage_time(Some(finished), "finished")?),
+                None => None,
+            },
+            cancelled: value.cancelled,
+            result: value.result,
+            error: value.error.map(Into::into),
+        })
+    }
+}
+
+impl From<&AsyncOperation> for PutAsyncOperationRequest {
+    fn from(value: &AsyncOperation) -> Self {
+        Self {
+            operation: Some(value.clone().into()),
+        }
+    }
+}
+
+impl ValidateRequestTypes for PutAsyncOperationRequest {
+    type Validated = AsyncOperation;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        match self.operation {
+            Some(operation) => AsyncOperation::try_from(operation),
+            None => Err(ReplyError::missing_argument(
+                ResourceKind::Unknown,
+                "operation",
+            )),
+        }
+    }
+}
+
+impl From<&GetAsyncOperations> for GetAsyncOperationsRequest {
+    fn from(value: &GetAsyncOperations) -> Self {
+        Self {
+            id: value.id.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
+impl ValidateRequestTypes for GetAsyncOperationsRequest {
+    type Validated = GetAsyncOperations;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let id = match self.id {
+            Some(id) => Some(async_operation_id(id)?),
+            None => None,
+        };
+        Ok(GetAsyncOperations::new(id))
+    }
+}
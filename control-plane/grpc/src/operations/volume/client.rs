@@ -5,27 +5,29 @@ use crate::{
         volume::{
             traits::{
                 CreateSnapshotVolumeInfo, CreateVolumeInfo, CreateVolumeSnapshotInfo,
-                DestroyShutdownTargetsInfo, DestroyVolumeInfo, PublishVolumeInfo,
-                RepublishVolumeInfo, ResizeVolumeInfo, SetVolumePropertyInfo, SetVolumeReplicaInfo,
-                ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
-                VolumeSnapshot, VolumeSnapshots,
+                DestroyShutdownTargetsInfo, DestroyVolumeInfo, GetVolumeAuthInfo,
+                PublishVolumeInfo, RepublishVolumeInfo, ResizeVolumeInfo, RotateVolumeAuthInfo,
+                SetVolumePropertyInfo, SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo,
+                UnshareVolumeInfo, VolumeFrontendNodeInfo, VolumeOperations, VolumeSnapshot,
+                VolumeSnapshots,
             },
             traits_snapshots::DestroyVolumeSnapshotInfo,
         },
         Pagination,
     },
     volume::{
-        create_snapshot_reply, create_snapshot_volume_reply, create_volume_reply,
-        get_snapshots_reply, get_snapshots_request, get_volumes_reply, get_volumes_request,
-        publish_volume_reply, republish_volume_reply, resize_volume_reply,
-        set_volume_property_reply, set_volume_replica_reply, share_volume_reply,
-        unpublish_volume_reply, volume_grpc_client::VolumeGrpcClient, GetSnapshotsRequest,
-        GetVolumesRequest, ProbeRequest,
+        add_volume_frontend_node_reply, create_snapshot_reply, create_snapshot_volume_reply,
+        create_volume_reply, get_snapshots_reply, get_snapshots_request, get_volume_auth_reply,
+        get_volumes_reply, get_volumes_request, publish_volume_reply,
+        remove_volume_frontend_node_reply, republish_volume_reply, resize_volume_reply,
+        rotate_volume_auth_reply, set_volume_property_reply, set_volume_replica_reply,
+        share_volume_reply, unpublish_volume_reply, volume_grpc_client::VolumeGrpcClient,
+        GetSnapshotsRequest, GetVolumesRequest, ProbeRequest,
     },
 };
 use stor_port::{
     transport_api::{v0::Volumes, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{Filter, MessageIdVs, Volume},
+    types::v0::transport::{DhChapKeys, Filter, MessageIdVs, Volume},
 };
 
 use std::{convert::TryFrom, ops::Deref};
@@ -89,6 +91,11 @@ impl VolumeOperations for VolumeClient {
                 pagination: pagination.map(|p| p.into()),
                 ignore_notfound,
             },
+            Filter::Labels(selector) => GetVolumesRequest {
+                filter: Some(get_volumes_request::Filter::Labels(selector.into())),
+                pagination: pagination.map(|p| p.into()),
+                ignore_notfound,
+            },
             _ => GetVolumesRequest {
                 filter: None,
                 pagination: pagination.map(|p| p.into()),
@@ -250,6 +257,92 @@ impl VolumeOperations for VolumeClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
         }
     }
+    #[tracing::instrument(name = "VolumeClient::get_auth", level = "debug", skip(self), err)]
+    async fn get_auth(
+        &self,
+        req: &dyn GetVolumeAuthInfo,
+        ctx: Option<Context>,
+    ) -> Result<DhChapKeys, ReplyError> {
+        let req = self.request(req, ctx, MessageIdVs::GetVolumeAuth);
+        let response = self.client().get_volume_auth(req).await?.into_inner();
+        match response.reply {
+            Some(get_volume_auth_reply) => match get_volume_auth_reply {
+                get_volume_auth_reply::Reply::Keys(keys) => Ok(DhChapKeys::try_from(keys)?),
+                get_volume_auth_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+    #[tracing::instrument(name = "VolumeClient::rotate_auth", level = "debug", skip(self), err)]
+    async fn rotate_auth(
+        &self,
+        req: &dyn RotateVolumeAuthInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(req, ctx, MessageIdVs::RotateVolumeAuth);
+        let response = self.client().rotate_volume_auth(req).await?.into_inner();
+        match response.reply {
+            Some(rotate_volume_auth_reply) => match rotate_volume_auth_reply {
+                rotate_volume_auth_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                rotate_volume_auth_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+    #[tracing::instrument(
+        name = "VolumeClient::add_frontend_node",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn add_frontend_node(
+        &self,
+        req: &dyn VolumeFrontendNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(req, ctx, MessageIdVs::AddVolumeFrontendNode);
+        let response = self
+            .client()
+            .add_volume_frontend_node(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(add_volume_frontend_node_reply) => match add_volume_frontend_node_reply {
+                add_volume_frontend_node_reply::Reply::Volume(volume) => {
+                    Ok(Volume::try_from(volume)?)
+                }
+                add_volume_frontend_node_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+    #[tracing::instrument(
+        name = "VolumeClient::remove_frontend_node",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn remove_frontend_node(
+        &self,
+        req: &dyn VolumeFrontendNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(req, ctx, MessageIdVs::RemoveVolumeFrontendNode);
+        let response = self
+            .client()
+            .remove_volume_frontend_node(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(remove_volume_frontend_node_reply) => match remove_volume_frontend_node_reply {
+                remove_volume_frontend_node_reply::Reply::Volume(volume) => {
+                    Ok(Volume::try_from(volume)?)
+                }
+                remove_volume_frontend_node_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
     #[tracing::instrument(name = "VolumeClient::probe", level = "debug", skip(self))]
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         match self.client().probe(ProbeRequest {}).await {
@@ -339,6 +432,11 @@ impl VolumeOperations for VolumeClient {
                 pagination: None,
                 ignore_notfound,
             },
+            Filter::Labels(selector) => GetSnapshotsRequest {
+                filter: Some(get_snapshots_request::Filter::Labels(selector.into())),
+                pagination: pagination.map(|p| p.into()),
+                ignore_notfound,
+            },
             _ => GetSnapshotsRequest {
                 filter: None,
                 pagination: pagination.map(|p| p.into()),
@@ -2,20 +2,24 @@ use crate::{
     misc::traits::ValidateRequestTypes,
     operations::{volume::traits::VolumeOperations, Event, Pagination},
     volume::{
-        create_snapshot_reply, create_snapshot_volume_reply, create_volume_reply,
-        get_snapshots_reply, get_volumes_reply, publish_volume_reply, republish_volume_reply,
-        resize_volume_reply, set_volume_property_reply, set_volume_replica_reply,
-        share_volume_reply, unpublish_volume_reply,
+        add_volume_frontend_node_reply, create_snapshot_reply, create_snapshot_volume_reply,
+        create_volume_reply, get_snapshots_reply, get_volume_auth_reply, get_volumes_reply,
+        publish_volume_reply, remove_volume_frontend_node_reply, republish_volume_reply,
+        resize_volume_reply, rotate_volume_auth_reply, set_volume_property_reply,
+        set_volume_replica_reply, share_volume_reply, unpublish_volume_reply,
         volume_grpc_server::{VolumeGrpc, VolumeGrpcServer},
-        CreateSnapshotReply, CreateSnapshotRequest, CreateSnapshotVolumeReply,
-        CreateSnapshotVolumeRequest, CreateVolumeReply, CreateVolumeRequest,
-        DestroyShutdownTargetReply, DestroyShutdownTargetRequest, DestroySnapshotReply,
-        DestroySnapshotRequest, DestroyVolumeReply, DestroyVolumeRequest, GetSnapshotsReply,
-        GetSnapshotsRequest, GetVolumesReply, GetVolumesRequest, ProbeRequest, ProbeResponse,
-        PublishVolumeReply, PublishVolumeRequest, RepublishVolumeReply, RepublishVolumeRequest,
-        ResizeVolumeReply, ResizeVolumeRequest, SetVolumePropertyReply, SetVolumePropertyRequest,
-        SetVolumeReplicaReply, SetVolumeReplicaRequest, ShareVolumeReply, ShareVolumeRequest,
-        UnpublishVolumeReply, UnpublishVolumeRequest, UnshareVolumeReply, UnshareVolumeRequest,
+        AddVolumeFrontendNodeReply, AddVolumeFrontendNodeRequest, CreateSnapshotReply,
+        CreateSnapshotRequest, CreateSnapshotVolumeReply, CreateSnapshotVolumeRequest,
+        CreateVolumeReply, CreateVolumeRequest, DestroyShutdownTargetReply,
+        DestroyShutdownTargetRequest, DestroySnapshotReply, DestroySnapshotRequest,
+        DestroyVolumeReply, DestroyVolumeRequest, GetSnapshotsReply, GetSnapshotsRequest,
+        GetVolumeAuthReply, GetVolumeAuthRequest, GetVolumesReply, GetVolumesRequest, ProbeRequest,
+        ProbeResponse, PublishVolumeReply, PublishVolumeRequest, RemoveVolumeFrontendNodeReply,
+        RemoveVolumeFrontendNodeRequest, RepublishVolumeReply, RepublishVolumeRequest,
+        ResizeVolumeReply, ResizeVolumeRequest, RotateVolumeAuthReply, RotateVolumeAuthRequest,
+        SetVolumePropertyReply, SetVolumePropertyRequest, SetVolumeReplicaReply,
+        SetVolumeReplicaRequest, ShareVolumeReply, ShareVolumeRequest, UnpublishVolumeReply,
+        UnpublishVolumeRequest, UnshareVolumeReply, UnshareVolumeRequest,
     },
 };
 use std::{convert::TryFrom, sync::Arc};
@@ -230,6 +234,64 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+    async fn get_volume_auth(
+        &self,
+        request: tonic::Request<GetVolumeAuthRequest>,
+    ) -> Result<tonic::Response<GetVolumeAuthReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_auth(&req, None).await {
+            Ok(keys) => Ok(Response::new(GetVolumeAuthReply {
+                reply: Some(get_volume_auth_reply::Reply::Keys(keys.into())),
+            })),
+            Err(err) => Ok(Response::new(GetVolumeAuthReply {
+                reply: Some(get_volume_auth_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn rotate_volume_auth(
+        &self,
+        request: tonic::Request<RotateVolumeAuthRequest>,
+    ) -> Result<tonic::Response<RotateVolumeAuthReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.rotate_auth(&req, None).await {
+            Ok(volume) => Ok(Response::new(RotateVolumeAuthReply {
+                reply: Some(rotate_volume_auth_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(RotateVolumeAuthReply {
+                reply: Some(rotate_volume_auth_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn add_volume_frontend_node(
+        &self,
+        request: tonic::Request<AddVolumeFrontendNodeRequest>,
+    ) -> Result<tonic::Response<AddVolumeFrontendNodeReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.add_frontend_node(&req, None).await {
+            Ok(volume) => Ok(Response::new(AddVolumeFrontendNodeReply {
+                reply: Some(add_volume_frontend_node_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(AddVolumeFrontendNodeReply {
+                reply: Some(add_volume_frontend_node_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn remove_volume_frontend_node(
+        &self,
+        request: tonic::Request<RemoveVolumeFrontendNodeRequest>,
+    ) -> Result<tonic::Response<RemoveVolumeFrontendNodeReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.remove_frontend_node(&req, None).await {
+            Ok(volume) => Ok(Response::new(RemoveVolumeFrontendNodeReply {
+                reply: Some(remove_volume_frontend_node_reply::Reply::Volume(
+                    volume.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(RemoveVolumeFrontendNodeReply {
+                reply: Some(remove_volume_frontend_node_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
     async fn probe(
         &self,
         _request: tonic::Request<ProbeRequest>,
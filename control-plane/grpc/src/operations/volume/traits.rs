@@ -4,13 +4,15 @@ use crate::{
     context::Context,
     misc::traits::{StringValue, ValidateRequestTypes},
     nexus,
-    operations::{Event, Pagination},
+    operations::{label_selector, Event, Pagination},
     replica, volume,
     volume::{
-        get_volumes_request, CreateSnapshotVolumeRequest, CreateVolumeRequest,
-        DestroyShutdownTargetRequest, DestroyVolumeRequest, PublishVolumeRequest,
-        RegisteredTargets, RepublishVolumeRequest, ResizeVolumeRequest, SetVolumePropertyRequest,
-        SetVolumeReplicaRequest, ShareVolumeRequest, UnpublishVolumeRequest, UnshareVolumeRequest,
+        get_volumes_request, AddVolumeFrontendNodeRequest, CreateSnapshotVolumeRequest,
+        CreateVolumeRequest, DestroyShutdownTargetRequest, DestroyVolumeRequest,
+        GetVolumeAuthRequest, PublishVolumeRequest, RegisteredTargets,
+        RemoveVolumeFrontendNodeRequest, RepublishVolumeRequest, ResizeVolumeRequest,
+        RotateVolumeAuthRequest, SetVolumePropertyRequest, SetVolumeReplicaRequest,
+        ShareVolumeRequest, UnpublishVolumeRequest, UnshareVolumeRequest,
     },
 };
 use events_api::event::{EventAction, EventCategory, EventMessage, EventMeta, EventSource};
@@ -18,18 +20,23 @@ use std::{borrow::Borrow, collections::HashMap, convert::TryFrom};
 use stor_port::{
     transport_api::{v0::Volumes, ReplyError, ResourceKind},
     types::v0::{
-        store::volume::{
-            AffinityGroupSpec, FrontendConfig, InitiatorAC, TargetConfig, VolumeContentSource,
-            VolumeMetadata, VolumeSpec, VolumeTarget,
+        store::{
+            auth::NvmfAuthRef,
+            volume::{
+                AffinityGroupSpec, FrontendConfig, InitiatorAC, TargetConfig, VolumeContentSource,
+                VolumeMetadata, VolumeSpec, VolumeTarget,
+            },
         },
         transport::{
-            AffinityGroup, CreateSnapshotVolume, CreateVolume, DestroyShutdownTargets,
-            DestroyVolume, ExplicitNodeTopology, Filter, LabelledTopology, Nexus, NexusId,
-            NexusNvmfConfig, NodeId, NodeTopology, NvmeNqn, PoolTopology, PublishVolume, ReplicaId,
-            ReplicaStatus, ReplicaTopology, ReplicaUsage, RepublishVolume, ResizeVolume,
-            SetVolumeProperty, SetVolumeReplica, ShareVolume, SnapshotId, Topology,
-            UnpublishVolume, UnshareVolume, Volume, VolumeId, VolumeLabels, VolumePolicy,
-            VolumeProperty, VolumeShareProtocol, VolumeState, VolumeUsage,
+            AddVolumeFrontendNode, AffinityGroup, CreateSnapshotVolume, CreateVolume,
+            DestroyShutdownTargets, DestroyVolume, DhChapKeys, DhChapSecret, ExplicitNodeTopology,
+            Filter, GetVolumeAuth, LabelledTopology, Nexus, NexusId, NexusNvmfConfig, NodeId,
+            NodeTopology, NvmeNqn, PoolTopology, PublishVolume, RemoveVolumeFrontendNode,
+            ReplicaId, ReplicaStatus, ReplicaTopology, ReplicaUsage, RepublishVolume, ResizeVolume,
+            RotateVolumeAuth, SetVolumeProperty, SetVolumeReplica, ShareVolume, SnapshotId,
+            TargetPlacement, Topology, UnpublishVolume, UnshareVolume, Volume, VolumeAuth,
+            VolumeAutoGrow, VolumeId, VolumeLabels, VolumePolicy, VolumeProperty,
+            VolumeShareProtocol, VolumeState, VolumeUsage,
         },
     },
     IntoOption, IntoVec, TryIntoOption,
@@ -100,6 +107,30 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn SetVolumePropertyInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Get the volume target authentication secrets.
+    async fn get_auth(
+        &self,
+        req: &dyn GetVolumeAuthInfo,
+        ctx: Option<Context>,
+    ) -> Result<DhChapKeys, ReplyError>;
+    /// Rotate the volume target authentication secrets.
+    async fn rotate_auth(
+        &self,
+        req: &dyn RotateVolumeAuthInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Attach another frontend node to a multi-node volume.
+    async fn add_frontend_node(
+        &self,
+        req: &dyn VolumeFrontendNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Detach a frontend node from a multi-node volume.
+    async fn remove_frontend_node(
+        &self,
+        req: &dyn VolumeFrontendNodeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
     /// Liveness probe for volume service
     async fn probe(&self, ctx: Option<Context>) -> Result<bool, ReplyError>;
     /// Destroy shutdown targets
@@ -167,6 +198,12 @@ impl From<VolumeSpec> for volume::VolumeDefinition {
                 content_source: volume_spec.content_source.into_opt(),
                 num_snapshots: volume_spec.metadata.num_snapshots() as u32,
                 max_snapshots: volume_spec.max_snapshots,
+                autogrow: volume_spec.autogrow.into_opt(),
+                auth: volume_spec.auth.into_opt(),
+                target_placement: volume_spec
+                    .target_placement
+                    .map(|placement| volume::TargetPlacement::from(placement) as i32),
+                revision: volume_spec.revision,
             }),
             metadata: Some(volume::Metadata {
                 spec_status: spec_status as i32,
@@ -239,6 +276,8 @@ impl From<volume::FrontendNode> for InitiatorAC {
 impl From<volume::FrontendConfig> for FrontendConfig {
     fn from(value: volume::FrontendConfig) -> Self {
         FrontendConfig::from_acls(value.nodes.into_vec())
+            .with_multi_node(value.multi_node)
+            .with_read_only(value.read_only)
     }
 }
 
@@ -293,16 +332,16 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
                             err.to_string(),
                         )
                     })?;
-                    let frontend = match volume_meta.target_config.and_then(|c| c.frontend) {
+                    let target_config = volume_meta.target_config.unwrap_or_default();
+                    let frontend = match target_config.frontend {
                         None => Default::default(),
                         Some(frontend) => frontend,
                     };
 
-                    Some(TargetConfig::new(
-                        target,
-                        NexusNvmfConfig::default(),
-                        frontend.into(),
-                    ))
+                    Some(
+                        TargetConfig::new(target, NexusNvmfConfig::default(), frontend.into())
+                            .with_auth(target_config.auth.try_into_opt()?),
+                    )
                 }
                 None => None,
             },
@@ -352,6 +391,13 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
             content_source: volume_spec.content_source.try_into_opt()?,
             num_snapshots: volume_spec.num_snapshots,
             max_snapshots: volume_spec.max_snapshots,
+            autogrow: volume_spec.autogrow.into_opt(),
+            auth: volume_spec.auth.try_into_opt()?,
+            target_placement: target_placement(
+                volume_spec.target_placement,
+                "volume.definition.spec.target_placement",
+            )?,
+            revision: volume_spec.revision,
         };
         Ok(volume_spec)
     }
@@ -709,6 +755,26 @@ impl From<VolumePolicy> for volume::VolumePolicy {
     }
 }
 
+impl From<volume::VolumeAutoGrow> for VolumeAutoGrow {
+    fn from(autogrow: volume::VolumeAutoGrow) -> Self {
+        VolumeAutoGrow::new(
+            u8::try_from(autogrow.threshold_percent).unwrap_or(u8::MAX),
+            autogrow.step,
+            autogrow.max_size,
+        )
+    }
+}
+
+impl From<VolumeAutoGrow> for volume::VolumeAutoGrow {
+    fn from(autogrow: VolumeAutoGrow) -> Self {
+        volume::VolumeAutoGrow {
+            threshold_percent: autogrow.threshold_percent().into(),
+            step: autogrow.step(),
+            max_size: autogrow.max_size(),
+        }
+    }
+}
+
 impl TryFrom<volume::VolumeTarget> for VolumeTarget {
     type Error = ReplyError;
     fn try_from(target: volume::VolumeTarget) -> Result<Self, Self::Error> {
@@ -768,7 +834,8 @@ impl TryFrom<volume::TargetConfig> for TargetConfig {
                 )),
             }?,
             FrontendConfig::default(),
-        ))
+        )
+        .with_auth(src.auth.try_into_opt()?))
     }
 }
 impl From<TargetConfig> for volume::TargetConfig {
@@ -777,6 +844,65 @@ impl From<TargetConfig> for volume::TargetConfig {
             target: Some((&src).into()),
             config: Some(src.config().clone().into()),
             frontend: Some(src.frontend().into()),
+            auth: src.auth().cloned().into_opt(),
+        }
+    }
+}
+
+impl TryFrom<volume::NvmfAuthRef> for NvmfAuthRef {
+    type Error = ReplyError;
+    fn try_from(src: volume::NvmfAuthRef) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            &VolumeId::try_from(StringValue(Some(src.volume)))?,
+            src.generation,
+        ))
+    }
+}
+impl From<NvmfAuthRef> for volume::NvmfAuthRef {
+    fn from(src: NvmfAuthRef) -> Self {
+        volume::NvmfAuthRef {
+            volume: src.volume().to_string(),
+            generation: src.generation(),
+        }
+    }
+}
+
+impl From<volume::VolumeAuth> for VolumeAuth {
+    fn from(src: volume::VolumeAuth) -> Self {
+        VolumeAuth::new(src.host_secret, src.ctrl_secret)
+    }
+}
+impl From<VolumeAuth> for volume::VolumeAuth {
+    fn from(src: VolumeAuth) -> Self {
+        volume::VolumeAuth {
+            host_secret: src.host_secret,
+            ctrl_secret: src.ctrl_secret,
+        }
+    }
+}
+
+impl TryFrom<volume::DhChapKeys> for DhChapKeys {
+    type Error = ReplyError;
+    fn try_from(src: volume::DhChapKeys) -> Result<Self, Self::Error> {
+        let secret = |secret: String, arg_name: &str| {
+            DhChapSecret::try_from(secret).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Volume, arg_name, error.to_string())
+            })
+        };
+        Ok(Self {
+            host: secret(src.host_secret, "dhchap_keys.host_secret")?,
+            ctrl: match src.ctrl_secret {
+                Some(ctrl) => Some(secret(ctrl, "dhchap_keys.ctrl_secret")?),
+                None => None,
+            },
+        })
+    }
+}
+impl From<DhChapKeys> for volume::DhChapKeys {
+    fn from(src: DhChapKeys) -> Self {
+        volume::DhChapKeys {
+            host_secret: src.host.into(),
+            ctrl_secret: src.ctrl.map(String::from),
         }
     }
 }
@@ -791,6 +917,8 @@ impl From<&FrontendConfig> for volume::FrontendConfig {
                     nqn: n.node_nqn().to_string(),
                 })
                 .collect::<Vec<_>>(),
+            multi_node: src.multi_node(),
+            read_only: src.read_only(),
         }
     }
 }
@@ -813,6 +941,42 @@ impl From<VolumeShareProtocol> for volume::VolumeShareProtocol {
     }
 }
 
+impl From<volume::TargetPlacement> for TargetPlacement {
+    fn from(src: volume::TargetPlacement) -> Self {
+        match src {
+            volume::TargetPlacement::Any => Self::Any,
+            volume::TargetPlacement::AppNode => Self::AppNode,
+        }
+    }
+}
+
+impl From<TargetPlacement> for volume::TargetPlacement {
+    fn from(src: TargetPlacement) -> Self {
+        match src {
+            TargetPlacement::Any => Self::Any,
+            TargetPlacement::AppNode => Self::AppNode,
+        }
+    }
+}
+
+/// Convert the raw target placement enum value, if any, into `TargetPlacement`.
+fn target_placement(
+    placement: Option<i32>,
+    arg_name: &str,
+) -> Result<Option<TargetPlacement>, ReplyError> {
+    match placement {
+        Some(i) => match volume::TargetPlacement::try_from(i) {
+            Ok(placement) => Ok(Some(placement.into())),
+            Err(error) => Err(ReplyError::invalid_argument(
+                ResourceKind::Volume,
+                arg_name,
+                error,
+            )),
+        },
+        None => Ok(None),
+    }
+}
+
 impl TryFrom<get_volumes_request::Filter> for Filter {
     type Error = ReplyError;
     fn try_from(filter: get_volumes_request::Filter) -> Result<Self, Self::Error> {
@@ -820,6 +984,9 @@ impl TryFrom<get_volumes_request::Filter> for Filter {
             get_volumes_request::Filter::Volume(volume_filter) => Filter::Volume(
                 VolumeId::try_from(StringValue(Some(volume_filter.volume_id)))?,
             ),
+            get_volumes_request::Filter::Labels(labels) => {
+                Filter::Labels(label_selector(labels, ResourceKind::Volume)?)
+            }
         })
     }
 }
@@ -901,6 +1068,12 @@ pub trait CreateVolumeInfo: Send + Sync + std::fmt::Debug {
     fn cluster_capacity_limit(&self) -> Option<u64>;
     /// Max snapshot limit per volume.
     fn max_snapshots(&self) -> Option<u32>;
+    /// Automatic volume expansion policy.
+    fn autogrow(&self) -> Option<VolumeAutoGrow>;
+    /// DH-HMAC-CHAP authentication of the volume target.
+    fn auth(&self) -> Option<VolumeAuth>;
+    /// Volume target placement policy.
+    fn target_placement(&self) -> Option<TargetPlacement>;
 }
 
 impl CreateVolumeInfo for CreateVolume {
@@ -943,6 +1116,18 @@ impl CreateVolumeInfo for CreateVolume {
     fn max_snapshots(&self) -> Option<u32> {
         self.max_snapshots
     }
+
+    fn autogrow(&self) -> Option<VolumeAutoGrow> {
+        self.autogrow.clone()
+    }
+
+    fn auth(&self) -> Option<VolumeAuth> {
+        self.auth.clone()
+    }
+
+    fn target_placement(&self) -> Option<TargetPlacement> {
+        self.target_placement
+    }
 }
 
 /// Intermediate structure that validates the conversion to CreateVolumeRequest type.
@@ -951,6 +1136,7 @@ pub struct ValidatedCreateVolumeRequest {
     inner: CreateVolumeRequest,
     uuid: VolumeId,
     topology: Option<Topology>,
+    target_placement: Option<TargetPlacement>,
 }
 
 impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
@@ -999,6 +1185,18 @@ impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
     fn max_snapshots(&self) -> Option<u32> {
         self.inner.max_snapshots
     }
+
+    fn autogrow(&self) -> Option<VolumeAutoGrow> {
+        self.inner.autogrow.clone().into_opt()
+    }
+
+    fn auth(&self) -> Option<VolumeAuth> {
+        self.inner.auth.clone().into_opt()
+    }
+
+    fn target_placement(&self) -> Option<TargetPlacement> {
+        self.target_placement
+    }
 }
 
 impl ValidateRequestTypes for CreateVolumeRequest {
@@ -1019,6 +1217,10 @@ impl ValidateRequestTypes for CreateVolumeRequest {
                 },
                 None => None,
             },
+            target_placement: target_placement(
+                self.target_placement,
+                "create_volume_request.target_placement",
+            )?,
             inner: self,
         })
     }
@@ -1037,6 +1239,9 @@ impl From<&dyn CreateVolumeInfo> for CreateVolume {
             affinity_group: data.affinity_group(),
             cluster_capacity_limit: data.cluster_capacity_limit(),
             max_snapshots: data.max_snapshots(),
+            autogrow: data.autogrow().into_opt(),
+            auth: data.auth().into_opt(),
+            target_placement: data.target_placement(),
         }
     }
 }
@@ -1056,6 +1261,11 @@ impl From<&dyn CreateVolumeInfo> for CreateVolumeRequest {
             affinity_group: data.affinity_group().map(|ag| ag.into()),
             cluster_capacity_limit: data.cluster_capacity_limit(),
             max_snapshots: data.max_snapshots(),
+            autogrow: data.autogrow().into_opt(),
+            auth: data.auth().into_opt(),
+            target_placement: data
+                .target_placement()
+                .map(|placement| volume::TargetPlacement::from(placement) as i32),
         }
     }
 }
@@ -1079,29 +1289,44 @@ impl From<&dyn CreateSnapshotVolumeInfo> for CreateSnapshotVolumeRequest {
 pub trait DestroyVolumeInfo: Send + Sync + std::fmt::Debug {
     /// Uuid of the volume to be destroyed
     fn uuid(&self) -> VolumeId;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl DestroyVolumeInfo for DestroyVolume {
     fn uuid(&self) -> VolumeId {
         self.uuid.clone()
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 impl DestroyVolumeInfo for Volume {
     fn uuid(&self) -> VolumeId {
         self.spec().uuid
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// Intermediate structure that validates the conversion to DestroyVolumeRequest type.
 #[derive(Debug)]
 pub struct ValidatedDestroyVolumeRequest {
     uuid: VolumeId,
+    expected_revision: Option<u64>,
 }
 
 impl DestroyVolumeInfo for ValidatedDestroyVolumeRequest {
     fn uuid(&self) -> VolumeId {
         self.uuid.clone()
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for DestroyVolumeRequest {
@@ -1109,13 +1334,17 @@ impl ValidateRequestTypes for DestroyVolumeRequest {
     fn validated(self) -> Result<Self::Validated, ReplyError> {
         Ok(ValidatedDestroyVolumeRequest {
             uuid: VolumeId::try_from(StringValue(self.uuid))?,
+            expected_revision: self.expected_revision,
         })
     }
 }
 
 impl From<&dyn DestroyVolumeInfo> for DestroyVolume {
     fn from(data: &dyn DestroyVolumeInfo) -> Self {
-        Self { uuid: data.uuid() }
+        Self {
+            uuid: data.uuid(),
+            expected_revision: data.expected_revision(),
+        }
     }
 }
 
@@ -1123,6 +1352,7 @@ impl From<&dyn DestroyVolumeInfo> for DestroyVolumeRequest {
     fn from(data: &dyn DestroyVolumeInfo) -> Self {
         Self {
             uuid: Some(data.uuid().to_string()),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1133,6 +1363,7 @@ pub struct ValidatedResizeVolumeRequest {
     uuid: VolumeId,
     requested_size: u64,
     cluster_capacity_limit: Option<u64>,
+    expected_revision: Option<u64>,
 }
 /// Trait to be implemented for ResizeVolume operation.
 pub trait ResizeVolumeInfo: Send + Sync + std::fmt::Debug {
@@ -1142,6 +1373,8 @@ pub trait ResizeVolumeInfo: Send + Sync + std::fmt::Debug {
     fn req_size(&self) -> u64;
     /// Total capacity limit for all volumes, in bytes
     fn cluster_capacity_limit(&self) -> Option<u64>;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl ResizeVolumeInfo for ResizeVolume {
@@ -1156,6 +1389,9 @@ impl ResizeVolumeInfo for ResizeVolume {
     fn cluster_capacity_limit(&self) -> Option<u64> {
         self.cluster_capacity_limit
     }
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for ResizeVolumeRequest {
@@ -1165,6 +1401,7 @@ impl ValidateRequestTypes for ResizeVolumeRequest {
             uuid: VolumeId::try_from(StringValue(Some(self.uuid)))?,
             requested_size: self.requested_size,
             cluster_capacity_limit: self.capacity_limit,
+            expected_revision: self.expected_revision,
         })
     }
 }
@@ -1175,6 +1412,7 @@ impl From<&dyn ResizeVolumeInfo> for ResizeVolume {
             uuid: data.uuid(),
             requested_size: data.req_size(),
             cluster_capacity_limit: data.cluster_capacity_limit(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1185,6 +1423,7 @@ impl From<&dyn ResizeVolumeInfo> for ResizeVolumeRequest {
             uuid: data.uuid().to_string(),
             requested_size: data.req_size(),
             capacity_limit: data.cluster_capacity_limit(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1201,6 +1440,9 @@ impl ResizeVolumeInfo for ValidatedResizeVolumeRequest {
     fn cluster_capacity_limit(&self) -> Option<u64> {
         self.cluster_capacity_limit
     }
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Trait to be implemented for ShareVolume operation.
@@ -1211,6 +1453,8 @@ pub trait ShareVolumeInfo: Send + Sync + std::fmt::Debug {
     fn share(&self) -> VolumeShareProtocol;
     /// Hosts allowed to access nexus.
     fn frontend_hosts(&self) -> Vec<String>;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl ShareVolumeInfo for ShareVolume {
@@ -1225,6 +1469,10 @@ impl ShareVolumeInfo for ShareVolume {
     fn frontend_hosts(&self) -> Vec<String> {
         self.frontend_hosts.clone()
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Intermediate structure that validates the conversion to ShareVolumeRequest type.
@@ -1233,6 +1481,7 @@ pub struct ValidatedShareVolumeRequest {
     uuid: VolumeId,
     share: VolumeShareProtocol,
     frontend_hosts: Vec<String>,
+    expected_revision: Option<u64>,
 }
 
 impl ShareVolumeInfo for ValidatedShareVolumeRequest {
@@ -1247,6 +1496,10 @@ impl ShareVolumeInfo for ValidatedShareVolumeRequest {
     fn frontend_hosts(&self) -> Vec<String> {
         self.frontend_hosts.clone()
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for ShareVolumeRequest {
@@ -1265,6 +1518,7 @@ impl ValidateRequestTypes for ShareVolumeRequest {
                 }
             },
             frontend_hosts: self.frontend_hosts,
+            expected_revision: self.expected_revision,
         })
     }
 }
@@ -1275,6 +1529,7 @@ impl From<&dyn ShareVolumeInfo> for ShareVolume {
             uuid: data.uuid(),
             protocol: data.share(),
             frontend_hosts: data.frontend_hosts(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1286,6 +1541,7 @@ impl From<&dyn ShareVolumeInfo> for ShareVolumeRequest {
             uuid: Some(data.uuid().to_string()),
             share: share as i32,
             frontend_hosts: data.frontend_hosts(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1294,24 +1550,35 @@ impl From<&dyn ShareVolumeInfo> for ShareVolumeRequest {
 pub trait UnshareVolumeInfo: Send + Sync + std::fmt::Debug {
     /// Uuid of the volume to be unshared
     fn uuid(&self) -> VolumeId;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl UnshareVolumeInfo for UnshareVolume {
     fn uuid(&self) -> VolumeId {
         self.uuid.clone()
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Intermediate structure that validates the conversion to UnshareVolumeRequest type.
 #[derive(Debug)]
 pub struct ValidatedUnshareVolumeRequest {
     uuid: VolumeId,
+    expected_revision: Option<u64>,
 }
 
 impl UnshareVolumeInfo for ValidatedUnshareVolumeRequest {
     fn uuid(&self) -> VolumeId {
         self.uuid.clone()
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for UnshareVolumeRequest {
@@ -1319,13 +1586,17 @@ impl ValidateRequestTypes for UnshareVolumeRequest {
     fn validated(self) -> Result<Self::Validated, ReplyError> {
         Ok(ValidatedUnshareVolumeRequest {
             uuid: VolumeId::try_from(StringValue(self.uuid))?,
+            expected_revision: self.expected_revision,
         })
     }
 }
 
 impl From<&dyn UnshareVolumeInfo> for UnshareVolume {
     fn from(data: &dyn UnshareVolumeInfo) -> Self {
-        Self { uuid: data.uuid() }
+        Self {
+            uuid: data.uuid(),
+            expected_revision: data.expected_revision(),
+        }
     }
 }
 
@@ -1333,6 +1604,7 @@ impl From<&dyn UnshareVolumeInfo> for UnshareVolumeRequest {
     fn from(data: &dyn UnshareVolumeInfo) -> Self {
         Self {
             uuid: Some(data.uuid().to_string()),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1349,6 +1621,12 @@ pub trait PublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn publish_context(&self) -> HashMap<String, String>;
     /// Hosts allowed to access the nexus.
     fn frontend_nodes(&self) -> Vec<String>;
+    /// Allows more than one frontend node to attach the volume.
+    fn multi_node(&self) -> bool;
+    /// The frontend nodes may only read from the volume.
+    fn read_only(&self) -> bool;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl PublishVolumeInfo for PublishVolume {
@@ -1371,6 +1649,18 @@ impl PublishVolumeInfo for PublishVolume {
     fn frontend_nodes(&self) -> Vec<String> {
         self.frontend_nodes.clone()
     }
+
+    fn multi_node(&self) -> bool {
+        self.multi_node
+    }
+
+    fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 impl PublishVolumeInfo for RepublishVolume {
@@ -1393,6 +1683,18 @@ impl PublishVolumeInfo for RepublishVolume {
     fn frontend_nodes(&self) -> Vec<String> {
         unimplemented!()
     }
+
+    fn multi_node(&self) -> bool {
+        unimplemented!()
+    }
+
+    fn read_only(&self) -> bool {
+        unimplemented!()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Intermediate structure that validates the conversion to PublishVolumeRequest type.
@@ -1427,6 +1729,18 @@ impl PublishVolumeInfo for ValidatedPublishVolumeRequest {
     fn frontend_nodes(&self) -> Vec<String> {
         self.frontend_nodes.clone()
     }
+
+    fn multi_node(&self) -> bool {
+        self.inner.multi_node
+    }
+
+    fn read_only(&self) -> bool {
+        self.inner.read_only
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.inner.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for PublishVolumeRequest {
@@ -1461,6 +1775,9 @@ impl From<&dyn PublishVolumeInfo> for PublishVolume {
             share: data.share(),
             publish_context: data.publish_context(),
             frontend_nodes: data.frontend_nodes(),
+            multi_node: data.multi_node(),
+            read_only: data.read_only(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1480,6 +1797,9 @@ impl From<&dyn PublishVolumeInfo> for PublishVolumeRequest {
             share,
             publish_context: data.publish_context(),
             frontend_nodes: data.frontend_nodes(),
+            multi_node: data.multi_node(),
+            read_only: data.read_only(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1498,6 +1818,8 @@ pub trait RepublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn reuse_existing(&self) -> bool;
     /// Allows reusing the existing target, but prefers a target move.
     fn reuse_existing_fallback(&self) -> bool;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl RepublishVolumeInfo for RepublishVolume {
@@ -1524,6 +1846,10 @@ impl RepublishVolumeInfo for RepublishVolume {
     fn reuse_existing_fallback(&self) -> bool {
         self.reuse_existing_fallback
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 impl From<&dyn RepublishVolumeInfo> for RepublishVolume {
@@ -1535,6 +1861,7 @@ impl From<&dyn RepublishVolumeInfo> for RepublishVolume {
             share: data.share(),
             reuse_existing: data.reuse_existing(),
             reuse_existing_fallback: data.reuse_existing_fallback(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1549,6 +1876,7 @@ impl From<&dyn RepublishVolumeInfo> for RepublishVolumeRequest {
             reuse_existing: data.reuse_existing(),
             frontend_node: data.frontend_node().to_string(),
             reuse_existing_fallback: data.reuse_existing_fallback(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1588,6 +1916,10 @@ impl RepublishVolumeInfo for ValidatedRepublishVolumeRequest {
     fn reuse_existing_fallback(&self) -> bool {
         self.inner.reuse_existing_fallback
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.inner.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for RepublishVolumeRequest {
@@ -1616,6 +1948,8 @@ pub trait UnpublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> VolumeId;
     /// Force unpublish
     fn force(&self) -> bool;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl UnpublishVolumeInfo for UnpublishVolume {
@@ -1626,6 +1960,10 @@ impl UnpublishVolumeInfo for UnpublishVolume {
     fn force(&self) -> bool {
         self.force()
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Intermediate structure that validates the conversion to UnpublishVolumeRequest type.
@@ -1642,6 +1980,9 @@ impl UnpublishVolumeInfo for ValidatedUnpublishVolumeRequest {
     fn force(&self) -> bool {
         self.inner.force
     }
+    fn expected_revision(&self) -> Option<u64> {
+        self.inner.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for UnpublishVolumeRequest {
@@ -1657,6 +1998,7 @@ impl ValidateRequestTypes for UnpublishVolumeRequest {
 impl From<&dyn UnpublishVolumeInfo> for UnpublishVolume {
     fn from(data: &dyn UnpublishVolumeInfo) -> Self {
         UnpublishVolume::new(&data.uuid(), data.force())
+            .with_expected_revision(data.expected_revision())
     }
 }
 
@@ -1665,6 +2007,7 @@ impl From<&dyn UnpublishVolumeInfo> for UnpublishVolumeRequest {
         Self {
             uuid: Some(data.uuid().to_string()),
             force: data.force(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1675,6 +2018,8 @@ pub trait SetVolumeReplicaInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> VolumeId;
     /// No of replicas we want to set for the volume
     fn replicas(&self) -> u8;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl SetVolumeReplicaInfo for SetVolumeReplica {
@@ -1685,6 +2030,10 @@ impl SetVolumeReplicaInfo for SetVolumeReplica {
     fn replicas(&self) -> u8 {
         self.replicas
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Intermediate structure that validates the conversion to SetVolumeReplicaRequest type.
@@ -1701,6 +2050,9 @@ impl SetVolumeReplicaInfo for ValidatedSetVolumeReplicaRequest {
     fn replicas(&self) -> u8 {
         self.inner.replicas as u8
     }
+    fn expected_revision(&self) -> Option<u64> {
+        self.inner.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for SetVolumeReplicaRequest {
@@ -1718,6 +2070,7 @@ impl From<&dyn SetVolumeReplicaInfo> for SetVolumeReplica {
         Self {
             uuid: data.uuid(),
             replicas: data.replicas(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1727,6 +2080,7 @@ impl From<&dyn SetVolumeReplicaInfo> for SetVolumeReplicaRequest {
         Self {
             uuid: Some(data.uuid().to_string()),
             replicas: data.replicas().into(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1736,6 +2090,8 @@ pub trait SetVolumePropertyInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> VolumeId;
     /// Property to be set for the volume.
     fn property(&self) -> Option<VolumeProperty>;
+    /// Revision which the volume spec must be at.
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl SetVolumePropertyInfo for SetVolumeProperty {
@@ -1746,6 +2102,10 @@ impl SetVolumePropertyInfo for SetVolumeProperty {
     fn property(&self) -> Option<VolumeProperty> {
         Some(self.property.clone())
     }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Intermediate structure that validates the conversion to SetVolumePropertyRequest type.
@@ -1762,6 +2122,9 @@ impl SetVolumePropertyInfo for ValidatedSetVolumePropertyRequest {
     fn property(&self) -> Option<VolumeProperty> {
         self.inner.clone().into()
     }
+    fn expected_revision(&self) -> Option<u64> {
+        self.inner.expected_revision
+    }
 }
 
 impl From<SetVolumePropertyRequest> for Option<VolumeProperty> {
@@ -1771,6 +2134,9 @@ impl From<SetVolumePropertyRequest> for Option<VolumeProperty> {
                 volume::volume_property::Attr::MaxSnapshots(volume::MaxSnapshotValue { value }) => {
                     VolumeProperty::MaxSnapshots(value)
                 }
+                volume::volume_property::Attr::Autogrow(volume::AutoGrowValue { policy }) => {
+                    VolumeProperty::AutoGrow(policy.into_opt())
+                }
             })
         })
     }
@@ -1784,6 +2150,13 @@ impl From<VolumeProperty> for volume::VolumeProperty {
                     volume::MaxSnapshotValue { value },
                 )),
             },
+            VolumeProperty::AutoGrow(policy) => volume::VolumeProperty {
+                attr: Some(volume::volume_property::Attr::Autogrow(
+                    volume::AutoGrowValue {
+                        policy: policy.into_opt(),
+                    },
+                )),
+            },
         }
     }
 }
@@ -1804,6 +2177,7 @@ impl TryFrom<&dyn SetVolumePropertyInfo> for SetVolumeProperty {
             Ok(Self {
                 uuid: data.uuid(),
                 property,
+                expected_revision: data.expected_revision(),
             })
         } else {
             Err(ReplyError::missing_argument(
@@ -1819,9 +2193,246 @@ impl From<&dyn SetVolumePropertyInfo> for SetVolumePropertyRequest {
         Self {
             uuid: data.uuid().to_string(),
             property: data.property().into_opt(),
+            expected_revision: data.expected_revision(),
         }
     }
 }
+
+/// Trait to be implemented for GetVolumeAuth operation.
+pub trait GetVolumeAuthInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume.
+    fn uuid(&self) -> VolumeId;
+}
+
+impl GetVolumeAuthInfo for GetVolumeAuth {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetVolumeAuthRequest type.
+#[derive(Debug)]
+pub struct ValidatedGetVolumeAuthRequest {
+    uuid: VolumeId,
+}
+
+impl GetVolumeAuthInfo for ValidatedGetVolumeAuthRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetVolumeAuthRequest {
+    type Validated = ValidatedGetVolumeAuthRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetVolumeAuthRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.uuid)))?,
+        })
+    }
+}
+
+impl From<&dyn GetVolumeAuthInfo> for GetVolumeAuth {
+    fn from(data: &dyn GetVolumeAuthInfo) -> Self {
+        GetVolumeAuth::new(&data.uuid())
+    }
+}
+
+impl From<&dyn GetVolumeAuthInfo> for GetVolumeAuthRequest {
+    fn from(data: &dyn GetVolumeAuthInfo) -> Self {
+        Self {
+            uuid: data.uuid().to_string(),
+        }
+    }
+}
+
+/// Trait to be implemented for RotateVolumeAuth operation.
+pub trait RotateVolumeAuthInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume.
+    fn uuid(&self) -> VolumeId;
+    /// The new secrets.
+    fn auth(&self) -> VolumeAuth;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
+}
+
+impl RotateVolumeAuthInfo for RotateVolumeAuth {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn auth(&self) -> VolumeAuth {
+        self.auth.clone()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
+}
+
+/// Intermediate structure that validates the conversion to RotateVolumeAuthRequest type.
+#[derive(Debug)]
+pub struct ValidatedRotateVolumeAuthRequest {
+    inner: RotateVolumeAuthRequest,
+    uuid: VolumeId,
+}
+
+impl RotateVolumeAuthInfo for ValidatedRotateVolumeAuthRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn auth(&self) -> VolumeAuth {
+        self.inner.auth.clone().map(Into::into).unwrap_or_default()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.inner.expected_revision
+    }
+}
+
+impl ValidateRequestTypes for RotateVolumeAuthRequest {
+    type Validated = ValidatedRotateVolumeAuthRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedRotateVolumeAuthRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.uuid.clone())))?,
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn RotateVolumeAuthInfo> for RotateVolumeAuth {
+    fn from(data: &dyn RotateVolumeAuthInfo) -> Self {
+        RotateVolumeAuth::new(&data.uuid(), data.auth())
+            .with_expected_revision(data.expected_revision())
+    }
+}
+
+impl From<&dyn RotateVolumeAuthInfo> for RotateVolumeAuthRequest {
+    fn from(data: &dyn RotateVolumeAuthInfo) -> Self {
+        Self {
+            uuid: data.uuid().to_string(),
+            auth: Some(data.auth().into()),
+            expected_revision: data.expected_revision(),
+        }
+    }
+}
+
+/// Trait to be implemented for AddVolumeFrontendNode and RemoveVolumeFrontendNode operations.
+pub trait VolumeFrontendNodeInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume.
+    fn uuid(&self) -> VolumeId;
+    /// The frontend node to attach or detach.
+    fn frontend_node(&self) -> NodeId;
+    /// Revision which the volume spec must be at
+    fn expected_revision(&self) -> Option<u64>;
+}
+
+impl VolumeFrontendNodeInfo for AddVolumeFrontendNode {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn frontend_node(&self) -> NodeId {
+        self.frontend_node.clone()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
+}
+
+impl VolumeFrontendNodeInfo for RemoveVolumeFrontendNode {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn frontend_node(&self) -> NodeId {
+        self.frontend_node.clone()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
+}
+
+/// Intermediate structure that validates the conversion to the frontend node request types.
+#[derive(Debug)]
+pub struct ValidatedVolumeFrontendNodeRequest {
+    uuid: VolumeId,
+    frontend_node: NodeId,
+    expected_revision: Option<u64>,
+}
+
+impl VolumeFrontendNodeInfo for ValidatedVolumeFrontendNodeRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn frontend_node(&self) -> NodeId {
+        self.frontend_node.clone()
+    }
+
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
+}
+
+impl ValidateRequestTypes for AddVolumeFrontendNodeRequest {
+    type Validated = ValidatedVolumeFrontendNodeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedVolumeFrontendNodeRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.uuid)))?,
+            frontend_node: self.frontend_node.into(),
+            expected_revision: self.expected_revision,
+        })
+    }
+}
+
+impl ValidateRequestTypes for RemoveVolumeFrontendNodeRequest {
+    type Validated = ValidatedVolumeFrontendNodeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedVolumeFrontendNodeRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.uuid)))?,
+            frontend_node: self.frontend_node.into(),
+            expected_revision: self.expected_revision,
+        })
+    }
+}
+
+impl From<&dyn VolumeFrontendNodeInfo> for AddVolumeFrontendNode {
+    fn from(data: &dyn VolumeFrontendNodeInfo) -> Self {
+        AddVolumeFrontendNode::new(data.uuid(), data.frontend_node())
+            .with_expected_revision(data.expected_revision())
+    }
+}
+
+impl From<&dyn VolumeFrontendNodeInfo> for RemoveVolumeFrontendNode {
+    fn from(data: &dyn VolumeFrontendNodeInfo) -> Self {
+        RemoveVolumeFrontendNode::new(data.uuid(), data.frontend_node())
+            .with_expected_revision(data.expected_revision())
+    }
+}
+
+impl From<&dyn VolumeFrontendNodeInfo> for AddVolumeFrontendNodeRequest {
+    fn from(data: &dyn VolumeFrontendNodeInfo) -> Self {
+        Self {
+            uuid: data.uuid().to_string(),
+            frontend_node: data.frontend_node().to_string(),
+            expected_revision: data.expected_revision(),
+        }
+    }
+}
+
+impl From<&dyn VolumeFrontendNodeInfo> for RemoveVolumeFrontendNodeRequest {
+    fn from(data: &dyn VolumeFrontendNodeInfo) -> Self {
+        Self {
+            uuid: data.uuid().to_string(),
+            frontend_node: data.frontend_node().to_string(),
+            expected_revision: data.expected_revision(),
+        }
+    }
+}
+
 /// A helper to convert the replica topology map form grpc type to corresponding control plane type.
 fn to_replica_topology_map(
     map: HashMap<String, volume::ReplicaTopology>,
@@ -1883,6 +2494,8 @@ pub trait DestroyShutdownTargetsInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> &VolumeId;
     /// List of all targets registered in the Application node for the volume.
     fn registered_targets(&self) -> Option<Vec<String>>;
+    /// Revision which the volume spec must be at.
+    fn expected_revision(&self) -> Option<u64>;
 }
 
 impl DestroyShutdownTargetsInfo for DestroyShutdownTargets {
@@ -1892,6 +2505,9 @@ impl DestroyShutdownTargetsInfo for DestroyShutdownTargets {
     fn registered_targets(&self) -> Option<Vec<String>> {
         self.registered_targets().clone()
     }
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision()
+    }
 }
 
 /// Intermediate structure that validates the conversion to DestroyShutdownNexusRequest type.
@@ -1899,6 +2515,7 @@ impl DestroyShutdownTargetsInfo for DestroyShutdownTargets {
 pub struct ValidatedDestroyShutdownTargetRequest {
     uuid: VolumeId,
     registered_targets: Option<Vec<String>>,
+    expected_revision: Option<u64>,
 }
 
 impl DestroyShutdownTargetsInfo for ValidatedDestroyShutdownTargetRequest {
@@ -1908,6 +2525,9 @@ impl DestroyShutdownTargetsInfo for ValidatedDestroyShutdownTargetRequest {
     fn registered_targets(&self) -> Option<Vec<String>> {
         self.registered_targets.clone()
     }
+    fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 impl ValidateRequestTypes for DestroyShutdownTargetRequest {
@@ -1919,6 +2539,7 @@ impl ValidateRequestTypes for DestroyShutdownTargetRequest {
                 Some(val) => Some(val.target_list),
                 None => None,
             },
+            expected_revision: self.expected_revision,
         })
     }
 }
@@ -1930,6 +2551,7 @@ impl From<&dyn DestroyShutdownTargetsInfo> for DestroyShutdownTargetRequest {
             registered_targets: data
                 .registered_targets()
                 .map(|val| RegisteredTargets { target_list: val }),
+            expected_revision: data.expected_revision(),
         }
     }
 }
@@ -1937,6 +2559,7 @@ impl From<&dyn DestroyShutdownTargetsInfo> for DestroyShutdownTargetRequest {
 impl From<&dyn DestroyShutdownTargetsInfo> for DestroyShutdownTargets {
     fn from(data: &dyn DestroyShutdownTargetsInfo) -> Self {
         Self::new(data.uuid().clone(), data.registered_targets())
+            .with_expected_revision(data.expected_revision())
     }
 }
 
@@ -2076,6 +2699,7 @@ mod test {
     fn volume_deletion_event() {
         let vol = DestroyVolumeRequest {
             uuid: Some(VolumeId::new().to_string()),
+            ..Default::default()
         };
         let req = tonic::Request::new(vol);
         let event = req.into_inner().validated().unwrap().event();
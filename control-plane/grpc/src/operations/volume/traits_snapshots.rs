@@ -1,6 +1,9 @@
 use crate::{
-    common, misc::traits::ValidateRequestTypes, operations::snapshot::SnapshotInfo, snapshot,
-    volume, volume::get_snapshots_request,
+    common,
+    misc::traits::ValidateRequestTypes,
+    operations::{label_selector, snapshot::SnapshotInfo},
+    snapshot, volume,
+    volume::get_snapshots_request,
 };
 
 use stor_port::{
@@ -738,6 +741,9 @@ impl TryFrom<get_snapshots_request::Filter> for Filter {
                     .snapshot_id
                     .try_into_id(ResourceKind::VolumeSnapshot, "snapshot_id")?,
             ),
+            get_snapshots_request::Filter::Labels(labels) => {
+                Filter::Labels(label_selector(labels, ResourceKind::VolumeSnapshot)?)
+            }
         })
     }
 }
@@ -1,13 +1,16 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    operations::watch::traits::{GetWatchInfo, WatchInfo, WatchOperations},
+    operations::watch::traits::{
+        GetWatchInfo, ResourceEventStream, WatchEventsInfo, WatchInfo, WatchOperations,
+    },
     watch::{get_watches_reply, watch_grpc_client::WatchGrpcClient},
 };
 use std::{convert::TryFrom, ops::Deref};
 use stor_port::{
     transport_api::{v0::Watches, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::MessageIdVs,
+    types::v0::transport::{MessageIdVs, ResourceEvent},
 };
+use tokio_stream::StreamExt;
 use tonic::transport::Uri;
 
 type WatchClientInner = Client<WatchGrpcClient<TracedChannel>>;
@@ -75,4 +78,16 @@ impl WatchOperations for WatchClient {
             Some(err) => Err(err.into()),
         }
     }
+
+    async fn events(
+        &self,
+        request: &dyn WatchEventsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ResourceEventStream, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::WatchEvents);
+        let response = self.client().watch_events(req).await?.into_inner();
+        Ok(Box::pin(
+            response.map(|event| ResourceEvent::try_from(event?)),
+        ))
+    }
 }
@@ -5,10 +5,11 @@ use crate::{
     watch::{
         get_watches_reply,
         watch_grpc_server::{WatchGrpc, WatchGrpcServer},
-        GetWatchesReply, GetWatchesRequest, WatchReply,
+        GetWatchesReply, GetWatchesRequest, WatchEventsRequest, WatchReply,
     },
 };
-use std::sync::Arc;
+use std::{pin::Pin, sync::Arc};
+use tokio_stream::{Stream, StreamExt};
 use tonic::Response;
 
 /// RPC Watch Server
@@ -32,6 +33,9 @@ impl WatchServer {
 /// Implementation of the RPC methods.
 #[tonic::async_trait]
 impl WatchGrpc for WatchServer {
+    type WatchEventsStream =
+        Pin<Box<dyn Stream<Item = Result<watch::ResourceEvent, tonic::Status>> + Send>>;
+
     async fn get_watches(
         &self,
         request: tonic::Request<GetWatchesRequest>,
@@ -70,4 +74,16 @@ impl WatchGrpc for WatchServer {
             })),
         }
     }
+    async fn watch_events(
+        &self,
+        request: tonic::Request<WatchEventsRequest>,
+    ) -> Result<tonic::Response<Self::WatchEventsStream>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        let events = self.service.events(&req, None).await?;
+        Ok(Response::new(Box::pin(events.map(|event| {
+            event
+                .map(watch::ResourceEvent::from)
+                .map_err(tonic::Status::from)
+        }))))
+    }
 }
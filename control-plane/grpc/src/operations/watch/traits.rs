@@ -4,14 +4,19 @@ use crate::{
     watch,
     watch::watch_resource_id,
 };
-use std::convert::TryFrom;
+use std::{collections::HashMap, convert::TryFrom, pin::Pin};
 use stor_port::{
     transport_api::{v0::Watches, ReplyError, ResourceKind},
     types::v0::transport::{
-        CreateWatch, DeleteWatch, GetWatches, NexusId, ReplicaId, VolumeId, Watch, WatchCallback,
-        WatchResourceId, WatchType,
+        CreateWatch, DeleteWatch, EventAction, EventResource, GetWatches, NexusId, ReplicaId,
+        ResourceEvent, VolumeId, Watch, WatchCallback, WatchEvents, WatchResourceId, WatchType,
     },
 };
+use tokio_stream::Stream;
+
+/// Stream of resource events.
+pub type ResourceEventStream =
+    Pin<Box<dyn Stream<Item = Result<ResourceEvent, ReplyError>> + Send>>;
 
 /// All watch crud operations to be a part of the WatchOperations trait
 #[tonic::async_trait]
@@ -26,6 +31,12 @@ pub trait WatchOperations: Send + Sync {
     ) -> Result<Watches, ReplyError>;
     /// Destroy a watch
     async fn destroy(&self, req: &dyn WatchInfo, ctx: Option<Context>) -> Result<(), ReplyError>;
+    /// Stream the spec and state change events of the resources
+    async fn events(
+        &self,
+        req: &dyn WatchEventsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ResourceEventStream, ReplyError>;
 }
 
 /// WatchInfo trait for the watch creation to be implemented by entities which want to avail
@@ -46,6 +57,19 @@ pub trait GetWatchInfo: Send + Sync + std::fmt::Debug {
     fn resource_id(&self) -> WatchResourceId;
 }
 
+/// WatchEventsInfo trait for the watch events operation to be implemented by entities which want
+/// to avail this operation
+pub trait WatchEventsInfo: Send + Sync + std::fmt::Debug {
+    /// types of resources to stream the events of
+    fn resources(&self) -> Vec<EventResource>;
+    /// id of the resource to stream the events of
+    fn id(&self) -> Option<String>;
+    /// labels which the resource must have
+    fn labels(&self) -> HashMap<String, String>;
+    /// type of events to stream
+    fn watch_type(&self) -> WatchType;
+}
+
 impl From<WatchResourceId> for watch::WatchResourceId {
     fn from(resource_id: WatchResourceId) -> Self {
         match resource_id {
@@ -406,3 +430,202 @@ impl From<&dyn GetWatchInfo> for watch::GetWatchesRequest {
         }
     }
 }
+
+impl From<EventResource> for watch::EventResource {
+    fn from(value: EventResource) -> Self {
+        match value {
+            EventResource::Volume => Self::Volume,
+            EventResource::Nexus => Self::Nexus,
+            EventResource::Pool => Self::Pool,
+            EventResource::Node => Self::Node,
+            EventResource::Snapshot => Self::Snapshot,
+        }
+    }
+}
+
+impl From<watch::EventResource> for EventResource {
+    fn from(value: watch::EventResource) -> Self {
+        match value {
+            watch::EventResource::Volume => Self::Volume,
+            watch::EventResource::Nexus => Self::Nexus,
+            watch::EventResource::Pool => Self::Pool,
+            watch::EventResource::Node => Self::Node,
+            watch::EventResource::Snapshot => Self::Snapshot,
+        }
+    }
+}
+
+impl From<EventAction> for watch::EventAction {
+    fn from(value: EventAction) -> Self {
+        match value {
+            EventAction::Updated => Self::Updated,
+            EventAction::Deleted => Self::Deleted,
+        }
+    }
+}
+
+impl From<watch::EventAction> for EventAction {
+    fn from(value: watch::EventAction) -> Self {
+        match value {
+            watch::EventAction::Updated => Self::Updated,
+            watch::EventAction::Deleted => Self::Deleted,
+        }
+    }
+}
+
+impl From<ResourceEvent> for watch::ResourceEvent {
+    fn from(value: ResourceEvent) -> Self {
+        let resource: watch::EventResource = value.resource.into();
+        let watch_type: watch::WatchType = value.watch_type.into();
+        let action: watch::EventAction = value.action.into();
+        Self {
+            resource: resource as i32,
+            id: value.id,
+            watch_type: watch_type as i32,
+            action: action as i32,
+            labels: value.labels,
+            value: value.value.map(|value| value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<watch::ResourceEvent> for ResourceEvent {
+    type Error = ReplyError;
+
+    fn try_from(value: watch::ResourceEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            resource: watch::EventResource::try_from(value.resource)
+                .map_err(|error| {
+                    ReplyError::invalid_argument(ResourceKind::Watch, "resource", error.to_string())
+                })?
+                .into(),
+            id: value.id,
+            watch_type: watch::WatchType::try_from(value.watch_type)
+                .map_err(|error| {
+                    ReplyError::invalid_argument(
+                        ResourceKind::Watch,
+                        "watch_type",
+                        error.to_string(),
+                    )
+                })?
+                .into(),
+            action: watch::EventAction::try_from(value.action)
+                .map_err(|error| {
+                    ReplyError::invalid_argument(ResourceKind::Watch, "action", error.to_string())
+                })?
+                .into(),
+            labels: value.labels,
+            value: match value.value {
+                Some(value) => Some(serde_json::from_str(&value).map_err(|error| {
+                    ReplyError::invalid_argument(ResourceKind::Watch, "value", error.to_string())
+                })?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl WatchEventsInfo for WatchEvents {
+    fn resources(&self) -> Vec<EventResource> {
+        self.resources.clone()
+    }
+
+    fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+
+    fn labels(&self) -> HashMap<String, String> {
+        self.labels.clone()
+    }
+
+    fn watch_type(&self) -> WatchType {
+        self.watch_type.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to WatchEventsRequest type
+#[derive(Debug)]
+pub struct ValidatedWatchEventsRequest {
+    inner: WatchEvents,
+}
+
+impl WatchEventsInfo for ValidatedWatchEventsRequest {
+    fn resources(&self) -> Vec<EventResource> {
+        self.inner.resources()
+    }
+
+    fn id(&self) -> Option<String> {
+        self.inner.id()
+    }
+
+    fn labels(&self) -> HashMap<String, String> {
+        self.inner.labels()
+    }
+
+    fn watch_type(&self) -> WatchType {
+        self.inner.watch_type()
+    }
+}
+
+impl ValidateRequestTypes for watch::WatchEventsRequest {
+    type Validated = ValidatedWatchEventsRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let mut resources = vec![];
+        for resource in self.resources {
+            match watch::EventResource::try_from(resource) {
+                Ok(resource) => resources.push(resource.into()),
+                Err(error) => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Watch,
+                        "resources",
+                        error.to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(ValidatedWatchEventsRequest {
+            inner: WatchEvents {
+                resources,
+                id: self.id,
+                labels: self.labels,
+                watch_type: match watch::WatchType::try_from(self.watch_type) {
+                    Ok(watch_type) => watch_type.into(),
+                    Err(error) => {
+                        return Err(ReplyError::invalid_argument(
+                            ResourceKind::Watch,
+                            "watch_type",
+                            error,
+                        ))
+                    }
+                },
+            },
+        })
+    }
+}
+
+impl From<&dyn WatchEventsInfo> for WatchEvents {
+    fn from(data: &dyn WatchEventsInfo) -> Self {
+        Self {
+            resources: data.resources(),
+            id: data.id(),
+            labels: data.labels(),
+            watch_type: data.watch_type(),
+        }
+    }
+}
+
+impl From<&dyn WatchEventsInfo> for watch::WatchEventsRequest {
+    fn from(data: &dyn WatchEventsInfo) -> Self {
+        let watch_type: watch::WatchType = data.watch_type().into();
+        Self {
+            resources: data
+                .resources()
+                .into_iter()
+                .map(|resource| watch::EventResource::from(resource) as i32)
+                .collect(),
+            id: data.id(),
+            labels: data.labels(),
+            watch_type: watch_type as i32,
+        }
+    }
+}
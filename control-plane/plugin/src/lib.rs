@@ -9,11 +9,12 @@ use resources::LabelResources;
 use crate::{
     operations::{
         Cordoning, Drain, Get, GetBlockDevices, GetSnapshots, List, ListExt, Operations,
-        PluginResult, RebuildHistory, ReplicaTopology, Scale,
+        PluginResult, RebuildHistory, ReplicaTopology, Scale, SwitchOver,
     },
     resources::{
-        blockdevice, cordon, drain, node, pool, snapshot, volume, CordonResources, DrainResources,
-        GetCordonArgs, GetDrainArgs, GetResources, ScaleResources, UnCordonResources,
+        blockdevice, cordon, drain, node, pool, snapshot, volume, CordonResources,
+        DecommissionResources, DrainResources, GetCordonArgs, GetDrainArgs, GetResources,
+        ScaleResources, SwitchOverResources, UnCordonResources,
     },
 };
 
@@ -88,6 +89,8 @@ impl ExecuteOperation for Operations {
             Operations::Cordon(resource) => resource.execute(cli_args).await,
             Operations::Uncordon(resource) => resource.execute(cli_args).await,
             Operations::Label(resource) => resource.execute(cli_args).await,
+            Operations::Switchover(resource) => resource.execute(cli_args).await,
+            Operations::Decommission(resource) => resource.execute(cli_args).await,
         }
     }
 }
@@ -102,11 +105,16 @@ impl ExecuteOperation for DrainResources {
                 node::Node::drain(
                     &drain_node_args.node_id(),
                     drain_node_args.label(),
+                    drain_node_args.evacuate(),
+                    drain_node_args.ttl(),
                     drain_node_args.drain_timeout(),
                     &cli_args.output,
                 )
                 .await
             }
+            DrainResources::Pool { id, label } => {
+                pool::Pool::drain(id, label, &cli_args.output).await
+            }
         }
     }
 }
@@ -142,9 +150,9 @@ impl ExecuteOperation for GetResources {
             GetResources::VolumeReplicaTopology { id } => {
                 volume::Volume::topology(id, &cli_args.output).await
             }
-            GetResources::Pools => pool::Pools::list(&cli_args.output).await,
+            GetResources::Pools(pool_args) => pool::Pools::list(&cli_args.output, pool_args).await,
             GetResources::Pool { id } => pool::Pool::get(id, &cli_args.output).await,
-            GetResources::Nodes => node::Nodes::list(&cli_args.output).await,
+            GetResources::Nodes(node_args) => node::Nodes::list(&cli_args.output, node_args).await,
             GetResources::Node(args) => node::Node::get(&args.node_id(), &cli_args.output).await,
             GetResources::BlockDevices(bdargs) => {
                 blockdevice::BlockDevice::get_blockdevices(
@@ -158,6 +166,7 @@ impl ExecuteOperation for GetResources {
                 snapshot::VolumeSnapshots::get_snapshots(
                     &snapargs.volume(),
                     &snapargs.snapshot(),
+                    &snapargs.selector(),
                     &cli_args.output,
                 )
                 .await
@@ -179,14 +188,41 @@ impl ExecuteOperation for ScaleResources {
     }
 }
 
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for SwitchOverResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
+        match self {
+            SwitchOverResources::Volume { id, node } => {
+                volume::Volume::switchover(id, node, &cli_args.output).await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ExecuteOperation for DecommissionResources {
+    type Args = CliArgs;
+    type Error = crate::resources::Error;
+    async fn execute(&self, _cli_args: &CliArgs) -> PluginResult {
+        match self {
+            DecommissionResources::Node { id, force } => node::Node::decommission(id, *force).await,
+        }
+    }
+}
+
 #[async_trait::async_trait(?Send)]
 impl ExecuteOperation for CordonResources {
     type Args = CliArgs;
     type Error = crate::resources::Error;
     async fn execute(&self, cli_args: &CliArgs) -> PluginResult {
         match self {
-            CordonResources::Node { id, label } => {
-                node::Node::cordon(id, label, &cli_args.output).await
+            CordonResources::Node { id, label, ttl } => {
+                node::Node::cordon_with_ttl(id, label, *ttl, &cli_args.output).await
+            }
+            CordonResources::Pool { id, label } => {
+                pool::Pool::cordon(id, label, &cli_args.output).await
             }
         }
     }
@@ -201,6 +237,9 @@ impl ExecuteOperation for UnCordonResources {
             UnCordonResources::Node { id, label } => {
                 node::Node::uncordon(id, label, &cli_args.output).await
             }
+            UnCordonResources::Pool { id, label } => {
+                pool::Pool::uncordon(id, label, &cli_args.output).await
+            }
         }
     }
 }
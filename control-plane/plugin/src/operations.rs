@@ -1,6 +1,6 @@
 use crate::resources::{
-    error::Error, utils, CordonResources, DrainResources, GetResources, LabelResources,
-    ScaleResources, UnCordonResources,
+    error::Error, utils, CordonResources, DecommissionResources, DrainResources, GetResources,
+    LabelResources, ScaleResources, SwitchOverResources, UnCordonResources,
 };
 use async_trait::async_trait;
 
@@ -28,6 +28,12 @@ pub enum Operations {
     /// 'Label' resources.
     #[clap(subcommand)]
     Label(LabelResources),
+    /// 'Switchover' resources.
+    #[clap(subcommand)]
+    Switchover(SwitchOverResources),
+    /// 'Decommission' resources.
+    #[clap(subcommand)]
+    Decommission(DecommissionResources),
 }
 
 /// Drain trait.
@@ -38,6 +44,8 @@ pub trait Drain {
     async fn drain(
         id: &Self::ID,
         label: String,
+        evacuate: bool,
+        ttl: Option<humantime::Duration>,
         drain_timeout: Option<humantime::Duration>,
         output: &utils::OutputFormat,
     ) -> PluginResult;
@@ -92,6 +100,19 @@ pub trait Scale {
     ) -> PluginResult;
 }
 
+/// SwitchOver trait.
+/// To be implemented by resources which support the 'switchover' operation.
+#[async_trait(?Send)]
+pub trait SwitchOver {
+    type ID;
+    type NodeID;
+    async fn switchover(
+        id: &Self::ID,
+        node: &Option<Self::NodeID>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult;
+}
+
 /// Replica topology trait.
 /// To be implemented by resources which support the 'replica-topology' operation
 #[async_trait(?Send)]
@@ -133,6 +154,7 @@ pub trait GetSnapshots {
     async fn get_snapshots(
         volid: &Self::SourceID,
         snapid: &Self::ResourceID,
+        selector: &Option<String>,
         output: &utils::OutputFormat,
     ) -> PluginResult;
 }
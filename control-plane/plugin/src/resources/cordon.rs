@@ -37,7 +37,7 @@ impl Get for NodeCordon {
 #[async_trait(?Send)]
 impl List for NodeCordons {
     async fn list(output: &OutputFormat) -> PluginResult {
-        match RestClient::client().nodes_api().get_nodes(None).await {
+        match RestClient::client().nodes_api().get_nodes(None, None).await {
             Ok(nodes) => {
                 // iterate through the nodes and filter for only those that have cordon or drain
                 // labels
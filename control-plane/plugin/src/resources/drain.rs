@@ -35,7 +35,7 @@ impl Get for NodeDrain {
 #[async_trait(?Send)]
 impl List for NodeDrains {
     async fn list(output: &OutputFormat) -> PluginResult {
-        match RestClient::client().nodes_api().get_nodes(None).await {
+        match RestClient::client().nodes_api().get_nodes(None, None).await {
             Ok(nodes) => {
                 // iterate through the nodes and filter for only those that have drain labels
                 // then print with the format NodeDisplayFormat::Drain
@@ -40,6 +40,12 @@ pub enum Error {
         id: String,
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
+    /// Error when node decommission request fails.
+    #[snafu(display("Failed to decommission node {id}. Error {source}"))]
+    NodeDecommissionError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
     /// Error when list nodes request fails.
     #[snafu(display("Failed to list nodes. Error {source}"))]
     ListNodesError {
@@ -51,6 +57,24 @@ pub enum Error {
         id: String,
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
+    /// Error when pool cordon request fails.
+    #[snafu(display("Failed to cordon pool {id}. Error {source}"))]
+    PoolCordonError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when pool uncordon request fails.
+    #[snafu(display("Failed to uncordon pool {id}. Error {source}"))]
+    PoolUncordonError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
+    /// Error when pool drain request fails.
+    #[snafu(display("Failed to drain pool {id}. Error {source}"))]
+    PoolDrainError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
     /// Error when list pools request fails.
     #[snafu(display("Failed to list pools. Error {source}"))]
     ListPoolsError {
@@ -74,6 +98,12 @@ pub enum Error {
         id: String,
         source: openapi::tower::client::Error<openapi::models::RestJsonError>,
     },
+    /// Error when volume target switchover request fails.
+    #[snafu(display("Failed to switchover the target of volume {id}. Error {source}"))]
+    SwitchOverVolumeError {
+        id: String,
+        source: openapi::tower::client::Error<openapi::models::RestJsonError>,
+    },
     /// Error when resize volume request fails.
     #[snafu(display("Failed to resize volume {id}. Error {source}"))]
     ResizeVolumeError {
@@ -1,6 +1,7 @@
 use crate::resources::{
     blockdevice::BlockDeviceArgs,
-    node::{DrainNodeArgs, GetNodeArgs},
+    node::{DrainNodeArgs, GetNodeArgs, NodesArgs},
+    pool::PoolsArgs,
     snapshot::VolumeSnapshotArgs,
     volume::VolumesArgs,
 };
@@ -44,11 +45,11 @@ pub enum GetResources {
     /// Get volume snapshots based on input args.
     VolumeSnapshots(VolumeSnapshotArgs),
     /// Get all pools.
-    Pools,
+    Pools(PoolsArgs),
     /// Get pool with the given ID.
     Pool { id: PoolId },
     /// Get all nodes.
-    Nodes,
+    Nodes(NodesArgs),
     /// Get node with the given ID.
     Node(GetNodeArgs),
     /// Get BlockDevices present on the Node. Lists usable devices by default.
@@ -69,11 +70,49 @@ pub enum ScaleResources {
     },
 }
 
+/// The types of resources that support the 'switchover' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum SwitchOverResources {
+    /// Move the target of a published volume to another node.
+    Volume {
+        /// ID of the volume.
+        id: VolumeId,
+        /// ID of the node where the target should be moved to.
+        /// If not specified, the control-plane picks a suitable node.
+        #[clap(long)]
+        node: Option<NodeId>,
+    },
+}
+
+/// The types of resources that support the 'decommission' operation.
+#[derive(clap::Subcommand, Debug)]
+pub enum DecommissionResources {
+    /// Permanently remove a drained node, whose io-engine is stopped, and its pools from the
+    /// cluster.
+    Node {
+        /// ID of the node.
+        id: NodeId,
+        /// Decommission the node even if it is not drained or is still in use by volumes.
+        /// Only use this for nodes which are gone for good.
+        #[clap(long)]
+        force: bool,
+    },
+}
+
 /// The types of resources that support cordoning.
 #[derive(clap::Subcommand, Debug)]
 pub enum CordonResources {
     /// Cordon the node with the given ID by applying the cordon label to that node.
-    Node { id: NodeId, label: String },
+    Node {
+        id: NodeId,
+        label: String,
+        /// Time to live of the cordon label, after which it's removed, eg: 30m or 2h.
+        #[clap(long)]
+        ttl: Option<humantime::Duration>,
+    },
+    /// Cordon the pool with the given ID by applying the cordon label to that pool.
+    /// New replicas, snapshots and clones are not placed on a cordoned pool.
+    Pool { id: PoolId, label: String },
 }
 
 /// The types of resources that support uncordoning.
@@ -82,6 +121,9 @@ pub enum UnCordonResources {
     /// Removes the cordon label from the node.
     /// When the node has no more cordon labels, it is effectively uncordoned.
     Node { id: NodeId, label: String },
+    /// Removes the cordon label from the pool.
+    /// When the pool has no more cordon labels, it is effectively uncordoned.
+    Pool { id: PoolId, label: String },
 }
 
 /// The types of resources that support the 'get cordon' operation.
@@ -99,6 +141,9 @@ pub enum GetCordonArgs {
 pub enum DrainResources {
     /// Drain node with the given ID.
     Node(DrainNodeArgs),
+    /// Drain the pool with the given ID by applying the drain label to that pool.
+    /// The volume replicas on the pool are moved onto other pools.
+    Pool { id: PoolId, label: String },
 }
 
 /// The types of resources that support the 'label' operation.
@@ -1,5 +1,5 @@
 use crate::{
-    operations::{Cordoning, Drain, Get, Label, List, PluginResult},
+    operations::{Cordoning, Drain, Get, Label, ListExt, PluginResult},
     resources::{
         error::Error,
         utils,
@@ -42,6 +42,15 @@ impl GetNodeArgs {
 #[derive(clap::Args, Debug)]
 pub struct Nodes {}
 
+/// Nodes args.
+#[derive(Debug, Clone, clap::Args)]
+pub struct NodesArgs {
+    /// Shows only nodes whose labels match the Kubernetes-style label selector,
+    /// eg: "tier=gold,zone in (a,b),!legacy".
+    #[clap(long)]
+    selector: Option<String>,
+}
+
 // CreateRows being trait for Node would create the rows from the list of
 // Nodes returned from REST call.
 impl CreateRow for openapi::models::Node {
@@ -90,9 +99,14 @@ impl GetHeaderRow for openapi::models::Node {
 }
 
 #[async_trait(?Send)]
-impl List for Nodes {
-    async fn list(output: &utils::OutputFormat) -> PluginResult {
-        match RestClient::client().nodes_api().get_nodes(None).await {
+impl ListExt for Nodes {
+    type Context = NodesArgs;
+    async fn list(output: &utils::OutputFormat, context: &Self::Context) -> PluginResult {
+        match RestClient::client()
+            .nodes_api()
+            .get_nodes(None, context.selector.as_deref())
+            .await
+        {
             Ok(nodes) => {
                 // Print table, json or yaml based on output format.
                 utils::print_table(output, nodes.into_body());
@@ -130,7 +144,7 @@ impl Get for Node {
 }
 
 /// Get the cordon labels from whichever state.
-fn cordon_labels_from_state(ds: &CordonDrainState) -> Vec<String> {
+pub(crate) fn cordon_labels_from_state(ds: &CordonDrainState) -> Vec<String> {
     match ds {
         CordonDrainState::cordonedstate(state) => state.cordonlabels.clone(),
         CordonDrainState::drainingstate(state) => state.cordonlabels.clone(),
@@ -138,7 +152,7 @@ fn cordon_labels_from_state(ds: &CordonDrainState) -> Vec<String> {
     }
 }
 
-fn drain_labels_from_state(ds: &CordonDrainState) -> Vec<String> {
+pub(crate) fn drain_labels_from_state(ds: &CordonDrainState) -> Vec<String> {
     match ds {
         CordonDrainState::cordonedstate(_) => Vec::<String>::new(),
         CordonDrainState::drainingstate(state) => state.drainlabels.clone(),
@@ -150,16 +164,81 @@ fn drain_labels_from_state(ds: &CordonDrainState) -> Vec<String> {
 impl Cordoning for Node {
     type ID = NodeId;
     async fn cordon(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        Self::cordon_with_ttl(id, label, None, output).await
+    }
+
+    async fn uncordon(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .nodes_api()
+            .delete_node_cordon(id, label, None)
+            .await
+        {
+            Ok(node) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, node.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    let mut cordon_labels: Vec<String> = vec![];
+                    let mut drain_labels: Vec<String> = vec![];
+                    match node.into_body().spec {
+                        Some(spec) => {
+                            if let Some(cds) = spec.cordondrainstate {
+                                cordon_labels = cordon_labels_from_state(&cds);
+                                drain_labels = drain_labels_from_state(&cds);
+                            }
+                        }
+                        /* shouldn't happen */
+                        None => {
+                            println!("Error: Node {id} has no spec");
+                        }
+                    }
+                    let labels = [cordon_labels, drain_labels].concat();
+                    if labels.is_empty() {
+                        println!("Node {id} successfully uncordoned");
+                    } else {
+                        println!(
+                            "Cordon label successfully removed. Remaining cordon labels {labels:?}",
+                        );
+                    }
+                }
+            },
+            Err(e) => {
+                return Err(Error::NodeUncordonError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Node {
+    /// Cordon the node with the given label, which is removed once its time to live expires.
+    pub async fn cordon_with_ttl(
+        id: &NodeId,
+        label: &str,
+        ttl: Option<humantime::Duration>,
+        output: &OutputFormat,
+    ) -> PluginResult {
         // is node already cordoned with the label?
-        let already_has_cordon_label: bool =
+        let (already_has_cordon_label, if_match) =
             match RestClient::client().nodes_api().get_node(id).await {
                 Ok(node) => {
                     let node_body = &node.into_body();
                     match &node_body.spec {
-                        Some(spec) => match &spec.cordondrainstate {
-                            Some(ds) => cordon_labels_from_state(ds).contains(&label.to_string()),
-                            None => false,
-                        },
+                        Some(spec) => (
+                            match &spec.cordondrainstate {
+                                Some(ds) => {
+                                    cordon_labels_from_state(ds).contains(&label.to_string())
+                                }
+                                None => false,
+                            },
+                            // the cordon fails if the node is modified in the meantime
+                            utils::if_match(spec.revision),
+                        ),
                         None => {
                             println!("Node {id} is not registered");
                             return Ok(());
@@ -177,7 +256,12 @@ impl Cordoning for Node {
             false => {
                 RestClient::client()
                     .nodes_api()
-                    .put_node_cordon(id, label)
+                    .put_node_cordon(
+                        id,
+                        label,
+                        ttl.map(|ttl| ttl.to_string()).as_deref(),
+                        if_match.as_deref(),
+                    )
                     .await
             }
             true => RestClient::client().nodes_api().get_node(id).await,
@@ -203,50 +287,19 @@ impl Cordoning for Node {
         Ok(())
     }
 
-    async fn uncordon(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
-        match RestClient::client()
+    /// Decommission the node, removing it and its pools from the cluster.
+    pub async fn decommission(id: &NodeId, force: bool) -> PluginResult {
+        if let Err(error) = RestClient::client()
             .nodes_api()
-            .delete_node_cordon(id, label)
+            .del_node(id, Some(force))
             .await
         {
-            Ok(node) => match output {
-                OutputFormat::Yaml | OutputFormat::Json => {
-                    // Print json or yaml based on output format.
-                    utils::print_table(output, node.into_body());
-                }
-                OutputFormat::None => {
-                    // In case the output format is not specified, show a success message.
-                    let mut cordon_labels: Vec<String> = vec![];
-                    let mut drain_labels: Vec<String> = vec![];
-                    match node.into_body().spec {
-                        Some(spec) => {
-                            if let Some(cds) = spec.cordondrainstate {
-                                cordon_labels = cordon_labels_from_state(&cds);
-                                drain_labels = drain_labels_from_state(&cds);
-                            }
-                        }
-                        /* shouldn't happen */
-                        None => {
-                            println!("Error: Node {id} has no spec");
-                        }
-                    }
-                    let labels = [cordon_labels, drain_labels].concat();
-                    if labels.is_empty() {
-                        println!("Node {id} successfully uncordoned");
-                    } else {
-                        println!(
-                            "Cordon label successfully removed. Remaining cordon labels {labels:?}",
-                        );
-                    }
-                }
-            },
-            Err(e) => {
-                return Err(Error::NodeUncordonError {
-                    id: id.to_string(),
-                    source: e,
-                });
-            }
+            return Err(Error::NodeDecommissionError {
+                id: id.to_string(),
+                source: error,
+            });
         }
+        println!("Node {id} decommission started successfully");
         Ok(())
     }
 }
@@ -300,6 +353,18 @@ impl NodeDisplay {
         }
         [cordon_labels, drain_labels].concat()
     }
+    /// Get the cordon or drain label along with its expiry, if it has one.
+    fn label_with_expiry(node: &openapi::models::Node, label: String) -> String {
+        let expiry = node
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.labelexpiry.as_ref())
+            .and_then(|expiry| expiry.get(&label));
+        match expiry {
+            Some(expiry) => format!("{label} (expires {expiry})"),
+            None => label,
+        }
+    }
     /// Get a list of node drain labels.
     pub(crate) fn get_drain_label_list(node: &openapi::models::Node) -> Vec<String> {
         let mut drain_labels: Vec<String> = vec![];
@@ -324,7 +389,11 @@ impl CreateRows for NodeDisplay {
                 let mut rows = vec![];
                 for node in self.inner.iter() {
                     let mut row = node.create_rows();
-                    let labelstring = NodeDisplay::get_label_list(node).join(", ");
+                    let labelstring = NodeDisplay::get_label_list(node)
+                        .into_iter()
+                        .map(|label| NodeDisplay::label_with_expiry(node, label))
+                        .collect::<Vec<_>>()
+                        .join(", ");
                     // Add the cordon labels to each row.
                     row[0].add_cell(Cell::new(&labelstring));
                     rows.push(row[0].clone());
@@ -417,8 +486,16 @@ pub struct DrainNodeArgs {
     /// Label of the drain.
     label: String,
     #[clap(long)]
+    /// Move the volume replicas off the node's pools as well as the volume targets, so that the
+    /// node holds no volume data once drained.
+    evacuate: bool,
+    #[clap(long)]
     /// Timeout for the drain operation.
     drain_timeout: Option<humantime::Duration>,
+    #[clap(long)]
+    /// Time to live of the drain label, after which it's removed and the node is uncordoned,
+    /// eg: 30m or 2h.
+    ttl: Option<humantime::Duration>,
 }
 
 impl DrainNodeArgs {
@@ -430,10 +507,18 @@ impl DrainNodeArgs {
     pub fn label(&self) -> String {
         self.label.clone()
     }
+    /// Return whether the volume replicas are moved off the node's pools.
+    pub fn evacuate(&self) -> bool {
+        self.evacuate
+    }
     /// Return the timeout for the drain operation.
     pub fn drain_timeout(&self) -> Option<humantime::Duration> {
         self.drain_timeout
     }
+    /// Return the time to live of the drain label.
+    pub fn ttl(&self) -> Option<humantime::Duration> {
+        self.ttl
+    }
 }
 
 #[async_trait(?Send)]
@@ -442,6 +527,8 @@ impl Drain for Node {
     async fn drain(
         id: &Self::ID,
         label: String,
+        evacuate: bool,
+        ttl: Option<humantime::Duration>,
         drain_timeout: Option<humantime::Duration>,
         output: &utils::OutputFormat,
     ) -> PluginResult {
@@ -449,15 +536,19 @@ impl Drain for Node {
         if let Some(dt) = drain_timeout {
             timeout_instant = time::Instant::now().checked_add(dt.into());
         }
-        let already_has_drain_label: bool =
+        let (already_has_drain_label, if_match) =
             match RestClient::client().nodes_api().get_node(id).await {
                 Ok(node) => {
                     let node_body = &node.into_body();
                     match &node_body.spec {
-                        Some(spec) => match &spec.cordondrainstate {
-                            Some(ds) => drain_labels_from_state(ds).contains(&label),
-                            None => false,
-                        },
+                        Some(spec) => (
+                            match &spec.cordondrainstate {
+                                Some(ds) => drain_labels_from_state(ds).contains(&label),
+                                None => false,
+                            },
+                            // the drain fails if the node is modified in the meantime
+                            utils::if_match(spec.revision),
+                        ),
                         None => {
                             println!("Node {id} is not registered");
                             return Ok(());
@@ -474,7 +565,13 @@ impl Drain for Node {
         if !already_has_drain_label {
             if let Err(error) = RestClient::client()
                 .nodes_api()
-                .put_node_drain(id, &label)
+                .put_node_drain(
+                    id,
+                    &label,
+                    Some(evacuate),
+                    ttl.map(|ttl| ttl.to_string()).as_deref(),
+                    if_match.as_deref(),
+                )
                 .await
             {
                 return Err(Error::PutNodeDrainError {
@@ -677,7 +774,7 @@ impl Label for Node {
             validate_topology_value(value).context(super::error::NodeLabelFormatSnafu)?;
             match RestClient::client()
                 .nodes_api()
-                .put_node_label(id, key, value, Some(overwrite))
+                .put_node_label(id, key, value, Some(overwrite), None)
                 .await
             {
                 Err(source) => match source.status() {
@@ -699,11 +796,11 @@ impl Label for Node {
             }
         } else {
             snafu::ensure!(label.len() >= 2 && label.ends_with('-'), LabelAssignSnafu);
-            let key = &label[.. label.len() - 1];
+            let key = &label[..label.len() - 1];
             validate_topology_key(key)?;
             match RestClient::client()
                 .nodes_api()
-                .delete_node_label(id, key)
+                .delete_node_label(id, key, None)
                 .await
             {
                 Err(source) => match source.status() {
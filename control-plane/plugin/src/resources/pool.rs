@@ -1,9 +1,10 @@
 use crate::{
-    operations::{Get, List, PluginResult},
+    operations::{Cordoning, Get, ListExt, PluginResult},
     resources::{
         error::Error,
+        node::{cordon_labels_from_state, drain_labels_from_state},
         utils,
-        utils::{CreateRow, GetHeaderRow},
+        utils::{CreateRow, GetHeaderRow, OutputFormat},
         PoolId,
     },
     rest_wrapper::RestClient,
@@ -15,6 +16,15 @@ use prettytable::Row;
 #[derive(clap::Args, Debug)]
 pub struct Pools {}
 
+/// Pools args.
+#[derive(Debug, Clone, clap::Args)]
+pub struct PoolsArgs {
+    /// Shows only pools whose labels match the Kubernetes-style label selector,
+    /// eg: "tier=gold,zone in (a,b),!legacy".
+    #[clap(long)]
+    selector: Option<String>,
+}
+
 impl CreateRow for openapi::models::Pool {
     fn row(&self) -> Row {
         // The spec would be empty if it was not created using
@@ -61,9 +71,14 @@ impl GetHeaderRow for openapi::models::Pool {
 }
 
 #[async_trait(?Send)]
-impl List for Pools {
-    async fn list(output: &utils::OutputFormat) -> PluginResult {
-        match RestClient::client().pools_api().get_pools().await {
+impl ListExt for Pools {
+    type Context = PoolsArgs;
+    async fn list(output: &utils::OutputFormat, context: &Self::Context) -> PluginResult {
+        match RestClient::client()
+            .pools_api()
+            .get_pools(context.selector.as_deref())
+            .await
+        {
             Ok(pools) => {
                 // Print table, json or yaml based on output format.
                 utils::print_table(output, pools.into_body());
@@ -86,8 +101,23 @@ impl Get for Pool {
     async fn get(id: &Self::ID, output: &utils::OutputFormat) -> PluginResult {
         match RestClient::client().pools_api().get_pool(id).await {
             Ok(pool) => {
+                let pool = pool.into_body();
+                let blocked = pool
+                    .spec
+                    .as_ref()
+                    .and_then(|spec| spec.drainprogress.as_ref())
+                    .map(|progress| progress.blocked.clone())
+                    .unwrap_or_default();
                 // Print table, json or yaml based on output format.
-                utils::print_table(output, pool.into_body());
+                utils::print_table(output, pool);
+                if matches!(output, OutputFormat::None) && !blocked.is_empty() {
+                    let volumes = blocked
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    println!("Drain is waiting for these volumes to be published: {volumes}");
+                }
             }
             Err(e) => {
                 return Err(Error::GetPoolError {
@@ -99,3 +129,130 @@ impl Get for Pool {
         Ok(())
     }
 }
+
+#[async_trait(?Send)]
+impl Cordoning for Pool {
+    type ID = PoolId;
+    async fn cordon(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        // is pool already cordoned with the label?
+        let (already_has_cordon_label, if_match) =
+            match RestClient::client().pools_api().get_pool(id).await {
+                Ok(pool) => match pool.into_body().spec {
+                    Some(spec) => (
+                        spec.cordondrainstate
+                            .map(|ds| cordon_labels_from_state(&ds).contains(&label.to_string()))
+                            .unwrap_or_default(),
+                        // the cordon fails if the pool is modified in the meantime
+                        utils::if_match(spec.revision),
+                    ),
+                    None => {
+                        println!("Pool {id} is not managed by the control plane");
+                        return Ok(());
+                    }
+                },
+                Err(e) => {
+                    return Err(Error::GetPoolError {
+                        id: id.to_string(),
+                        source: e,
+                    });
+                }
+            };
+        let result = match already_has_cordon_label {
+            false => {
+                RestClient::client()
+                    .pools_api()
+                    .put_pool_cordon(id, label, if_match.as_deref())
+                    .await
+            }
+            true => RestClient::client().pools_api().get_pool(id).await,
+        };
+        match result {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Pool {id} cordoned successfully")
+                }
+            },
+            Err(e) => {
+                return Err(Error::PoolCordonError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn uncordon(id: &Self::ID, label: &str, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .pools_api()
+            .delete_pool_cordon(id, label, None)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    let labels = match pool.into_body().spec.and_then(|s| s.cordondrainstate) {
+                        Some(cds) => [
+                            cordon_labels_from_state(&cds),
+                            drain_labels_from_state(&cds),
+                        ]
+                        .concat(),
+                        None => vec![],
+                    };
+                    if labels.is_empty() {
+                        println!("Pool {id} successfully uncordoned");
+                    } else {
+                        println!(
+                            "Cordon label successfully removed. Remaining cordon labels {labels:?}",
+                        );
+                    }
+                }
+            },
+            Err(e) => {
+                return Err(Error::PoolUncordonError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Pool {
+    /// Drain the pool with the given ID, moving its volume replicas onto other pools.
+    pub async fn drain(id: &PoolId, label: &str, output: &OutputFormat) -> PluginResult {
+        match RestClient::client()
+            .pools_api()
+            .put_pool_drain(id, label, None)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Pool {id} drain started successfully")
+                }
+            },
+            Err(e) => {
+                return Err(Error::PoolDrainError {
+                    id: id.to_string(),
+                    source: e,
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -23,6 +23,10 @@ pub struct VolumeSnapshotArgs {
     /// Uuid of the snapshot (Optional).
     #[clap(long)]
     snapshot: Option<SnapshotId>,
+    /// Kubernetes-style label selector, matched against the labels of the source volume
+    /// (Optional), eg: "tier=gold,zone in (a,b),!legacy".
+    #[clap(long)]
+    selector: Option<String>,
 }
 
 impl VolumeSnapshotArgs {
@@ -35,6 +39,11 @@ impl VolumeSnapshotArgs {
     pub fn snapshot(&self) -> Option<SnapshotId> {
         self.snapshot
     }
+
+    /// Get the label selector from args.
+    pub fn selector(&self) -> Option<String> {
+        self.selector.clone()
+    }
 }
 
 impl CreateRow for openapi::models::VolumeSnapshot {
@@ -70,9 +79,10 @@ impl GetSnapshots for VolumeSnapshots {
     async fn get_snapshots(
         volid: &Self::SourceID,
         snapid: &Self::ResourceID,
+        selector: &Option<String>,
         output: &utils::OutputFormat,
     ) -> PluginResult {
-        match get_snapshots(volid, snapid).await {
+        match get_snapshots(volid, snapid, selector).await {
             Ok(snapshots) => {
                 // Print table, json or yaml based on output format.
                 utils::print_table(output, snapshots);
@@ -92,6 +102,7 @@ impl GetHeaderRow for openapi::models::VolumeSnapshot {
 async fn get_snapshots(
     volid: &Option<VolumeId>,
     snapid: &Option<SnapshotId>,
+    selector: &Option<String>,
 ) -> Result<Vec<openapi::models::VolumeSnapshot>, Error> {
     let max_entries = 100;
     let mut starting_token = Some(0);
@@ -101,7 +112,13 @@ async fn get_snapshots(
     while starting_token.is_some() {
         match RestClient::client()
             .snapshots_api()
-            .get_volumes_snapshots(max_entries, snapid.as_ref(), volid.as_ref(), starting_token)
+            .get_volumes_snapshots(
+                max_entries,
+                snapid.as_ref(),
+                volid.as_ref(),
+                starting_token,
+                selector.as_deref(),
+            )
             .await
         {
             Ok(snaps) => {
@@ -37,6 +37,9 @@ async fn setup() {
                 thin: false,
                 affinity_group: None,
                 max_snapshots: None,
+                autogrow: None,
+                auth: None,
+                target_placement: None,
             },
         )
         .await
@@ -76,7 +79,7 @@ async fn get_volumes() {
         .await
         .rest_v00()
         .volumes_api()
-        .get_volumes(0, None, None)
+        .get_volumes(0, None, None, None)
         .await
         .unwrap();
     let volume_state = volumes.entries[0].state.clone();
@@ -110,6 +113,9 @@ async fn get_volumes_paginated() {
                     labels: None,
                     affinity_group: None,
                     max_snapshots: None,
+                    autogrow: None,
+                    auth: None,
+                    target_placement: None,
                 },
             )
             .await
@@ -120,7 +126,7 @@ async fn get_volumes_paginated() {
         .await
         .rest_v00()
         .volumes_api()
-        .get_volumes(0, None, None)
+        .get_volumes(0, None, None, None)
         .await
         .unwrap()
         .entries
@@ -137,7 +143,7 @@ async fn get_volumes_paginated() {
             .await
             .rest_v00()
             .volumes_api()
-            .get_volumes(max_entries, None, starting_token)
+            .get_volumes(max_entries, None, starting_token, None)
             .await
             .unwrap();
         // The number of returned volumes should be equal to the number of specified max entries.
@@ -193,7 +199,7 @@ async fn get_snapshots() {
     let snapshots = test_cluster
         .rest_v00()
         .snapshots_api()
-        .get_volumes_snapshots(0, None, None, Some(0))
+        .get_volumes_snapshots(0, None, None, Some(0), None)
         .await
         .expect("Listing the snapshots failed");
     assert_eq!(snapshots.entries.len(), snap_uuids.len());
@@ -202,7 +208,7 @@ async fn get_snapshots() {
     let snapshots = test_cluster
         .rest_v00()
         .snapshots_api()
-        .get_volumes_snapshots(0, None, Some(&vol.spec.uuid), Some(0))
+        .get_volumes_snapshots(0, None, Some(&vol.spec.uuid), Some(0), None)
         .await
         .expect("Listing the snapshots failed");
     assert_eq!(snapshots.entries.len(), snap_uuids.len());
@@ -212,7 +218,7 @@ async fn get_snapshots() {
     let snapshot = test_cluster
         .rest_v00()
         .snapshots_api()
-        .get_volumes_snapshots(0, Some(&snapid), None, Some(0))
+        .get_volumes_snapshots(0, Some(&snapid), None, Some(0), None)
         .await
         .expect("Listing the snapshots failed");
     assert_eq!(snapshot.entries.len(), 1);
@@ -232,7 +238,7 @@ async fn get_snapshots() {
         let snapshots = test_cluster
             .rest_v00()
             .snapshots_api()
-            .get_volumes_snapshots(max_entries, None, None, starting_token)
+            .get_volumes_snapshots(max_entries, None, None, starting_token, None)
             .await
             .expect("Listing the snapshots failed");
         assert_eq!(snapshots.entries.len(), max_entries as usize);
@@ -248,7 +254,7 @@ async fn get_pools() {
         .await
         .rest_v00()
         .pools_api()
-        .get_pools()
+        .get_pools(None)
         .await
         .unwrap();
     let pool_state = pools[0].state.as_ref().unwrap().clone();
@@ -274,7 +280,7 @@ async fn get_nodes() {
         .await
         .rest_v00()
         .nodes_api()
-        .get_nodes(None)
+        .get_nodes(None, None)
         .await
         .unwrap();
     let node_state = nodes[0].state.as_ref().unwrap().clone();
@@ -10,6 +10,11 @@ pub fn optional_cell<T: ToString>(field: Option<T>) -> String {
         .unwrap_or_else(|| CELL_NO_CONTENT.to_string())
 }
 
+/// The `If-Match` entity tag which only matches the given spec revision, if any.
+pub fn if_match(revision: Option<u64>) -> Option<String> {
+    revision.map(|revision| format!("\"{revision}\""))
+}
+
 // Constants to store the table headers of the Tabular output formats.
 lazy_static! {
     pub static ref VOLUME_HEADERS: Row = row![
@@ -70,6 +75,16 @@ lazy_static! {
         "START-TIME",
         "END-TIME"
     ];
+    pub static ref SWITCHOVER_HISTORY_HEADER: Row = row![
+        "FRONTEND-NODE",
+        "PLANNED",
+        "STATUS",
+        "STAGE",
+        "TARGET-NODE",
+        "START-TIME",
+        "END-TIME",
+        "ERROR"
+    ];
     pub static ref BLOCKDEVICE_HEADERS_ALL: Row = row![
         "DEVNAME",
         "DEVTYPE",
@@ -1,10 +1,10 @@
 use crate::{
-    operations::{Get, ListExt, PluginResult, RebuildHistory, ReplicaTopology, Scale},
+    operations::{Get, ListExt, PluginResult, RebuildHistory, ReplicaTopology, Scale, SwitchOver},
     resources::{
         error::Error,
         utils,
         utils::{optional_cell, CreateRow, CreateRows, GetHeaderRow, OutputFormat},
-        VolumeId,
+        NodeId, VolumeId,
     },
     rest_wrapper::RestClient,
 };
@@ -32,6 +32,10 @@ pub struct VolumesArgs {
     #[clap(long)]
     /// Shows only volumes created from specific source, viz none, snapshot
     source: Option<VolumeSource>,
+    #[clap(long)]
+    /// Shows only volumes whose labels match the Kubernetes-style label selector,
+    /// eg: "tier=gold,zone in (a,b),!legacy"
+    selector: Option<String>,
 }
 
 impl CreateRow for openapi::models::Volume {
@@ -104,7 +108,12 @@ async fn get_paginated_volumes(volume_args: &VolumesArgs) -> Option<Vec<openapi:
     while starting_token.is_some() {
         match RestClient::client()
             .volumes_api()
-            .get_volumes(max_entries, None, starting_token)
+            .get_volumes(
+                max_entries,
+                None,
+                starting_token,
+                volume_args.selector.as_deref(),
+            )
             .await
         {
             Ok(vols) => {
@@ -158,6 +167,20 @@ impl Get for Volume {
                 });
             }
         }
+        if let OutputFormat::None = output {
+            // The switchover history is only available when the HA Cluster Agent is deployed.
+            if let Ok(history) = RestClient::client()
+                .volumes_api()
+                .get_switchover_history(id)
+                .await
+            {
+                let history = history.into_body();
+                if !history.records.is_empty() {
+                    println!();
+                    utils::print_table(output, history);
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -225,6 +248,41 @@ impl Scale for Volume {
     }
 }
 
+#[async_trait(?Send)]
+impl SwitchOver for Volume {
+    type ID = VolumeId;
+    type NodeID = NodeId;
+    async fn switchover(
+        id: &Self::ID,
+        node: &Option<Self::NodeID>,
+        output: &utils::OutputFormat,
+    ) -> PluginResult {
+        match RestClient::client()
+            .volumes_api()
+            .put_volume_target_switchover(id, node.as_deref())
+            .await
+        {
+            Ok(volume) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, volume.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Volume {id} target switchover started successfully 🚀")
+                }
+            },
+            Err(source) => {
+                return Err(Error::SwitchOverVolumeError {
+                    id: id.to_string(),
+                    source,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 #[async_trait(?Send)]
 impl ReplicaTopology for Volume {
     type ID = VolumeId;
@@ -378,6 +436,37 @@ impl CreateRows for openapi::models::RebuildHistory {
     }
 }
 
+impl GetHeaderRow for openapi::models::SwitchOverHistory {
+    fn get_header_row(&self) -> Row {
+        (*utils::SWITCHOVER_HISTORY_HEADER).clone()
+    }
+}
+
+impl CreateRows for openapi::models::SwitchOverHistory {
+    fn create_rows(&self) -> Vec<Row> {
+        self.records
+            .iter()
+            .map(|rec| {
+                row![
+                    rec.frontend_node,
+                    rec.planned,
+                    rec.status,
+                    optional_cell(rec.stages.last().map(|stage| stage.stage.as_str())),
+                    optional_cell(rec.target_node.as_deref()),
+                    format_time(rec.start_time.as_str()),
+                    optional_cell(rec.end_time.as_deref().map(format_time)),
+                    optional_cell(rec.error.as_deref())
+                ]
+            })
+            .collect()
+    }
+}
+
+fn format_time(time: &str) -> String {
+    let time: DateTime<Utc> = DateTime::from_str(time).expect("Cant map time to required format");
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
 fn child_uuid(uri: &str) -> String {
     let Ok(uri) = Url::from_str(uri) else {
         return "".into();
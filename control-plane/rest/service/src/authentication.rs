@@ -1,8 +1,17 @@
 use actix_web::HttpRequest;
-use jsonwebtoken::{crypto, Algorithm, DecodingKey};
+use jsonwebtoken::{
+    jwk::{Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
 
-use http::HeaderValue;
-use std::fs::File;
+use http::{HeaderValue, Method};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant, SystemTime},
+};
 
 use snafu::{ResultExt, Snafu};
 
@@ -16,104 +25,344 @@ pub enum AuthError {
     NoBearerToken {},
     #[snafu(display("Invalid token, cannot be parsed into a string: {}", source.to_string()))]
     InvalidTokenStr { source: http::header::ToStrError },
-    #[snafu(display("Unauthorized token({}) for uri({})", token, uri))]
-    Unauthorized { token: String, uri: String },
     #[snafu(display(
         "Verification process failed, {}. Please check your json web token.",
         source
     ))]
     Verification { source: jsonwebtoken::errors::Error },
-    #[snafu(display("Invalid Bearer Token: {}", details))]
-    InvalidToken { details: String },
+    #[snafu(display("No key matches the token's key id({:?})", kid))]
+    NoMatchingKey { kid: Option<String> },
+    #[snafu(display(
+        "Permission '{}' is required for uri({}), but the token only grants '{}'",
+        required,
+        uri,
+        granted
+    ))]
+    Forbidden {
+        required: Permission,
+        granted: Permission,
+        uri: String,
+    },
+}
+
+/// Permissions which may be granted to a bearer token through its role or scope claims.
+/// Each permission includes all of the lesser ones.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Permission {
+    /// Get any resource.
+    ReadOnly,
+    /// Create and modify resources, eg: cordon, drain and scale.
+    Operator,
+    /// Destroy resources and issue raw json-grpc calls.
+    /// Granted by default to tokens without role or scope claims, so that existing tokens keep
+    /// their access.
+    #[default]
+    Admin,
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(Self::ReadOnly),
+            "operator" => Ok(Self::Operator),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!(
+                "Invalid permission '{s}', expected one of: read-only, operator, admin"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReadOnly => write!(f, "read-only"),
+            Self::Operator => write!(f, "operator"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// Routes which are removed with the DELETE method but which don't destroy any resource.
+const OPERATOR_DELETE_ROUTES: [&str; 8] = [
+    "/cordon/",
+    "/label/",
+    "/target",
+    "/frontend_nodes/",
+    "/share",
+    "/autogrow",
+    "/watches/",
+    "/operations/",
+];
+
+impl Permission {
+    /// Get the permission required to access the given route with the given method.
+    /// The route is the matched pattern, eg: `/v0/nodes/{id}/cordon/{label}`.
+    pub(crate) fn required(method: &Method, route: &str) -> Self {
+        let route = route.strip_prefix("/v0").unwrap_or(route);
+        // Raw json-grpc calls may do anything and the volume auth holds the target secrets.
+        if route.contains("/jsongrpc/") || route == "/volumes/{volume_id}/auth" {
+            return Self::Admin;
+        }
+        match *method {
+            Method::GET | Method::HEAD => Self::ReadOnly,
+            Method::DELETE if !OPERATOR_DELETE_ROUTES.iter().any(|r| route.contains(r)) => {
+                Self::Admin
+            }
+            _ => Self::Operator,
+        }
+    }
+}
+
+/// Claims of the bearer token used to grant permissions.
+/// The registered claims (`exp`, `nbf`, `aud` and `iss`) are validated by `jsonwebtoken`.
+#[derive(serde::Deserialize, Debug)]
+struct Claims {
+    #[serde(default)]
+    role: Option<String>,
+    #[serde(default)]
+    roles: Vec<String>,
+    /// Space separated list of scopes.
+    #[serde(default)]
+    scope: Option<String>,
 }
 
-/// Initialise JWK with the contents of the file at 'jwk_path'.
+impl Claims {
+    /// Get the highest permission granted by the role and scope claims, if any.
+    fn permission(&self) -> Option<Permission> {
+        let scopes = self.scope.iter().flat_map(|scope| scope.split_whitespace());
+        self.role
+            .iter()
+            .map(String::as_str)
+            .chain(self.roles.iter().map(String::as_str))
+            .chain(scopes)
+            .filter_map(|claim| Permission::from_str(claim).ok())
+            .max()
+    }
+}
+
+/// How often the key file is checked for changes.
+const KEY_FILE_CHECK_PERIOD: Duration = Duration::from_secs(10);
+
+/// Initialise the JWK Set with the contents of the file at 'jwk_path'.
+/// The file may hold a single JWK or a JWK Set, and it is reloaded when it changes on disk.
 /// If jwk_path is 'None', authentication is disabled.
 pub fn init(jwk_path: Option<String>) -> JsonWebKey {
     match jwk_path {
-        Some(path) => {
-            let jwk_file = File::open(path).expect("Failed to open JWK file");
-            JsonWebKey::from(Some(jwk_file))
+        Some(path) => JsonWebKey::from(PathBuf::from(path)),
+        None => JsonWebKey::default(),
+    }
+}
+
+/// Contents of the key file.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum KeyFileContents {
+    /// A JWK Set, allowing for multiple keys which are chosen by the token's `kid`.
+    Set(JwkSet),
+    /// A single JWK.
+    Key(Jwk),
+}
+
+impl From<KeyFileContents> for JwkSet {
+    fn from(contents: KeyFileContents) -> Self {
+        match contents {
+            KeyFileContents::Set(set) => set,
+            KeyFileContents::Key(key) => JwkSet { keys: vec![key] },
         }
-        None => JsonWebKey::from(None),
     }
 }
 
-#[derive(serde::Deserialize, Default, Debug)]
-pub struct JsonWebKey {
-    #[serde(skip_deserializing)]
-    enabled: bool,
-    #[serde(alias = "alg")]
-    algorithm: Algorithm,
-    #[serde(alias = "n")]
-    modulus: String,
-    #[serde(alias = "e")]
-    exponent: String,
+/// The keys loaded from the key file.
+#[derive(Debug)]
+struct KeyFile {
+    path: PathBuf,
+    keys: JwkSet,
+    /// Modification time and length of the file when it was last loaded.
+    version: Option<(SystemTime, u64)>,
+    checked: Instant,
 }
 
-impl JsonWebKey {
-    /// Validates and returns new JsonWebKey
-    pub(crate) fn from(jwk_file: Option<File>) -> Self {
-        match jwk_file {
-            Some(jwk_file) => {
-                let mut jwk: Self = match serde_json::from_reader(jwk_file) {
-                    Ok(jwk) => jwk,
-                    Err(e) => panic!("Failed to deserialize the jwk: {}", e),
-                };
-                jwk.enabled = true;
-                jwk
+impl KeyFile {
+    /// Load the keys from the file at the given path.
+    fn load(path: PathBuf) -> Result<Self, String> {
+        let version = Self::version_of(&path);
+        let file =
+            File::open(&path).map_err(|error| format!("Failed to open the JWK file: {error}"))?;
+        let contents: KeyFileContents = serde_json::from_reader(file)
+            .map_err(|error| format!("Failed to deserialize the jwk: {error}"))?;
+        Ok(Self {
+            path,
+            keys: contents.into(),
+            version,
+            checked: Instant::now(),
+        })
+    }
+    fn version_of(path: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+    /// Reload the keys if the file has changed since it was last loaded.
+    /// Unless forced, the file is checked at most once per `KEY_FILE_CHECK_PERIOD`.
+    /// If the file can't be loaded the current keys are kept.
+    fn refresh(&mut self, force: bool) {
+        if !force && self.checked.elapsed() < KEY_FILE_CHECK_PERIOD {
+            return;
+        }
+        self.checked = Instant::now();
+        let version = Self::version_of(&self.path);
+        if version == self.version {
+            return;
+        }
+        match Self::load(self.path.clone()) {
+            Ok(key_file) => {
+                tracing::info!(
+                    path = %self.path.display(),
+                    keys = key_file.keys.keys.len(),
+                    "Reloaded the JWK file"
+                );
+                *self = key_file;
+            }
+            Err(error) => {
+                tracing::error!(
+                    path = %self.path.display(),
+                    error,
+                    "Failed to reload the JWK file, keeping the current keys"
+                );
+                // Don't retry until the file changes again.
+                self.version = version;
             }
-            None => Self::default(),
         }
     }
+    /// Check if a key has the given key id.
+    fn has_key(&self, kid: &str) -> bool {
+        self.keys
+            .keys
+            .iter()
+            .any(|key| key.common.key_id.as_deref() == Some(kid))
+    }
+    /// Get the keys which may have signed a token with the given key id.
+    /// Tokens without a key id may have been signed by any key, and keys without a key id may
+    /// have signed any token.
+    fn keys(&self, kid: Option<&str>) -> Vec<Jwk> {
+        self.keys
+            .keys
+            .iter()
+            .filter(|key| match (kid, key.common.key_id.as_deref()) {
+                (Some(kid), Some(key_id)) => kid == key_id,
+                _ => true,
+            })
+            .cloned()
+            .collect()
+    }
+}
 
-    /// Validate a bearer token
-    pub(crate) fn validate(&self, token: &str, uri: &str) -> Result<(), AuthError> {
-        let (message, signature) = split_token(token)?;
-        match crypto::verify(
-            &signature,
-            message.as_bytes(),
-            &self.decoding_key()?,
-            self.algorithm(),
-        ) {
-            Ok(true) => Ok(()),
-            Ok(false) => Err(AuthError::Unauthorized {
-                token: token.to_string(),
-                uri: uri.to_string(),
-            }),
-            Err(source) => Err(AuthError::Verification { source }),
+#[derive(Default, Debug, Clone)]
+pub struct JsonWebKey {
+    key_file: Option<Arc<Mutex<KeyFile>>>,
+    audience: Vec<String>,
+    issuer: Vec<String>,
+    default_permission: Permission,
+}
+
+impl From<PathBuf> for JsonWebKey {
+    /// Loads the keys from the key file and returns new JsonWebKey.
+    fn from(path: PathBuf) -> Self {
+        let key_file = match KeyFile::load(path) {
+            Ok(key_file) => key_file,
+            Err(error) => panic!("{error}"),
+        };
+        Self {
+            key_file: Some(Arc::new(Mutex::new(key_file))),
+            ..Default::default()
         }
     }
+}
 
-    // Returns true if REST calls should be authenticated.
-    fn auth_enabled(&self) -> bool {
-        self.enabled
+impl JsonWebKey {
+    /// Only accept tokens whose `aud` claim contains one of the given audiences.
+    pub(crate) fn with_audience(mut self, audience: Vec<String>) -> Self {
+        self.audience = audience;
+        self
+    }
+    /// Only accept tokens whose `iss` claim is one of the given issuers.
+    pub(crate) fn with_issuer(mut self, issuer: Vec<String>) -> Self {
+        self.issuer = issuer;
+        self
+    }
+    /// Grant the given permission to tokens which have no role or scope claims.
+    pub(crate) fn with_default_permission(mut self, permission: Permission) -> Self {
+        self.default_permission = permission;
+        self
     }
 
-    // Return the algorithm.
-    fn algorithm(&self) -> Algorithm {
-        self.algorithm
+    /// Validate a bearer token, returning the permission which it grants.
+    /// During a key rotation both the old and the new keys are in the key set, so that tokens
+    /// signed by either of them are valid.
+    pub(crate) fn validate(&self, token: &str) -> Result<Permission, AuthError> {
+        let header = jsonwebtoken::decode_header(token).context(Verification)?;
+        let mut error = None;
+        for key in self.keys(header.kid.as_deref()) {
+            let algorithm = key.common.algorithm.unwrap_or(header.alg);
+            let result = DecodingKey::from_jwk(&key).and_then(|decoding_key| {
+                jsonwebtoken::decode::<Claims>(token, &decoding_key, &self.validation(algorithm))
+            });
+            match result {
+                Ok(token) => {
+                    return Ok(token.claims.permission().unwrap_or(self.default_permission))
+                }
+                Err(source) => error = Some(source),
+            }
+        }
+        match error {
+            Some(source) => Err(AuthError::Verification { source }),
+            None => Err(AuthError::NoMatchingKey { kid: header.kid }),
+        }
     }
 
-    // Return the modulus.
-    fn modulus(&self) -> &str {
-        &self.modulus
+    // Returns true if REST calls should be authenticated.
+    fn auth_enabled(&self) -> bool {
+        self.key_file.is_some()
     }
 
-    // Return the exponent.
-    fn exponent(&self) -> &str {
-        &self.exponent
+    // Return the keys which may have signed a token with the given key id.
+    // If no key has the key id, the key file is reloaded right away, as the token may have been
+    // signed by a key which was just rotated in.
+    fn keys(&self, kid: Option<&str>) -> Vec<Jwk> {
+        let Some(key_file) = &self.key_file else {
+            return vec![];
+        };
+        let mut key_file = key_file.lock().unwrap_or_else(PoisonError::into_inner);
+        key_file.refresh(false);
+        if matches!(kid, Some(kid) if !key_file.has_key(kid)) {
+            key_file.refresh(true);
+        }
+        key_file.keys(kid)
     }
 
-    // Return the decoding key
-    fn decoding_key(&self) -> Result<DecodingKey, AuthError> {
-        DecodingKey::from_rsa_components(self.modulus(), self.exponent())
-            .map_err(|e| AuthError::Verification { source: e })
+    // Return the validation of the token signature and registered claims.
+    fn validation(&self, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        validation.validate_nbf = true;
+        let mut required = vec!["exp"];
+        if !self.audience.is_empty() {
+            validation.set_audience(&self.audience);
+            required.push("aud");
+        }
+        if !self.issuer.is_empty() {
+            validation.set_issuer(&self.issuer);
+            required.push("iss");
+        }
+        validation.set_required_spec_claims(&required);
+        validation
     }
 }
 
 /// Authenticate the HTTP request by checking the authorisation token to ensure
-/// the sender is who they claim to be.
+/// the sender is who they claim to be, and authorize it by checking that the token grants
+/// the permission required by the route.
 pub fn authenticate(req: &HttpRequest) -> Result<(), AuthError> {
     let jwk: &JsonWebKey = match req.app_data() {
         Some(jwk) => Ok(jwk),
@@ -127,10 +376,22 @@ pub fn authenticate(req: &HttpRequest) -> Result<(), AuthError> {
         return Ok(());
     }
 
-    match req.headers().get(http::header::AUTHORIZATION) {
-        Some(token) => jwk.validate(&format_token(token)?, &req.uri().to_string()),
+    let granted = match req.headers().get(http::header::AUTHORIZATION) {
+        Some(token) => jwk.validate(&format_token(token)?),
         None => Err(AuthError::NoBearerToken {}),
+    }?;
+    let route = req
+        .match_pattern()
+        .unwrap_or_else(|| req.path().to_string());
+    let required = Permission::required(req.method(), &route);
+    if granted < required {
+        return Err(AuthError::Forbidden {
+            required,
+            granted,
+            uri: req.uri().to_string(),
+        });
     }
+    Ok(())
 }
 
 // Ensure the token is formatted correctly by removing the "Bearer " prefix if
@@ -143,27 +404,6 @@ fn format_token(token: &HeaderValue) -> Result<String, AuthError> {
     Ok(token.trim().into())
 }
 
-// Split the JSON Web Token (JWT) into 2 parts, message and signature.
-// The message comprises the header and payload.
-//
-// JWT format:
-//      <header>.<payload>.<signature>
-//      \______  ________/
-//             \/
-//           message
-fn split_token(token: &str) -> Result<(String, String), AuthError> {
-    let elems = token.split('.').collect::<Vec<&str>>();
-    if elems.len() == 3 {
-        let message = format!("{}.{}", elems[0], elems[1]);
-        let signature = elems[2];
-        Ok((message, signature.into()))
-    } else {
-        Err(AuthError::InvalidToken {
-            details: "Should be formatted as: header.payload.signature".to_string(),
-        })
-    }
-}
-
 #[test]
 fn validate_test() {
     let token_file = std::env::current_dir()
@@ -177,9 +417,160 @@ fn validate_test() {
         .join("jwk");
     let jwk = init(Some(jwk_file.to_str().unwrap().into()));
 
-    jwk.validate(&token, "uri").expect("Validation should pass");
+    // The token carries no role claims.
+    let permission = jwk.validate(&token).expect("Validation should pass");
+    assert_eq!(permission, Permission::Admin);
+    let operator_jwk =
+        init(Some(jwk_file.to_str().unwrap().into())).with_default_permission(Permission::Operator);
+    let permission = operator_jwk
+        .validate(&token)
+        .expect("Validation should pass");
+    assert_eq!(permission, Permission::Operator);
+    // The token has no audience.
+    let audience_jwk =
+        init(Some(jwk_file.to_str().unwrap().into())).with_audience(vec!["mayastor".to_string()]);
+    audience_jwk
+        .validate(&token)
+        .expect_err("Validation should fail without the audience");
     // create invalid token
     token.push_str("invalid");
-    jwk.validate(&token, "uri")
+    jwk.validate(&token)
         .expect_err("Validation should fail with an invalid token");
 }
+
+#[test]
+fn claims_test() {
+    let sign = |claims: serde_json::Value| {
+        let key = std::env::current_dir()
+            .expect("Failed to get current directory")
+            .join("authentication")
+            .join("id_rsa");
+        let key = std::fs::read(key).expect("Failed to get the private key");
+        jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(Algorithm::RS256),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_rsa_pem(&key).unwrap(),
+        )
+        .unwrap()
+    };
+    let jwk_file = std::env::current_dir()
+        .expect("Failed to get current directory")
+        .join("authentication")
+        .join("jwk");
+    let jwk = init(Some(jwk_file.to_str().unwrap().into()))
+        .with_audience(vec!["mayastor".to_string()])
+        .with_issuer(vec!["issuer".to_string()]);
+    let exp = 10000000000u64;
+
+    let token = sign(serde_json::json!({
+        "exp": exp, "aud": "mayastor", "iss": "issuer", "roles": ["read-only", "operator"]
+    }));
+    assert_eq!(jwk.validate(&token).unwrap(), Permission::Operator);
+    let token = sign(serde_json::json!({
+        "exp": exp, "aud": "mayastor", "iss": "issuer", "scope": "openid admin"
+    }));
+    assert_eq!(jwk.validate(&token).unwrap(), Permission::Admin);
+
+    let expired = sign(serde_json::json!({
+        "exp": 1000, "aud": "mayastor", "iss": "issuer", "role": "admin"
+    }));
+    jwk.validate(&expired).expect_err("Token has expired");
+    let immature = sign(serde_json::json!({
+        "exp": exp, "nbf": exp - 1, "aud": "mayastor", "iss": "issuer", "role": "admin"
+    }));
+    jwk.validate(&immature).expect_err("Token is not valid yet");
+    let wrong_aud = sign(serde_json::json!({
+        "exp": exp, "aud": "other", "iss": "issuer", "role": "admin"
+    }));
+    jwk.validate(&wrong_aud)
+        .expect_err("Token has the wrong audience");
+    let wrong_iss = sign(serde_json::json!({
+        "exp": exp, "aud": "mayastor", "iss": "other", "role": "admin"
+    }));
+    jwk.validate(&wrong_iss)
+        .expect_err("Token has the wrong issuer");
+}
+
+#[test]
+fn key_rotation_test() {
+    let authentication = std::env::current_dir()
+        .expect("Failed to get current directory")
+        .join("authentication");
+    let sign = |algorithm: Algorithm, kid: Option<&str>| {
+        let mut header = jsonwebtoken::Header::new(algorithm);
+        header.kid = kid.map(ToString::to_string);
+        let key = match algorithm {
+            Algorithm::ES256 => {
+                let key = std::fs::read(authentication.join("id_ec")).unwrap();
+                jsonwebtoken::EncodingKey::from_ec_pem(&key).unwrap()
+            }
+            _ => {
+                let key = std::fs::read(authentication.join("id_rsa")).unwrap();
+                jsonwebtoken::EncodingKey::from_rsa_pem(&key).unwrap()
+            }
+        };
+        let claims = serde_json::json!({ "exp": 10000000000u64, "role": "admin" });
+        jsonwebtoken::encode(&header, &claims, &key).unwrap()
+    };
+    let rsa_token = sign(Algorithm::RS256, Some("rsa-1"));
+    let ec_token = sign(Algorithm::ES256, Some("ec-1"));
+    let legacy_token = std::fs::read_to_string(authentication.join("token")).unwrap();
+
+    // Start with the old RSA key only.
+    let jwk_path = std::env::temp_dir().join(format!("jwks-{}", std::process::id()));
+    std::fs::copy(authentication.join("jwk"), &jwk_path).unwrap();
+    let jwk = init(Some(jwk_path.to_str().unwrap().into()));
+    jwk.validate(&rsa_token).expect("The key has no kid");
+    jwk.validate(&legacy_token).expect("The token has no kid");
+    jwk.validate(&ec_token)
+        .expect_err("The EC key has not been rotated in yet");
+
+    // Rotate in the EC key, keeping the RSA key around for the old tokens.
+    std::fs::copy(authentication.join("jwks"), &jwk_path).unwrap();
+    jwk.validate(&ec_token)
+        .expect("The EC key has been rotated in");
+    jwk.validate(&rsa_token)
+        .expect("The RSA key is still in the set");
+    jwk.validate(&legacy_token).expect("The token has no kid");
+    jwk.validate(&sign(Algorithm::RS256, Some("rsa-2")))
+        .expect_err("No such key");
+
+    std::fs::remove_file(jwk_path).ok();
+}
+
+#[test]
+fn required_permission_test() {
+    let required = Permission::required;
+    assert_eq!(required(&Method::GET, "/v0/volumes"), Permission::ReadOnly);
+    assert_eq!(
+        required(&Method::GET, "/v0/volumes/{volume_id}/auth"),
+        Permission::Admin
+    );
+    assert_eq!(
+        required(&Method::PUT, "/v0/nodes/{id}/cordon/{label}"),
+        Permission::Operator
+    );
+    assert_eq!(
+        required(&Method::DELETE, "/v0/nodes/{id}/cordon/{label}"),
+        Permission::Operator
+    );
+    assert_eq!(
+        required(
+            &Method::PUT,
+            "/v0/volumes/{volume_id}/replica_count/{replica_count}"
+        ),
+        Permission::Operator
+    );
+    assert_eq!(
+        required(&Method::DELETE, "/v0/volumes/{volume_id}"),
+        Permission::Admin
+    );
+    assert_eq!(
+        required(&Method::PUT, "/v0/nodes/{node}/jsongrpc/{method}"),
+        Permission::Admin
+    );
+    assert_eq!(
+        required(&Method::DELETE, "/v0/operations/{operation_id}"),
+        Permission::Operator
+    );
+}
@@ -1,7 +1,7 @@
 mod authentication;
 mod v0;
 
-use crate::v0::{CORE_CLIENT, JSON_GRPC_CLIENT};
+use crate::v0::{CORE_CLIENT, HA_CLUSTER_CLIENT, JSON_GRPC_CLIENT};
 use actix_service::ServiceFactory;
 use actix_web::{
     body::MessageBody,
@@ -32,6 +32,10 @@ pub(crate) struct CliArgs {
     #[clap(long, short = 'J')]
     json_grpc: Option<Uri>,
 
+    /// The HA Cluster Agent gRPC Server URL or address to connect to the service.
+    #[clap(long)]
+    ha_cluster_grpc: Option<Uri>,
+
     /// Path to the certificate file
     #[clap(long, short, required_unless_present = "dummy_certificates")]
     cert_file: Option<String>,
@@ -47,7 +51,8 @@ pub(crate) struct CliArgs {
     #[clap(long, short)]
     jaeger: Option<String>,
 
-    /// Path to JSON Web KEY file used for authenticating REST requests
+    /// Path to JSON Web KEY file used for authenticating REST requests.
+    /// The file may hold a single JWK or a JWK Set, and it's reloaded when it changes.
     #[clap(long, required_unless_present = "no_auth")]
     jwk: Option<String>,
 
@@ -55,6 +60,21 @@ pub(crate) struct CliArgs {
     #[clap(long, required_unless_present = "jwk")]
     no_auth: bool,
 
+    /// Accepted audience of the JSON Web Tokens, matched against their `aud` claim.
+    /// If not set, the audience is not validated.
+    #[clap(long, value_delimiter = ',')]
+    jwt_audience: Vec<String>,
+
+    /// Accepted issuer of the JSON Web Tokens, matched against their `iss` claim.
+    /// If not set, the issuer is not validated.
+    #[clap(long, value_delimiter = ',')]
+    jwt_issuer: Vec<String>,
+
+    /// Permission granted to JSON Web Tokens which have no role or scope claims.
+    /// One of: read-only, operator, admin.
+    #[clap(long, default_value = "admin")]
+    jwt_default_permission: authentication::Permission,
+
     /// The default timeout for backend requests issued by the REST Server
     #[clap(long, short, default_value = utils::DEFAULT_REQ_TIMEOUT)]
     request_timeout: humantime::Duration,
@@ -102,7 +122,10 @@ fn timeout_opts() -> TimeoutOptions {
 
 use actix_web_opentelemetry::RequestTracing;
 use clap::Parser;
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient},
+};
 use http::Uri;
 use stor_port::transport_api::{RequestMinTimeout, TimeoutOptions};
 use utils::tracing_telemetry::KeyValue;
@@ -211,11 +234,16 @@ async fn main() -> anyhow::Result<()> {
         cli_args.jaeger.clone(),
     );
 
+    // The keys are shared by all workers, so that they're reloaded only once on rotation.
+    let jwk = authentication::init(get_jwk_path())
+        .with_audience(CliArgs::args().jwt_audience)
+        .with_issuer(CliArgs::args().jwt_issuer)
+        .with_default_permission(CliArgs::args().jwt_default_permission);
     let app = move || {
         App::new()
             .wrap(RequestTracing::new())
             .wrap(middleware::Logger::default())
-            .app_data(authentication::init(get_jwk_path()))
+            .app_data(jwk.clone())
             .configure_api(&v0::configure_api)
     };
 
@@ -233,6 +261,14 @@ async fn main() -> anyhow::Result<()> {
             .expect("Expect to be initialised only once");
     }
 
+    // Initialise the ha cluster agent client to be used in rest
+    if let Some(ha_cluster_grpc) = CliArgs::args().ha_cluster_grpc {
+        HA_CLUSTER_CLIENT
+            .set(ClusterAgentClient::new(ha_cluster_grpc, timeout_opts()).await)
+            .ok()
+            .expect("Expect to be initialised only once");
+    }
+
     let server =
         HttpServer::new(app).bind_rustls_021(CliArgs::args().https, get_certificates()?)?;
     let result = if let Some(http) = CliArgs::args().http {
@@ -0,0 +1,121 @@
+use super::*;
+use actix_web::{http::header, web::Bytes, HttpResponse};
+use futures::{future::Either, StreamExt};
+use grpc::operations::watch::traits::WatchOperations;
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use stor_port::types::v0::transport::{EventResource, ResourceEvent, WatchEvents, WatchType};
+
+/// Period at which a comment is sent to keep idle event streams alive.
+const KEEP_ALIVE_PERIOD: Duration = Duration::from_secs(15);
+
+fn client() -> impl WatchOperations {
+    core_grpc().watch()
+}
+
+// todo: the openapi spec cannot describe a server-sent event stream, so it's added by hand
+pub(super) fn configure(cfg: &mut actix_web::web::ServiceConfig) {
+    cfg.service(
+        actix_web::web::resource("/events")
+            .name("get_events")
+            .guard(actix_web::guard::Get())
+            .route(actix_web::web::get().to(get_events)),
+    );
+}
+
+/// Filter of the events stream.
+#[derive(Deserialize, Debug, Default)]
+struct EventsQuery {
+    /// Comma separated types of resources, eg: `volumes,pools`.
+    resources: Option<String>,
+    /// Id of the resource.
+    id: Option<String>,
+    /// Comma separated labels which the resource must have, eg: `zone=a,tier=gold`.
+    labels: Option<String>,
+    /// Either `spec` or `state` events, or both if unspecified.
+    #[serde(rename = "type")]
+    type_: Option<String>,
+}
+
+impl TryFrom<EventsQuery> for WatchEvents {
+    type Error = String;
+
+    fn try_from(query: EventsQuery) -> Result<Self, Self::Error> {
+        let resources = match query.resources {
+            Some(resources) => resources
+                .split(',')
+                .map(EventResource::from_str)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+        let labels = match query.labels {
+            Some(labels) => labels
+                .split(',')
+                .map(|label| match label.split_once('=') {
+                    Some((key, value)) => Ok((key.to_string(), value.to_string())),
+                    None => Err(format!("Label '{label}' is not in the key=value format")),
+                })
+                .collect::<Result<HashMap<_, _>, _>>()?,
+            None => HashMap::new(),
+        };
+        let watch_type = match query.type_.as_deref() {
+            None => WatchType::All,
+            Some("spec") => WatchType::Desired,
+            Some("state") => WatchType::Actual,
+            Some(other) => return Err(format!("Unknown event type '{other}'")),
+        };
+        Ok(Self {
+            resources,
+            id: query.id,
+            labels,
+            watch_type,
+        })
+    }
+}
+
+/// Format the `event` as a server-sent event.
+fn sse_event(event: &ResourceEvent) -> Bytes {
+    let name = match event.watch_type {
+        WatchType::Actual => "state",
+        _ => "spec",
+    };
+    let data = serde_json::to_string(event).unwrap_or_default();
+    Bytes::from(format!("event: {name}\ndata: {data}\n\n"))
+}
+
+async fn get_events(
+    _token: BearerToken,
+    actix_web::web::Query(query): actix_web::web::Query<EventsQuery>,
+) -> Result<HttpResponse, RestError<RestJsonError>> {
+    let filter = WatchEvents::try_from(query).map_err(|error| {
+        RestError::from(ReplyError::invalid_argument(
+            ResourceKind::Watch,
+            "events",
+            error,
+        ))
+    })?;
+    let events = client()
+        .events(&filter, None)
+        .await?
+        .map(|event| match event {
+            Ok(event) => sse_event(&event),
+            Err(error) => Bytes::from(format!("event: error\ndata: {error}\n\n")),
+        });
+    let interval = actix_web::rt::time::interval(KEEP_ALIVE_PERIOD);
+    // interleave the events with keep-alive comments, until the events stream ends
+    let stream = futures::stream::unfold(
+        (events, interval),
+        |(mut events, mut interval)| async move {
+            let next = futures::future::select(events.next(), Box::pin(interval.tick())).await;
+            let chunk = match next {
+                Either::Left((event, _)) => event,
+                Either::Right(_) => Some(Bytes::from_static(b": keep-alive\n\n")),
+            };
+            chunk.map(|chunk| (Ok::<_, actix_web::Error>(chunk), (events, interval)))
+        },
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream))
+}
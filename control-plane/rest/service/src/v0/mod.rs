@@ -5,19 +5,23 @@
 pub mod app_node;
 pub mod block_devices;
 pub mod children;
+pub mod events;
 pub mod jsongrpc;
 pub mod nexuses;
 pub mod nodes;
+pub mod operations;
 pub mod pools;
+pub mod preconditions;
 pub mod replicas;
 pub mod snapshots;
 pub mod specs;
 pub mod states;
 pub mod swagger_ui;
+pub mod usage;
 pub mod volumes;
 pub mod watches;
 
-use crate::authentication::authenticate;
+use crate::authentication::{authenticate, AuthError};
 use actix_service::ServiceFactory;
 use actix_web::{
     body::MessageBody,
@@ -25,10 +29,15 @@ use actix_web::{
     web, FromRequest, HttpRequest,
 };
 use futures::future::Ready;
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient},
+};
 use once_cell::sync::OnceCell;
 use rest_client::versions::v0::*;
 use serde::Deserialize;
+use std::str::FromStr;
+use stor_port::types::v0::transport::LabelSelector;
 pub use stor_port::{
     types::v0::openapi::{
         apis::actix_server::{Body, Path, Query, RestError},
@@ -42,6 +51,8 @@ use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 pub static CORE_CLIENT: OnceCell<CoreClient> = OnceCell::new();
 /// Once cell static variable to store the json grpc client and initialise once at startup
 pub static JSON_GRPC_CLIENT: OnceCell<JsonGrpcClient> = OnceCell::new();
+/// Once cell static variable to store the ha cluster agent client and initialise once at startup
+pub static HA_CLUSTER_CLIENT: OnceCell<ClusterAgentClient> = OnceCell::new();
 
 /// Get Core gRPC Client
 pub(crate) fn core_grpc<'a>() -> &'a CoreClient {
@@ -63,6 +74,41 @@ pub(crate) fn json_grpc<'a>() -> Result<&'a JsonGrpcClient, ReplyError> {
     }
 }
 
+/// Get HA Cluster Agent gRPC Client
+pub(crate) fn ha_cluster_grpc<'a>() -> Result<&'a ClusterAgentClient, ReplyError> {
+    match HA_CLUSTER_CLIENT.get() {
+        None => Err(ReplyError {
+            kind: ReplyErrorKind::Unavailable,
+            resource: ResourceKind::Volume,
+            source: "HA Cluster Agent is not configured/running.".to_string(),
+            extra: "".to_string(),
+        }),
+        Some(client) => Ok(client),
+    }
+}
+
+/// Parse the `label_selector` query parameter of a request for the resources of the given `kind`.
+fn label_selector(
+    selector: Option<String>,
+    kind: ResourceKind,
+) -> Result<Option<LabelSelector>, ReplyError> {
+    selector
+        .map(|selector| {
+            LabelSelector::from_str(&selector)
+                .map_err(|error| ReplyError::invalid_argument(kind, "label_selector", error))
+        })
+        .transpose()
+}
+
+/// Error for a request which filters by both the id and the labels of the resources.
+fn id_and_label_selector(kind: ResourceKind) -> ReplyError {
+    ReplyError::invalid_argument(
+        kind,
+        "label_selector",
+        "the resources cannot be filtered by both their id and labels",
+    )
+}
+
 fn version() -> String {
     "v0".into()
 }
@@ -76,6 +122,7 @@ fn configure(cfg: &mut actix_web::web::ServiceConfig) {
     apis::actix_server::configure::<RestApi, BearerToken>(cfg);
     // todo: remove when the /states is added to the spec
     states::configure(cfg);
+    events::configure(cfg);
 }
 
 fn json_error(err: impl std::fmt::Display, _req: &actix_web::HttpRequest) -> actix_web::Error {
@@ -106,6 +153,8 @@ where
             .app_data(web::PathConfig::default().error_handler(json_error))
             .app_data(web::JsonConfig::default().error_handler(json_error))
             .app_data(web::QueryConfig::default().error_handler(json_error))
+            .wrap_fn(preconditions::scope)
+            .wrap_fn(operations::respond_async)
             .configure(configure),
     )
 }
@@ -119,12 +168,23 @@ impl FromRequest for BearerToken {
 
     fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
         futures::future::ready(authenticate(req).map(|_| Self {}).map_err(|auth_error| {
-            RestError::from(ReplyError {
-                kind: ReplyErrorKind::Unauthorized,
-                resource: ResourceKind::Unknown,
-                source: req.uri().to_string(),
-                extra: auth_error.to_string(),
-            })
+            match auth_error {
+                // The token is valid but it doesn't grant the permission required by the route.
+                AuthError::Forbidden { .. } => RestError::new(
+                    apis::StatusCode::FORBIDDEN,
+                    RestJsonError::new(
+                        auth_error.to_string(),
+                        req.uri().to_string(),
+                        RestJsonErrorKind::PermissionDenied,
+                    ),
+                ),
+                _ => RestError::from(ReplyError {
+                    kind: ReplyErrorKind::Unauthorized,
+                    resource: ResourceKind::Unknown,
+                    source: req.uri().to_string(),
+                    extra: auth_error.to_string(),
+                }),
+            }
         }))
     }
 }
@@ -16,20 +16,36 @@ impl apis::actix_server::Nodes for RestApi {
                 .into_inner()
                 .get(0),
         )?;
+        set_etag(&node);
         Ok(node.into())
     }
 
+    async fn del_node(
+        Path(id): Path<String>,
+        Query(force): Query<Option<bool>>,
+    ) -> Result<(), RestError<RestJsonError>> {
+        let force = force.unwrap_or(false);
+        client().decommission(id.into(), force).await?;
+        Ok(())
+    }
+
     async fn get_nodes(
-        Query(node_id): Query<Option<String>>,
+        Query((node_id, label_selector)): Query<(Option<String>, Option<String>)>,
     ) -> Result<Vec<models::Node>, RestError<RestJsonError>> {
-        match node_id {
-            Some(node_id) => {
+        let label_selector = super::label_selector(label_selector, ResourceKind::Node)?;
+        match (node_id, label_selector) {
+            (Some(_), Some(_)) => Err(id_and_label_selector(ResourceKind::Node).into()),
+            (Some(node_id), None) => {
                 let nodes = client()
                     .get(Filter::Node(node_id.into()), true, None)
                     .await?;
                 Ok(nodes.into_inner().into_vec())
             }
-            None => {
+            (None, Some(selector)) => {
+                let nodes = client().get(Filter::Labels(selector), false, None).await?;
+                Ok(nodes.into_inner().into_vec())
+            }
+            (None, None) => {
                 let nodes = client().get(Filter::None, false, None).await?;
                 Ok(nodes.into_inner().into_vec())
             }
@@ -38,22 +54,35 @@ impl apis::actix_server::Nodes for RestApi {
 
     async fn put_node_cordon(
         Path((id, label)): Path<(String, String)>,
+        Query(ttl): Query<Option<String>>,
     ) -> Result<models::Node, RestError<RestJsonError>> {
-        let node = client().cordon(id.into(), label).await?;
+        let ttl = label_ttl(ttl)?;
+        let revision = preconditions::if_match(ResourceKind::Node)?;
+        let node = client().cordon(id.into(), label, ttl, revision).await?;
+        set_etag(&node);
         Ok(node.into())
     }
 
     async fn delete_node_cordon(
         Path((id, label)): Path<(String, String)>,
     ) -> Result<models::Node, RestError<RestJsonError>> {
-        let node = client().uncordon(id.into(), label).await?;
+        let revision = preconditions::if_match(ResourceKind::Node)?;
+        let node = client().uncordon(id.into(), label, revision).await?;
+        set_etag(&node);
         Ok(node.into())
     }
 
     async fn put_node_drain(
         Path((id, label)): Path<(String, String)>,
+        Query((evacuate, ttl)): Query<(Option<bool>, Option<String>)>,
     ) -> Result<models::Node, RestError<RestJsonError>> {
-        let node = client().drain(id.into(), label).await?;
+        let evacuate = evacuate.unwrap_or(false);
+        let ttl = label_ttl(ttl)?;
+        let revision = preconditions::if_match(ResourceKind::Node)?;
+        let node = client()
+            .drain(id.into(), label, evacuate, ttl, revision)
+            .await?;
+        set_etag(&node);
         Ok(node.into())
     }
 
@@ -62,20 +91,31 @@ impl apis::actix_server::Nodes for RestApi {
         Query(overwrite): Query<Option<bool>>,
     ) -> Result<models::Node, RestError<RestJsonError>> {
         let overwrite = overwrite.unwrap_or(false);
+        let revision = preconditions::if_match(ResourceKind::Node)?;
         let node = client()
-            .label(id.into(), [(key, value)].into(), overwrite)
+            .label(id.into(), [(key, value)].into(), overwrite, revision)
             .await?;
+        set_etag(&node);
         Ok(node.into())
     }
 
     async fn delete_node_label(
         Path((id, label_key)): Path<(String, String)>,
     ) -> Result<models::Node, RestError<RestJsonError>> {
-        let node = client().unlabel(id.into(), label_key).await?;
+        let revision = preconditions::if_match(ResourceKind::Node)?;
+        let node = client().unlabel(id.into(), label_key, revision).await?;
+        set_etag(&node);
         Ok(node.into())
     }
 }
 
+/// Set the spec revision of the node as the `ETag` of the response.
+fn set_etag(node: &Node) {
+    if let Some(spec) = node.spec() {
+        preconditions::set_etag(spec.revision());
+    }
+}
+
 /// returns node from node option and returns an error on non existence
 fn node(node_id: String, node: Option<&Node>) -> Result<Node, ReplyError> {
     match node {
@@ -88,3 +128,13 @@ fn node(node_id: String, node: Option<&Node>) -> Result<Node, ReplyError> {
         }),
     }
 }
+
+/// Parse the optional TTL of a cordon or drain label, eg: 30m or 2h.
+fn label_ttl(ttl: Option<String>) -> Result<Option<std::time::Duration>, ReplyError> {
+    ttl.map(|ttl| {
+        humantime::parse_duration(&ttl).map_err(|error| {
+            ReplyError::invalid_argument(ResourceKind::Node, "ttl", error.to_string())
+        })
+    })
+    .transpose()
+}
@@ -0,0 +1,386 @@
+//! Long-running operations which the client doesn't have to wait for.
+//! A mutating request with the `Prefer: respond-async` header is accepted straight away with a
+//! `202 Accepted` response, whilst the request carries on in the background. Its progress and
+//! outcome may then be polled from the operation's `Location`, ie: `/v0/operations/{id}`.
+//! The operations are recorded through the core agent, which persists them in the store so that
+//! they can be polled from any REST server and expires them a while after they've finished.
+//! The REST server running an operation refreshes its heartbeat, so that the core agent can tell
+//! when it's been abandoned, and stops running it once it's cancelled.
+
+use super::*;
+use actix_web::{
+    body,
+    dev::Service,
+    http::{header, Method},
+    HttpResponse,
+};
+use futures::future::LocalBoxFuture;
+use grpc::operations::{
+    node::traits::NodeOperations, pool::traits::PoolOperations,
+    registry::traits::RegistryOperations, volume::traits::VolumeOperations,
+};
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+use stor_port::types::v0::{
+    openapi::apis::Uuid,
+    transport::{
+        AsyncOperation, AsyncOperationError, AsyncOperationId, Filter, GetAsyncOperations,
+        ASYNC_OPERATION_HEARTBEAT,
+    },
+};
+use tokio::task::JoinHandle;
+
+/// The tasks running the operations which were accepted by this REST server.
+static TASKS: Lazy<Mutex<HashMap<AsyncOperationId, JoinHandle<()>>>> = Lazy::new(Default::default);
+
+fn tasks() -> MutexGuard<'static, HashMap<AsyncOperationId, JoinHandle<()>>> {
+    TASKS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn client() -> impl RegistryOperations {
+    core_grpc().registry()
+}
+
+/// Get the REST representation of the given operation.
+fn operation_to_rest(operation: AsyncOperation) -> models::Operation {
+    let status = if operation.running() {
+        models::OperationStatus::Running
+    } else if operation.cancelled {
+        models::OperationStatus::Cancelled
+    } else if operation.error.is_some() {
+        models::OperationStatus::Failed
+    } else {
+        models::OperationStatus::Succeeded
+    };
+    models::Operation::new_all(
+        operation.id.into(),
+        operation.method,
+        operation.uri,
+        status,
+        humantime::format_rfc3339_seconds(operation.started).to_string(),
+        operation
+            .finished
+            .map(|finished| humantime::format_rfc3339_seconds(finished).to_string()),
+        None,
+        operation
+            .result
+            .and_then(|result| serde_json::from_str(&result).ok()),
+        operation.error.map(error_to_rest),
+    )
+}
+
+/// Get the REST representation of the error of a failed operation.
+fn error_to_rest(error: AsyncOperationError) -> RestJsonError {
+    let kind = serde_json::from_value(serde_json::Value::String(error.kind))
+        .unwrap_or(RestJsonErrorKind::Internal);
+    RestJsonError::new(error.details, error.message, kind)
+}
+
+/// Get the error of a failed operation from its REST representation.
+fn error_from_rest(error: RestJsonError) -> AsyncOperationError {
+    let kind = match serde_json::to_value(error.kind) {
+        Ok(serde_json::Value::String(kind)) => kind,
+        _ => "Internal".to_string(),
+    };
+    AsyncOperationError {
+        kind,
+        details: error.details,
+        message: error.message,
+    }
+}
+
+/// Check if the client prefers the mutating `request` to be handled asynchronously.
+fn prefers_async(request: &ServiceRequest) -> bool {
+    matches!(
+        *request.method(),
+        Method::PUT | Method::POST | Method::DELETE
+    ) && !request.path().starts_with("/v0/operations")
+        && request
+            .headers()
+            .get_all("prefer")
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|preference| preference.split(';').next())
+            .any(|preference| preference.trim().eq_ignore_ascii_case("respond-async"))
+}
+
+/// Call the `service` in the background if the client prefers it, in which case the request
+/// is accepted straight away with the location of the operation which tracks it.
+/// Requests which fail to authenticate are not accepted, so their handlers can reject them.
+pub(super) fn respond_async<S, B>(
+    request: ServiceRequest,
+    service: &S,
+) -> LocalBoxFuture<'static, Result<ServiceResponse, actix_web::Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    if !prefers_async(&request) || authenticate(request.request()).is_err() {
+        let response = service.call(request);
+        return Box::pin(async move { response.await.map(ServiceResponse::map_into_boxed_body) });
+    }
+
+    let http_request = request.request().clone();
+    let mut operation =
+        AsyncOperation::new(request.method().to_string(), request.uri().to_string());
+    let response = service.call(request);
+
+    Box::pin(async move {
+        // record the operation before accepting the request, otherwise it can't be polled
+        client()
+            .put_async_operation(&operation, None)
+            .await
+            .map_err(RestError::from)?;
+        let model = operation_to_rest(operation.clone());
+
+        // the task can't finish before it's added, as it runs on this same thread
+        let mut tasks = tasks();
+        let id = operation.id.clone();
+        let task = actix_web::rt::spawn(async move {
+            let outcome = run(&mut operation, async { outcome(response.await).await }).await;
+            tasks().remove(&operation.id);
+            // it was cancelled, and its cancellation has already been recorded
+            let Some(outcome) = outcome else {
+                return;
+            };
+            if stopped(&operation).await {
+                return;
+            }
+            operation.finish(outcome.map_err(error_from_rest));
+            if let Err(error) = client().put_async_operation(&operation, None).await {
+                tracing::error!(
+                    id = %operation.id,
+                    %error,
+                    "Failed to record the outcome of the operation"
+                );
+            }
+        });
+        tasks.insert(id, task);
+        drop(tasks);
+
+        let response = HttpResponse::Accepted()
+            .insert_header((header::LOCATION, format!("/v0/operations/{}", model.id)))
+            .insert_header(("preference-applied", "respond-async"))
+            .json(model);
+        Ok(ServiceResponse::new(http_request, response))
+    })
+}
+
+/// Wait for the `outcome` of the running operation, refreshing its heartbeat in the meantime.
+/// Returns `None` if the operation is cancelled, possibly through another REST server.
+async fn run<F: std::future::Future<Output = Result<Option<String>, RestJsonError>>>(
+    operation: &mut AsyncOperation,
+    outcome: F,
+) -> Option<Result<Option<String>, RestJsonError>> {
+    tokio::pin!(outcome);
+    let mut heartbeat = tokio::time::interval(ASYNC_OPERATION_HEARTBEAT);
+    // the first tick completes immediately, and the operation has only just been recorded
+    heartbeat.tick().await;
+    loop {
+        tokio::select! {
+            outcome = &mut outcome => return Some(outcome),
+            _ = heartbeat.tick() => {
+                if !beat(operation).await {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Check if the recorded operation is no longer running, eg: it was cancelled or abandoned.
+async fn stopped(operation: &AsyncOperation) -> bool {
+    let get = GetAsyncOperations::new(Some(operation.id.clone()));
+    match client().get_async_operations(&get, None).await {
+        Ok(operations) => operations.iter().any(|recorded| !recorded.running()),
+        Err(error) => {
+            tracing::warn!(id = %operation.id, %error, "Failed to get the operation");
+            false
+        }
+    }
+}
+
+/// Refresh the heartbeat of the running operation, unless it's no longer running.
+async fn beat(operation: &mut AsyncOperation) -> bool {
+    if stopped(operation).await {
+        return false;
+    }
+    operation.beat();
+    if let Err(error) = client().put_async_operation(operation, None).await {
+        tracing::warn!(id = %operation.id, %error, "Failed to refresh the operation");
+    }
+    true
+}
+
+/// Get the json result or the error from the `response` of a finished request.
+async fn outcome<B: MessageBody>(
+    response: Result<ServiceResponse<B>, actix_web::Error>,
+) -> Result<Option<String>, RestJsonError> {
+    let (status, bytes) = match response {
+        Ok(response) => {
+            let status = response.status();
+            let bytes = body::to_bytes(response.into_body()).await;
+            let bytes = bytes.map_err(Into::<Box<dyn std::error::Error>>::into);
+            (status, bytes.map_err(|error| error.to_string()))
+        }
+        Err(error) => {
+            let response = error.error_response();
+            let status = response.status();
+            let bytes = body::to_bytes(response.into_body()).await;
+            (status, bytes.map_err(|error| error.to_string()))
+        }
+    };
+    let bytes = bytes.map_err(|error| {
+        RestJsonError::new(
+            error,
+            "Failed to read the response body".to_string(),
+            RestJsonErrorKind::Internal,
+        )
+    })?;
+
+    if status.is_success() {
+        if bytes.is_empty() {
+            return Ok(None);
+        }
+        // some responses are not json, eg: the volume share uri
+        Ok(Some(
+            match serde_json::from_slice::<serde_json::Value>(&bytes) {
+                Ok(_) => String::from_utf8_lossy(&bytes).to_string(),
+                Err(_) => serde_json::Value::String(String::from_utf8_lossy(&bytes).to_string())
+                    .to_string(),
+            },
+        ))
+    } else {
+        Err(serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+            RestJsonError::new(
+                String::from_utf8_lossy(&bytes).to_string(),
+                status.to_string(),
+                RestJsonErrorKind::Internal,
+            )
+        }))
+    }
+}
+
+/// Get the progress of a running operation, from the spec of the resource it's operating on.
+async fn progress(uri: &str) -> Option<serde_json::Value> {
+    let path = uri.split('?').next()?.strip_prefix("/v0/")?;
+    let segments = path.split('/').collect::<Vec<_>>();
+    let (resource, fields): (serde_json::Value, &[&str]) = match segments.as_slice() {
+        ["volumes", id, ..] | ["snapshots", _, "volumes", id] => {
+            let id = Uuid::parse_str(id).ok()?;
+            let volumes = core_grpc()
+                .volume()
+                .get(Filter::Volume(id.into()), false, None, None)
+                .await
+                .ok()?;
+            let volume = volumes.entries.into_iter().next()?;
+            (
+                serde_json::to_value(models::Volume::from(volume)).ok()?,
+                &["operation"],
+            )
+        }
+        ["nodes", id, "drain", ..] => {
+            let nodes = core_grpc()
+                .node()
+                .get(Filter::Node(id.to_string().into()), false, None)
+                .await
+                .ok()?;
+            let node = nodes.into_inner().into_iter().next()?;
+            (
+                serde_json::to_value(models::Node::from(node)).ok()?,
+                &["cordondrainstate", "drainingvolumes"],
+            )
+        }
+        ["pools", id, ..] | ["nodes", _, "pools", id, ..] => {
+            let pools = core_grpc()
+                .pool()
+                .get(Filter::Pool(id.to_string().into()), None)
+                .await
+                .ok()?;
+            let pool = pools.into_inner().into_iter().next()?;
+            (
+                serde_json::to_value(models::Pool::from(pool)).ok()?,
+                &["status", "cordondrainstate", "drainprogress"],
+            )
+        }
+        _ => return None,
+    };
+    let spec = resource.get("spec")?;
+    let progress = fields
+        .iter()
+        .filter_map(|field| Some((field.to_string(), spec.get(*field)?.clone())))
+        .collect::<serde_json::Map<_, _>>();
+    Some(progress.into())
+}
+
+/// Get the REST representation of the given operation, along with its progress.
+async fn operation_with_progress(operation: AsyncOperation) -> models::Operation {
+    let uri = operation.running().then(|| operation.uri.clone());
+    let mut model = operation_to_rest(operation);
+    if let Some(uri) = uri {
+        model.progress = progress(&uri).await;
+    }
+    model
+}
+
+fn not_found(id: Uuid) -> RestError<RestJsonError> {
+    RestError::from(ReplyError {
+        kind: ReplyErrorKind::NotFound,
+        resource: ResourceKind::Unknown,
+        source: "Requested operation was not found".to_string(),
+        extra: format!("Operation id : {id}"),
+    })
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Operations for RestApi {
+    async fn del_operation(Path(operation_id): Path<Uuid>) -> Result<(), RestError<RestJsonError>> {
+        let operations = client()
+            .get_async_operations(&GetAsyncOperations::new(Some(operation_id.into())), None)
+            .await?;
+        let Some(mut operation) = operations.into_iter().next() else {
+            return Err(not_found(operation_id));
+        };
+        // a finished operation is left to expire
+        if !operation.running() {
+            return Ok(());
+        }
+        // stop waiting for the request, the core agent carries on with any change which it had
+        // already started making
+        // if another REST server is running it, it stops on its next heartbeat instead
+        if let Some(task) = tasks().remove(&operation.id) {
+            task.abort();
+        }
+        operation.cancel();
+        client().put_async_operation(&operation, None).await?;
+        Ok(())
+    }
+
+    async fn get_operation(
+        Path(operation_id): Path<Uuid>,
+    ) -> Result<models::Operation, RestError<RestJsonError>> {
+        let operations = client()
+            .get_async_operations(&GetAsyncOperations::new(Some(operation_id.into())), None)
+            .await?;
+        match operations.into_iter().next() {
+            Some(operation) => Ok(operation_with_progress(operation).await),
+            None => Err(not_found(operation_id)),
+        }
+    }
+
+    async fn get_operations() -> Result<Vec<models::Operation>, RestError<RestJsonError>> {
+        let mut operations = client()
+            .get_async_operations(&GetAsyncOperations::default(), None)
+            .await?;
+        operations.sort_by(|a, b| a.started.cmp(&b.started));
+        let mut models = Vec::with_capacity(operations.len());
+        for operation in operations {
+            models.push(operation_with_progress(operation).await);
+        }
+        Ok(models)
+    }
+}
@@ -1,6 +1,6 @@
 use super::*;
 use grpc::operations::pool::traits::PoolOperations;
-use stor_port::types::v0::transport::{DestroyPool, Filter};
+use stor_port::types::v0::transport::{DestroyPool, ExpandPool, Filter};
 use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 
 fn client() -> impl PoolOperations {
@@ -59,6 +59,7 @@ impl apis::actix_server::Pools for RestApi {
                 .into_inner()
                 .get(0),
         )?;
+        set_etag(&pool);
         Ok(pool.into())
     }
 
@@ -80,11 +81,18 @@ impl apis::actix_server::Pools for RestApi {
                 .into_inner()
                 .get(0),
         )?;
+        set_etag(&pool);
         Ok(pool.into())
     }
 
-    async fn get_pools() -> Result<Vec<models::Pool>, RestError<RestJsonError>> {
-        let pools = client().get(Filter::None, None).await?;
+    async fn get_pools(
+        Query(label_selector): Query<Option<String>>,
+    ) -> Result<Vec<models::Pool>, RestError<RestJsonError>> {
+        let filter = match super::label_selector(label_selector, ResourceKind::Pool)? {
+            Some(selector) => Filter::Labels(selector),
+            None => Filter::None,
+        };
+        let pools = client().get(filter, None).await?;
         Ok(pools.into_inner().into_iter().map(From::from).collect())
     }
 
@@ -97,6 +105,61 @@ impl apis::actix_server::Pools for RestApi {
         let pool = client().create(&create, None).await?;
         Ok(pool.into())
     }
+
+    async fn put_node_pool_disks(
+        Path((node_id, pool_id)): Path<(String, String)>,
+        Body(expand_pool_body): Body<models::ExpandPoolBody>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let expand = ExpandPool {
+            node: node_id.into(),
+            id: pool_id.into(),
+            disks: expand_pool_body.disks.into_iter().map(From::from).collect(),
+            expected_revision: preconditions::if_match(ResourceKind::Pool)?,
+        };
+        let pool = client().expand(&expand, None).await?;
+        set_etag(&pool);
+        Ok(pool.into())
+    }
+
+    async fn put_pool_cordon(
+        Path((pool_id, label)): Path<(String, String)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let revision = preconditions::if_match(ResourceKind::Pool)?;
+        let pool = client()
+            .cordon(pool_id.into(), label, revision, None)
+            .await?;
+        set_etag(&pool);
+        Ok(pool.into())
+    }
+
+    async fn delete_pool_cordon(
+        Path((pool_id, label)): Path<(String, String)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let revision = preconditions::if_match(ResourceKind::Pool)?;
+        let pool = client()
+            .uncordon(pool_id.into(), label, revision, None)
+            .await?;
+        set_etag(&pool);
+        Ok(pool.into())
+    }
+
+    async fn put_pool_drain(
+        Path((pool_id, label)): Path<(String, String)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let revision = preconditions::if_match(ResourceKind::Pool)?;
+        let pool = client()
+            .drain(pool_id.into(), label, revision, None)
+            .await?;
+        set_etag(&pool);
+        Ok(pool.into())
+    }
+}
+
+/// Set the spec revision of the pool as the `ETag` of the response.
+fn set_etag(pool: &Pool) {
+    if let Some(spec) = pool.spec() {
+        preconditions::set_etag(spec.revision);
+    }
 }
 
 /// returns pool from pool option and returns an error on non existence
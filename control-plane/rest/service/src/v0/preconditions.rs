@@ -0,0 +1,103 @@
+//! Optimistic concurrency control of the resource specs.
+//! The spec revision of a resource is returned as its `ETag`, which the mutating requests may
+//! then specify through the `If-Match` header, so they fail if the spec has since been modified.
+
+use super::*;
+use actix_web::{
+    dev::Service,
+    http::header::{self, HeaderValue},
+};
+use std::{cell::Cell, future::Future};
+
+/// The preconditions of the request currently being handled.
+struct Preconditions {
+    /// The value of the `If-Match` header, if any.
+    if_match: Option<String>,
+    /// The revision of the resource spec to return as the `ETag`, if any.
+    etag: Cell<Option<u64>>,
+}
+
+tokio::task_local! {
+    static PRECONDITIONS: Preconditions;
+}
+
+/// Call the `service` with the preconditions of the `request`, adding the `ETag` header to the
+/// response if the handler has set it.
+pub(super) fn scope<S, B>(
+    request: ServiceRequest,
+    service: &S,
+) -> impl Future<Output = Result<ServiceResponse<B>, actix_web::Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+{
+    let preconditions = Preconditions {
+        if_match: request
+            .headers()
+            .get(header::IF_MATCH)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string()),
+        etag: Cell::new(None),
+    };
+    let response = service.call(request);
+    PRECONDITIONS.scope(preconditions, async move {
+        let mut response = response.await?;
+        if let Some(revision) = PRECONDITIONS.with(|p| p.etag.get()) {
+            if let Ok(etag) = HeaderValue::from_str(&format!("\"{revision}\"")) {
+                response.headers_mut().insert(header::ETAG, etag);
+            }
+        }
+        Ok(response)
+    })
+}
+
+/// Set the spec `revision` of the resource returned by the request, as its `ETag`.
+pub(super) fn set_etag(revision: u64) {
+    let _ = PRECONDITIONS.try_with(|p| p.etag.set(Some(revision)));
+}
+
+/// Get the spec revision which the resource must be at, as specified by the `If-Match` header.
+/// A missing header, or the `*` wildcard, matches any revision.
+pub(super) fn if_match(kind: ResourceKind) -> Result<Option<u64>, ReplyError> {
+    let Ok(Some(if_match)) = PRECONDITIONS.try_with(|p| p.if_match.clone()) else {
+        return Ok(None);
+    };
+    let if_match = if_match.trim();
+    if if_match == "*" {
+        return Ok(None);
+    }
+    // weak tags can be used, as the revisions are only compared for equality
+    let tag = if_match.strip_prefix("W/").unwrap_or(if_match);
+    let Some(tag) = tag
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .filter(|tag| !tag.contains('"'))
+    else {
+        return Err(ReplyError::invalid_argument(
+            kind,
+            "If-Match",
+            format!("'{if_match}' is not a single entity tag"),
+        ));
+    };
+    match tag.parse::<u64>() {
+        Ok(revision) => Ok(Some(revision)),
+        // we never return such a tag, and so it can never match
+        Err(_) => Err(ReplyError {
+            kind: ReplyErrorKind::FailedPrecondition,
+            resource: kind,
+            source: "The entity tag does not match the current revision".to_string(),
+            extra: format!("If-Match: {if_match}"),
+        }),
+    }
+}
+
+/// Reject the `If-Match` header on requests which cannot be made conditional on the revision.
+/// The `*` wildcard is accepted, as it matches any revision.
+pub(super) fn if_match_unsupported(kind: ResourceKind) -> Result<(), ReplyError> {
+    match if_match(kind.clone())? {
+        None => Ok(()),
+        Some(_) => Err(ReplyError::invalid_argument(
+            kind,
+            "If-Match",
+            "the request cannot be made conditional on the spec revision".to_string(),
+        )),
+    }
+}
@@ -113,14 +113,16 @@ impl apis::actix_server::Snapshots for RestApi {
     }
 
     async fn get_volumes_snapshots(
-        Query((snapshot_id, volume_id, max_entries, starting_token)): Query<(
+        Query((snapshot_id, volume_id, max_entries, starting_token, label_selector)): Query<(
             Option<Uuid>,
             Option<Uuid>,
             isize,
             Option<isize>,
+            Option<String>,
         )>,
     ) -> Result<models::VolumeSnapshots, RestError<RestJsonError>> {
         let starting_token = starting_token.unwrap_or_default();
+        let label_selector = super::label_selector(label_selector, ResourceKind::VolumeSnapshot)?;
         // If max entries is 0, pagination is disabled. All snapshots will be returned in a single
         // call.
         let pagination = if max_entries > 0 {
@@ -132,13 +134,17 @@ impl apis::actix_server::Snapshots for RestApi {
             None
         };
 
-        let filter = match (snapshot_id, volume_id) {
-            (Some(snapshot), Some(volume)) => {
+        let filter = match (snapshot_id, volume_id, label_selector) {
+            (Some(_), _, Some(_)) | (_, Some(_), Some(_)) => {
+                return Err(id_and_label_selector(ResourceKind::VolumeSnapshot).into())
+            }
+            (Some(snapshot), Some(volume), None) => {
                 Filter::VolumeSnapshot(volume.into(), snapshot.into())
             }
-            (Some(snapshot), None) => Filter::Snapshot(snapshot.into()),
-            (None, Some(volume)) => Filter::Volume(volume.into()),
-            _ => Filter::None,
+            (Some(snapshot), None, None) => Filter::Snapshot(snapshot.into()),
+            (None, Some(volume), None) => Filter::Volume(volume.into()),
+            (None, None, Some(selector)) => Filter::Labels(selector),
+            (None, None, None) => Filter::None,
         };
 
         let snaps = client()
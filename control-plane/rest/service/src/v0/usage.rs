@@ -0,0 +1,70 @@
+use super::*;
+use grpc::operations::registry::traits::RegistryOperations;
+use humantime::Timestamp;
+use stor_port::types::v0::{
+    openapi::apis::Uuid,
+    transport::{GetUsageHistory, UsageHistory, UsageResource, UsageRollup, UsageSample},
+};
+
+fn client() -> impl RegistryOperations {
+    core_grpc().registry()
+}
+
+async fn usage_history(
+    resource: UsageResource,
+) -> Result<models::UsageHistory, RestError<RestJsonError>> {
+    let history = client()
+        .get_usage_history(&GetUsageHistory::new(resource), None)
+        .await?;
+    Ok(usage_history_to_rest(history))
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Usage for RestApi {
+    async fn get_pool_usage_history(
+        Path(pool_id): Path<String>,
+    ) -> Result<models::UsageHistory, RestError<RestJsonError>> {
+        usage_history(UsageResource::Pool(pool_id.into())).await
+    }
+
+    async fn get_volume_usage_history(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::UsageHistory, RestError<RestJsonError>> {
+        usage_history(UsageResource::Volume(volume_id.into())).await
+    }
+}
+
+fn usage_history_to_rest(value: UsageHistory) -> models::UsageHistory {
+    models::UsageHistory::new(
+        value
+            .samples
+            .into_iter()
+            .map(usage_sample_to_rest)
+            .collect::<Vec<_>>(),
+        value
+            .rollups
+            .into_iter()
+            .map(usage_rollup_to_rest)
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn usage_sample_to_rest(value: UsageSample) -> models::UsageSample {
+    models::UsageSample::new(
+        Timestamp::from(value.timestamp).to_string(),
+        value.capacity,
+        value.used,
+    )
+}
+
+fn usage_rollup_to_rest(value: UsageRollup) -> models::UsageRollup {
+    models::UsageRollup::new(
+        Timestamp::from(value.start).to_string(),
+        Timestamp::from(value.end).to_string(),
+        value.samples,
+        value.capacity,
+        value.used_min,
+        value.used_max,
+        value.used_avg,
+    )
+}
@@ -1,14 +1,16 @@
 use super::*;
 use grpc::operations::{
-    nexus::traits::NexusOperations, volume::traits::VolumeOperations, MaxEntries, Pagination,
-    StartingToken,
+    ha_node::traits::ClusterAgentOperations, nexus::traits::NexusOperations,
+    volume::traits::VolumeOperations, MaxEntries, Pagination, StartingToken,
 };
 use stor_port::types::v0::{
     openapi::apis::Uuid,
     transport::{
-        DestroyShutdownTargets, DestroyVolume, Filter, GetRebuildRecord, PublishVolume,
-        RebuildHistory, RebuildJobState, RebuildRecord, RepublishVolume, ResizeVolume,
-        SetVolumeReplica, ShareVolume, UnpublishVolume, UnshareVolume, Volume,
+        AddVolumeFrontendNode, DestroyShutdownTargets, DestroyVolume, Filter, GetRebuildRecord,
+        GetSwitchOverHistory, GetVolumeAuth, PublishVolume, RebuildHistory, RebuildJobState,
+        RebuildRecord, RemoveVolumeFrontendNode, RepublishVolume, ResizeVolume, RotateVolumeAuth,
+        SetVolumeProperty, SetVolumeReplica, ShareVolume, SwitchOverHistory, SwitchOverRecord,
+        SwitchOverStatus, SwitchOverVolume, UnpublishVolume, UnshareVolume, Volume, VolumeProperty,
     },
 };
 
@@ -29,6 +31,7 @@ impl apis::actix_server::Volumes for RestApi {
             .unshare(
                 &UnshareVolume {
                     uuid: volume_id.into(),
+                    expected_revision: preconditions::if_match(ResourceKind::Volume)?,
                 },
                 None,
             )
@@ -39,19 +42,47 @@ impl apis::actix_server::Volumes for RestApi {
     async fn del_volume(Path(volume_id): Path<Uuid>) -> Result<(), RestError<RestJsonError>> {
         client()
             .destroy(
-                &DestroyVolume {
-                    uuid: volume_id.into(),
-                },
+                &DestroyVolume::new(&volume_id.into())
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
                 None,
             )
             .await?;
         Ok(())
     }
 
+    async fn del_volume_autogrow(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .set_property(
+                &SetVolumeProperty::new(volume_id.into(), VolumeProperty::AutoGrow(None))
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
+                None,
+            )
+            .await?;
+        preconditions::set_etag(volume.spec().revision);
+        Ok(volume.into())
+    }
+
+    async fn del_volume_frontend_node(
+        Path((volume_id, node_id)): Path<(Uuid, String)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .remove_frontend_node(
+                &RemoveVolumeFrontendNode::new(volume_id.into(), node_id.into())
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
+                None,
+            )
+            .await?;
+        preconditions::set_etag(volume.spec().revision);
+        Ok(volume.into())
+    }
+
     async fn del_volume_shutdown_targets(
         Path(volume_id): Path<Uuid>,
     ) -> Result<(), RestError<RestJsonError>> {
-        let destroy = DestroyShutdownTargets::new(volume_id.into(), None);
+        let destroy = DestroyShutdownTargets::new(volume_id.into(), None)
+            .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?);
         client().destroy_shutdown_target(&destroy, None).await?;
         Ok(())
     }
@@ -62,10 +93,12 @@ impl apis::actix_server::Volumes for RestApi {
     ) -> Result<models::Volume, RestError<RestJsonError>> {
         let volume = client()
             .unpublish(
-                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false)),
+                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false))
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
                 None,
             )
             .await?;
+        preconditions::set_etag(volume.spec().revision);
         Ok(volume.into())
     }
 
@@ -94,6 +127,15 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(rebuild_history_to_rest(record))
     }
 
+    async fn get_switchover_history(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::SwitchOverHistory, RestError<RestJsonError>> {
+        let history = ha_cluster_grpc()?
+            .get_switchover_history(&GetSwitchOverHistory::new(volume_id.into()), None)
+            .await?;
+        Ok(switchover_history_to_rest(history))
+    }
+
     async fn get_volume(
         Path(volume_id): Path<Uuid>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
@@ -105,17 +147,29 @@ impl apis::actix_server::Volumes for RestApi {
                 .entries
                 .get(0),
         )?;
+        preconditions::set_etag(volume.spec().revision);
         Ok(volume.into())
     }
 
+    async fn get_volume_auth(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::DhChapKeys, RestError<RestJsonError>> {
+        let keys = client()
+            .get_auth(&GetVolumeAuth::new(&volume_id.into()), None)
+            .await?;
+        Ok(keys.into())
+    }
+
     async fn get_volumes(
-        Query((volume_id, max_entries, starting_token)): Query<(
+        Query((volume_id, max_entries, starting_token, label_selector)): Query<(
             Option<Uuid>,
             isize,
             Option<isize>,
+            Option<String>,
         )>,
     ) -> Result<models::Volumes, RestError<RestJsonError>> {
         let starting_token = starting_token.unwrap_or_default();
+        let label_selector = super::label_selector(label_selector, ResourceKind::Volume)?;
 
         // If max entries is 0, pagination is disabled. All volumes will be returned in a single
         // call.
@@ -127,13 +181,19 @@ impl apis::actix_server::Volumes for RestApi {
         } else {
             None
         };
-        let volumes = match volume_id {
-            Some(volume_id) => {
+        let volumes = match (volume_id, label_selector) {
+            (Some(_), Some(_)) => return Err(id_and_label_selector(ResourceKind::Volume).into()),
+            (Some(volume_id), None) => {
                 client()
                     .get(Filter::Volume(volume_id.into()), true, pagination, None)
                     .await?
             }
-            None => client().get(Filter::None, false, pagination, None).await?,
+            (None, Some(selector)) => {
+                client()
+                    .get(Filter::Labels(selector), false, pagination, None)
+                    .await?
+            }
+            (None, None) => client().get(Filter::None, false, pagination, None).await?,
         };
 
         Ok(models::Volumes {
@@ -161,18 +221,49 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn put_volume_autogrow(
+        Path(volume_id): Path<Uuid>,
+        Body(autogrow): Body<models::VolumeAutoGrow>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .set_property(
+                &SetVolumeProperty::new(
+                    volume_id.into(),
+                    VolumeProperty::AutoGrow(Some(autogrow.into())),
+                )
+                .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
+                None,
+            )
+            .await?;
+        preconditions::set_etag(volume.spec().revision);
+        Ok(volume.into())
+    }
+
+    async fn put_volume_frontend_node(
+        Path((volume_id, node_id)): Path<(Uuid, String)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .add_frontend_node(
+                &AddVolumeFrontendNode::new(volume_id.into(), node_id.into())
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
+                None,
+            )
+            .await?;
+        preconditions::set_etag(volume.spec().revision);
+        Ok(volume.into())
+    }
+
     async fn put_volume_replica_count(
         Path((volume_id, replica_count)): Path<(Uuid, u8)>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
         let volume = client()
             .set_replica(
-                &SetVolumeReplica {
-                    uuid: volume_id.into(),
-                    replicas: replica_count,
-                },
+                &SetVolumeReplica::new(volume_id.into(), replica_count)
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
                 None,
             )
             .await?;
+        preconditions::set_etag(volume.spec().revision);
         Ok(volume.into())
     }
 
@@ -189,6 +280,7 @@ impl apis::actix_server::Volumes for RestApi {
                         Some(host) => vec![host],
                         None => vec![],
                     },
+                    expected_revision: preconditions::if_match(ResourceKind::Volume)?,
                 },
                 None,
             )
@@ -196,20 +288,33 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(share_uri)
     }
 
+    async fn put_volume_auth(
+        Path(volume_id): Path<Uuid>,
+        Body(volume_auth): Body<models::VolumeAuth>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .rotate_auth(
+                &RotateVolumeAuth::new(&volume_id.into(), volume_auth.into())
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
+                None,
+            )
+            .await?;
+        preconditions::set_etag(volume.spec().revision);
+        Ok(volume.into())
+    }
+
     async fn put_volume_size(
         Path(volume_id): Path<Uuid>,
         Body(resize_volume_body): Body<models::ResizeVolumeBody>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
         let volume = client()
             .resize(
-                &ResizeVolume {
-                    uuid: volume_id.into(),
-                    requested_size: resize_volume_body.size as u64,
-                    cluster_capacity_limit: None,
-                },
+                &ResizeVolume::new(volume_id.into(), resize_volume_body.size as u64, None)
+                    .with_expected_revision(preconditions::if_match(ResourceKind::Volume)?),
                 None,
             )
             .await?;
+        preconditions::set_etag(volume.spec().revision);
 
         Ok(volume.into())
     }
@@ -218,6 +323,7 @@ impl apis::actix_server::Volumes for RestApi {
         Path(volume_id): Path<Uuid>,
         Body(publish_volume_body): Body<models::PublishVolumeBody>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let expected_revision = preconditions::if_match(ResourceKind::Volume)?;
         let volume = match publish_volume_body.republish.unwrap_or(false) {
             true => {
                 client()
@@ -232,6 +338,7 @@ impl apis::actix_server::Volumes for RestApi {
                                 .frontend_node
                                 .unwrap_or_default()
                                 .into(),
+                            expected_revision,
                         },
                         None,
                     )
@@ -246,15 +353,43 @@ impl apis::actix_server::Volumes for RestApi {
                             share: Some(publish_volume_body.protocol.into()),
                             publish_context: publish_volume_body.publish_context,
                             frontend_nodes: publish_volume_body.frontend_node.into_iter().collect(),
+                            multi_node: publish_volume_body.multi_node.unwrap_or_default(),
+                            read_only: publish_volume_body.read_only.unwrap_or_default(),
+                            expected_revision,
                         },
                         None,
                     )
                     .await?
             }
         };
+        preconditions::set_etag(volume.spec().revision);
 
         Ok(volume.into())
     }
+
+    async fn put_volume_target_switchover(
+        Path(volume_id): Path<Uuid>,
+        Query(node): Query<Option<String>>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        // the switchover is driven asynchronously by the ha cluster agent
+        preconditions::if_match_unsupported(ResourceKind::Volume)?;
+        ha_cluster_grpc()?
+            .switchover_volume(
+                &SwitchOverVolume::new(volume_id.into(), node.map(Into::into)),
+                None,
+            )
+            .await?;
+        let volume = volume(
+            volume_id.to_string(),
+            client()
+                .get(Filter::Volume(volume_id.into()), false, None, None)
+                .await?
+                .entries
+                .get(0),
+        )?;
+        preconditions::set_etag(volume.spec().revision);
+        Ok(volume.into())
+    }
 }
 
 /// returns volume from volume option and returns an error on non existence
@@ -298,6 +433,45 @@ fn rebuild_record_to_rest(value: RebuildRecord) -> models::RebuildRecord {
     )
 }
 
+fn switchover_history_to_rest(value: SwitchOverHistory) -> models::SwitchOverHistory {
+    let records: Vec<_> = value
+        .records
+        .into_iter()
+        .map(switchover_record_to_rest)
+        .collect();
+    models::SwitchOverHistory::new(value.volume, records)
+}
+
+fn switchover_record_to_rest(value: SwitchOverRecord) -> models::SwitchOverRecord {
+    let stages = value
+        .stages
+        .into_iter()
+        .map(|stage| {
+            models::SwitchOverStageRecord::new(
+                stage.stage,
+                Timestamp::from(stage.timestamp).to_string(),
+            )
+        })
+        .collect();
+    let status = match value.status {
+        SwitchOverStatus::InProgress => models::SwitchOverStatus::InProgress,
+        SwitchOverStatus::Successful => models::SwitchOverStatus::Successful,
+        SwitchOverStatus::Errored => models::SwitchOverStatus::Errored,
+    };
+    models::SwitchOverRecord::new_all(
+        value.frontend_node,
+        value.nqn,
+        value.planned,
+        value.target_node.map(Into::into),
+        value.new_path,
+        status,
+        Timestamp::from(value.start_time).to_string(),
+        value.end_time.map(|end| Timestamp::from(end).to_string()),
+        stages,
+        value.error,
+    )
+}
+
 fn rebuild_state_to_rest(value: RebuildJobState) -> models::RebuildJobState {
     match value {
         RebuildJobState::Init => models::RebuildJobState::Init,
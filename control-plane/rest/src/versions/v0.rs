@@ -13,9 +13,9 @@ pub use stor_port::{
             CreateVolume, DestroyNexus, DestroyPool, DestroyReplica, DestroyVolume, Filter,
             GetBlockDevices, JsonGrpcRequest, Nexus, NexusId, NexusShareProtocol, Node, NodeId,
             Pool, PoolDeviceUri, PoolId, Protocol, RemoveNexusChild, Replica, ReplicaId,
-            ReplicaShareProtocol, ShareNexus, ShareReplica, Specs, Topology, UnshareNexus,
-            UnshareReplica, VolumeId, VolumeLabels, VolumePolicy, Watch, WatchCallback,
-            WatchResourceId,
+            ReplicaShareProtocol, ShareNexus, ShareReplica, Specs, TargetPlacement, Topology,
+            UnshareNexus, UnshareReplica, VolumeAuth, VolumeAutoGrow, VolumeId, VolumeLabels,
+            VolumePolicy, Watch, WatchCallback, WatchResourceId,
         },
     },
 };
@@ -194,6 +194,12 @@ pub struct CreateVolumeBody {
     pub affinity_group: Option<AffinityGroup>,
     /// Max snapshot limit per volume.
     pub max_snapshots: Option<u32>,
+    /// Automatic volume expansion policy.
+    pub autogrow: Option<VolumeAutoGrow>,
+    /// DH-HMAC-CHAP authentication of the volume target.
+    pub auth: Option<VolumeAuth>,
+    /// Volume target placement policy.
+    pub target_placement: Option<TargetPlacement>,
 }
 impl From<models::CreateVolumeBody> for CreateVolumeBody {
     fn from(src: models::CreateVolumeBody) -> Self {
@@ -206,6 +212,9 @@ impl From<models::CreateVolumeBody> for CreateVolumeBody {
             thin: src.thin,
             affinity_group: src.affinity_group.map(|ag| ag.into()),
             max_snapshots: src.max_snapshots,
+            autogrow: src.autogrow.into_opt(),
+            auth: src.auth.into_opt(),
+            target_placement: src.target_placement.into_opt(),
         }
     }
 }
@@ -220,6 +229,9 @@ impl From<CreateVolume> for CreateVolumeBody {
             thin: create.thin,
             affinity_group: create.affinity_group,
             max_snapshots: create.max_snapshots,
+            autogrow: create.autogrow,
+            auth: create.auth,
+            target_placement: create.target_placement,
         }
     }
 }
@@ -237,6 +249,9 @@ impl CreateVolumeBody {
             affinity_group: self.affinity_group.clone(),
             cluster_capacity_limit: None,
             max_snapshots: self.max_snapshots,
+            autogrow: self.autogrow.clone(),
+            auth: self.auth.clone(),
+            target_placement: self.target_placement,
         }
     }
     /// Convert into rpc request type.
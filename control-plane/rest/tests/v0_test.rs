@@ -82,7 +82,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
     .unwrap()
     .v00();
 
-    let nodes = client.nodes_api().get_nodes(None).await.unwrap();
+    let nodes = client.nodes_api().get_nodes(None, None).await.unwrap();
     info!("Nodes: {:#?}", nodes);
     assert_eq!(nodes.len(), 2);
     let io_engine1 = cluster.node(0);
@@ -100,6 +100,9 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
             labels: None,
             cordondrainstate: None,
             node_nqn: Some(HostNqn::from_nodename(&io_engine1.to_string()).to_string()),
+            evacuate: None,
+            drainingvolumes: None,
+            labelexpiry: None,
         }),
         state: Some(models::NodeState {
             id: io_engine1.to_string(),
@@ -113,7 +116,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
     };
     assert_eq!(listed_node.unwrap(), node);
 
-    let _ = client.pools_api().get_pools().await.unwrap();
+    let _ = client.pools_api().get_pools(None).await.unwrap();
     let pool = client
         .pools_api()
         .put_node_pool(
@@ -138,7 +141,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     assert_eq!(
         Some(&pool),
-        client.pools_api().get_pools().await.unwrap().first()
+        client.pools_api().get_pools(None).await.unwrap().first()
     );
 
     let pool = client
@@ -302,6 +305,8 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -374,7 +379,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     let volumes = client
         .volumes_api()
-        .get_volumes(0, None, None)
+        .get_volumes(0, None, None, None)
         .await
         .unwrap();
     assert!(volumes.entries.is_empty());
@@ -457,7 +462,7 @@ async fn client_invalid_token() {
 
     let error = client
         .nodes_api()
-        .get_nodes(None)
+        .get_nodes(None, None)
         .await
         .expect_err("Request should fail with invalid token");
 
@@ -467,3 +472,60 @@ async fn client_invalid_token() {
     };
     assert!(unauthorized);
 }
+
+#[tokio::test]
+async fn async_operation() {
+    let cluster = test_setup(&false).await;
+    let node = cluster.node(0);
+
+    // the generated client can't state its preference, so send the request directly
+    let http = reqwest::Client::new();
+    let response = http
+        .put(format!(
+            "http://localhost:8081/v0/nodes/{node}/cordon/async"
+        ))
+        .header("Prefer", "respond-async")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+    let location = response
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .expect("Accepted operations should have a location")
+        .to_str()
+        .unwrap()
+        .to_string();
+    let operation: models::Operation = response.json().await.unwrap();
+    assert_eq!(location, format!("/v0/operations/{}", operation.id));
+
+    let timeout = Duration::from_secs(30);
+    let start = std::time::Instant::now();
+    let operation = loop {
+        let operation: models::Operation = http
+            .get(format!("http://localhost:8081{location}"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if matches!(
+            operation.status,
+            models::OperationStatus::Succeeded | models::OperationStatus::Failed
+        ) {
+            break operation;
+        }
+        assert!(
+            start.elapsed() < timeout,
+            "Timeout waiting for the operation to finish: '{operation:#?}'"
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    assert_eq!(operation.status, models::OperationStatus::Succeeded);
+    assert!(operation.error.is_none());
+
+    // the result is the body of the response, ie: the cordoned node
+    let result: models::Node = serde_json::from_value(operation.result.unwrap()).unwrap();
+    assert_eq!(result.id, node.to_string());
+}
@@ -31,6 +31,7 @@ impl_message!(GetAppNode);
 impl_message!(CreatePool);
 impl_message!(DestroyPool);
 impl_message!(ImportPool);
+impl_message!(ExpandPool);
 impl_vector_request!(Pools, Pool);
 impl_message!(GetPools);
 
@@ -87,6 +88,7 @@ impl_vector_request!(Watches, Watch);
 impl_message!(CreateWatch);
 impl_message!(GetWatches);
 impl_message!(DeleteWatch);
+impl_message!(WatchEvents);
 
 impl_message!(GetSpecs);
 impl_message!(GetStates);
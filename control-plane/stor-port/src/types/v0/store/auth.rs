@@ -0,0 +1,112 @@
+use crate::types::v0::{
+    store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+    transport::VolumeId,
+};
+use pstor::ApiVersion;
+use serde::{Deserialize, Serialize};
+
+/// Reference to the DH-HMAC-CHAP secrets of a volume target.
+/// The secrets themselves are kept in the store separately from the volume spec, so they're not
+/// exposed through the spec.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct NvmfAuthRef {
+    /// The volume which owns the secrets.
+    volume: VolumeId,
+    /// The generation of the secrets, incremented on every rotation.
+    generation: u64,
+}
+impl NvmfAuthRef {
+    /// Return new `Self` from the given parameters.
+    pub fn new(volume: &VolumeId, generation: u64) -> Self {
+        Self {
+            volume: volume.clone(),
+            generation,
+        }
+    }
+    /// Get the volume which owns the secrets.
+    pub fn volume(&self) -> &VolumeId {
+        &self.volume
+    }
+    /// Get the generation of the secrets.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Get the store key of the referenced secrets.
+    pub fn key(&self) -> NvmfAuthSecretKey {
+        NvmfAuthSecretKey::new(&self.volume, self.generation)
+    }
+}
+
+/// The DH-HMAC-CHAP secrets of a volume target.
+/// The secrets are sealed by the core agent before they're stored, and so they're never persisted
+/// in the clear.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct NvmfAuthSecret {
+    /// The volume which owns the secrets.
+    volume: VolumeId,
+    /// The generation of the secrets, incremented on every rotation.
+    generation: u64,
+    /// The sealed secrets, base64 encoded.
+    sealed: String,
+}
+impl NvmfAuthSecret {
+    /// Return new `Self` from the given parameters.
+    pub fn new(volume: &VolumeId, generation: u64, sealed: String) -> Self {
+        Self {
+            volume: volume.clone(),
+            generation,
+            sealed,
+        }
+    }
+    /// Get a reference to these secrets.
+    pub fn auth_ref(&self) -> NvmfAuthRef {
+        NvmfAuthRef::new(&self.volume, self.generation)
+    }
+    /// Get the sealed secrets.
+    pub fn sealed(&self) -> &str {
+        &self.sealed
+    }
+}
+
+/// Key used by the store to uniquely identify a `NvmfAuthSecret`.
+/// Each generation is stored separately, so the secrets which the target is currently shared
+/// with are kept until it's republished with the rotated ones.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NvmfAuthSecretKey {
+    volume: VolumeId,
+    generation: u64,
+}
+
+impl NvmfAuthSecretKey {
+    /// Return new `Self` from the given parameters.
+    pub fn new(volume: &VolumeId, generation: u64) -> Self {
+        Self {
+            volume: volume.clone(),
+            generation,
+        }
+    }
+}
+
+impl ObjectKey for NvmfAuthSecretKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::NvmfAuthSecret
+    }
+
+    fn key_uuid(&self) -> String {
+        format!("{}/{}", self.volume, self.generation)
+    }
+}
+
+impl StorableObject for NvmfAuthSecret {
+    type Key = NvmfAuthSecretKey;
+
+    fn key(&self) -> Self::Key {
+        NvmfAuthSecretKey::new(&self.volume, self.generation)
+    }
+}
@@ -1,15 +1,18 @@
 pub mod app_node;
+pub mod auth;
 pub mod child;
 pub mod definitions;
 pub mod nexus;
 pub mod nexus_child;
 pub mod nexus_persistence;
 pub mod node;
+pub mod operation;
 pub mod pool;
 pub mod registry;
 pub mod replica;
 pub mod snapshots;
 pub mod switchover;
+pub mod usage;
 pub mod volume;
 pub mod watch;
 
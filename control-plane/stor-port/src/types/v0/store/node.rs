@@ -6,10 +6,11 @@ use crate::{
             definitions::{ObjectKey, StorableObject, StorableObjectType},
             AsOperationSequencer, OperationSequence, SpecTransaction,
         },
-        transport::{self, HostNqn, NodeId, VolumeId},
+        transport::{self, HostNqn, NodeId, PoolId, VolumeId},
     },
     IntoOption,
 };
+use chrono::{DateTime, Utc};
 use pstor::ApiVersion;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -94,6 +95,32 @@ impl CordonDrainState {
     pub fn cordon(label: &str) -> Self {
         CordonDrainState::Cordoned(CordonedState::new(vec![String::from(label)]))
     }
+    /// Remove the given label from either the cordon or the drain labels.
+    pub fn remove_label(&mut self, label: &str) {
+        match self {
+            CordonDrainState::Cordoned(state) => state.remove_label(label),
+            CordonDrainState::Draining(state) | CordonDrainState::Drained(state) => {
+                state.remove_label(label)
+            }
+        }
+    }
+    /// Get the state which is consistent with the labels, if any labels are left.
+    pub fn resolve(self) -> Option<Self> {
+        match self {
+            CordonDrainState::Cordoned(state) if state.cordonlabels.is_empty() => None,
+            CordonDrainState::Draining(state) | CordonDrainState::Drained(state)
+                if state.drainlabels.is_empty() =>
+            {
+                match state.cordonlabels.is_empty() {
+                    true => None,
+                    false => Some(CordonDrainState::Cordoned(CordonedState::new(
+                        state.cordonlabels,
+                    ))),
+                }
+            }
+            state => Some(state),
+        }
+    }
 
     /// Returns whether the state has the specified cordon label.
     pub fn has_cordon_only_label(&self, label: &str) -> bool {
@@ -162,6 +189,28 @@ pub struct NodeSpec {
     node_nqn: Option<HostNqn>,
     #[serde(default)] // Ensure backwards compatibility.
     draining_volumes: HashSet<VolumeId>,
+    /// Move the volume replicas off the node's pools as well as the volume targets when draining.
+    #[serde(default)] // Ensure backwards compatibility.
+    evacuate: bool,
+    /// Progress of the replicas being moved off the node's pools, per volume.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)] // Ensure backwards compatibility.
+    volumes_drain_progress: HashMap<VolumeId, VolumeDrainProgress>,
+    /// The pools which were drained by the evacuation of the node, with their drain label.
+    /// Only these pool cordons are removed when the node is uncordoned.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)] // Ensure backwards compatibility.
+    evacuated_pools: HashMap<PoolId, String>,
+    /// The node is being decommissioned, its pools and spec are removed by the reconciler.
+    #[serde(default)] // Ensure backwards compatibility.
+    decommissioning: bool,
+    /// Time at which the cordon or drain labels expire, for labels applied with a TTL.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)] // Ensure backwards compatibility.
+    label_expiry: HashMap<String, DateTime<Utc>>,
+    /// Revision of the spec, incremented whenever a change to the spec is committed.
+    #[serde(default)] // Ensure backwards compatibility.
+    revision: u64,
     #[serde(skip)] // Do not store.
     draining_timestamp: Option<SystemTime>,
     /// The operation sequence resource is in.
@@ -188,12 +237,41 @@ impl NodeSpec {
             cordon_drain_state,
             node_nqn,
             draining_volumes: HashSet::new(),
+            evacuate: false,
+            volumes_drain_progress: HashMap::new(),
+            evacuated_pools: HashMap::new(),
+            decommissioning: false,
+            label_expiry: HashMap::new(),
+            revision: 0,
             draining_timestamp: None,
             sequencer: OperationSequence::new(),
             operation: None,
         }
     }
 
+    /// Add the evacuation mode and progress of the drain.
+    pub fn with_evacuation(
+        mut self,
+        evacuate: bool,
+        volumes_drain_progress: HashMap<VolumeId, VolumeDrainProgress>,
+    ) -> Self {
+        self.evacuate = evacuate;
+        self.volumes_drain_progress = volumes_drain_progress;
+        self
+    }
+
+    /// Add the expiry of the cordon and drain labels.
+    pub fn with_label_expiry(mut self, label_expiry: HashMap<String, DateTime<Utc>>) -> Self {
+        self.label_expiry = label_expiry;
+        self
+    }
+
+    /// Add the revision of the spec.
+    pub fn with_revision(mut self, revision: u64) -> Self {
+        self.revision = revision;
+        self
+    }
+
     /// Node Nvme HOSTNQN.
     pub fn node_nqn(&self) -> &Option<HostNqn> {
         &self.node_nqn
@@ -210,6 +288,10 @@ impl NodeSpec {
     pub fn labels(&self) -> &NodeLabels {
         &self.labels
     }
+    /// Revision of the spec, incremented whenever a change to the spec is committed.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
     /// Node labels.
     pub fn cordon_drain_state(&self) -> &Option<CordonDrainState> {
         &self.cordon_drain_state
@@ -251,6 +333,19 @@ impl NodeSpec {
         if !matches!(self.cordon_drain_state, Some(CordonDrainState::Draining(_))) {
             self.remove_all_draining_volumes();
         }
+        if !self.is_draining() && !self.is_drained() {
+            self.evacuate = false;
+            self.volumes_drain_progress.clear();
+        }
+        let labels = self
+            .label_expiry
+            .keys()
+            .filter(|label| !self.has_cordon_label(label))
+            .cloned()
+            .collect::<Vec<_>>();
+        for label in labels {
+            self.label_expiry.remove(&label);
+        }
     }
 
     /// Cordon node by applying the label.
@@ -309,6 +404,58 @@ impl NodeSpec {
         self.resolve();
     }
 
+    /// Drain node by applying the drain label, moving the volume replicas off the node's pools
+    /// as well as the volume targets.
+    pub fn set_evacuate(&mut self, label: String) {
+        self.set_drain(label);
+        self.evacuate = true;
+    }
+
+    /// Returns true if the volume replicas are moved off the node's pools when draining.
+    pub fn evacuate(&self) -> bool {
+        self.evacuate
+    }
+
+    /// Get the progress of the replicas being moved off the node's pools, per volume.
+    pub fn volumes_drain_progress(&self) -> &HashMap<VolumeId, VolumeDrainProgress> {
+        &self.volumes_drain_progress
+    }
+
+    /// Set the progress of the replicas being moved off the node's pools.
+    pub fn set_volumes_drain_progress(&mut self, draining_volumes: DrainingVolumes) {
+        self.volumes_drain_progress = draining_volumes.progress;
+    }
+
+    /// Get the pools which were drained by the evacuation of the node, with their drain label.
+    pub fn evacuated_pools(&self) -> &HashMap<PoolId, String> {
+        &self.evacuated_pools
+    }
+
+    /// Get the pools which were drained by the evacuation of the node with the given label.
+    pub fn evacuated_pools_with_label(&self, label: &str) -> Vec<PoolId> {
+        self.evacuated_pools
+            .iter()
+            .filter(|(_, pool_label)| pool_label.as_str() == label)
+            .map(|(pool, _)| pool.clone())
+            .collect()
+    }
+
+    /// Record a pool which is drained by the evacuation of the node.
+    pub fn add_evacuated_pool(&mut self, evacuated_pool: EvacuatedPool) {
+        self.evacuated_pools
+            .insert(evacuated_pool.pool, evacuated_pool.label);
+    }
+
+    /// Returns true if the node is being decommissioned.
+    pub fn decommissioning(&self) -> bool {
+        self.decommissioning
+    }
+
+    /// Mark the node as being decommissioned.
+    pub fn set_decommissioning(&mut self) {
+        self.decommissioning = true;
+    }
+
     /// Move state from Draining to Drained, no change to the labels.
     pub fn set_drained(&mut self) {
         if let Some(CordonDrainState::Draining(state)) = &mut self.cordon_drain_state {
@@ -335,9 +482,34 @@ impl NodeSpec {
                 // should not be possible
             }
         }
+        self.evacuated_pools
+            .retain(|_, pool_label| pool_label != &label);
         self.resolve();
     }
 
+    /// Get the time at which the cordon and drain labels expire, for labels applied with a TTL.
+    pub fn label_expiry(&self) -> &HashMap<String, DateTime<Utc>> {
+        &self.label_expiry
+    }
+
+    /// Set the time at which the given cordon or drain label expires, if it has an expiry.
+    pub fn set_label_expiry(&mut self, label: String, expiry: Option<DateTime<Utc>>) {
+        if let Some(expiry) = expiry {
+            if self.has_cordon_label(&label) {
+                self.label_expiry.insert(label, expiry);
+            }
+        }
+    }
+
+    /// Get the cordon and drain labels which have expired by the given time.
+    pub fn expired_labels(&self, now: DateTime<Utc>) -> Vec<String> {
+        self.label_expiry
+            .iter()
+            .filter(|(_, expiry)| **expiry <= now)
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+
     /// Returns whether or not the node is cordoned.
     pub fn cordoned(&self) -> bool {
         self.cordon_drain_state.is_some()
@@ -460,6 +632,22 @@ impl From<NodeSpec> for models::NodeSpec {
             labels,
             src.cordon_drain_state.into_opt(),
             src.node_nqn.into_opt(),
+            src.evacuate.then_some(true),
+            (!src.volumes_drain_progress.is_empty()).then(|| {
+                src.volumes_drain_progress
+                    .into_iter()
+                    .map(|(volume, progress)| {
+                        models::DrainingVolume::new_all(volume, progress.remaining, progress.moved)
+                    })
+                    .collect()
+            }),
+            (!src.label_expiry.is_empty()).then(|| {
+                src.label_expiry
+                    .into_iter()
+                    .map(|(label, expiry)| (label, expiry.to_rfc3339()))
+                    .collect()
+            }),
+            src.revision,
         )
     }
 }
@@ -538,24 +726,65 @@ impl StorableObject for NodeSpec {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct DrainingVolumes {
     volumes: HashSet<VolumeId>,
+    /// Progress of the replicas being moved off the node's pools, per volume.
+    #[serde(default)]
+    progress: HashMap<VolumeId, VolumeDrainProgress>,
 }
 impl DrainingVolumes {
     /// Create a new DrainingVolumes object.
     pub fn new(volumes: HashSet<VolumeId>) -> Self {
-        Self { volumes }
+        Self {
+            volumes,
+            progress: HashMap::new(),
+        }
+    }
+    /// Create a new DrainingVolumes object with the progress of each volume.
+    pub fn with_progress(progress: HashMap<VolumeId, VolumeDrainProgress>) -> Self {
+        Self {
+            volumes: progress.keys().cloned().collect(),
+            progress,
+        }
     }
 }
 
+/// Parameter for recording a pool which is drained by the evacuation of a node.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EvacuatedPool {
+    /// The evacuated pool.
+    pub pool: PoolId,
+    /// The drain label which the pool is drained with.
+    pub label: String,
+}
+impl EvacuatedPool {
+    /// Create a new EvacuatedPool object.
+    pub fn new(pool: PoolId, label: String) -> Self {
+        Self { pool, label }
+    }
+}
+
+/// Progress of the replicas of a volume being moved off a draining node's pools.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+pub struct VolumeDrainProgress {
+    /// Number of replicas which are still on the node's pools.
+    pub remaining: u64,
+    /// Number of replicas which have been moved off the node's pools.
+    pub moved: u64,
+}
+
 /// Available Node Operations.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum NodeOperation {
-    Cordon(String),
+    Cordon(String, Option<DateTime<Utc>>),
     Uncordon(String),
-    Drain(String),
+    Drain(String, Option<DateTime<Utc>>),
+    Evacuate(String, Option<DateTime<Utc>>),
     AddDrainingVolumes(DrainingVolumes),
     RemoveDrainingVolumes(DrainingVolumes),
     RemoveAllDrainingVolumes(),
     SetDrained(),
+    SetVolumesDrainProgress(DrainingVolumes),
+    AddEvacuatedPool(EvacuatedPool),
+    Decommission(),
     Label(NodeLabelOp),
     Unlabel(NodeUnLabelOp),
 }
@@ -598,12 +827,19 @@ impl SpecTransaction<NodeOperation> for NodeSpec {
 
     fn commit_op(&mut self) {
         if let Some(op) = self.operation.clone() {
+            self.revision += 1;
             match op.operation {
-                NodeOperation::Cordon(label) => {
-                    self.cordon(label);
+                NodeOperation::Cordon(label, expiry) => {
+                    self.cordon(label.clone());
+                    self.set_label_expiry(label, expiry);
                 }
-                NodeOperation::Drain(label) => {
-                    self.set_drain(label);
+                NodeOperation::Drain(label, expiry) => {
+                    self.set_drain(label.clone());
+                    self.set_label_expiry(label, expiry);
+                }
+                NodeOperation::Evacuate(label, expiry) => {
+                    self.set_evacuate(label.clone());
+                    self.set_label_expiry(label, expiry);
                 }
                 NodeOperation::Uncordon(label) => {
                     self.uncordon(label);
@@ -620,6 +856,15 @@ impl SpecTransaction<NodeOperation> for NodeSpec {
                 NodeOperation::SetDrained() => {
                     self.set_drained();
                 }
+                NodeOperation::SetVolumesDrainProgress(volumes) => {
+                    self.set_volumes_drain_progress(volumes);
+                }
+                NodeOperation::AddEvacuatedPool(evacuated_pool) => {
+                    self.add_evacuated_pool(evacuated_pool);
+                }
+                NodeOperation::Decommission() => {
+                    self.set_decommissioning();
+                }
                 NodeOperation::Label(NodeLabelOp { labels, .. }) => {
                     self.label(labels);
                 }
@@ -650,13 +895,17 @@ impl SpecTransaction<NodeOperation> for NodeSpec {
 
     fn log_op(&self, operation: &NodeOperation) -> (bool, bool) {
         match operation {
-            NodeOperation::Cordon(_) => (false, true),
+            NodeOperation::Cordon(_, _) => (false, true),
             NodeOperation::Uncordon(_) => (false, true),
-            NodeOperation::Drain(_) => (false, true),
+            NodeOperation::Drain(_, _) => (false, true),
+            NodeOperation::Evacuate(_, _) => (false, true),
             NodeOperation::AddDrainingVolumes(_) => (false, true),
             NodeOperation::RemoveDrainingVolumes(_) => (false, true),
             NodeOperation::RemoveAllDrainingVolumes() => (false, true),
             NodeOperation::SetDrained() => (false, true),
+            NodeOperation::SetVolumesDrainProgress(_) => (false, true),
+            NodeOperation::AddEvacuatedPool(_) => (false, true),
+            NodeOperation::Decommission() => (false, true),
             NodeOperation::Label(_) => (false, true),
             NodeOperation::Unlabel(_) => (false, true),
         }
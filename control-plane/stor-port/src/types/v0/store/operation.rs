@@ -0,0 +1,40 @@
+use crate::types::v0::{
+    store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+    transport::{AsyncOperation, AsyncOperationId},
+};
+use pstor::ApiVersion;
+use serde::{Deserialize, Serialize};
+
+/// Key used by the store to uniquely identify an `AsyncOperation`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AsyncOperationKey(AsyncOperationId);
+
+impl From<&AsyncOperationId> for AsyncOperationKey {
+    fn from(id: &AsyncOperationId) -> Self {
+        Self(id.clone())
+    }
+}
+
+impl ObjectKey for AsyncOperationKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::AsyncOperation
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for AsyncOperation {
+    type Key = AsyncOperationKey;
+
+    fn key(&self) -> Self::Key {
+        AsyncOperationKey(self.id.clone())
+    }
+}
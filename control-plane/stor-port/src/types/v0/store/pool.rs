@@ -4,18 +4,19 @@ use crate::types::v0::{
     openapi::models,
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
+        node::{CordonDrainState, DrainState},
         AsOperationSequencer, OperationSequence, SpecStatus, SpecTransaction,
     },
-    transport::{self, CreatePool, NodeId, PoolDeviceUri, PoolId},
+    transport::{self, CreatePool, NodeId, PoolDeviceUri, PoolId, ReplicaId, VolumeId},
 };
 
 // PoolLabel is the type for the labels
 pub type PoolLabel = std::collections::HashMap<String, String>;
 
-use crate::types::v0::transport::ImportPool;
+use crate::{types::v0::transport::ImportPool, IntoOption};
 use pstor::ApiVersion;
 use serde::{Deserialize, Serialize};
-use std::{convert::From, fmt::Debug};
+use std::{collections::HashMap, convert::From, fmt::Debug};
 
 /// Pool data structure used by the persistent store.
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -51,6 +52,9 @@ impl From<&CreatePool> for PoolSpec {
             disks: request.disks.clone(),
             status: PoolSpecStatus::Creating,
             labels: request.labels.clone(),
+            cordon_drain_state: None,
+            drain_progress: None,
+            revision: 0,
             sequencer: OperationSequence::new(),
             operation: None,
         }
@@ -60,6 +64,9 @@ impl PartialEq<CreatePool> for PoolSpec {
     fn eq(&self, other: &CreatePool) -> bool {
         let mut other = PoolSpec::from(other);
         other.status = self.status.clone();
+        other.cordon_drain_state = self.cordon_drain_state.clone();
+        other.drain_progress = self.drain_progress.clone();
+        other.revision = self.revision;
         other.sequencer = self.sequencer.clone();
         &other == self
     }
@@ -78,6 +85,17 @@ pub struct PoolSpec {
     pub status: PoolSpecStatus,
     /// labels to be set on the pool
     pub labels: Option<PoolLabel>,
+    /// Cordon state of the pool, preventing new replicas from being placed on it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)] // Ensure backwards compatibility in etcd when upgrading.
+    pub cordon_drain_state: Option<CordonDrainState>,
+    /// Progress of the drain of the pool, if it's draining or drained.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)] // Ensure backwards compatibility in etcd when upgrading.
+    pub drain_progress: Option<PoolDrainProgress>,
+    /// Revision of the spec, incremented whenever a change to the spec is committed.
+    #[serde(default)]
+    pub revision: u64,
     /// Update in progress
     #[serde(skip)]
     pub sequencer: OperationSequence,
@@ -85,6 +103,111 @@ pub struct PoolSpec {
     pub operation: Option<PoolOperationState>,
 }
 
+impl PoolSpec {
+    /// Cordon the pool by applying the label.
+    pub fn cordon(&mut self, label: String) {
+        match &mut self.cordon_drain_state {
+            Some(state) => state.add_cordon_label(&label),
+            None => self.cordon_drain_state = Some(CordonDrainState::cordon(&label)),
+        }
+    }
+    /// Uncordon the pool by removing the corresponding label, which may also stop the drain.
+    pub fn uncordon(&mut self, label: String) {
+        if let Some(mut state) = self.cordon_drain_state.take() {
+            state.remove_label(&label);
+            self.cordon_drain_state = state.resolve();
+        }
+        if !self.is_draining() && !self.is_drained() {
+            self.drain_progress = None;
+        }
+    }
+    /// Drain the pool by applying the drain label.
+    pub fn drain(&mut self, label: String) {
+        self.cordon_drain_state = Some(match self.cordon_drain_state.take() {
+            None => CordonDrainState::Draining(DrainState::new(vec![], vec![label])),
+            Some(CordonDrainState::Cordoned(state)) => {
+                CordonDrainState::Draining(state.into_drain(&label))
+            }
+            Some(CordonDrainState::Draining(mut state)) => {
+                state.add_drain_label(&label);
+                CordonDrainState::Draining(state)
+            }
+            Some(CordonDrainState::Drained(mut state)) => {
+                state.add_drain_label(&label);
+                CordonDrainState::Drained(state)
+            }
+        });
+        if self.drain_progress.is_none() {
+            self.drain_progress = Some(PoolDrainProgress::default());
+        }
+    }
+    /// Move the pool from draining to drained, no change to the labels.
+    pub fn set_drained(&mut self) {
+        if let Some(CordonDrainState::Draining(state)) = self.cordon_drain_state.take() {
+            self.cordon_drain_state = Some(CordonDrainState::Drained(state));
+        }
+    }
+    /// Returns true if the pool is draining.
+    pub fn is_draining(&self) -> bool {
+        matches!(&self.cordon_drain_state, Some(state) if state.is_draining())
+    }
+    /// Returns true if the pool is drained.
+    pub fn is_drained(&self) -> bool {
+        matches!(&self.cordon_drain_state, Some(state) if state.is_drained())
+    }
+    /// Returns whether or not the pool is cordoned.
+    pub fn cordoned(&self) -> bool {
+        self.cordon_drain_state.is_some()
+    }
+    /// Returns true if the pool has the given cordon label.
+    pub fn has_cordon_label(&self, label: &str) -> bool {
+        match &self.cordon_drain_state {
+            Some(state) => state.has_cordon_only_label(label) || state.has_drain_label(label),
+            None => false,
+        }
+    }
+}
+
+/// A volume replica which is being moved off a draining pool.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DrainingReplica {
+    /// The volume which owns the replica.
+    pub volume: VolumeId,
+    /// The replica count of the volume once the replica has been moved.
+    pub replicas: u8,
+}
+
+/// Progress of the drain of a pool.
+/// This is persisted with the spec so that the drain can resume after a restart.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct PoolDrainProgress {
+    /// The replicas which are being moved off the pool.
+    pub moving: HashMap<ReplicaId, DrainingReplica>,
+    /// The number of replicas which have been moved off the pool.
+    pub moved: u64,
+    /// The replicas on the pool which are not owned by a volume, and so are not moved.
+    pub unowned: Vec<ReplicaId>,
+    /// The volumes which are not published, and so their replicas cannot be moved until they are.
+    #[serde(default)]
+    pub blocked: Vec<VolumeId>,
+}
+
+impl From<PoolDrainProgress> for models::PoolDrainProgress {
+    fn from(src: PoolDrainProgress) -> Self {
+        Self::new_all(
+            src.moving
+                .into_iter()
+                .map(|(replica, moving)| {
+                    models::DrainingReplica::new_all(replica.into(), moving.volume.into())
+                })
+                .collect::<Vec<_>>(),
+            src.moved,
+            src.unowned.into_iter().map(Into::into).collect::<Vec<_>>(),
+            src.blocked.into_iter().map(Into::into).collect::<Vec<_>>(),
+        )
+    }
+}
+
 impl From<&PoolSpec> for ImportPool {
     fn from(value: &PoolSpec) -> Self {
         Self {
@@ -108,7 +231,16 @@ impl AsOperationSequencer for PoolSpec {
 
 impl From<PoolSpec> for models::PoolSpec {
     fn from(src: PoolSpec) -> Self {
-        Self::new_all(src.disks, src.id, src.labels, src.node, src.status)
+        Self::new_all(
+            src.disks,
+            src.id,
+            src.labels,
+            src.node,
+            src.status,
+            src.cordon_drain_state.into_opt(),
+            src.drain_progress.into_opt(),
+            src.revision,
+        )
     }
 }
 
@@ -127,6 +259,7 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
 
     fn commit_op(&mut self) {
         if let Some(op) = self.operation.clone() {
+            self.revision += 1;
             match op.operation {
                 PoolOperation::Destroy => {
                     self.status = SpecStatus::Deleted;
@@ -134,6 +267,24 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
                 PoolOperation::Create => {
                     self.status = SpecStatus::Created(transport::PoolStatus::Online);
                 }
+                PoolOperation::Expand(disks) => {
+                    self.disks.extend(disks);
+                }
+                PoolOperation::Cordon(label) => {
+                    self.cordon(label);
+                }
+                PoolOperation::Uncordon(label) => {
+                    self.uncordon(label);
+                }
+                PoolOperation::Drain(label) => {
+                    self.drain(label);
+                }
+                PoolOperation::SetDrained => {
+                    self.set_drained();
+                }
+                PoolOperation::DrainProgress(progress) => {
+                    self.drain_progress = Some(progress);
+                }
             }
         }
         self.clear_op();
@@ -166,6 +317,12 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
 pub enum PoolOperation {
     Create,
     Destroy,
+    Expand(Vec<PoolDeviceUri>),
+    Cordon(String),
+    Uncordon(String),
+    Drain(String),
+    SetDrained,
+    DrainProgress(PoolDrainProgress),
 }
 
 impl PartialEq<transport::PoolState> for PoolSpec {
@@ -3,18 +3,23 @@ use crate::types::v0::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
         SpecTransaction,
     },
-    transport::{NodeId, VolumeId},
+    transport::{NodeId, SwitchOverHistory, SwitchOverRecord, SwitchOverStageRecord, VolumeId},
 };
 use chrono::{DateTime, Utc};
 use pstor::ApiVersion;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+};
 
 /// Defines operation for SwitchOverSpec.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum Operation {
     /// Initialize switchover request.
     Init,
+    /// Fence the node of the original/old volume target.
+    FenceTarget,
     /// Shutdown original/old volume target. Create new nexus for existing vol obj.
     RepublishVolume,
     /// Send updated path of volume to node-agent.
@@ -67,6 +72,24 @@ pub struct SwitchOverSpec {
     pub reuse_existing: bool,
     /// Publish context of the volume.
     pub publish_context: Option<HashMap<String, String>>,
+    /// Node where the new target should be placed, if requested by the operator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_node: Option<NodeId>,
+    /// The switchover was requested by the operator, rather than by a path failure.
+    #[serde(default)]
+    pub planned: bool,
+    /// Stages reached so far, in order.
+    #[serde(default)]
+    pub stages: Vec<SwitchOverStageRecord>,
+    /// The last error encountered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Node of the volume target when the switchover was initialized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_node: Option<NodeId>,
+    /// The volume is in use by the frontend node.
+    #[serde(default)]
+    pub io_active: bool,
 }
 
 impl SwitchOverSpec {
@@ -147,7 +170,8 @@ impl SpecTransaction<Operation> for SwitchOverSpec {
     fn commit_op(&mut self) {
         let next_op = if let Some(op) = self.operation.clone() {
             match op.operation {
-                Operation::Init => Some(Operation::RepublishVolume),
+                Operation::Init => Some(Operation::FenceTarget),
+                Operation::FenceTarget => Some(Operation::RepublishVolume),
                 Operation::RepublishVolume => Some(Operation::ReplacePath),
                 Operation::ReplacePath => Some(Operation::DeleteTarget),
                 Operation::DeleteTarget => Some(Operation::Successful),
@@ -184,3 +208,69 @@ impl SpecTransaction<Operation> for SwitchOverSpec {
         self.operation.as_ref().map(|o| &o.operation)
     }
 }
+
+/// Bounded switchover history of a volume, which keeps the records of completed switchovers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SwitchOverHistorySpec {
+    /// The volume these records belong to.
+    volume: VolumeId,
+    /// The switchover records, oldest first.
+    records: VecDeque<SwitchOverRecord>,
+}
+
+impl SwitchOverHistorySpec {
+    /// Return a new empty `Self` for the given volume.
+    pub fn new(volume: VolumeId) -> Self {
+        Self {
+            volume,
+            records: VecDeque::new(),
+        }
+    }
+    /// Add a new record, dropping the oldest records so that at most `max_records` are kept.
+    pub fn add(&mut self, record: SwitchOverRecord, max_records: usize) {
+        self.records.push_back(record);
+        while self.records.len() > max_records {
+            self.records.pop_front();
+        }
+    }
+    /// Get the `SwitchOverHistory` of the volume.
+    pub fn history(&self) -> SwitchOverHistory {
+        SwitchOverHistory {
+            volume: self.volume.clone(),
+            records: self.records.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Persistent Store key for `SwitchOverHistorySpec`.
+pub struct SwitchOverHistoryKey(VolumeId);
+
+impl SwitchOverHistoryKey {
+    pub fn new(id: VolumeId) -> Self {
+        SwitchOverHistoryKey(id)
+    }
+}
+
+impl StorableObject for SwitchOverHistorySpec {
+    type Key = SwitchOverHistoryKey;
+
+    fn key(&self) -> Self::Key {
+        SwitchOverHistoryKey(self.volume.clone())
+    }
+}
+
+impl ObjectKey for SwitchOverHistoryKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::VolumeSwitchOverHistory
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
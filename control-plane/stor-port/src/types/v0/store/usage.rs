@@ -0,0 +1,155 @@
+use crate::types::v0::{
+    store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+    transport::{UsageHistory, UsageResource, UsageRollup, UsageSample},
+};
+use pstor::ApiVersion;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Bounds of a `UsageSeries`.
+#[derive(Debug, Clone, Copy)]
+pub struct UsageSeriesBounds {
+    /// Maximum number of raw samples to keep.
+    pub samples: usize,
+    /// Number of raw samples aggregated into each rollup.
+    pub rollup_samples: u32,
+    /// Maximum number of rollups to keep.
+    pub rollups: usize,
+}
+
+/// Bounded time-series of the usage of a resource.
+/// The most recent samples are kept at full resolution, and every `rollup_samples` samples are
+/// also aggregated into a rollup, which allows us to keep a longer history with less memory.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UsageSeries {
+    /// The resource this series belongs to.
+    resource: UsageResource,
+    /// The most recent samples.
+    samples: VecDeque<UsageSample>,
+    /// The completed rollups.
+    rollups: VecDeque<UsageRollup>,
+    /// The rollup which is still aggregating samples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending: Option<UsageRollup>,
+}
+
+impl UsageSeries {
+    /// Return a new empty `Self` for the given resource.
+    pub fn new(resource: UsageResource) -> Self {
+        Self {
+            resource,
+            samples: VecDeque::new(),
+            rollups: VecDeque::new(),
+            pending: None,
+        }
+    }
+    /// Get the resource this series belongs to.
+    pub fn resource(&self) -> &UsageResource {
+        &self.resource
+    }
+    /// Add a new sample, dropping the oldest entries which no longer fit within the `bounds`.
+    /// Returns true if a new rollup was completed.
+    pub fn add(&mut self, sample: UsageSample, bounds: &UsageSeriesBounds) -> bool {
+        let pending = match self.pending.as_mut() {
+            Some(pending) => {
+                pending.add(&sample);
+                pending
+            }
+            None => self.pending.insert(UsageRollup::from(&sample)),
+        };
+        let rolled_up = pending.samples >= bounds.rollup_samples;
+        if rolled_up {
+            if let Some(rollup) = self.pending.take() {
+                self.rollups.push_back(rollup);
+            }
+        }
+
+        self.samples.push_back(sample);
+        while self.samples.len() > bounds.samples {
+            self.samples.pop_front();
+        }
+        while self.rollups.len() > bounds.rollups {
+            self.rollups.pop_front();
+        }
+        rolled_up
+    }
+    /// Get the `UsageHistory` of this series.
+    pub fn history(&self) -> UsageHistory {
+        UsageHistory {
+            samples: self.samples.iter().cloned().collect(),
+            rollups: self.rollups.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Key used by the store to uniquely identify a `UsageSeries`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UsageSeriesKey(UsageResource);
+
+impl From<&UsageResource> for UsageSeriesKey {
+    fn from(resource: &UsageResource) -> Self {
+        Self(resource.clone())
+    }
+}
+
+impl ObjectKey for UsageSeriesKey {
+    type Kind = StorableObjectType;
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V0
+    }
+
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::UsageHistory
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for UsageSeries {
+    type Key = UsageSeriesKey;
+
+    fn key(&self) -> Self::Key {
+        UsageSeriesKey(self.resource.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::v0::transport::PoolId;
+
+    #[test]
+    fn usage_series_bounds() {
+        let bounds = UsageSeriesBounds {
+            samples: 3,
+            rollup_samples: 2,
+            rollups: 2,
+        };
+        let mut series = UsageSeries::new(UsageResource::Pool(PoolId::from("p0")));
+
+        let rolled_up = (1 ..= 7)
+            .map(|used| series.add(UsageSample::now(100, used * 10), &bounds))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rolled_up,
+            vec![false, true, false, true, false, true, false]
+        );
+
+        let history = series.history();
+        let used = history.samples.iter().map(|s| s.used).collect::<Vec<_>>();
+        assert_eq!(used, vec![50, 60, 70]);
+
+        // the first rollup of (10, 20) has been dropped
+        assert_eq!(history.rollups.len(), 2);
+        let rollup = &history.rollups[0];
+        assert_eq!(rollup.samples, 2);
+        assert_eq!(rollup.used_min, 30);
+        assert_eq!(rollup.used_max, 40);
+        assert_eq!(rollup.used_avg, 35);
+        assert_eq!(rollup.capacity, 100);
+        assert_eq!(history.rollups[1].used_avg, 55);
+    }
+}
@@ -4,13 +4,14 @@ use crate::{
     types::v0::{
         openapi::models,
         store::{
+            auth::NvmfAuthRef,
             definitions::{ObjectKey, StorableObject, StorableObjectType},
             AsOperationSequencer, OperationSequence, SpecStatus, SpecTransaction,
         },
         transport::{
             self, AffinityGroup, CreateVolume, HostNqn, NexusId, NexusNvmfConfig, NodeId,
-            ReplicaId, SnapshotId, Topology, VolumeId, VolumeLabels, VolumePolicy, VolumeProperty,
-            VolumeShareProtocol, VolumeStatus,
+            ReplicaId, SnapshotId, TargetPlacement, Topology, VolumeAutoGrow, VolumeId,
+            VolumeLabels, VolumePolicy, VolumeProperty, VolumeShareProtocol, VolumeStatus,
         },
     },
     IntoOption,
@@ -49,11 +50,58 @@ impl ObjectKey for VolumeStateKey {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct FrontendConfig {
     host_acl: Vec<InitiatorAC>,
+    /// Allows more than one frontend node to attach the target, eg: for multi-writer raw block
+    /// access. The host access list is always enforced for such targets, so that detached
+    /// nodes are fenced off.
+    #[serde(default)]
+    multi_node: bool,
+    /// The frontend nodes may only read from the target.
+    /// The io-engine has no read-only share, so this is enforced by the frontend nodes.
+    #[serde(default)]
+    read_only: bool,
 }
 impl FrontendConfig {
     /// Create new `Self` based on the host access list.
     pub fn from_acls(host_acl: Vec<InitiatorAC>) -> Self {
-        Self { host_acl }
+        Self {
+            host_acl,
+            multi_node: false,
+            read_only: false,
+        }
+    }
+    /// Allow more than one frontend node to attach the target.
+    pub fn with_multi_node(mut self, multi_node: bool) -> Self {
+        self.multi_node = multi_node;
+        self
+    }
+    /// Only allow the frontend nodes to read from the target.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+    /// Add the given initiator to the host access list, if not already present.
+    pub fn with_node(mut self, node: InitiatorAC) -> Self {
+        if !self
+            .host_acl
+            .iter()
+            .any(|n| n.node_name() == node.node_name())
+        {
+            self.host_acl.push(node);
+        }
+        self
+    }
+    /// Remove the given nodename from the host access list.
+    pub fn without_node(mut self, nodename: &str) -> Self {
+        self.host_acl.retain(|n| n.node_name() != nodename);
+        self
+    }
+    /// Check if more than one frontend node may attach the target.
+    pub fn multi_node(&self) -> bool {
+        self.multi_node
+    }
+    /// Check if the frontend nodes may only read from the target.
+    pub fn read_only(&self) -> bool {
+        self.read_only
     }
     /// Check if the nodename is allowed.
     pub fn nodename_allowed(&self, nodename: &str) -> bool {
@@ -101,6 +149,18 @@ impl InitiatorAC {
     pub fn node_nqn(&self) -> &HostNqn {
         &self.node_nqn
     }
+    /// Get the reservation key which the front-end initiator registers with.
+    /// It's derived from the hostnqn (FNV-1a) so it remains stable across target republishes.
+    pub fn resv_key(&self) -> u64 {
+        let key = self
+            .node_nqn
+            .to_string()
+            .bytes()
+            .fold(0xcbf29ce484222325u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+        key.max(1)
+    }
 }
 
 /// Volume Target (node and nexus)
@@ -157,6 +217,8 @@ impl From<&TargetConfig> for models::VolumeTarget {
             src.target.node.clone(),
             src.target.protocol.into_opt(),
             node_names,
+            Some(src.frontend.multi_node),
+            Some(src.frontend.read_only),
         )
     }
 }
@@ -209,6 +271,18 @@ pub struct VolumeSpec {
     /// Max snapshots limit per volume.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_snapshots: Option<u32>,
+    /// Automatic volume expansion policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autogrow: Option<VolumeAutoGrow>,
+    /// DH-HMAC-CHAP secrets of the volume target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<NvmfAuthRef>,
+    /// Volume target placement policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_placement: Option<TargetPlacement>,
+    /// Revision of the spec, incremented whenever a change to the spec is committed.
+    #[serde(default)]
+    pub revision: u64,
 }
 
 /// Volume Content Source i.e the snapshot or a volume.
@@ -294,6 +368,9 @@ pub struct TargetConfig {
     /// Config of frontend-nodes where IO will be sent from.
     #[serde(default)]
     frontend: FrontendConfig,
+    /// The DH-HMAC-CHAP secrets which the target was shared with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth: Option<NvmfAuthRef>,
 }
 
 /// Default value for the active field in TargetConfig.
@@ -309,8 +386,19 @@ impl TargetConfig {
             active: true,
             config,
             frontend,
+            auth: None,
         }
     }
+    /// Preempt the reservation registration of the given key when the target is created.
+    pub fn with_preempt_key(mut self, key: u64) -> Self {
+        self.config = self.config.with_preempt_key(key);
+        self
+    }
+    /// Set the DH-HMAC-CHAP secrets which the target is shared with.
+    pub fn with_auth(mut self, auth: Option<NvmfAuthRef>) -> Self {
+        self.auth = auth;
+        self
+    }
 
     /// Get the last target configuration.
     /// # Note: It may or may not the the current active target.
@@ -336,6 +424,10 @@ impl TargetConfig {
     pub fn config(&self) -> &NexusNvmfConfig {
         &self.config
     }
+    /// Get the DH-HMAC-CHAP secrets which the target was shared with.
+    pub fn auth(&self) -> Option<&NvmfAuthRef> {
+        self.auth.as_ref()
+    }
 }
 
 impl AsOperationSequencer for VolumeSpec {
@@ -435,6 +527,26 @@ impl VolumeSpec {
     pub fn set_content_source(&mut self, content_source: Option<VolumeContentSource>) {
         self.content_source = content_source;
     }
+    /// Get the automatic expansion policy, if any.
+    pub fn autogrow(&self) -> Option<&VolumeAutoGrow> {
+        self.autogrow.as_ref()
+    }
+    /// Get the DH-HMAC-CHAP secrets reference, if any.
+    pub fn auth(&self) -> Option<&NvmfAuthRef> {
+        self.auth.as_ref()
+    }
+    /// Get the DH-HMAC-CHAP secrets reference which the active target is shared with, if any.
+    /// After a rotation, this refers to the previous secrets until the target is republished.
+    pub fn target_auth(&self) -> Option<&NvmfAuthRef> {
+        match self.active_config() {
+            Some(config) => config.auth(),
+            None => self.auth(),
+        }
+    }
+    /// Get the target placement policy.
+    pub fn target_placement(&self) -> TargetPlacement {
+        self.target_placement.unwrap_or_default()
+    }
 }
 
 /// Operation State for a Volume resource.
@@ -459,6 +571,9 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
 
     fn commit_op(&mut self) {
         if let Some(op) = self.operation.take() {
+            if op.operation.updates_revision() {
+                self.revision += 1;
+            }
             match op.operation {
                 VolumeOperation::Destroy => {
                     self.status = SpecStatus::Deleted;
@@ -513,7 +628,18 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
                     VolumeProperty::MaxSnapshots(max_snapshots) => {
                         self.max_snapshots = Some(max_snapshots);
                     }
+                    VolumeProperty::AutoGrow(autogrow) => {
+                        self.autogrow = autogrow;
+                    }
                 },
+                VolumeOperation::RotateAuth(auth) => {
+                    self.auth = Some(auth);
+                }
+                VolumeOperation::SetFrontendNodes(frontend) => {
+                    if let Some(cfg) = self.target_config.as_mut() {
+                        cfg.frontend = frontend;
+                    }
+                }
             }
         }
         self.clear_op();
@@ -579,6 +705,20 @@ pub enum VolumeOperation {
     DestroySnapshot(SnapshotId),
     Resize(u64),
     SetVolumeProperty(VolumeProperty),
+    RotateAuth(NvmfAuthRef),
+    SetFrontendNodes(FrontendConfig),
+}
+
+impl VolumeOperation {
+    /// Check if committing the operation bumps the spec revision.
+    /// The snapshot and unused replica operations are not persisted on completion, and
+    /// so they must not change the revision either.
+    fn updates_revision(&self) -> bool {
+        !matches!(
+            self,
+            Self::CreateSnapshot(_) | Self::DestroySnapshot(_) | Self::RemoveUnusedReplica(_)
+        )
+    }
 }
 
 #[test]
@@ -678,9 +818,13 @@ impl From<VolumeOperation> for models::volume_spec_operation::Operation {
                 models::volume_spec_operation::Operation::DestroySnapshot
             }
             VolumeOperation::SetVolumeProperty(_) => {
-                todo!()
+                models::volume_spec_operation::Operation::SetProperty
             }
             VolumeOperation::Resize(_) => models::volume_spec_operation::Operation::Resize,
+            VolumeOperation::RotateAuth(_) => models::volume_spec_operation::Operation::RotateAuth,
+            VolumeOperation::SetFrontendNodes(_) => {
+                models::volume_spec_operation::Operation::SetFrontendNodes
+            }
         }
     }
 }
@@ -739,6 +883,12 @@ impl From<&CreateVolume> for VolumeSpec {
             publish_context: None,
             affinity_group: request.affinity_group.clone(),
             max_snapshots: request.max_snapshots,
+            autogrow: request.autogrow.clone(),
+            auth: request
+                .auth
+                .as_ref()
+                .map(|_| NvmfAuthRef::new(&request.uuid, 0)),
+            target_placement: request.target_placement,
             ..Default::default()
         }
     }
@@ -749,6 +899,7 @@ impl PartialEq<CreateVolume> for VolumeSpec {
         other.status = self.status.clone();
         other.sequencer = self.sequencer.clone();
         other.content_source = self.content_source.clone();
+        other.revision = self.revision;
         &other == self
     }
 }
@@ -796,6 +947,9 @@ impl From<VolumeSpec> for models::VolumeSpec {
             src.content_source.into_opt(),
             src.num_snapshots,
             src.max_snapshots,
+            src.autogrow.into_opt(),
+            src.target_placement.into_opt(),
+            src.revision,
         )
     }
 }
@@ -1,6 +1,6 @@
 use super::*;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, net::SocketAddr};
+use std::{collections::HashMap, net::SocketAddr, time::SystemTime};
 
 #[derive(Debug)]
 pub struct NodeAgentInfo {
@@ -215,3 +215,112 @@ impl ReplacePath {
         self.publish_context.clone()
     }
 }
+
+/// Move the target of a published volume to another node, eg: ahead of a planned maintenance
+/// of the node where the target currently resides.
+#[derive(Debug, Clone)]
+pub struct SwitchOverVolume {
+    volume: VolumeId,
+    target_node: Option<NodeId>,
+}
+
+impl SwitchOverVolume {
+    /// Create a new `Self` to move the volume target to the given node, or to a node chosen by
+    /// the control-plane if none is given.
+    pub fn new(volume: VolumeId, target_node: Option<NodeId>) -> Self {
+        Self {
+            volume,
+            target_node,
+        }
+    }
+
+    /// Get the volume whose target is moved.
+    pub fn volume(&self) -> &VolumeId {
+        &self.volume
+    }
+
+    /// Get the node where the volume target should be moved to.
+    pub fn target_node(&self) -> Option<&NodeId> {
+        self.target_node.as_ref()
+    }
+}
+
+/// Get the switchover history of a volume.
+#[derive(Debug, Clone)]
+pub struct GetSwitchOverHistory {
+    volume: VolumeId,
+}
+
+impl GetSwitchOverHistory {
+    /// Create a new `Self` for the given volume.
+    pub fn new(volume: VolumeId) -> Self {
+        Self { volume }
+    }
+
+    /// Get the volume whose switchover history we want.
+    pub fn volume(&self) -> &VolumeId {
+        &self.volume
+    }
+}
+
+/// Status of a volume target switchover.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SwitchOverStatus {
+    /// The switchover is still being worked on.
+    InProgress,
+    /// The volume target was switched over successfully.
+    Successful,
+    /// The switchover was cancelled after an unrecoverable error.
+    Errored,
+}
+
+/// A stage reached by a switchover.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchOverStageRecord {
+    /// Name of the stage.
+    pub stage: String,
+    /// Time at which the stage was reached.
+    pub timestamp: SystemTime,
+}
+
+/// Record of a volume target switchover.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchOverRecord {
+    /// The frontend node which is connected to the volume target.
+    pub frontend_node: NodeId,
+    /// The nqn of the path which was switched over.
+    pub nqn: String,
+    /// The switchover was requested by the operator, rather than by a path failure.
+    pub planned: bool,
+    /// The node where the new target was requested to be placed, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_node: Option<NodeId>,
+    /// New nexus path of the volume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_path: Option<String>,
+    /// Current or final status of the switchover.
+    pub status: SwitchOverStatus,
+    /// Time at which the switchover was started.
+    pub start_time: SystemTime,
+    /// Time at which the switchover completed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<SystemTime>,
+    /// Stages reached by the switchover, in order.
+    pub stages: Vec<SwitchOverStageRecord>,
+    /// The last error encountered by the switchover, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Switchover history of a volume, oldest records first.
+/// A switchover which is still in progress is the last record.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchOverHistory {
+    /// The volume these records belong to.
+    pub volume: VolumeId,
+    /// The switchover records.
+    pub records: Vec<SwitchOverRecord>,
+}
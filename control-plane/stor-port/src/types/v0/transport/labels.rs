@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// A Kubernetes-style label selector, eg: "tier=gold,zone in (a,b),!legacy".
+/// The labels match the selector if they meet all of its requirements, and so an empty selector
+/// matches any labels.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct LabelSelector {
+    requirements: Vec<LabelRequirement>,
+}
+
+impl LabelSelector {
+    /// Create a new `Self` from the given requirements.
+    pub fn new(requirements: Vec<LabelRequirement>) -> Self {
+        Self { requirements }
+    }
+    /// Get the requirements of the selector.
+    pub fn requirements(&self) -> &Vec<LabelRequirement> {
+        &self.requirements
+    }
+    /// Check if the selector has no requirements.
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+    /// Check if the `labels` meet all requirements, where missing labels are treated as empty.
+    pub fn matches(&self, labels: Option<&HashMap<String, String>>) -> bool {
+        self.requirements
+            .iter()
+            .all(|requirement| requirement.matches(labels))
+    }
+}
+
+/// A single requirement of a `LabelSelector`.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct LabelRequirement {
+    key: String,
+    operator: LabelOperator,
+    values: Vec<String>,
+}
+
+impl LabelRequirement {
+    /// Create a new `Self` from the given parameters.
+    /// The `values` are only meaningful for the `Equals`, `NotEquals`, `In` and `NotIn` operators.
+    pub fn new(key: impl Into<String>, operator: LabelOperator, values: Vec<String>) -> Self {
+        Self {
+            key: key.into(),
+            operator,
+            values,
+        }
+    }
+    /// Get the label key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+    /// Get the operator which is applied to the label.
+    pub fn operator(&self) -> LabelOperator {
+        self.operator
+    }
+    /// Get the values which the operator is applied with.
+    pub fn values(&self) -> &Vec<String> {
+        &self.values
+    }
+    /// Check if the `labels` meet the requirement.
+    /// As with Kubernetes, a missing label meets the `NotEquals` and `NotIn` requirements.
+    pub fn matches(&self, labels: Option<&HashMap<String, String>>) -> bool {
+        let value = labels.and_then(|labels| labels.get(&self.key));
+        let listed = value.map_or(false, |value| self.values.contains(value));
+        match self.operator {
+            LabelOperator::Equals | LabelOperator::In => listed,
+            LabelOperator::NotEquals | LabelOperator::NotIn => !listed,
+            LabelOperator::Exists => value.is_some(),
+            LabelOperator::DoesNotExist => value.is_none(),
+        }
+    }
+}
+
+/// The operator of a `LabelRequirement`.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LabelOperator {
+    /// The label has the value, ie: "key=value" or "key==value".
+    Equals,
+    /// The label does not have the value, ie: "key!=value".
+    NotEquals,
+    /// The label has one of the values, ie: "key in (a,b)".
+    In,
+    /// The label has none of the values, ie: "key notin (a,b)".
+    NotIn,
+    /// The label is present, ie: "key".
+    Exists,
+    /// The label is not present, ie: "!key".
+    DoesNotExist,
+}
+
+/// Errors encountered when parsing a `LabelSelector`.
+#[derive(Debug, Eq, PartialEq)]
+pub enum LabelSelectorParseError {
+    /// A requirement is empty, eg: "a=b,,c=d".
+    EmptyRequirement,
+    /// The parentheses of a set requirement are not balanced.
+    UnbalancedParentheses,
+    /// The key is empty or contains reserved characters.
+    InvalidKey(String),
+    /// The value contains reserved characters.
+    InvalidValue(String),
+    /// The set operator is neither "in" nor "notin".
+    InvalidOperator(String),
+    /// The set of values is empty.
+    EmptySet,
+}
+impl Display for LabelSelectorParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyRequirement => write!(f, "Invalid label selector, empty requirement"),
+            Self::UnbalancedParentheses => {
+                write!(f, "Invalid label selector, unbalanced parentheses")
+            }
+            Self::InvalidKey(key) => write!(f, "Invalid label selector, invalid key '{key}'"),
+            Self::InvalidValue(value) => {
+                write!(f, "Invalid label selector, invalid value '{value}'")
+            }
+            Self::InvalidOperator(operator) => {
+                write!(f, "Invalid label selector, unknown operator '{operator}'")
+            }
+            Self::EmptySet => write!(f, "Invalid label selector, empty set of values"),
+        }
+    }
+}
+
+/// Check if the label `token` is free of whitespace and of the selector's reserved characters.
+fn valid_token(token: &str) -> bool {
+    !token
+        .chars()
+        .any(|c| c.is_whitespace() || "=!(),".contains(c))
+}
+fn parse_key(key: &str) -> Result<String, LabelSelectorParseError> {
+    let key = key.trim();
+    match !key.is_empty() && valid_token(key) {
+        true => Ok(key.to_string()),
+        false => Err(LabelSelectorParseError::InvalidKey(key.to_string())),
+    }
+}
+fn parse_value(value: &str) -> Result<String, LabelSelectorParseError> {
+    let value = value.trim();
+    match valid_token(value) {
+        true => Ok(value.to_string()),
+        false => Err(LabelSelectorParseError::InvalidValue(value.to_string())),
+    }
+}
+
+impl FromStr for LabelRequirement {
+    type Err = LabelSelectorParseError;
+
+    fn from_str(requirement: &str) -> Result<Self, Self::Err> {
+        let requirement = requirement.trim();
+        if requirement.is_empty() {
+            return Err(LabelSelectorParseError::EmptyRequirement);
+        }
+        if let Some((head, set)) = requirement.split_once('(') {
+            let Some(set) = set.strip_suffix(')') else {
+                return Err(LabelSelectorParseError::UnbalancedParentheses);
+            };
+            let [key, operator] = head.split_whitespace().collect::<Vec<_>>()[..] else {
+                return Err(LabelSelectorParseError::InvalidOperator(
+                    head.trim().to_string(),
+                ));
+            };
+            let operator = match operator {
+                "in" => LabelOperator::In,
+                "notin" => LabelOperator::NotIn,
+                _ => {
+                    return Err(LabelSelectorParseError::InvalidOperator(
+                        operator.to_string(),
+                    ))
+                }
+            };
+            let values = set
+                .split(',')
+                .map(parse_value)
+                .collect::<Result<Vec<_>, _>>()?;
+            if values.iter().all(String::is_empty) {
+                return Err(LabelSelectorParseError::EmptySet);
+            }
+            return Ok(Self::new(parse_key(key)?, operator, values));
+        }
+
+        let (key, operator, values) = if let Some(key) = requirement.strip_prefix('!') {
+            (key, LabelOperator::DoesNotExist, vec![])
+        } else if let Some((key, value)) = requirement.split_once("!=") {
+            (key, LabelOperator::NotEquals, vec![parse_value(value)?])
+        } else if let Some((key, value)) = requirement
+            .split_once("==")
+            .or_else(|| requirement.split_once('='))
+        {
+            (key, LabelOperator::Equals, vec![parse_value(value)?])
+        } else {
+            (requirement, LabelOperator::Exists, vec![])
+        };
+        Ok(Self::new(parse_key(key)?, operator, values))
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = LabelSelectorParseError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        if selector.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        // the requirements are separated by commas, which may also separate the values of a set
+        let mut requirements = vec![];
+        let mut depth = 0;
+        let mut start = 0;
+        for (index, c) in selector.char_indices() {
+            match c {
+                '(' if depth == 0 => depth += 1,
+                ')' if depth == 1 => depth -= 1,
+                '(' | ')' => return Err(LabelSelectorParseError::UnbalancedParentheses),
+                ',' if depth == 0 => {
+                    requirements.push(selector[start .. index].parse()?);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        if depth != 0 {
+            return Err(LabelSelectorParseError::UnbalancedParentheses);
+        }
+        requirements.push(selector[start ..].parse()?);
+        Ok(Self::new(requirements))
+    }
+}
+
+impl Display for LabelRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let key = &self.key;
+        let values = self.values.join(",");
+        match self.operator {
+            LabelOperator::Equals => write!(f, "{key}={values}"),
+            LabelOperator::NotEquals => write!(f, "{key}!={values}"),
+            LabelOperator::In => write!(f, "{key} in ({values})"),
+            LabelOperator::NotIn => write!(f, "{key} notin ({values})"),
+            LabelOperator::Exists => write!(f, "{key}"),
+            LabelOperator::DoesNotExist => write!(f, "!{key}"),
+        }
+    }
+}
+
+impl Display for LabelSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let requirements = self
+            .requirements
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{}", requirements.join(","))
+    }
+}
+
+#[test]
+fn label_selector() {
+    let selector =
+        LabelSelector::from_str("tier=gold, zone in (a, b),!legacy,env!=dev,app").unwrap();
+    assert_eq!(
+        selector.to_string(),
+        "tier=gold,zone in (a,b),!legacy,env!=dev,app"
+    );
+    assert_eq!(selector.requirements().len(), 5);
+
+    let labels = |labels: &[(&str, &str)]| {
+        labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>()
+    };
+    let matching = labels(&[("tier", "gold"), ("zone", "b"), ("app", "")]);
+    assert!(selector.matches(Some(&matching)));
+    let wrong_zone = labels(&[("tier", "gold"), ("zone", "c"), ("app", "")]);
+    assert!(!selector.matches(Some(&wrong_zone)));
+    let legacy = labels(&[("tier", "gold"), ("zone", "a"), ("app", ""), ("legacy", "")]);
+    assert!(!selector.matches(Some(&legacy)));
+    let dev = labels(&[("tier", "gold"), ("zone", "a"), ("app", ""), ("env", "dev")]);
+    assert!(!selector.matches(Some(&dev)));
+    assert!(!selector.matches(None));
+
+    let selector = LabelSelector::from_str("env notin (dev,test),!legacy").unwrap();
+    assert!(selector.matches(None));
+    assert!(!selector.matches(Some(&labels(&[("env", "test")]))));
+    assert!(LabelSelector::from_str(" ").unwrap().matches(None));
+    assert_eq!(
+        LabelSelector::from_str("a==b").unwrap(),
+        LabelSelector::from_str("a=b").unwrap()
+    );
+
+    let error = |selector: &str| LabelSelector::from_str(selector).unwrap_err();
+    assert_eq!(error("a=b,,c"), LabelSelectorParseError::EmptyRequirement);
+    assert_eq!(
+        error("a in (b"),
+        LabelSelectorParseError::UnbalancedParentheses
+    );
+    assert_eq!(
+        error("a in ((b))"),
+        LabelSelectorParseError::UnbalancedParentheses
+    );
+    assert_eq!(error("a in ()"), LabelSelectorParseError::EmptySet);
+    assert_eq!(
+        error("a within (b)"),
+        LabelSelectorParseError::InvalidOperator("within".to_string())
+    );
+    assert_eq!(
+        error("=b"),
+        LabelSelectorParseError::InvalidKey(String::new())
+    );
+    assert_eq!(
+        error("a=b=c"),
+        LabelSelectorParseError::InvalidValue("b=c".to_string())
+    );
+}
@@ -48,6 +48,8 @@ pub enum Filter {
     Snapshot(SnapshotId),
     /// Filter by Volume and Snapshot.
     VolumeSnapshot(VolumeId, SnapshotId),
+    /// Filter by the labels of the resource.
+    Labels(LabelSelector),
 }
 impl Default for Filter {
     fn default() -> Self {
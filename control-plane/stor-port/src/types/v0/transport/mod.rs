@@ -3,15 +3,19 @@ pub mod blockdevice;
 pub mod child;
 pub mod cluster_agent;
 pub mod jsongrpc;
+pub mod labels;
 pub mod misc;
 pub mod nexus;
 pub mod node;
+pub mod nvme_auth;
 pub mod nvme_nqn;
+pub mod operation;
 pub mod pool;
 pub mod replica;
 pub mod snapshot;
 pub mod spec;
 pub mod state;
+pub mod usage;
 pub mod volume;
 pub mod watch;
 
@@ -20,15 +24,19 @@ pub use blockdevice::*;
 pub use child::*;
 pub use cluster_agent::*;
 pub use jsongrpc::*;
+pub use labels::*;
 pub use misc::*;
 pub use nexus::*;
 pub use node::*;
+pub use nvme_auth::*;
 pub use nvme_nqn::{NvmeNqn as HostNqn, NvmeNqnParseError as HostNqnParseError, *};
+pub use operation::*;
 pub use pool::*;
 pub use replica::*;
 pub use snapshot::*;
 pub use spec::*;
 pub use state::*;
+pub use usage::*;
 pub use volume::*;
 pub use watch::*;
 
@@ -79,6 +87,13 @@ pub enum MessageIdVs {
     DestroyPool,
     /// Import Pool.
     ImportPool,
+    /// Expand Pool.
+    ExpandPool,
+    /// Cordon a pool.
+    CordonPool,
+    /// Uncordon a pool.
+    UncordonPool,
+    DrainPool,
     /// Get replicas with filter.
     GetReplicas,
     /// Create Replica.
@@ -151,6 +166,14 @@ pub enum MessageIdVs {
     SetVolumeReplica,
     /// Set volume property.
     SetVolumeProperty,
+    /// Get the volume target authentication secrets.
+    GetVolumeAuth,
+    /// Rotate the volume target authentication secrets.
+    RotateVolumeAuth,
+    /// Attach another frontend node to a multi-node volume.
+    AddVolumeFrontendNode,
+    /// Detach a frontend node from a multi-node volume.
+    RemoveVolumeFrontendNode,
     /// Create volume snapshot.
     CreateVolumeSnapshot,
     /// Delete volume snapshot.
@@ -169,14 +192,26 @@ pub enum MessageIdVs {
     GetWatches,
     /// Delete Resource Watch.
     DeleteWatch,
+    /// Watch Resource Events.
+    WatchEvents,
     /// Get Specs.
     GetSpecs,
     /// Get States.
     GetStates,
+    /// Get the usage history of a resource.
+    GetUsageHistory,
+    /// Create or update a long-running operation.
+    PutAsyncOperation,
+    /// Get the long-running operations.
+    GetAsyncOperations,
     /// High Availability Agents.
     RegisterHaNode,
     /// Report failed NVMe paths.
     ReportFailedPaths,
+    /// Move the target of a published volume.
+    SwitchOverVolume,
+    /// Get the switchover history of a volume.
+    GetSwitchOverHistory,
     /// Shutdown Nexus
     ShutdownNexus,
     /// Replace Path
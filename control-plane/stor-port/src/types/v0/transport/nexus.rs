@@ -496,6 +496,12 @@ impl NexusNvmfConfig {
         self.controller_id_range.clone()
     }
 
+    /// Preempt the registration of the given reservation key.
+    pub fn with_preempt_key(mut self, key: u64) -> Self {
+        self.preempt_policy = NexusNvmePreemption::ArgKey(Some(key));
+        self
+    }
+
     /// Disable reservations, mostly useful for testing only.
     pub fn with_no_resv(mut self) -> Self {
         self.reservation_key = 0;
@@ -698,6 +704,9 @@ pub struct ShareNexus {
     pub protocol: NexusShareProtocol,
     /// host nqn's allowed to connect to the target.
     pub allowed_hosts: Vec<HostNqn>,
+    /// DH-HMAC-CHAP secrets which hosts must authenticate with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dhchap: Option<DhChapKeys>,
 }
 impl ShareNexus {
     /// Return new `Self` from the given parameters.
@@ -710,6 +719,11 @@ impl ShareNexus {
             ..Default::default()
         }
     }
+    /// Set the DH-HMAC-CHAP secrets which hosts must authenticate with.
+    pub fn with_dhchap(mut self, dhchap: Option<DhChapKeys>) -> Self {
+        self.dhchap = dhchap;
+        self
+    }
 }
 impl From<&Nexus> for UnshareNexus {
     fn from(from: &Nexus) -> Self {
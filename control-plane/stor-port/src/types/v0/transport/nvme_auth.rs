@@ -0,0 +1,288 @@
+use super::*;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+const DHCHAP_SECRET_PREFIX: &str = "DHHC-1";
+const DHCHAP_SECRET_LEN: usize = 32;
+
+/// An NVMe DH-HMAC-CHAP secret in the representation defined by the NVMe Base Specification,
+/// eg: "DHHC-1:00:<base64 of the secret followed by its crc32>:".
+/// The secret is validated on conversion from a string and is never printed by `Debug`.
+#[derive(Clone, Eq, PartialEq)]
+pub struct DhChapSecret(String);
+
+impl DhChapSecret {
+    /// Generate a new random `Self`, not transformed by any hash function.
+    pub fn generate() -> Self {
+        let mut key = vec![0u8; DHCHAP_SECRET_LEN];
+        rand::thread_rng().fill_bytes(&mut key);
+        let crc = crc32fast::hash(&key);
+        key.extend_from_slice(&crc.to_le_bytes());
+        Self(format!("{DHCHAP_SECRET_PREFIX}:00:{}:", BASE64.encode(key)))
+    }
+    /// Get the secret as a str, which may be passed to the target or to nvme connect.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl std::fmt::Debug for DhChapSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DhChapSecret(<redacted>)")
+    }
+}
+impl Serialize for DhChapSecret {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+impl<'de> Deserialize<'de> for DhChapSecret {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::try_from(s).map_err(|e| serde::de::Error::custom(e.to_string()))
+    }
+}
+impl From<DhChapSecret> for String {
+    fn from(secret: DhChapSecret) -> Self {
+        secret.0
+    }
+}
+impl TryFrom<String> for DhChapSecret {
+    type Error = DhChapSecretParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+impl TryFrom<&str> for DhChapSecret {
+    type Error = DhChapSecretParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let ["DHHC-1", hmac, encoded, ""] = value.split(':').collect::<Vec<_>>()[..] else {
+            return Err(DhChapSecretParseError::InvalidFormat);
+        };
+        // the key length must match the hash function it was transformed with, if any
+        let key_lengths: &[usize] = match hmac {
+            "00" => &[32, 48, 64],
+            "01" => &[32],
+            "02" => &[48],
+            "03" => &[64],
+            _ => return Err(DhChapSecretParseError::InvalidHmac),
+        };
+        let decoded = BASE64
+            .decode(encoded)
+            .map_err(|_| DhChapSecretParseError::InvalidEncoding)?;
+        let Some(key_len) = decoded.len().checked_sub(4) else {
+            return Err(DhChapSecretParseError::InvalidLength);
+        };
+        if !key_lengths.contains(&key_len) {
+            return Err(DhChapSecretParseError::InvalidLength);
+        }
+        let (key, crc) = decoded.split_at(key_len);
+        if crc32fast::hash(key).to_le_bytes() != crc {
+            return Err(DhChapSecretParseError::InvalidCrc);
+        }
+        Ok(Self(value.to_string()))
+    }
+}
+
+/// Errors encountered when parsing a `DhChapSecret`.
+#[derive(Debug, Eq, PartialEq)]
+pub enum DhChapSecretParseError {
+    /// Not in the "DHHC-1:<hmac>:<secret>:" format.
+    InvalidFormat,
+    /// The hmac identifier is not known.
+    InvalidHmac,
+    /// The secret is not valid base64.
+    InvalidEncoding,
+    /// The secret length is not valid for the hmac.
+    InvalidLength,
+    /// The secret crc does not match.
+    InvalidCrc,
+}
+impl Display for DhChapSecretParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            Self::InvalidFormat => "expected format is DHHC-1:<hmac>:<secret>:",
+            Self::InvalidHmac => "unknown hmac identifier",
+            Self::InvalidEncoding => "secret is not base64 encoded",
+            Self::InvalidLength => "invalid secret length",
+            Self::InvalidCrc => "secret crc mismatch",
+        };
+        write!(f, "Invalid DH-HMAC-CHAP secret, {reason}")
+    }
+}
+
+/// The DH-HMAC-CHAP secrets of a volume target.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DhChapKeys {
+    /// Secret used by the host to authenticate itself to the target.
+    pub host: DhChapSecret,
+    /// Secret used by the target to authenticate itself to the host, for bidirectional
+    /// authentication.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ctrl: Option<DhChapSecret>,
+}
+impl DhChapKeys {
+    /// Generate new random host and controller secrets.
+    pub fn generate() -> Self {
+        Self {
+            host: DhChapSecret::generate(),
+            ctrl: Some(DhChapSecret::generate()),
+        }
+    }
+}
+
+impl From<DhChapKeys> for models::DhChapKeys {
+    fn from(keys: DhChapKeys) -> Self {
+        Self::new_all(keys.host, keys.ctrl.map(String::from))
+    }
+}
+
+/// Volume target authentication request.
+/// Any secret which is not supplied is generated, with the exception of the controller secret
+/// which is only generated if the host secret is also generated.
+#[derive(Serialize, Deserialize, Default, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeAuth {
+    /// The host secret.
+    pub host_secret: Option<String>,
+    /// The controller secret.
+    pub ctrl_secret: Option<String>,
+}
+impl std::fmt::Debug for VolumeAuth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let redact = |secret: &Option<String>| secret.as_ref().map(|_| "<redacted>");
+        f.debug_struct("VolumeAuth")
+            .field("host_secret", &redact(&self.host_secret))
+            .field("ctrl_secret", &redact(&self.ctrl_secret))
+            .finish()
+    }
+}
+impl VolumeAuth {
+    /// Return new `Self` from the given parameters.
+    pub fn new(host_secret: Option<String>, ctrl_secret: Option<String>) -> Self {
+        Self {
+            host_secret,
+            ctrl_secret,
+        }
+    }
+    /// Check that the supplied secrets are valid.
+    /// A controller secret may not be supplied without its host secret.
+    pub fn valid(&self) -> bool {
+        let valid = |secret: &Option<String>| {
+            secret
+                .as_deref()
+                .map_or(true, |s| DhChapSecret::try_from(s).is_ok())
+        };
+        (self.host_secret.is_some() || self.ctrl_secret.is_none())
+            && valid(&self.host_secret)
+            && valid(&self.ctrl_secret)
+    }
+    /// Get the `DhChapKeys` for this request, generating the secrets which were not supplied.
+    pub fn keys(&self) -> Result<DhChapKeys, DhChapSecretParseError> {
+        match &self.host_secret {
+            None => Ok(DhChapKeys::generate()),
+            Some(host) => Ok(DhChapKeys {
+                host: DhChapSecret::try_from(host.as_str())?,
+                ctrl: match &self.ctrl_secret {
+                    None => None,
+                    Some(ctrl) => Some(DhChapSecret::try_from(ctrl.as_str())?),
+                },
+            }),
+        }
+    }
+}
+
+impl From<models::VolumeAuth> for VolumeAuth {
+    fn from(auth: models::VolumeAuth) -> Self {
+        Self::new(auth.host_secret, auth.ctrl_secret)
+    }
+}
+impl From<VolumeAuth> for models::VolumeAuth {
+    fn from(auth: VolumeAuth) -> Self {
+        Self::new_all(auth.host_secret, auth.ctrl_secret)
+    }
+}
+
+/// Get the DH-HMAC-CHAP secrets of a volume.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeAuth {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+}
+impl GetVolumeAuth {
+    /// Create a new `GetVolumeAuth` request.
+    pub fn new(uuid: &VolumeId) -> Self {
+        Self { uuid: uuid.clone() }
+    }
+}
+
+/// Rotate the DH-HMAC-CHAP secrets of a volume.
+/// The new secrets are used by the volume target on its next publish or republish.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RotateVolumeAuth {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+    /// The new secrets.
+    pub auth: VolumeAuth,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
+}
+impl RotateVolumeAuth {
+    /// Create a new `RotateVolumeAuth` request.
+    pub fn new(uuid: &VolumeId, auth: VolumeAuth) -> Self {
+        Self {
+            uuid: uuid.clone(),
+            auth,
+            expected_revision: None,
+        }
+    }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dhchap_secret() {
+        let secret = DhChapSecret::generate();
+        assert!(secret.as_str().starts_with("DHHC-1:00:"));
+        assert_eq!(DhChapSecret::try_from(secret.as_str()), Ok(secret.clone()));
+        assert!(!format!("{secret:?}").contains(secret.as_str()));
+
+        let mut corrupted = secret.as_str().to_string();
+        corrupted.replace_range(10 .. 11, if &corrupted[10 .. 11] == "A" { "B" } else { "A" });
+        assert_eq!(
+            DhChapSecret::try_from(corrupted.as_str()),
+            Err(DhChapSecretParseError::InvalidCrc)
+        );
+        let sha256 = secret.as_str().replace(":00:", ":01:");
+        assert!(DhChapSecret::try_from(sha256.as_str()).is_ok());
+        let sha512 = secret.as_str().replace(":00:", ":03:");
+        assert_eq!(
+            DhChapSecret::try_from(sha512.as_str()),
+            Err(DhChapSecretParseError::InvalidLength)
+        );
+        assert_eq!(
+            DhChapSecret::try_from("DHHC-1:00:secret"),
+            Err(DhChapSecretParseError::InvalidFormat)
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+rpc_impl_string_uuid!(AsyncOperationId, "UUID of a long-running operation");
+
+/// Period at which a REST server refreshes the heartbeat of the operations it's running.
+pub const ASYNC_OPERATION_HEARTBEAT: Duration = Duration::from_secs(30);
+/// A running operation which missed three heartbeats has been abandoned, eg: its REST server
+/// was restarted.
+const ASYNC_OPERATION_ABANDONED: Duration = Duration::from_secs(90);
+
+/// A long-running operation, ie: a request which a REST server handles in the background.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncOperation {
+    /// Id of the operation.
+    pub id: AsyncOperationId,
+    /// HTTP method of the request.
+    pub method: String,
+    /// URI of the request.
+    pub uri: String,
+    /// Time at which the operation was started.
+    pub started: SystemTime,
+    /// Time at which the REST server running the operation last refreshed it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<SystemTime>,
+    /// Time at which the operation finished, if it has.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished: Option<SystemTime>,
+    /// The operation was cancelled before it finished.
+    #[serde(default)]
+    pub cancelled: bool,
+    /// Json response body of the operation, if it succeeded with one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// Error of the operation, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<AsyncOperationError>,
+}
+impl AsyncOperation {
+    /// Return a new running `Self` for the request with the given method and uri.
+    pub fn new(method: String, uri: String) -> Self {
+        Self {
+            id: AsyncOperationId::new(),
+            method,
+            uri,
+            started: SystemTime::now(),
+            heartbeat: None,
+            finished: None,
+            cancelled: false,
+            result: None,
+            error: None,
+        }
+    }
+    /// Check if the operation is still running.
+    pub fn running(&self) -> bool {
+        self.finished.is_none()
+    }
+    /// Finish the operation, with its json result if it succeeded or its error if it failed.
+    pub fn finish(&mut self, outcome: Result<Option<String>, AsyncOperationError>) {
+        self.finished = Some(SystemTime::now());
+        match outcome {
+            Ok(result) => self.result = result,
+            Err(error) => self.error = Some(error),
+        }
+    }
+    /// Cancel the running operation.
+    pub fn cancel(&mut self) {
+        self.finished = Some(SystemTime::now());
+        self.cancelled = true;
+    }
+    /// Refresh the heartbeat of the running operation.
+    pub fn beat(&mut self) {
+        self.heartbeat = Some(SystemTime::now());
+    }
+    /// Check if the operation is still running but its REST server stopped refreshing it.
+    pub fn abandoned(&self) -> bool {
+        let since = self.heartbeat.unwrap_or(self.started);
+        self.running() && elapsed(since, ASYNC_OPERATION_ABANDONED)
+    }
+    /// Fail the abandoned operation, as its outcome is unknown.
+    pub fn abandon(&mut self) {
+        self.finish(Err(AsyncOperationError {
+            kind: "Aborted".to_string(),
+            details: "The REST server running the operation stopped refreshing it".to_string(),
+            message: "The operation was abandoned".to_string(),
+        }));
+    }
+    /// Check if the operation finished longer than `ttl` ago.
+    /// Running operations never expire, they're abandoned instead.
+    pub fn expired(&self, ttl: Duration) -> bool {
+        self.finished
+            .map_or(false, |finished| elapsed(finished, ttl))
+    }
+}
+
+/// Check if at least `period` has elapsed since the given time.
+fn elapsed(since: SystemTime, period: Duration) -> bool {
+    SystemTime::now()
+        .duration_since(since)
+        .map(|elapsed| elapsed >= period)
+        .unwrap_or(false)
+}
+
+/// The error of a failed long-running operation, as reported by the REST API.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct AsyncOperationError {
+    /// The kind of the error.
+    pub kind: String,
+    /// Detailed error information.
+    pub details: String,
+    /// Last reported error information.
+    pub message: String,
+}
+
+/// Get the long-running operations.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct GetAsyncOperations {
+    /// Only get the operation with this id, if specified.
+    pub id: Option<AsyncOperationId>,
+}
+impl GetAsyncOperations {
+    /// Return a new `Self` which gets the operation with the given id, or all of them.
+    pub fn new(id: Option<AsyncOperationId>) -> Self {
+        Self { id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn async_operation_expiry() {
+        let ttl = Duration::from_secs(60);
+        let mut operation = AsyncOperation::new("PUT".to_string(), "/v0/volumes".to_string());
+        assert!(operation.running());
+        assert!(!operation.expired(ttl));
+        assert!(!operation.abandoned());
+
+        // a running operation never expires, even once it's older than the ttl
+        operation.started -= ASYNC_OPERATION_ABANDONED;
+        assert!(!operation.expired(ttl));
+        // but it's abandoned unless its heartbeat is refreshed
+        assert!(operation.abandoned());
+        operation.beat();
+        assert!(!operation.abandoned());
+
+        operation.finish(Ok(Some("{}".to_string())));
+        assert!(!operation.running());
+        assert!(!operation.expired(ttl));
+        assert_eq!(operation.result.as_deref(), Some("{}"));
+
+        operation.finished = operation.finished.map(|finished| finished - ttl);
+        assert!(operation.expired(ttl));
+    }
+
+    #[test]
+    fn async_operation_abandoned() {
+        let mut operation = AsyncOperation::new("PUT".to_string(), "/v0/volumes".to_string());
+        operation.started -= ASYNC_OPERATION_ABANDONED;
+        assert!(operation.abandoned());
+        operation.abandon();
+        assert!(!operation.running());
+        assert!(!operation.abandoned());
+        assert!(operation.error.is_some());
+
+        let mut operation = AsyncOperation::new("PUT".to_string(), "/v0/volumes".to_string());
+        operation.cancel();
+        assert!(!operation.running());
+        assert!(operation.cancelled);
+    }
+}
@@ -346,3 +346,34 @@ impl DestroyPool {
         Self { node, id }
     }
 }
+
+/// Expand Pool Request.
+/// Adds more disk devices to an existing pool, growing its capacity.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandPool {
+    /// Id of the io-engine instance.
+    pub node: NodeId,
+    /// Id of the pool.
+    pub id: PoolId,
+    /// Disk device paths or URIs to be added to the pool.
+    pub disks: Vec<PoolDeviceUri>,
+    /// Fail unless the pool spec is at this revision.
+    pub expected_revision: Option<u64>,
+}
+impl ExpandPool {
+    /// Create a new `Self` from the given parameters.
+    pub fn new(node: &NodeId, id: &PoolId, disks: &[PoolDeviceUri]) -> Self {
+        Self {
+            node: node.clone(),
+            id: id.clone(),
+            disks: disks.to_vec(),
+            expected_revision: None,
+        }
+    }
+    /// Fail unless the pool spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// A resource whose capacity usage is sampled over time.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+pub enum UsageResource {
+    /// Usage of a volume, based on its largest healthy replica allocation.
+    Volume(VolumeId),
+    /// Usage of a pool.
+    Pool(PoolId),
+}
+impl std::fmt::Display for UsageResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Volume(id) => write!(f, "volume/{id}"),
+            Self::Pool(id) => write!(f, "pool/{id}"),
+        }
+    }
+}
+
+/// Get the usage history of a resource.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GetUsageHistory {
+    /// The resource whose usage history we want.
+    pub resource: UsageResource,
+}
+impl GetUsageHistory {
+    /// Return a new `Self` for the given resource.
+    pub fn new(resource: UsageResource) -> Self {
+        Self { resource }
+    }
+    /// Get the resource.
+    pub fn resource(&self) -> &UsageResource {
+        &self.resource
+    }
+}
+
+/// A point-in-time sample of the capacity usage of a resource.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSample {
+    /// Time at which the sample was taken.
+    pub timestamp: SystemTime,
+    /// Capacity of the resource in bytes.
+    pub capacity: u64,
+    /// Used capacity of the resource in bytes.
+    pub used: u64,
+}
+impl UsageSample {
+    /// Return a new `Self` taken now.
+    pub fn now(capacity: u64, used: u64) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            capacity,
+            used,
+        }
+    }
+}
+
+/// An aggregate of consecutive usage samples of a resource.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRollup {
+    /// Time of the first sample in the rollup.
+    pub start: SystemTime,
+    /// Time of the last sample in the rollup.
+    pub end: SystemTime,
+    /// Number of samples aggregated into the rollup.
+    pub samples: u32,
+    /// Capacity of the resource in bytes, as of the last sample.
+    pub capacity: u64,
+    /// Minimum used capacity in bytes.
+    pub used_min: u64,
+    /// Maximum used capacity in bytes.
+    pub used_max: u64,
+    /// Average used capacity in bytes.
+    pub used_avg: u64,
+}
+impl From<&UsageSample> for UsageRollup {
+    fn from(sample: &UsageSample) -> Self {
+        Self {
+            start: sample.timestamp,
+            end: sample.timestamp,
+            samples: 1,
+            capacity: sample.capacity,
+            used_min: sample.used,
+            used_max: sample.used,
+            used_avg: sample.used,
+        }
+    }
+}
+impl UsageRollup {
+    /// Aggregate the given sample into this rollup.
+    pub fn add(&mut self, sample: &UsageSample) {
+        let total = self.used_avg as u128 * self.samples as u128 + sample.used as u128;
+        self.samples += 1;
+        self.used_avg = (total / self.samples as u128) as u64;
+        self.used_min = self.used_min.min(sample.used);
+        self.used_max = self.used_max.max(sample.used);
+        self.capacity = sample.capacity;
+        self.end = sample.timestamp;
+    }
+}
+
+/// Usage history of a resource, oldest entries first.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageHistory {
+    /// The most recent samples.
+    pub samples: Vec<UsageSample>,
+    /// Rollups of older samples, covering a longer time span at a lower resolution.
+    pub rollups: Vec<UsageRollup>,
+}
@@ -77,6 +77,110 @@ pub struct VolumeState {
 pub enum VolumeProperty {
     /// Max number of snapshots allowed per volume.
     MaxSnapshots(u32),
+    /// Automatic volume expansion policy, if None the policy is removed.
+    AutoGrow(Option<VolumeAutoGrow>),
+}
+
+/// Automatic volume expansion policy.
+/// When the allocated size of a thin provisioned volume crosses the threshold, the volume is
+/// grown by the given step, up to the maximum size.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct VolumeAutoGrow {
+    /// Allocation threshold, as a percentage of the volume size, which triggers the expansion.
+    threshold_percent: u8,
+    /// Number of bytes to grow the volume by.
+    step: u64,
+    /// The volume is never grown beyond this size in bytes.
+    max_size: u64,
+}
+impl VolumeAutoGrow {
+    /// Return a new `Self` from the given parameters.
+    pub fn new(threshold_percent: u8, step: u64, max_size: u64) -> Self {
+        Self {
+            threshold_percent,
+            step,
+            max_size,
+        }
+    }
+    /// Get the allocation threshold percentage.
+    pub fn threshold_percent(&self) -> u8 {
+        self.threshold_percent
+    }
+    /// Get the growth step in bytes.
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+    /// Get the maximum size in bytes.
+    pub fn max_size(&self) -> u64 {
+        self.max_size
+    }
+    /// Check if the policy parameters are sensible for a volume of the given size.
+    pub fn valid(&self, size: u64) -> bool {
+        (1 ..= 100).contains(&self.threshold_percent) && self.step > 0 && self.max_size >= size
+    }
+    /// Get the size the volume should be grown to, if its allocation has crossed the threshold.
+    pub fn next_size(&self, size: u64, allocated: u64) -> Option<u64> {
+        if size == 0 || size >= self.max_size {
+            return None;
+        }
+        let threshold = (size as u128 * self.threshold_percent as u128 / 100) as u64;
+        if allocated < threshold {
+            return None;
+        }
+        Some(size.saturating_add(self.step).min(self.max_size))
+    }
+}
+#[test]
+fn volume_autogrow_next_size() {
+    let gib = 1024 * 1024 * 1024;
+    let autogrow = VolumeAutoGrow::new(80, gib, 3 * gib);
+    assert!(autogrow.valid(gib));
+    assert!(!autogrow.valid(4 * gib));
+    assert_eq!(autogrow.next_size(gib, gib / 2), None);
+    assert_eq!(autogrow.next_size(gib, gib * 8 / 10), Some(2 * gib));
+    assert_eq!(
+        autogrow.next_size(2 * gib + gib / 2, 2 * gib + gib / 4),
+        Some(3 * gib)
+    );
+    assert_eq!(autogrow.next_size(3 * gib, 3 * gib), None);
+}
+
+impl From<VolumeAutoGrow> for models::VolumeAutoGrow {
+    fn from(src: VolumeAutoGrow) -> Self {
+        Self::new(src.threshold_percent, src.step, src.max_size)
+    }
+}
+impl From<models::VolumeAutoGrow> for VolumeAutoGrow {
+    fn from(src: models::VolumeAutoGrow) -> Self {
+        Self::new(src.threshold_percent, src.step, src.max_size)
+    }
+}
+
+/// Volume target placement policy, used when choosing the node which hosts the volume target.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum TargetPlacement {
+    /// The target may be placed on any suitable node.
+    #[default]
+    Any,
+    /// Prefer the application node, or a node in the same topology zone, avoiding a remote
+    /// NVMe-oF hop when possible.
+    AppNode,
+}
+impl From<TargetPlacement> for models::TargetPlacement {
+    fn from(src: TargetPlacement) -> Self {
+        match src {
+            TargetPlacement::Any => Self::Any,
+            TargetPlacement::AppNode => Self::AppNode,
+        }
+    }
+}
+impl From<models::TargetPlacement> for TargetPlacement {
+    fn from(src: models::TargetPlacement) -> Self {
+        match src {
+            models::TargetPlacement::Any => Self::Any,
+            models::TargetPlacement::AppNode => Self::AppNode,
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
@@ -466,6 +570,12 @@ pub struct CreateVolume {
     pub cluster_capacity_limit: Option<u64>,
     /// Max Snapshots to limit per volume.
     pub max_snapshots: Option<u32>,
+    /// Automatic volume expansion policy.
+    pub autogrow: Option<VolumeAutoGrow>,
+    /// DH-HMAC-CHAP authentication of the volume target.
+    pub auth: Option<VolumeAuth>,
+    /// Volume target placement policy.
+    pub target_placement: Option<TargetPlacement>,
 }
 
 /// Resize volume request.
@@ -478,6 +588,8 @@ pub struct ResizeVolume {
     pub requested_size: u64,
     /// Total capacity limit of all volumes' provisioning.
     pub cluster_capacity_limit: Option<u64>,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl ResizeVolume {
     /// Create a new `ResizeVolume` request.
@@ -486,8 +598,14 @@ impl ResizeVolume {
             uuid,
             requested_size,
             cluster_capacity_limit,
+            expected_revision: None,
         }
     }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
 }
 
 /// Affinity Group related information.
@@ -572,6 +690,14 @@ pub struct PublishVolume {
     pub publish_context: HashMap<String, String>,
     /// Hosts allowed to access nexus.
     pub frontend_nodes: Vec<String>,
+    /// Allows more than one frontend node to attach the volume.
+    #[serde(default)]
+    pub multi_node: bool,
+    /// The frontend nodes may only read from the volume.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl PublishVolume {
     /// Create new `PublishVolume` based on the provided arguments.
@@ -588,8 +714,80 @@ impl PublishVolume {
             share,
             publish_context,
             frontend_nodes,
+            multi_node: false,
+            read_only: false,
+            expected_revision: None,
         }
     }
+    /// Allow more than one frontend node to attach the volume.
+    pub fn with_multi_node(mut self, multi_node: bool) -> Self {
+        self.multi_node = multi_node;
+        self
+    }
+    /// Only allow the frontend nodes to read from the volume.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
+}
+
+/// Allow another frontend node to attach a volume published for multiple frontend nodes.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddVolumeFrontendNode {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+    /// The node where front-end IO will be sent from.
+    pub frontend_node: NodeId,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
+}
+impl AddVolumeFrontendNode {
+    /// Create new `AddVolumeFrontendNode` based on the provided arguments.
+    pub fn new(uuid: VolumeId, frontend_node: NodeId) -> Self {
+        Self {
+            uuid,
+            frontend_node,
+            expected_revision: None,
+        }
+    }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
+}
+
+/// Detach a frontend node from a volume published for multiple frontend nodes.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveVolumeFrontendNode {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+    /// The node where front-end IO was sent from.
+    pub frontend_node: NodeId,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
+}
+impl RemoveVolumeFrontendNode {
+    /// Create new `RemoveVolumeFrontendNode` based on the provided arguments.
+    pub fn new(uuid: VolumeId, frontend_node: NodeId) -> Self {
+        Self {
+            uuid,
+            frontend_node,
+            expected_revision: None,
+        }
+    }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
 }
 
 /// Republishes the target on a new node (pre-selected or determined by the control-plane).
@@ -611,6 +809,8 @@ pub struct RepublishVolume {
     pub reuse_existing: bool,
     /// Allows reusing the existing target, but prefers a target move.
     pub reuse_existing_fallback: bool,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl RepublishVolume {
     /// Create new `RepublishVolume` based on the provided arguments.
@@ -629,8 +829,14 @@ impl RepublishVolume {
             share,
             reuse_existing,
             reuse_existing_fallback,
+            expected_revision: None,
         }
     }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
 }
 
 /// Unpublish a volume from any node where it may be published.
@@ -644,6 +850,8 @@ pub struct UnpublishVolume {
     /// the nexus. Note: this option should be used only when we know the node will not become
     /// accessible again and it is safe to do so.
     force: bool,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl UnpublishVolume {
     /// Create a new `UnpublishVolume` for the given uuid.
@@ -651,8 +859,14 @@ impl UnpublishVolume {
         Self {
             uuid: uuid.clone(),
             force,
+            expected_revision: None,
         }
     }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
     /// It's a force `Self`.
     pub fn force(&self) -> bool {
         self.force
@@ -669,6 +883,8 @@ pub struct ShareVolume {
     pub protocol: VolumeShareProtocol,
     /// Hosts allowed to connect nexus.
     pub frontend_hosts: Vec<String>,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl ShareVolume {
     /// Create a new `ShareVolume` request.
@@ -677,8 +893,14 @@ impl ShareVolume {
             uuid,
             protocol,
             frontend_hosts,
+            expected_revision: None,
         }
     }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
 }
 
 /// Unshare Volume request.
@@ -687,11 +909,21 @@ impl ShareVolume {
 pub struct UnshareVolume {
     /// The uuid of the volume.
     pub uuid: VolumeId,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl UnshareVolume {
     /// Create a new `UnshareVolume` request.
     pub fn new(uuid: VolumeId) -> Self {
-        Self { uuid }
+        Self {
+            uuid,
+            expected_revision: None,
+        }
+    }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
     }
 }
 /// Set the volume replica count.
@@ -702,11 +934,22 @@ pub struct SetVolumeReplica {
     pub uuid: VolumeId,
     /// The replica count.
     pub replicas: u8,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl SetVolumeReplica {
     /// Create new `Self` based on the provided arguments.
     pub fn new(uuid: VolumeId, replicas: u8) -> Self {
-        Self { uuid, replicas }
+        Self {
+            uuid,
+            replicas,
+            expected_revision: None,
+        }
+    }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
     }
 }
 
@@ -718,11 +961,22 @@ pub struct SetVolumeProperty {
     pub uuid: VolumeId,
     /// The property to set.
     pub property: VolumeProperty,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl SetVolumeProperty {
     /// Create new `Self` based on the provided arguments.
     pub fn new(uuid: VolumeId, property: VolumeProperty) -> Self {
-        Self { uuid, property }
+        Self {
+            uuid,
+            property,
+            expected_revision: None,
+        }
+    }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
     }
 }
 
@@ -732,14 +986,22 @@ impl SetVolumeProperty {
 pub struct DestroyVolume {
     /// The uuid of the volume.
     pub uuid: VolumeId,
+    /// Fail unless the volume spec is at this revision.
+    pub expected_revision: Option<u64>,
 }
 impl DestroyVolume {
     /// Create new `Self` to destroy the specified volume.
     pub fn new(volume: &VolumeId) -> Self {
         Self {
             uuid: volume.clone(),
+            expected_revision: None,
         }
     }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
     /// Get the volume's identification.
     pub fn uuid(&self) -> &VolumeId {
         &self.uuid
@@ -914,6 +1176,8 @@ pub struct DestroyShutdownTargets {
     uuid: VolumeId,
     /// List of target address registered as Nvme Subsystems in the Frontend nodes.
     registered_targets: Option<Vec<String>>,
+    /// Fail unless the volume spec is at this revision.
+    expected_revision: Option<u64>,
 }
 
 impl DestroyShutdownTargets {
@@ -922,8 +1186,14 @@ impl DestroyShutdownTargets {
         DestroyShutdownTargets {
             uuid,
             registered_targets,
+            expected_revision: None,
         }
     }
+    /// Fail unless the volume spec is at the given revision.
+    pub fn with_expected_revision(mut self, revision: Option<u64>) -> Self {
+        self.expected_revision = revision;
+        self
+    }
     /// Get volumeId.
     pub fn uuid(&self) -> &VolumeId {
         &self.uuid
@@ -932,6 +1202,10 @@ impl DestroyShutdownTargets {
     pub fn registered_targets(&self) -> &Option<Vec<String>> {
         &self.registered_targets
     }
+    /// Get the revision which the volume spec must be at.
+    pub fn expected_revision(&self) -> Option<u64> {
+        self.expected_revision
+    }
 }
 
 /// Request to create a new volume from a snapshot source.
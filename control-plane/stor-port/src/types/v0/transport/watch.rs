@@ -1,7 +1,7 @@
 use super::*;
 
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt::Debug};
+use std::{collections::HashMap, convert::TryFrom, fmt::Debug};
 
 ///
 /// Watch Agent
@@ -134,3 +134,91 @@ impl Default for WatchCallback {
         Self::Uri(Default::default())
     }
 }
+
+/// Stream the events of the resources which match the filter
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchEvents {
+    /// types of resources to stream the events of, all types if empty
+    pub resources: Vec<EventResource>,
+    /// id of the resource to stream the events of, all resources if none
+    pub id: Option<String>,
+    /// labels which the resource must have
+    pub labels: HashMap<String, String>,
+    /// type of events to stream
+    pub watch_type: WatchType,
+}
+
+impl WatchEvents {
+    /// Check if the `event` matches this filter.
+    pub fn matches(&self, event: &ResourceEvent) -> bool {
+        (self.resources.is_empty() || self.resources.contains(&event.resource))
+            && self.id.as_ref().map_or(true, |id| id == &event.id)
+            && self
+                .labels
+                .iter()
+                .all(|(key, value)| event.labels.get(key) == Some(value))
+            && match self.watch_type {
+                WatchType::All => true,
+                _ => self.watch_type == event.watch_type,
+            }
+    }
+}
+
+/// The different resource types which have their events streamed
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum EventResource {
+    /// volumes
+    Volume,
+    /// nexuses
+    Nexus,
+    /// pools
+    Pool,
+    /// nodes
+    Node,
+    /// volume snapshots
+    Snapshot,
+}
+impl std::str::FromStr for EventResource {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "volume" | "volumes" => Ok(Self::Volume),
+            "nexus" | "nexuses" => Ok(Self::Nexus),
+            "pool" | "pools" => Ok(Self::Pool),
+            "node" | "nodes" => Ok(Self::Node),
+            "snapshot" | "snapshots" => Ok(Self::Snapshot),
+            _ => Err(format!("Unknown resource type '{source}'")),
+        }
+    }
+}
+
+/// The change which a resource event records
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum EventAction {
+    /// the resource has been created or modified
+    Updated,
+    /// the resource has been deleted
+    Deleted,
+}
+
+/// Change of the spec or state of a resource
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceEvent {
+    /// type of the resource
+    pub resource: EventResource,
+    /// id of the resource
+    pub id: String,
+    /// `Desired` for spec changes and `Actual` for state changes
+    pub watch_type: WatchType,
+    /// the change
+    pub action: EventAction,
+    /// labels of the resource
+    pub labels: HashMap<String, String>,
+    /// the new spec or state, none when deleted
+    pub value: Option<serde_json::Value>,
+}
@@ -71,6 +71,12 @@ impl ComponentAction for CoreAgent {
         if let Some(max_rebuilds) = &options.max_rebuilds {
             binary = binary.with_args(vec!["--max-rebuilds", &max_rebuilds.to_string()]);
         }
+        if let Some(threshold) = &options.pool_error_threshold {
+            binary = binary.with_args(vec!["--pool-error-threshold", &threshold.to_string()]);
+        }
+        if let Some(key) = &options.nvmf_auth_seal_key {
+            binary = binary.with_env("NVMF_AUTH_SEAL_KEY", key);
+        }
         Ok(cfg.add_container_spec(
             ContainerSpec::from_binary(name, binary).with_portmap("50051", "50051"),
         ))
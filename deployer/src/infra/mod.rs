@@ -440,8 +440,8 @@ impl_component! {
     Kibana,         1,
     CoreAgent,      2,
     JsonGrpcAgent,  2,
-    Rest,           2,
     HaClusterAgent, 2,
+    Rest,           2,
     IoEngine,       3,
     CsiNode,        3,
     CsiController,  3,
@@ -300,6 +300,15 @@ pub struct StartOptions {
     #[clap(long)]
     max_rebuilds: Option<u32>,
 
+    /// Override the core agent's pool error threshold, after which a pool is marked as degraded.
+    #[clap(long)]
+    pool_error_threshold: Option<u32>,
+
+    /// The core agent's key for sealing the volume targets' secrets, 32 base64 encoded bytes.
+    /// Volume target authentication is disabled without it.
+    #[clap(long)]
+    nvmf_auth_seal_key: Option<String>,
+
     /// Deploy a fio-spdk container.
     /// This can be used for userspace io against a volume's nvmf target using the spdk ioengine.
     #[clap(long)]
@@ -498,6 +507,18 @@ impl StartOptions {
         self.max_rebuilds = max;
         self
     }
+    /// With the pool error threshold, after which a pool is marked as degraded.
+    #[must_use]
+    pub fn with_pool_error_threshold(mut self, threshold: u32) -> Self {
+        self.pool_error_threshold = Some(threshold);
+        self
+    }
+    /// With the key for sealing the volume targets' secrets.
+    #[must_use]
+    pub fn with_nvmf_auth_seal_key(mut self, key: &str) -> Self {
+        self.nvmf_auth_seal_key = Some(key.to_string());
+        self
+    }
     /// Enable/Disable the fio-spdk container.
     #[must_use]
     pub fn with_fio_spdk(mut self, fio_spdk: bool) -> Self {
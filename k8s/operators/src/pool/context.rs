@@ -11,7 +11,7 @@ use kube::{
 use openapi::{
     apis::StatusCode,
     clients,
-    models::{CreatePoolBody, Pool},
+    models::{CreatePoolBody, ExpandPoolBody, Pool},
 };
 
 use super::{normalize_disk, v1beta2_api};
@@ -478,10 +478,61 @@ impl ResourceContext {
                 return self.mark_pool_not_found().await
             }
         }.into_body();
+        let pool = self.expand_if_grown(pool).await;
         // As pool exists, set the status based on the presence of pool state.
         self.set_status_or_unknown(pool).await
     }
 
+    /// Expand the pool with the disks which have been added to the CR since the pool was created,
+    /// returning the pool as it is after the expansion.
+    /// A failed expansion is reported once and the pool is returned as is, so that its status
+    /// is still kept up to date.
+    #[tracing::instrument(fields(name = ?self.name_any()) skip(self, pool))]
+    async fn expand_if_grown(&self, pool: Pool) -> Pool {
+        if self.metadata.deletion_timestamp.is_some() {
+            return pool;
+        }
+        let Some(spec) = &pool.spec else {
+            return pool;
+        };
+        let new_disks = self
+            .spec
+            .disks()
+            .into_iter()
+            .filter(|disk| !spec.disks.contains(disk))
+            .collect::<Vec<_>>();
+        if new_disks.is_empty() {
+            return pool;
+        }
+
+        let body = ExpandPoolBody::new(new_disks.clone());
+        match self
+            .pools_api()
+            .put_node_pool_disks(&self.spec.node(), &self.name_any(), body, None)
+            .await
+        {
+            Ok(pool) => {
+                self.k8s_notify(
+                    "Expand",
+                    "Expanded",
+                    &format!("Expanded pool with disk(s): {}", new_disks.join(", ")),
+                    "Normal",
+                )
+                .await;
+                pool.into_body()
+            }
+            Err(error) => {
+                let message = format!("Unable to expand pool with disk(s) {new_disks:?}: {error}");
+                if !self.notified(&message) {
+                    error!("Unable to expand pool {}", error);
+                    self.k8s_notify("Expand Failure", "Failure", &message, "Warning")
+                        .await;
+                }
+                pool
+            }
+        }
+    }
+
     /// If the pool, has a state we set that status to the CR and if it does not have a state
     /// we set the status as unknown so that we can try again later.
     async fn set_status_or_unknown(&self, pool: Pool) -> Result<Action, Error> {
@@ -509,6 +560,15 @@ impl ResourceContext {
         Ok(Action::requeue(Duration::from_secs(self.ctx.interval)))
     }
 
+    /// Check if the event with the given message has already been posted.
+    fn notified(&self, message: &str) -> bool {
+        self.event_info
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|posted| posted == message)
+    }
+
     /// Post an event, typically these events are used to indicate that
     /// something happened. They should not be used to "log" generic
     /// information. Events are GC-ed by k8s automatically.
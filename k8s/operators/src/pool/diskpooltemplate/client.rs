@@ -0,0 +1,29 @@
+use super::crd::DiskPoolTemplate;
+use crate::error::Error;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use kube::{api::PostParams, Api, Client, CustomResourceExt};
+use openapi::apis::StatusCode;
+use tracing::info;
+
+/// Get the DiskPoolTemplate api.
+pub(crate) fn template_api(client: &Client, namespace: &str) -> Api<DiskPoolTemplate> {
+    Api::namespaced(client.clone(), namespace)
+}
+
+/// Create the DiskPoolTemplate crd, if it doesn't exist already.
+pub(crate) async fn ensure_template_crd(k8s: &Client) -> Result<(), Error> {
+    let crd_api: Api<CustomResourceDefinition> = Api::all(k8s.clone());
+    let new_crd = DiskPoolTemplate::crd();
+    info!(
+        "Creating CRD: {}",
+        serde_json::to_string_pretty(&new_crd).unwrap_or_default()
+    );
+    match crd_api.create(&PostParams::default(), &new_crd).await {
+        Ok(_) => Ok(()),
+        Err(kube::Error::Api(e)) if e.code == StatusCode::CONFLICT => {
+            info!("DiskPoolTemplate CRD already exists. Skipping CRD Operations");
+            Ok(())
+        }
+        Err(source) => Err(Error::Kube { source }),
+    }
+}
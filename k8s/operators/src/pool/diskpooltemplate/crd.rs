@@ -0,0 +1,263 @@
+use crate::diskpool::crd::v1beta2::Topology;
+use kube::CustomResource;
+use openapi::models::BlockDevice;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Size of the blocks which the block device size is reported in.
+const BLOCK_SIZE: u64 = 512;
+
+#[derive(
+    CustomResource, Serialize, Deserialize, Default, Debug, Eq, PartialEq, Clone, JsonSchema,
+)]
+#[kube(
+group = "openebs.io",
+version = "v1beta2",
+kind = "DiskPoolTemplate",
+plural = "diskpooltemplates",
+// The name of the struct that gets created that represents a resource
+namespaced,
+derive = "PartialEq",
+derive = "Default",
+shortname = "dspt",
+printcolumn = r#"{ "name":"device_type", "type":"string", "description":"type of the matching devices", "jsonPath":".spec.device_selector.device_type"}"#,
+printcolumn = r#"{ "name":"path", "type":"string", "description":"path glob of the matching devices", "jsonPath":".spec.device_selector.path"}"#
+)]
+
+/// The template spec which selects the nodes and the block devices to create the pools on.
+pub struct DiskPoolTemplateSpec {
+    /// The labels the node must have for pools to be created on it.
+    /// All io-engine nodes are selected when empty.
+    #[serde(default)]
+    node_selector: HashMap<String, String>,
+    /// The selector of the block devices to create pools on.
+    #[serde(default)]
+    device_selector: DeviceSelector,
+    /// The topology of the created pools.
+    topology: Option<Topology>,
+}
+
+/// Selects block devices, all the specified fields must match.
+#[derive(Serialize, Deserialize, Default, Debug, Eq, PartialEq, Clone, JsonSchema)]
+pub struct DeviceSelector {
+    /// The minimum size of the device in bytes.
+    min_size: Option<u64>,
+    /// The maximum size of the device in bytes.
+    max_size: Option<u64>,
+    /// The device type, eg: disk or partition.
+    device_type: Option<String>,
+    /// The device model.
+    model: Option<String>,
+    /// Glob matched against the device name and its links, eg: /dev/disk/by-id/nvme-*.
+    path: Option<String>,
+}
+
+impl DiskPoolTemplateSpec {
+    /// Create a new DiskPoolTemplateSpec from the node and device selectors.
+    pub fn new(
+        node_selector: HashMap<String, String>,
+        device_selector: DeviceSelector,
+        topology: Option<Topology>,
+    ) -> Self {
+        Self {
+            node_selector,
+            device_selector,
+            topology,
+        }
+    }
+    /// The labels the node must have for pools to be created on it.
+    pub fn node_selector(&self) -> &HashMap<String, String> {
+        &self.node_selector
+    }
+    /// The selector of the block devices to create pools on.
+    pub fn device_selector(&self) -> &DeviceSelector {
+        &self.device_selector
+    }
+    /// The topology of the created pools.
+    pub fn topology(&self) -> Option<Topology> {
+        self.topology.clone()
+    }
+    /// Check if a node with the given labels is selected by the template.
+    pub fn selects_node(&self, labels: &HashMap<String, String>) -> bool {
+        self.node_selector
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+    }
+}
+
+impl DeviceSelector {
+    /// Create a new DeviceSelector.
+    pub fn new(
+        min_size: Option<u64>,
+        max_size: Option<u64>,
+        device_type: Option<String>,
+        model: Option<String>,
+        path: Option<String>,
+    ) -> Self {
+        Self {
+            min_size,
+            max_size,
+            device_type,
+            model,
+            path,
+        }
+    }
+    /// The glob which the device name or one of its links must match.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+    /// Check if the block device is selected, with the path glob already compiled.
+    pub fn selects(&self, device: &BlockDevice, path: Option<&glob::Pattern>) -> bool {
+        let size = device.size * BLOCK_SIZE;
+        self.min_size.map_or(true, |min| size >= min)
+            && self.max_size.map_or(true, |max| size <= max)
+            && self
+                .device_type
+                .as_ref()
+                .map_or(true, |devtype| devtype == &device.devtype)
+            && self
+                .model
+                .as_ref()
+                .map_or(true, |model| model == &device.model)
+            && path.map_or(true, |pattern| {
+                pattern.matches(&device.devname)
+                    || device.devlinks.iter().any(|link| pattern.matches(link))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn device(devname: &str, devlinks: &[&str], devtype: &str, model: &str) -> BlockDevice {
+        BlockDevice::new_all(
+            true,
+            devlinks.iter().map(|link| link.to_string()).collect(),
+            259,
+            0,
+            devname.to_string(),
+            format!("/devices/{devname}"),
+            devtype.to_string(),
+            None,
+            model.to_string(),
+            None,
+            // 10GiB in 512 byte blocks.
+            10 * 1024 * 1024 * 2,
+        )
+    }
+
+    #[test]
+    fn device_selector() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let nvme = device(
+            "/dev/nvme0n1",
+            &["/dev/disk/by-id/nvme-Samsung_1", "/dev/disk/by-path/pci-0"],
+            "disk",
+            "Samsung",
+        );
+        let sda1 = device("/dev/sda1", &[], "partition", "QEMU");
+
+        let selector =
+            |min, max, devtype: Option<&str>, model: Option<&str>, path: Option<&str>| {
+                DeviceSelector::new(
+                    min,
+                    max,
+                    devtype.map(ToString::to_string),
+                    model.map(ToString::to_string),
+                    path.map(ToString::to_string),
+                )
+            };
+        let cases = [
+            (selector(None, None, None, None, None), &nvme, true),
+            (selector(None, None, None, None, None), &sda1, true),
+            (
+                selector(Some(10 * GIB), None, None, None, None),
+                &nvme,
+                true,
+            ),
+            (
+                selector(Some(11 * GIB), None, None, None, None),
+                &nvme,
+                false,
+            ),
+            (
+                selector(None, Some(10 * GIB), None, None, None),
+                &nvme,
+                true,
+            ),
+            (
+                selector(None, Some(9 * GIB), None, None, None),
+                &nvme,
+                false,
+            ),
+            (selector(None, None, Some("disk"), None, None), &nvme, true),
+            (selector(None, None, Some("disk"), None, None), &sda1, false),
+            (
+                selector(None, None, None, Some("Samsung"), None),
+                &nvme,
+                true,
+            ),
+            (
+                selector(None, None, None, Some("Samsung"), None),
+                &sda1,
+                false,
+            ),
+            (
+                selector(None, None, None, None, Some("/dev/nvme*")),
+                &nvme,
+                true,
+            ),
+            (
+                selector(None, None, None, None, Some("/dev/disk/by-id/nvme-*")),
+                &nvme,
+                true,
+            ),
+            (
+                selector(None, None, None, None, Some("/dev/disk/by-id/nvme-*")),
+                &sda1,
+                false,
+            ),
+            (
+                selector(None, None, None, None, Some("/dev/sdb*")),
+                &sda1,
+                false,
+            ),
+            (
+                selector(
+                    Some(GIB),
+                    Some(20 * GIB),
+                    Some("disk"),
+                    Some("Samsung"),
+                    Some("/dev/nvme*"),
+                ),
+                &nvme,
+                true,
+            ),
+            (
+                selector(
+                    Some(GIB),
+                    Some(20 * GIB),
+                    Some("disk"),
+                    Some("QEMU"),
+                    Some("/dev/nvme*"),
+                ),
+                &nvme,
+                false,
+            ),
+        ];
+
+        for (selector, device, selected) in cases {
+            let path = selector
+                .path()
+                .map(|path| glob::Pattern::new(path).unwrap());
+            assert_eq!(
+                selector.selects(device, path.as_ref()),
+                selected,
+                "{selector:?} selecting {}",
+                device.devname
+            );
+        }
+    }
+}
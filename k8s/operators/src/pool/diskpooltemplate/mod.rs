@@ -0,0 +1,9 @@
+//! This module has the definitions and the operations for the DiskPoolTemplate CRs, which
+//! provision DiskPools on the matching unused block devices of the matching nodes.
+
+/// DiskPoolTemplate client operations.
+pub(crate) mod client;
+/// The DiskPoolTemplate custom resource definition.
+pub(crate) mod crd;
+/// Provisioning of the DiskPools from the templates.
+pub(crate) mod provisioner;
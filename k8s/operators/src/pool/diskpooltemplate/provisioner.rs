@@ -0,0 +1,246 @@
+use super::crd::DiskPoolTemplate;
+use crate::{
+    diskpool::{
+        client::{create_v1beta2_cr, list_existing_cr},
+        crd::v1beta2::{DiskPool, DiskPoolSpec},
+    },
+    error::Error,
+    normalize_disk, BACKOFF_PERIOD, PAGINATION_LIMIT,
+};
+use kube::{runtime::controller::Action, Client, ResourceExt};
+use openapi::{
+    clients,
+    models::{BlockDevice, Node, NodeStatus},
+};
+use std::{sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+/// The prefix of the stable links of the block devices.
+const BY_ID_PREFIX: &str = "/dev/disk/by-id/";
+
+/// Data we want access to in the template reconcile calls.
+pub(crate) struct TemplateContext {
+    /// Reference to our k8s client
+    k8s: Client,
+    /// HTTP client
+    http: clients::tower::ApiClient,
+    /// Interval
+    interval: u64,
+}
+
+impl TemplateContext {
+    /// Constructor for the template context.
+    pub(crate) fn new(k8s: Client, http: clients::tower::ApiClient, interval: u64) -> Self {
+        Self {
+            k8s,
+            http,
+            interval,
+        }
+    }
+}
+
+/// Create a DiskPool CR for every unused block device matching the template, on every online
+/// node matching the template.
+/// The template is reconciled periodically so pools are also created on newly joined nodes and
+/// on newly attached devices.
+#[tracing::instrument(fields(name = %template.name_any()) skip(template, ctx))]
+pub(crate) async fn reconcile(
+    template: Arc<DiskPoolTemplate>,
+    ctx: Arc<TemplateContext>,
+) -> Result<Action, Error> {
+    let namespace = template.namespace().ok_or(Error::InvalidCRField {
+        field: "diskpooltemplate.metadata.namespace".to_string(),
+    })?;
+    let path = template
+        .spec
+        .device_selector()
+        .path()
+        .map(glob::Pattern::new)
+        .transpose()
+        .map_err(|error| Error::SpecError {
+            value: format!("Invalid device path glob: {error}"),
+            timeout: BACKOFF_PERIOD as u32,
+        })?;
+
+    let nodes = ctx
+        .http
+        .nodes_api()
+        .get_nodes(None, None)
+        .await?
+        .into_body();
+    let mut pools = list_existing_cr(&ctx.k8s, &namespace, PAGINATION_LIMIT).await?;
+
+    for node in nodes.iter().filter(|node| selects_node(&template, node)) {
+        let devices = match ctx
+            .http
+            .block_devices_api()
+            .get_node_block_devices(&node.id, Some(false))
+            .await
+        {
+            Ok(devices) => devices.into_body(),
+            Err(error) => {
+                warn!(node.id, %error, "Failed to list the block devices of the node");
+                continue;
+            }
+        };
+        let devices = devices
+            .into_iter()
+            .filter(|device| {
+                template
+                    .spec
+                    .device_selector()
+                    .selects(device, path.as_ref())
+                    && !in_use(&pools, &node.id, device)
+            })
+            .collect::<Vec<_>>();
+
+        for device in devices {
+            let name = pool_name(&template.name_any(), &node.id, &device);
+            let spec = DiskPoolSpec::new(
+                node.id.clone(),
+                vec![device_path(&device)],
+                template.spec.topology(),
+            );
+            create_v1beta2_cr(&ctx.k8s, &namespace, &name, spec.clone()).await?;
+            info!(
+                pool.name = name.as_str(),
+                node.id,
+                device = device.devname.as_str(),
+                "Created DiskPool from template"
+            );
+            pools.push(DiskPool::new(&name, spec));
+        }
+    }
+
+    Ok(Action::requeue(Duration::from_secs(ctx.interval)))
+}
+
+/// Check if the node is online and selected by the template.
+fn selects_node(template: &DiskPoolTemplate, node: &Node) -> bool {
+    let online = node
+        .state
+        .as_ref()
+        .map_or(false, |state| state.status == NodeStatus::Online);
+    let labels = node
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.labels.clone())
+        .unwrap_or_default();
+    online && template.spec.selects_node(&labels)
+}
+
+/// Check if the device is already used by a DiskPool CR of the node.
+fn in_use(pools: &[DiskPool], node: &str, device: &BlockDevice) -> bool {
+    pools
+        .iter()
+        .filter(|pool| pool.spec.node() == node)
+        .flat_map(|pool| pool.spec.disks())
+        .map(|disk| normalize_disk(&disk))
+        .any(|disk| disk == device.devname || device.devlinks.contains(&disk))
+}
+
+/// The disk of the pool created on the device, preferring its stable link.
+fn device_path(device: &BlockDevice) -> String {
+    device
+        .devlinks
+        .iter()
+        .find(|link| link.starts_with(BY_ID_PREFIX))
+        .cloned()
+        .unwrap_or_else(|| device.devname.clone())
+}
+
+/// The name of the DiskPool CR created by the template on the device of the node.
+fn pool_name(template: &str, node: &str, device: &BlockDevice) -> String {
+    let device = device.devname.rsplit('/').next().unwrap_or(&device.devname);
+    format!("{template}-{node}-{device}").to_lowercase()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn device(devname: &str, devlinks: &[&str]) -> BlockDevice {
+        BlockDevice::new_all(
+            true,
+            devlinks.iter().map(|link| link.to_string()).collect(),
+            259,
+            0,
+            devname.to_string(),
+            format!("/devices/{devname}"),
+            "disk".to_string(),
+            None,
+            "Samsung".to_string(),
+            None,
+            2048,
+        )
+    }
+
+    fn pool(name: &str, node: &str, disk: &str) -> DiskPool {
+        DiskPool::new(
+            name,
+            DiskPoolSpec::new(node.into(), vec![disk.into()], None),
+        )
+    }
+
+    #[test]
+    fn device_in_use() {
+        let nvme = device("/dev/nvme0n1", &["/dev/disk/by-id/nvme-Samsung_1"]);
+        let sdb = device("/dev/sdb", &[]);
+        let pools = [
+            pool("pool-1", "node-1", "/dev/disk/by-id/nvme-Samsung_1"),
+            pool("pool-2", "node-1", "aio:///dev/sdb"),
+            pool("pool-3", "node-2", "/dev/nvme0n1"),
+        ];
+
+        let cases = [
+            // used through its stable link
+            ("node-1", &nvme, true),
+            // used through its uri
+            ("node-1", &sdb, true),
+            // used through its name
+            ("node-2", &nvme, true),
+            // a device with the same name on another node
+            ("node-2", &sdb, false),
+            ("node-3", &nvme, false),
+        ];
+        for (node, device, used) in cases {
+            assert_eq!(
+                in_use(&pools, node, device),
+                used,
+                "{} on {node}",
+                device.devname
+            );
+        }
+        assert!(!in_use(&[], "node-1", &nvme));
+    }
+
+    #[test]
+    fn pool_name() {
+        let cases = [
+            (
+                "template",
+                "node-1",
+                "/dev/nvme0n1",
+                "template-node-1-nvme0n1",
+            ),
+            ("Template", "Node-1", "/dev/sdb", "template-node-1-sdb"),
+            ("template", "node-1", "sdc", "template-node-1-sdc"),
+        ];
+        for (template, node, devname, name) in cases {
+            assert_eq!(
+                super::pool_name(template, node, &device(devname, &[])),
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn device_path() {
+        let nvme = device(
+            "/dev/nvme0n1",
+            &["/dev/disk/by-path/pci-0", "/dev/disk/by-id/nvme-Samsung_1"],
+        );
+        assert_eq!(super::device_path(&nvme), "/dev/disk/by-id/nvme-Samsung_1");
+        assert_eq!(super::device_path(&device("/dev/sdb", &[])), "/dev/sdb");
+    }
+}
@@ -1,10 +1,12 @@
 //! K8S pool operator watches for pool CRs and creates the pool on the given node.
 //! There is a maximum retry limit that will put the pool into a steady error state.
+//! It also watches for pool template CRs and creates pool CRs on the matching devices.
 //!
 //! Successfully created pools are recreated by the control plane.
 
 pub(crate) mod context;
 mod diskpool;
+mod diskpooltemplate;
 pub(crate) mod error;
 mod mayastorpool;
 
@@ -18,6 +20,11 @@ use diskpool::crd::{
     migration::ensure_and_migrate_crd,
     v1beta2::{CrPoolState, DiskPool, DiskPoolSpec, DiskPoolStatus},
 };
+use diskpooltemplate::{
+    client::{ensure_template_crd, template_api},
+    crd::DiskPoolTemplate,
+    provisioner::TemplateContext,
+};
 use error::Error;
 use futures::StreamExt;
 use kube::{
@@ -60,6 +67,26 @@ fn error_policy(_object: Arc<DiskPool>, error: &Error, _ctx: Arc<OperatorContext
     Action::requeue(duration)
 }
 
+/// Determine what we want to do when dealing with errors from the template
+/// reconciliation loop
+fn template_error_policy(
+    template: Arc<DiskPoolTemplate>,
+    error: &Error,
+    _ctx: Arc<TemplateContext>,
+) -> Action {
+    let duration = Duration::from_secs(match error {
+        Error::SpecError { timeout, .. } => (*timeout).into(),
+        _ => BACKOFF_PERIOD,
+    });
+    warn!(
+        name = template.name_any(),
+        "{}, template retry scheduled in {} seconds",
+        error,
+        duration.as_secs()
+    );
+    Action::requeue(duration)
+}
+
 /// The main work horse
 #[tracing::instrument(fields(name = %dsp.spec.node(), status = ?dsp.status) skip(dsp, ctx))]
 async fn reconcile(dsp: Arc<DiskPool>, ctx: Arc<OperatorContext>) -> Result<Action, Error> {
@@ -158,9 +185,17 @@ async fn pool_controller(args: ArgMatches) -> anyhow::Result<()> {
 
     create_missing_cr(&k8s, clients::tower::ApiClient::new(cfg.clone()), namespace).await?;
 
+    ensure_template_crd(&k8s).await?;
+    let templates: Api<DiskPoolTemplate> = template_api(&k8s, namespace);
+    let template_context = TemplateContext::new(
+        k8s.clone(),
+        clients::tower::ApiClient::new(cfg.clone()),
+        interval,
+    );
+
     info!(namespace, "Starting DiskPool Operator (dsp)");
 
-    Controller::new(newdsp, watcher::Config::default())
+    let pools = Controller::new(newdsp, watcher::Config::default())
         .run(reconcile, error_policy, Arc::new(context))
         .for_each(|res| async move {
             match res {
@@ -171,8 +206,24 @@ async fn pool_controller(args: ArgMatches) -> anyhow::Result<()> {
                     trace!(?e);
                 }
             }
-        })
-        .await;
+        });
+    let templates = Controller::new(templates, watcher::Config::default())
+        .run(
+            diskpooltemplate::provisioner::reconcile,
+            template_error_policy,
+            Arc::new(template_context),
+        )
+        .for_each(|res| async move {
+            match res {
+                Ok(o) => {
+                    trace!(?o);
+                }
+                Err(e) => {
+                    trace!(?e);
+                }
+            }
+        });
+    futures::join!(pools, templates);
 
     Ok(())
 }
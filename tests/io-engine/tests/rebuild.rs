@@ -61,6 +61,8 @@ async fn concurrent_rebuilds() {
                     models::VolumeShareProtocol::Nvmf,
                     None,
                     "".to_string(),
+                    None,
+                    None,
                 ),
             )
             .await
@@ -114,7 +116,11 @@ async fn concurrent_rebuilds() {
         let mut added_slack = false;
         let check_interval = std::time::Duration::from_secs(5);
         loop {
-            let curr_volumes = vol_cli.get_volumes(0, None, None).await.unwrap().entries;
+            let curr_volumes = vol_cli
+                .get_volumes(0, None, None, None)
+                .await
+                .unwrap()
+                .entries;
             assert_eq!(volumes.len(), curr_volumes.len());
             // volumes should either be online or degraded (while rebuilding)
             let not_expected = curr_volumes
@@ -150,7 +156,7 @@ async fn concurrent_rebuilds() {
                 let nodes = cluster
                     .rest_v00()
                     .nodes_api()
-                    .get_nodes(None)
+                    .get_nodes(None, None)
                     .await
                     .unwrap();
                 for node in nodes {
@@ -402,6 +408,8 @@ async fn repeated_volume_rebuilds() {
                     models::VolumeShareProtocol::Nvmf,
                     None,
                     cluster.csi_node(0),
+                    None,
+                    None,
                 ),
             )
             .await
@@ -646,6 +654,8 @@ async fn repeated_volume_double_rebuilds() {
                     models::VolumeShareProtocol::Nvmf,
                     None,
                     cluster.csi_node(0),
+                    None,
+                    None,
                 ),
             )
             .await
@@ -824,6 +834,8 @@ async fn fault_rebuild_verify() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
+                None,
             ),
         )
         .await
@@ -950,6 +962,8 @@ async fn destroy_rebuilding_nexus() {
                     models::VolumeShareProtocol::Nvmf,
                     None,
                     cluster.csi_node(0),
+                    None,
+                    None,
                 ),
             )
             .await
@@ -793,6 +793,20 @@ impl ClusterBuilder {
         self
     }
 
+    /// With the pool error threshold, after which a pool is marked as degraded.
+    #[must_use]
+    pub fn with_pool_error_threshold(mut self, threshold: u32) -> Self {
+        self.opts = self.opts.with_pool_error_threshold(threshold);
+        self
+    }
+
+    /// With the key for sealing the volume targets' secrets, enabling volume target authentication.
+    #[must_use]
+    pub fn with_nvmf_auth_seal_key(mut self, key: &str) -> Self {
+        self.opts = self.opts.with_nvmf_auth_seal_key(key);
+        self
+    }
+
     /// With store operation timeout.
     #[must_use]
     pub fn with_store_timeout(mut self, timeout: Duration) -> Self {
@@ -25,6 +25,10 @@ pub trait StoreKv: Sync + Send + Clone {
     /// Returns a channel which is signalled when an event occurs.
     /// # Warning: Events may be lost if we are restarted.
     async fn watch_kv<K: StoreKey>(&mut self, key: &K) -> Result<StoreWatchReceiver, Error>;
+    /// Watches for changes under all entries with the given key prefix.
+    /// Unlike `watch_kv`, the watch carries on when an entry is deleted.
+    /// # Warning: Events may be lost if we are restarted.
+    async fn watch_prefix(&mut self, key_prefix: &str) -> Result<StoreWatchReceiver, Error>;
 
     /// Returns a vector of tuples. Each tuple represents a key-value pair.
     async fn get_values_prefix(&mut self, key_prefix: &str) -> Result<Vec<(String, Value)>, Error>;
@@ -71,8 +75,8 @@ impl<T> StoreValue for T where T: Sync + serde::Serialize {}
 pub enum WatchEvent {
     /// Put operation containing the key and value.
     Put(String, Value),
-    /// Delete operation.
-    Delete,
+    /// Delete operation containing the key.
+    Delete(String),
 }
 /// Channel used to receive events from a watch setup through `StoreKv::watch_kv`.
 pub type StoreWatchReceiver = Receiver<Result<WatchEvent, Error>>;
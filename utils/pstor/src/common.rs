@@ -28,6 +28,10 @@ pub enum StorableObjectType {
     StoreLeaseOwner,
     SwitchOver,
     AppNodeSpec,
+    UsageHistory,
+    VolumeSwitchOverHistory,
+    NvmfAuthSecret,
+    AsyncOperation,
 }
 
 /// Control plane api versions.
@@ -9,7 +9,7 @@ use crate::{
 use async_trait::async_trait;
 use etcd_client::{
     Client, Compare, CompareOp, DeleteOptions, EventType, GetOptions, KeyValue, SortOrder,
-    SortTarget, Txn, TxnOp, WatchStream, Watcher,
+    SortTarget, Txn, TxnOp, WatchOptions, WatchStream, Watcher,
 };
 use serde_json::Value;
 use snafu::ResultExt;
@@ -190,7 +190,26 @@ impl StoreKv for Etcd {
             .context(Watch {
                 key: key.to_string(),
             })?;
-        watch(watcher, stream, sender);
+        watch(watcher, stream, sender, false);
+        Ok(receiver)
+    }
+
+    /// 'Watch' all etcd entries with the given key prefix.
+    /// A receiver channel is returned which is signalled when any of the entries
+    /// is changed or deleted.
+    async fn watch_prefix(
+        &mut self,
+        key_prefix: &str,
+    ) -> Result<Receiver<Result<WatchEvent, Error>>, Error> {
+        let (sender, receiver) = channel(100);
+        let (watcher, stream) = self
+            .client
+            .watch(key_prefix, Some(WatchOptions::new().with_prefix()))
+            .await
+            .context(Watch {
+                key: key_prefix.to_string(),
+            })?;
+        watch(watcher, stream, sender, true);
         Ok(receiver)
     }
 
@@ -379,7 +398,7 @@ impl StoreObj for Etcd {
             .watch(key.key(), None)
             .await
             .context(Watch { key: key.key() })?;
-        watch(watcher, stream, sender);
+        watch(watcher, stream, sender, false);
         Ok(receiver)
     }
 }
@@ -393,8 +412,13 @@ impl Store for Etcd {
 
 /// Watch for events in the key-value store.
 /// When an event occurs, a WatchEvent is sent over the channel.
-/// When a 'delete' event is received, the watch stops watching.
-fn watch(_watcher: Watcher, mut stream: WatchStream, sender: Sender<Result<WatchEvent, Error>>) {
+/// When a 'delete' event is received, the watch stops watching, unless it's watching a `prefix`.
+fn watch(
+    _watcher: Watcher,
+    mut stream: WatchStream,
+    sender: Sender<Result<WatchEvent, Error>>,
+    prefix: bool,
+) {
     // For now we spawn a thread for each value that is watched.
     // If we find that we are watching lots of events, this can be optimised.
     // TODO: Optimise the spawning of threads if required.
@@ -432,11 +456,17 @@ fn watch(_watcher: Watcher, mut stream: WatchStream, sender: Sender<Result<Watch
                         }
                     }
                     EventType::Delete => {
-                        // Send only fails if the receiver is closed. We are
-                        // returning here anyway, so the error doesn't need to
-                        // be handled.
-                        let _ = sender.send(Ok(WatchEvent::Delete)).await;
-                        return;
+                        let key = event
+                            .kv()
+                            .and_then(|kv| kv.key_str().ok())
+                            .unwrap_or_default()
+                            .to_string();
+                        let sent = sender.send(Ok(WatchEvent::Delete(key))).await;
+                        // Send only fails if the receiver is closed, so just stop watching.
+                        // A single entry is gone, so there's nothing left to watch either.
+                        if sent.is_err() || !prefix {
+                            return;
+                        }
                     }
                 }
             }